@@ -184,7 +184,7 @@ where
 	C::Api: crml_cennzx_rpc::CennzxRuntimeApi<Block, AssetId, Balance, AccountId>,
 	C::Api: crml_nft_rpc::NftRuntimeApi<Block, AccountId, Runtime>,
 	C::Api: crml_staking_rpc::StakingRuntimeApi<Block, AccountId>,
-	C::Api: crml_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: crml_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance, AssetId>,
 	C::Api: crml_generic_asset_rpc::GenericAssetRuntimeApi<Block, AssetId, Balance, AccountId>,
 	C::Api: crml_governance_rpc::GovernanceRuntimeApi<Block, AccountId>,
 	C::Api: TxPoolRuntimeApi<Block>,
@@ -21,6 +21,7 @@
 use codec::{Codec, Decode, Encode};
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 /// A result of querying the exchange
 #[derive(Eq, PartialEq, Encode, Decode, RuntimeDebug)]
@@ -62,5 +63,27 @@ sp_api::decl_runtime_apis! {
 			asset_id: AssetId,
 			liquidity_to_buy: Balance,
 		) -> (Balance, Balance);
+		/// Query the time-weighted average prices of the `asset_id` exchange over the last `window` blocks
+		/// Returns prices scaled by 10^18 as (asset price in core, core price in asset)
+		fn twap(
+			asset_id: AssetId,
+			window: u32,
+		) -> CennzxResult<(Balance, Balance)>;
+		/// Query the cheapest route to buy `amount` of `asset_to_buy` with `asset_to_sell`
+		/// Every exchange pairs the core asset, so routes are direct or via the core asset only
+		/// Returns the route and the amount of `asset_to_sell` required
+		fn best_buy_route(
+			asset_to_buy: AssetId,
+			amount: Balance,
+			asset_to_sell: AssetId,
+		) -> CennzxResult<(Vec<AssetId>, Balance)>;
+		/// Query the most valuable route to sell `amount` of `asset_to_sell` for `asset_to_buy`
+		/// Every exchange pairs the core asset, so routes are direct or via the core asset only
+		/// Returns the route and the amount of `asset_to_buy` received
+		fn best_sell_route(
+			asset_to_sell: AssetId,
+			amount: Balance,
+			asset_to_buy: AssetId,
+		) -> CennzxResult<(Vec<AssetId>, Balance)>;
 	}
 }
@@ -61,6 +61,25 @@ where
 		asset_id: AssetId,
 		liquidity_to_buy: WrappedBalance,
 	) -> Result<LiquidityPriceResponse<Balance>>;
+
+	#[rpc(name = "cennzx_twap")]
+	fn twap(&self, asset_id: AssetId, window: u32) -> Result<TwapResponse<Balance>>;
+
+	#[rpc(name = "cennzx_bestBuyRoute")]
+	fn best_buy_route(
+		&self,
+		asset_to_buy: AssetId,
+		amount_to_buy: WrappedBalance,
+		asset_to_pay: AssetId,
+	) -> Result<RouteResponse<AssetId, Balance>>;
+
+	#[rpc(name = "cennzx_bestSellRoute")]
+	fn best_sell_route(
+		&self,
+		asset_to_sell: AssetId,
+		amount_to_sell: WrappedBalance,
+		asset_to_payout: AssetId,
+	) -> Result<RouteResponse<AssetId, Balance>>;
 }
 
 /// An implementation of CENNZX Spot Exchange specific RPC methods.
@@ -117,6 +136,25 @@ pub struct LiquidityPriceResponse<Balance> {
 	asset: Balance,
 }
 
+#[derive(Eq, PartialEq, Decode, Encode, Default, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "Balance: std::fmt::Display"))]
+#[serde(bound(deserialize = "Balance: std::str::FromStr"))]
+pub struct TwapResponse<Balance> {
+	#[serde(with = "serde_balance")]
+	asset_price: Balance,
+	#[serde(with = "serde_balance")]
+	core_price: Balance,
+}
+
+#[derive(Eq, PartialEq, Decode, Encode, Default, Debug, Serialize, Deserialize)]
+#[serde(bound(serialize = "AssetId: Serialize, Balance: std::fmt::Display"))]
+#[serde(bound(deserialize = "AssetId: Deserialize<'de>, Balance: std::str::FromStr"))]
+pub struct RouteResponse<AssetId, Balance> {
+	path: Vec<AssetId>,
+	#[serde(with = "serde_balance")]
+	price: Balance,
+}
+
 mod serde_balance {
 	use serde::{Deserialize, Deserializer, Serializer};
 
@@ -198,6 +236,7 @@ pub enum Error {
 	Runtime,
 	CannotExchange,
 	PriceOverflow,
+	InsufficientPriceHistory,
 }
 
 impl From<Error> for i64 {
@@ -206,6 +245,7 @@ impl From<Error> for i64 {
 			Error::Runtime => 1,
 			Error::CannotExchange => 2,
 			Error::PriceOverflow => 3,
+			Error::InsufficientPriceHistory => 4,
 		}
 	}
 }
@@ -315,6 +355,86 @@ where
 			asset: result.1,
 		})
 	}
+
+	fn twap(&self, asset_id: AssetId, window: u32) -> Result<TwapResponse<Balance>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let result = api.twap(&at, asset_id, window).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::Runtime.into()),
+			message: "Unable to query TWAP.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		match result {
+			CennzxResult::Success((asset_price, core_price)) => Ok(TwapResponse {
+				asset_price,
+				core_price,
+			}),
+			CennzxResult::Error => Err(RpcError {
+				code: ErrorCode::ServerError(Error::InsufficientPriceHistory.into()),
+				message: "Insufficient price history for requested window.".into(),
+				data: Some("".into()),
+			}),
+		}
+	}
+
+	fn best_buy_route(
+		&self,
+		asset_to_buy: AssetId,
+		amount_to_buy: WrappedBalance,
+		asset_to_pay: AssetId,
+	) -> Result<RouteResponse<AssetId, Balance>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let result = api
+			.best_buy_route(&at, asset_to_buy, amount_to_buy.0.into(), asset_to_pay)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::Runtime.into()),
+				message: "Unable to query buy route.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		match result {
+			CennzxResult::Success((path, price)) => Ok(RouteResponse { path, price }),
+			CennzxResult::Error => Err(RpcError {
+				code: ErrorCode::ServerError(Error::CannotExchange.into()),
+				message: "Cannot exchange for requested amount.".into(),
+				data: Some("".into()),
+			}),
+		}
+	}
+
+	fn best_sell_route(
+		&self,
+		asset_to_sell: AssetId,
+		amount_to_sell: WrappedBalance,
+		asset_to_payout: AssetId,
+	) -> Result<RouteResponse<AssetId, Balance>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+
+		let result = api
+			.best_sell_route(&at, asset_to_sell, amount_to_sell.0.into(), asset_to_payout)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::Runtime.into()),
+				message: "Unable to query sell route.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		match result {
+			CennzxResult::Success((path, price)) => Ok(RouteResponse { path, price }),
+			CennzxResult::Error => Err(RpcError {
+				code: ErrorCode::ServerError(Error::CannotExchange.into()),
+				message: "Cannot exchange by requested amount.".into(),
+				data: Some("".into()),
+			}),
+		}
+	}
 }
 
 #[test]
@@ -70,6 +70,92 @@ benchmarks! {
 		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_a), 80u32.into());
 	}
 
+	buy_asset_via_path {
+		let investor: T::AccountId = whitelisted_caller();
+		let buyer: T::AccountId = account("buyer", 0, 0);
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_b, 300u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&buyer, asset_a, 100u32.into());
+
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_a, 20u32.into(), 20u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_b, 30u32.into(), 30u32.into(), 100u32.into());
+
+	}: _(RawOrigin::Signed(buyer.clone()), None, vec![asset_a, core_asset_id, asset_b], 10u32.into(), 50u32.into())
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(&buyer, asset_a), 79u32.into());
+	}
+
+	sell_asset_via_path {
+		let investor: T::AccountId = whitelisted_caller();
+		let seller: T::AccountId = account("seller", 0, 0);
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_b, 300u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&seller, asset_a, 100u32.into());
+
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_a, 20u32.into(), 20u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_b, 30u32.into(), 30u32.into(), 100u32.into());
+
+	}: _(RawOrigin::Signed(seller.clone()), None, vec![asset_a, core_asset_id, asset_b], 20u32.into(), 5u32.into())
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_a), 80u32.into());
+	}
+
+	buy_asset_stable_swap {
+		let investor: T::AccountId = whitelisted_caller();
+		let buyer: T::AccountId = account("buyer", 0, 0);
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_b, 300u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&buyer, asset_a, 100u32.into());
+
+		let _ = <Cennzx<T>>::create_exchange(RawOrigin::Signed(investor.clone()).into(), asset_a, curve, 1u32.into(), 100u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::create_exchange(RawOrigin::Signed(investor.clone()).into(), asset_b, curve, 1u32.into(), 100u32.into(), 100u32.into());
+
+	}: buy_asset(RawOrigin::Signed(buyer.clone()), None, asset_a, asset_b, 10u32.into(), 50u32.into())
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(&buyer, asset_a), 88u32.into());
+	}
+
+	sell_asset_stable_swap {
+		let investor: T::AccountId = whitelisted_caller();
+		let seller: T::AccountId = account("seller", 0, 0);
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_b, 300u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&seller, asset_a, 100u32.into());
+
+		let _ = <Cennzx<T>>::create_exchange(RawOrigin::Signed(investor.clone()).into(), asset_a, curve, 1u32.into(), 100u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::create_exchange(RawOrigin::Signed(investor.clone()).into(), asset_b, curve, 1u32.into(), 100u32.into(), 100u32.into());
+
+	}: sell_asset(RawOrigin::Signed(seller.clone()), None, asset_a, asset_b, 20u32.into(), 5u32.into())
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_b), 16u32.into());
+	}
+
 	add_liquidity {
 		let investor: T::AccountId = whitelisted_caller();
 
@@ -116,6 +202,141 @@ benchmarks! {
 	verify {
 		assert_eq!(<Cennzx<T>>::fee_rate(), rate);
 	}
+
+	set_exchange_fee_rate {
+		let asset_id: T::AssetId = TRADE_ASSET_A_ID.into();
+		let rate = FeeRate::<PerMillion>::from(1234u128);
+	}: _(RawOrigin::Root, asset_id, Some(rate))
+	verify {
+		assert_eq!(<Cennzx<T>>::effective_fee_rate(asset_id), rate);
+	}
+
+	create_exchange {
+		let investor: T::AccountId = whitelisted_caller();
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let trade_asset_id: T::AssetId = TRADE_ASSET_A_ID.into();
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, trade_asset_id, 100u32.into());
+
+	}: _(RawOrigin::Signed(investor.clone()), trade_asset_id, curve, 1u32.into(), 100u32.into(), 100u32.into())
+	verify {
+		assert_eq!(<Cennzx<T>>::exchange_curve((core_asset_id, trade_asset_id)), curve);
+		assert_eq!(
+			<Cennzx<T>>::liquidity_balance((core_asset_id, trade_asset_id), &investor), 200u32.into()
+		);
+	}
+
+	set_protocol_fee_share {
+		let share = FeeRate::<PerMillion>::from(166_667u128);
+	}: _(RawOrigin::Root, Some(share))
+	verify {
+		assert_eq!(<Cennzx<T>>::protocol_fee_share(), Some(share));
+	}
+
+	fund_reward_program {
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+		let _ = T::MultiCurrency::deposit_creating(&T::TreasuryPalletId::get().into_account(), asset_b, 10_000u32.into());
+		let end_block = <frame_system::Pallet<T>>::block_number() + 100u32.into();
+
+	}: _(RawOrigin::Root, asset_a, asset_b, 10u32.into(), end_block)
+	verify {
+		let exchange_key = (<Cennzx<T>>::core_asset_id(), asset_a);
+		assert!(<Cennzx<T>>::reward_program(exchange_key).is_some());
+		assert_eq!(T::MultiCurrency::free_balance(&<Cennzx<T>>::rewards_account(&exchange_key), asset_b), 1000u32.into());
+	}
+
+	claim_rewards {
+		let investor: T::AccountId = whitelisted_caller();
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 100u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&T::TreasuryPalletId::get().into_account(), asset_b, 10_000u32.into());
+
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_a, 10u32.into(), 20u32.into(), 20u32.into());
+		let now = <frame_system::Pallet<T>>::block_number();
+		let _ = <Cennzx<T>>::fund_reward_program(RawOrigin::Root.into(), asset_a, asset_b, 10u32.into(), now + 100u32.into());
+		<frame_system::Pallet<T>>::set_block_number(now + 10u32.into());
+
+	}: _(RawOrigin::Signed(investor.clone()), asset_a)
+	verify {
+		assert_eq!(T::MultiCurrency::free_balance(&investor, asset_b), 100u32.into());
+	}
+
+	flash_swap {
+		let investor: T::AccountId = whitelisted_caller();
+		let borrower = T::AddressMapping::into_account_id(H160::from_low_u64_be(1));
+		let target = T::AddressMapping::into_account_id(H160::zero());
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&target, asset_a, 10u32.into());
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor).into(), asset_a, 10u32.into(), 1000u32.into(), 1000u32.into());
+
+	}: _(RawOrigin::Signed(borrower), asset_a, 500u32.into(), asset_a, H160::zero(), vec![], 21_000)
+	verify {
+		// repaid 500 plus the 0.3% fee
+		assert_eq!(T::MultiCurrency::free_balance(&target, asset_a), 8u32.into());
+	}
+
+	place_order {
+		let seller: T::AccountId = whitelisted_caller();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+		let _ = T::MultiCurrency::deposit_creating(&seller, asset_a, 100u32.into());
+
+	}: _(RawOrigin::Signed(seller.clone()), asset_a, asset_b, 20u32.into(), 5u32.into(), 100u32.into())
+	verify {
+		assert!(<Cennzx<T>>::orders(0).is_some());
+		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_a), 80u32.into());
+	}
+
+	cancel_order {
+		let seller: T::AccountId = whitelisted_caller();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+		let _ = T::MultiCurrency::deposit_creating(&seller, asset_a, 100u32.into());
+		let _ = <Cennzx<T>>::place_order(RawOrigin::Signed(seller.clone()).into(), asset_a, asset_b, 20u32.into(), 5u32.into(), 100u32.into());
+
+	}: _(RawOrigin::Signed(seller.clone()), 0)
+	verify {
+		assert!(<Cennzx<T>>::orders(0).is_none());
+		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_a), 100u32.into());
+	}
+
+	fill_order {
+		let investor: T::AccountId = whitelisted_caller();
+		let seller: T::AccountId = account("seller", 0, 0);
+		let keeper: T::AccountId = account("keeper", 0, 0);
+
+		let core_asset_id = <Cennzx<T>>::core_asset_id();
+		let asset_a: T::AssetId = TRADE_ASSET_A_ID.into();
+		let asset_b: T::AssetId = TRADE_ASSET_B_ID.into();
+
+		let _ = T::MultiCurrency::deposit_creating(&investor, core_asset_id, 1000u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_a, 200u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&investor, asset_b, 300u32.into());
+		let _ = T::MultiCurrency::deposit_creating(&seller, asset_a, 100u32.into());
+
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_a, 20u32.into(), 20u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::add_liquidity(RawOrigin::Signed(investor.clone()).into(), asset_b, 30u32.into(), 30u32.into(), 100u32.into());
+		let _ = <Cennzx<T>>::place_order(RawOrigin::Signed(seller.clone()).into(), asset_a, asset_b, 20u32.into(), 5u32.into(), 100u32.into());
+
+	}: _(RawOrigin::Signed(keeper.clone()), 0, 20u32.into())
+	verify {
+		assert!(<Cennzx<T>>::orders(0).is_none());
+		assert_eq!(T::MultiCurrency::free_balance(&seller, asset_a), 80u32.into());
+	}
 }
 
 #[cfg(test)]
@@ -138,6 +359,34 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn buy_asset_via_path() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_buy_asset_via_path::<Test>());
+		});
+	}
+
+	#[test]
+	fn sell_asset_via_path() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_sell_asset_via_path::<Test>());
+		});
+	}
+
+	#[test]
+	fn buy_asset_stable_swap() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_buy_asset_stable_swap::<Test>());
+		});
+	}
+
+	#[test]
+	fn sell_asset_stable_swap() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_sell_asset_stable_swap::<Test>());
+		});
+	}
+
 	#[test]
 	fn add_liquidity() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -158,4 +407,67 @@ mod tests {
 			assert_ok!(test_benchmark_set_fee_rate::<Test>());
 		});
 	}
+
+	#[test]
+	fn set_exchange_fee_rate() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_exchange_fee_rate::<Test>());
+		});
+	}
+
+	#[test]
+	fn create_exchange() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_create_exchange::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_protocol_fee_share() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_protocol_fee_share::<Test>());
+		});
+	}
+
+	#[test]
+	fn fund_reward_program() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_fund_reward_program::<Test>());
+		});
+	}
+
+	#[test]
+	fn claim_rewards() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_claim_rewards::<Test>());
+		});
+	}
+
+	#[test]
+	fn flash_swap() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_flash_swap::<Test>());
+		});
+	}
+
+	#[test]
+	fn place_order() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_place_order::<Test>());
+		});
+	}
+
+	#[test]
+	fn cancel_order() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_order::<Test>());
+		});
+	}
+
+	#[test]
+	fn fill_order() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_fill_order::<Test>());
+		});
+	}
 }
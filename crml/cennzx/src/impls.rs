@@ -15,12 +15,17 @@
 //!
 //! Extra CENNZX-Spot traits + implementations
 //!
-use crate::{weights::WeightInfo, Config, Module};
+use crate::{weights::WeightInfo, Config, Error, Module};
 use cennznet_primitives::{traits::BuyFeeAsset, types::FeeExchange};
-use crml_support::MultiCurrency;
-use frame_support::{dispatch::DispatchError, weights::Weight};
+use crml_support::{Exchange, FlashSwap, MultiCurrency, OnAssetTransferSubscriber, PriceOracle, U256};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::Get,
+	transactional,
+	weights::Weight,
+};
 use sp_core::crypto::{UncheckedFrom, UncheckedInto};
-use sp_runtime::traits::Hash;
+use sp_runtime::traits::{Hash, One};
 use sp_std::{marker::PhantomData, prelude::*};
 
 /// A function that generates an `AccountId` for a CENNZX exchange / (core, asset) pair
@@ -60,8 +65,9 @@ where
 
 impl<T: Config> BuyFeeAsset for Module<T> {
 	type AccountId = T::AccountId;
+	type AssetId = T::AssetId;
 	type Balance = T::Balance;
-	type FeeExchange = FeeExchange<T::AssetId, T::Balance>;
+	type FeeExchange = FeeExchange<T::AssetId, T::Balance, T::AccountId>;
 
 	/// Use CENNZX to seamlessly buy fee asset
 	fn buy_fee_asset(
@@ -69,17 +75,39 @@ impl<T: Config> BuyFeeAsset for Module<T> {
 		amount: Self::Balance,
 		exchange_op: &Self::FeeExchange,
 	) -> Result<Self::Balance, DispatchError> {
-		let fee_exchange_asset_id = exchange_op.asset_id();
+		// sponsored fees are paid by the sponsor and never exchanged
+		let (fee_exchange_asset_id, max_payment) = match exchange_op {
+			FeeExchange::V1(x) => (x.asset_id, x.max_payment),
+			FeeExchange::Sponsored(_) => return Err(Error::<T>::InvalidAssetId.into()),
+		};
 		let fee_asset_id = <T::MultiCurrency as MultiCurrency>::fee_currency();
 
-		Self::execute_buy(
-			&who,
-			&who,
-			fee_exchange_asset_id,
-			fee_asset_id,
-			amount,
-			exchange_op.max_payment(),
-		)
+		Self::execute_buy(&who, &who, fee_exchange_asset_id, fee_asset_id, amount, max_payment)
+	}
+
+	/// Use CENNZX to sell a fee refund back to the asset the fee was bought with
+	#[transactional]
+	fn sell_fee_asset(
+		who: &Self::AccountId,
+		amount: Self::Balance,
+		asset_id: Self::AssetId,
+		min_buy: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		let fee_asset_id = <T::MultiCurrency as MultiCurrency>::fee_currency();
+		Self::execute_sell(&who, &who, fee_asset_id, asset_id, amount, min_buy.max(One::one()))
+	}
+
+	/// Price the fee asset as `buy_fee_asset` would, the fee asset is priced in itself without an exchange
+	fn buy_fee_asset_price(asset_id: Self::AssetId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		let fee_asset_id = <T::MultiCurrency as MultiCurrency>::fee_currency();
+		if asset_id == fee_asset_id {
+			return Ok(amount);
+		}
+		Self::get_buy_price(fee_asset_id, amount, asset_id)
+	}
+
+	fn fee_asset_id() -> Self::AssetId {
+		<T::MultiCurrency as MultiCurrency>::fee_currency()
 	}
 
 	fn buy_fee_weight() -> Weight {
@@ -87,11 +115,171 @@ impl<T: Config> BuyFeeAsset for Module<T> {
 	}
 }
 
+impl<T: Config> OnAssetTransferSubscriber<T::AccountId, T::AssetId> for Module<T> {
+	/// Accrue both sides' rewards on the liquidity they held before it moves
+	fn on_before_asset_transfer(asset_id: T::AssetId, from: &T::AccountId, to: &T::AccountId) {
+		if let Some(exchange_key) = Module::<T>::liquidity_asset_exchange(asset_id) {
+			Module::<T>::accrue_rewards(&exchange_key, from);
+			Module::<T>::accrue_rewards(&exchange_key, to);
+		}
+	}
+
+	/// Rewards accrue on the liquidity both sides hold after it moved
+	fn on_asset_transfer(asset_id: T::AssetId, from: &T::AccountId, to: &T::AccountId) {
+		if let Some(exchange_key) = Module::<T>::liquidity_asset_exchange(asset_id) {
+			Module::<T>::checkpoint_reward_stake(&exchange_key, from);
+			Module::<T>::checkpoint_reward_stake(&exchange_key, to);
+		}
+	}
+
+	fn on_asset_transfer_weight() -> Weight {
+		// each hook reads the liquidity asset's exchange, then for both accounts:
+		// before: the reward program (updated), total liquidity, liquidity balance (3) and reward stake (updated)
+		// after: the liquidity balance (3) and reward stake (updated)
+		T::DbWeight::get().reads_writes(2 + 2 * (6 + 4), 2 * (2 + 1))
+	}
+}
+
+impl<T: Config> PriceOracle for Module<T> {
+	type AssetId = T::AssetId;
+	type BlockNumber = T::BlockNumber;
+
+	/// Use the CENNZX exchange price observations as an oracle
+	fn twap(asset_id: Self::AssetId, window: Self::BlockNumber) -> Option<(U256, U256)> {
+		Module::<T>::twap(asset_id, window)
+	}
+
+	fn twap_weight() -> Weight {
+		// at each end of the window: the cursor, latest and oldest observations and a binary search of the rest
+		let search_reads = (u32::BITS - T::MaxPriceObservations::get().leading_zeros()) as Weight;
+		T::DbWeight::get().reads(2 * (3 + search_reads))
+	}
+}
+
+impl<T: Config> FlashSwap for Module<T> {
+	type AccountId = T::AccountId;
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	/// Flash swap from a CENNZX exchange, the exchange invariant is checked after the callback returns
+	fn flash_swap(
+		borrower: &Self::AccountId,
+		asset_to_borrow: Self::AssetId,
+		borrow_amount: Self::Balance,
+		asset_to_repay: Self::AssetId,
+		callback: impl FnOnce() -> DispatchResult,
+	) -> Result<Self::Balance, DispatchError> {
+		Module::<T>::execute_flash_swap(borrower, asset_to_borrow, borrow_amount, asset_to_repay, callback)
+	}
+
+	fn flash_swap_weight() -> Weight {
+		T::WeightInfo::flash_swap()
+	}
+}
+
+impl<T: Config> Exchange for Module<T> {
+	type AccountId = T::AccountId;
+	type AssetId = T::AssetId;
+	type Balance = T::Balance;
+
+	fn core_asset_id() -> Self::AssetId {
+		Module::<T>::core_asset_id()
+	}
+
+	fn reserves(asset_id: Self::AssetId) -> (Self::Balance, Self::Balance) {
+		Module::<T>::get_exchange_reserves(asset_id)
+	}
+
+	fn sell_price_via_path(
+		path: &[Self::AssetId],
+		amount_in: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError> {
+		Module::<T>::get_sell_price_via_path(path, amount_in)
+	}
+
+	fn buy_price_via_path(
+		path: &[Self::AssetId],
+		amount_out: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError> {
+		Module::<T>::get_buy_price_via_path(path, amount_out)
+	}
+
+	fn sell_via_path(
+		trader: &Self::AccountId,
+		recipient: &Self::AccountId,
+		path: &[Self::AssetId],
+		amount_in: Self::Balance,
+		min_amount_out: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError> {
+		// prices are unchanged until the trade executes
+		let amounts = Module::<T>::get_sell_price_via_path(path, amount_in)?;
+		Module::<T>::execute_sell_via_path(trader, recipient, path, amount_in, min_amount_out)?;
+		Ok(amounts)
+	}
+
+	fn buy_via_path(
+		trader: &Self::AccountId,
+		recipient: &Self::AccountId,
+		path: &[Self::AssetId],
+		amount_out: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError> {
+		let amounts = Module::<T>::get_buy_price_via_path(path, amount_out)?;
+		Module::<T>::execute_buy_via_path(trader, recipient, path, amount_out, max_amount_in)?;
+		Ok(amounts)
+	}
+
+	fn add_liquidity(
+		provider: &Self::AccountId,
+		asset_id: Self::AssetId,
+		min_liquidity: Self::Balance,
+		max_asset_amount: Self::Balance,
+		core_amount: Self::Balance,
+	) -> Result<(Self::Balance, Self::Balance), DispatchError> {
+		Module::<T>::execute_add_liquidity(provider, asset_id, min_liquidity, max_asset_amount, core_amount)
+	}
+
+	fn remove_liquidity(
+		provider: &Self::AccountId,
+		asset_id: Self::AssetId,
+		liquidity: Self::Balance,
+		min_asset_withdraw: Self::Balance,
+		min_core_withdraw: Self::Balance,
+	) -> Result<(Self::Balance, Self::Balance), DispatchError> {
+		let withdrawn = Module::<T>::execute_remove_liquidity(
+			provider,
+			asset_id,
+			liquidity,
+			min_asset_withdraw,
+			min_core_withdraw,
+		)?;
+		Ok((withdrawn.core, withdrawn.asset))
+	}
+
+	fn trade_weight() -> Weight {
+		T::WeightInfo::buy_asset_via_path().max(T::WeightInfo::sell_asset_via_path())
+	}
+
+	fn price_weight() -> Weight {
+		// each of the (at most) two hops reads the exchange reserves, fee rate, curve and, for StableSwap exchanges,
+		// the decimal places of both assets
+		T::DbWeight::get().reads(1 + 2 * 6)
+	}
+
+	fn add_liquidity_weight() -> Weight {
+		T::WeightInfo::add_liquidity()
+	}
+
+	fn remove_liquidity_weight() -> Weight {
+		T::WeightInfo::remove_liquidity()
+	}
+}
+
 #[cfg(test)]
 pub(crate) mod impl_tests {
 	use super::*;
 	use crate::{
-		mock::{Cennzx, ExtBuilder, Test, CORE_ASSET_ID, FEE_ASSET_ID, TRADE_ASSET_A_ID},
+		mock::{Cennzx, ExtBuilder, Test, CORE_ASSET_ID, FEE_ASSET_ID, TRADE_ASSET_A_ID, TRADE_ASSET_B_ID},
 		Error,
 	};
 	use cennznet_primitives::types::FeeExchange;
@@ -177,6 +365,58 @@ pub(crate) mod impl_tests {
 		});
 	}
 
+	#[test]
+	fn buy_fee_asset_price_matches_buy_fee_asset() {
+		ExtBuilder::default().build().execute_with(|| {
+			with_exchange!(CORE_ASSET_ID => 10_000, TRADE_ASSET_A_ID => 10_000);
+			with_exchange!(CORE_ASSET_ID => 10_000, FEE_ASSET_ID => 10_000);
+			let user = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 1_000);
+
+			assert_ok!(<Cennzx as BuyFeeAsset>::buy_fee_asset_price(TRADE_ASSET_A_ID, 510), 571);
+			assert_ok!(
+				<Cennzx as BuyFeeAsset>::buy_fee_asset(&user, 510, &FeeExchange::new_v1(TRADE_ASSET_A_ID, 571)),
+				571
+			);
+			// the fee asset costs itself
+			assert_ok!(<Cennzx as BuyFeeAsset>::buy_fee_asset_price(FEE_ASSET_ID, 510), 510);
+		});
+	}
+
+	#[test]
+	fn sell_fee_asset_refund() {
+		ExtBuilder::default().build().execute_with(|| {
+			with_exchange!(CORE_ASSET_ID => 10_000, TRADE_ASSET_A_ID => 10_000);
+			with_exchange!(CORE_ASSET_ID => 10_000, FEE_ASSET_ID => 10_000);
+			let user = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 1_000);
+
+			assert_ok!(
+				<Cennzx as BuyFeeAsset>::buy_fee_asset(&user, 510, &FeeExchange::new_v1(TRADE_ASSET_A_ID, 571)),
+				571
+			);
+			// refund part of the fee, it's sold back at the current prices
+			let expected = Cennzx::get_sell_price(FEE_ASSET_ID, 200, TRADE_ASSET_A_ID).unwrap();
+			// a sale below the minimum changes nothing
+			assert_err!(
+				<Cennzx as BuyFeeAsset>::sell_fee_asset(&user, 200, TRADE_ASSET_A_ID, expected + 1),
+				Error::<Test>::MinimumBuyRequirementNotMet
+			);
+			assert_balance_eq!(user, FEE_ASSET_ID => 510);
+			assert_ok!(
+				<Cennzx as BuyFeeAsset>::sell_fee_asset(&user, 200, TRADE_ASSET_A_ID, expected),
+				expected
+			);
+			assert_balance_eq!(user, FEE_ASSET_ID => 310);
+			assert_balance_eq!(user, TRADE_ASSET_A_ID => 1_000 - 571 + expected);
+
+			// a failed sale changes nothing
+			assert_err!(
+				<Cennzx as BuyFeeAsset>::sell_fee_asset(&user, 311, TRADE_ASSET_A_ID, 1),
+				Error::<Test>::InsufficientBalance
+			);
+			assert_balance_eq!(user, FEE_ASSET_ID => 310);
+		});
+	}
+
 	#[test]
 	fn buy_fee_asset_insufficient_trade_asset() {
 		ExtBuilder::default().build().execute_with(|| {
@@ -214,4 +454,46 @@ pub(crate) mod impl_tests {
 			);
 		});
 	}
+	#[test]
+	fn exchange_sell_via_path() {
+		ExtBuilder::default().build().execute_with(|| {
+			with_exchange!(CORE_ASSET_ID => 1_000, TRADE_ASSET_A_ID => 1_000);
+			with_exchange!(CORE_ASSET_ID => 1_000, TRADE_ASSET_B_ID => 1_000);
+			let user = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 100);
+			let path = [TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID];
+
+			assert_eq!(
+				<Cennzx as Exchange>::sell_price_via_path(&path, 100),
+				Ok(vec![100, 90, 81])
+			);
+			assert_eq!(
+				<Cennzx as Exchange>::sell_via_path(&user, &user, &path, 100, 81),
+				Ok(vec![100, 90, 81])
+			);
+			assert_balance_eq!(user, TRADE_ASSET_A_ID => 0);
+			assert_balance_eq!(user, TRADE_ASSET_B_ID => 81);
+			assert_eq!(<Cennzx as Exchange>::reserves(TRADE_ASSET_B_ID), (1_090, 919));
+		});
+	}
+
+	#[test]
+	fn exchange_add_and_remove_liquidity() {
+		ExtBuilder::default().build().execute_with(|| {
+			let user = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A_ID => 200);
+
+			assert_eq!(
+				<Cennzx as Exchange>::add_liquidity(&user, TRADE_ASSET_A_ID, 1, 50, 100),
+				Ok((50, 100))
+			);
+			assert_eq!(
+				<Cennzx as Exchange>::remove_liquidity(&user, TRADE_ASSET_A_ID, 40, 20, 40),
+				Ok((40, 20))
+			);
+			assert_eq!(<Cennzx as Exchange>::reserves(TRADE_ASSET_A_ID), (60, 30));
+			assert_err!(
+				<Cennzx as Exchange>::remove_liquidity(&user, TRADE_ASSET_A_ID, 61, 0, 0),
+				Error::<Test>::InsufficientLiquidity
+			);
+		});
+	}
 }
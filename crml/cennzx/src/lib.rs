@@ -20,34 +20,62 @@
 
 use codec::FullCodec;
 use core::convert::TryFrom;
-use crml_support::MultiCurrency;
+use crml_support::{AddressMappingReversibleExt, ContractCaller, MultiCurrency, H160};
 use frame_support::{
-	decl_error, decl_event, decl_module, decl_storage, pallet_prelude::*, traits::ExistenceRequirement, transactional,
-	Parameter, StorageDoubleMap,
+	decl_error, decl_event, decl_module, decl_storage,
+	pallet_prelude::*,
+	traits::{ExistenceRequirement, WithdrawReasons},
+	transactional, IterableStorageMap, PalletId, Parameter, StorageDoubleMap,
 };
 use frame_system::pallet_prelude::*;
+use pallet_evm::{AddressMapping, GasWeightMapping};
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, One, Saturating, Zero},
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedMul, CheckedSub, MaybeSerializeDeserialize, Member, One,
+		Saturating, Zero,
+	},
 	DispatchError, DispatchResult, SaturatedConversion,
 };
-use sp_std::{fmt::Debug, prelude::*};
+use sp_std::{collections::btree_set::BTreeSet, fmt::Debug, prelude::*, vec};
 
 // import `mock` first so its macros are defined in `impl` and `tests`.
 #[macro_use]
 mod mock;
 mod benchmarking;
 mod impls;
+mod migration;
 mod tests;
 mod types;
 mod weights;
 
 pub use impls::{ExchangeAddressFor, ExchangeAddressGenerator};
-pub use types::{FeeRate, HighPrecisionUnsigned, LowPrecisionUnsigned, PerMillion, PerThousand};
+pub use types::{
+	ExchangeCurve, FeeRate, HighPrecisionUnsigned, LimitOrder, LowPrecisionUnsigned, OrderId, PerMillion, PerThousand,
+	PriceObservation, Releases, RewardProgram, RewardStake, MAX_AMPLIFICATION, PRICE_SCALE, REWARD_PER_SHARE_SCALE,
+};
 use weights::WeightInfo;
 
 // (core_asset_id, asset_id)
 pub type ExchangeKey<T> = (<T as Config>::AssetId, <T as Config>::AssetId);
 
+pub type LimitOrderOf<T> = LimitOrder<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::AssetId,
+	<T as Config>::Balance,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+pub type RewardProgramOf<T> =
+	RewardProgram<<T as Config>::AssetId, <T as Config>::Balance, <T as frame_system::Config>::BlockNumber>;
+
+/// Decimal places of exchange liquidity assets
+/// The first liquidity minted in an exchange is denominated in the core asset (or the StableSwap invariant of both
+/// reserves, which shares its scale) so this matches the core asset
+pub const LIQUIDITY_ASSET_DECIMALS: u8 = 4;
+
+/// Newton's method iterations allowed when solving the StableSwap invariant
+const STABLE_SWAP_MAX_ITERATIONS: u32 = 255;
+
 /// Represents the value of an amount of liquidity in an exchange
 /// Liquidity is always traded for a combination of `core_asset` and `trade_asset`
 ///
@@ -87,6 +115,18 @@ pub trait Config: frame_system::Config {
 	type MultiCurrency: MultiCurrency<AccountId = Self::AccountId, CurrencyId = Self::AssetId, Balance = Self::Balance>;
 	/// Something which can generate addresses for exchange pools
 	type ExchangeAddressFor: ExchangeAddressFor<AccountId = Self::AccountId, AssetId = Self::AssetId>;
+	/// The treasury, receives the protocol's share of trading fees
+	type TreasuryPalletId: Get<PalletId>;
+	/// The number of price observations kept per exchange, bounds the longest TWAP window available
+	type MaxPriceObservations: Get<u32>;
+	/// The share of a limit order's proceeds paid to the keeper filling it
+	type KeeperFeeRate: Get<FeeRate<PerMillion>>;
+	/// Map evm address into ss58 address and back
+	type AddressMapping: AddressMappingReversibleExt<Self::AccountId>;
+	/// Calls flash swap callback contracts
+	type ContractCaller: ContractCaller<Address = H160>;
+	/// Convert gas to weight according to runtime config
+	type GasWeightMapping: GasWeightMapping;
 	/// Provides the public call to weight mapping
 	type WeightInfo: WeightInfo;
 }
@@ -111,6 +151,38 @@ decl_error! {
 		InvalidAssetId,
 		Overflow,
 		DivideByZero,
+		/// The protocol fee share must not exceed 100% of the trading fee
+		InvalidProtocolFeeShare,
+		/// A trade path must have at least 2 assets, each adjacent pair an exchange used at most once
+		InvalidPath,
+		/// The limit order does not exist
+		OrderNotFound,
+		/// Only the owner of a limit order may cancel it
+		NotOrderOwner,
+		/// The limit order expired
+		OrderExpired,
+		/// A limit order expiry must be a future block
+		InvalidOrderExpiry,
+		/// A fill must be non-zero and no more than the order's unfilled sell amount
+		InvalidFillAmount,
+		/// StableSwap amplification must be between 1 and `MAX_AMPLIFICATION`
+		InvalidAmplification,
+		/// An exchange can only be created while it holds no liquidity
+		ExchangeExists,
+		/// The StableSwap invariant could not be solved for the exchange reserves
+		StableSwapNotConverged,
+		/// Reward programs must emit a non-zero reward and end after the current block
+		InvalidRewardProgram,
+		/// The reward asset of an exchange's reward program can't be changed
+		RewardAssetMismatch,
+		/// Flash swaps borrow and repay in the assets of a single exchange
+		InvalidFlashSwap,
+		/// The exchange's invariant per unit of liquidity fell over the flash swap
+		FlashSwapNotRepaid,
+		/// Flash swap callbacks are called from the borrower's EVM address, which it must have
+		BorrowerNotEvmAccount,
+		/// The flash swap callback failed or reverted
+		FlashSwapCallbackFailed,
 	}
 }
 
@@ -121,6 +193,27 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::get() == Releases::V0 as u32 {
+				migration::do_v0_to_v1::<T>()
+			} else {
+				Zero::zero()
+			}
+		}
+
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			// continue the liquidity migration started by the runtime upgrade
+			if StorageVersion::get() == Releases::V0 as u32 {
+				migration::do_v0_to_v1::<T>().saturating_add(T::DbWeight::get().reads(1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::match_orders(now, remaining_weight)
+		}
+
 		/// Buy `asset_to_buy` with `asset_to_sell`.
 		/// Caller specifies an exact `buy_amount` and a `maximum_sell` amount to pay.
 		///
@@ -129,7 +222,7 @@ decl_module! {
 		/// `asset_to_buy` - asset ID to buy
 		/// `buy_amount` - The amount of `asset_to_buy` to receive
 		/// `maximum_sell` - Maximum `asset_to_sell` caller should pay
-		#[weight = T::WeightInfo::buy_asset()]
+		#[weight = T::WeightInfo::buy_asset().max(T::WeightInfo::buy_asset_stable_swap())]
 		pub fn buy_asset(
 			origin,
 			recipient: Option<T::AccountId>,
@@ -158,7 +251,7 @@ decl_module! {
 		/// `asset_to_buy` - asset ID to buy
 		/// `sell_amount` - The amount of `asset_to_sell` the caller should pay
 		/// `minimum_buy` - The minimum `asset_to_buy` to receive
-		#[weight = T::WeightInfo::sell_asset()]
+		#[weight = T::WeightInfo::sell_asset().max(T::WeightInfo::sell_asset_stable_swap())]
 		pub fn sell_asset(
 			origin,
 			recipient: Option<T::AccountId>,
@@ -179,6 +272,105 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Buy the last asset in `path` with the first, trading through each exchange along `path`.
+		/// Caller specifies an exact `buy_amount` and a `maximum_sell` amount to pay for the whole route.
+		///
+		/// `recipient` - Account to receive assets, defaults to `origin` if None
+		/// `path` - asset IDs to trade through, adjacent assets must share an exchange e.g. `[asset_a, core_asset, asset_b]`
+		/// `buy_amount` - The amount of the last asset in `path` to receive
+		/// `maximum_sell` - Maximum amount of the first asset in `path` caller should pay
+		#[weight = T::WeightInfo::buy_asset_via_path()]
+		#[transactional]
+		pub fn buy_asset_via_path(
+			origin,
+			recipient: Option<T::AccountId>,
+			path: Vec<T::AssetId>,
+			#[compact] buy_amount: T::Balance,
+			#[compact] maximum_sell: T::Balance
+		) -> DispatchResult {
+			let trader = ensure_signed(origin)?;
+			let _ = Self::execute_buy_via_path(
+				&trader,
+				&recipient.unwrap_or_else(|| trader.clone()),
+				&path,
+				buy_amount,
+				maximum_sell,
+			)?;
+			Ok(())
+		}
+
+		/// Sell the first asset in `path` for the last, trading through each exchange along `path`.
+		/// Caller specifies an exact `sell_amount` and a `minimum_buy` amount to receive for the whole route.
+		///
+		/// `recipient` - Account to receive assets, defaults to `origin` if None
+		/// `path` - asset IDs to trade through, adjacent assets must share an exchange e.g. `[asset_a, core_asset, asset_b]`
+		/// `sell_amount` - The amount of the first asset in `path` the caller should pay
+		/// `minimum_buy` - The minimum amount of the last asset in `path` to receive
+		#[weight = T::WeightInfo::sell_asset_via_path()]
+		#[transactional]
+		pub fn sell_asset_via_path(
+			origin,
+			recipient: Option<T::AccountId>,
+			path: Vec<T::AssetId>,
+			#[compact] sell_amount: T::Balance,
+			#[compact] minimum_buy: T::Balance
+		) -> DispatchResult {
+			let trader = ensure_signed(origin)?;
+			let _ = Self::execute_sell_via_path(
+				&trader,
+				&recipient.unwrap_or_else(|| trader.clone()),
+				&path,
+				sell_amount,
+				minimum_buy,
+			)?;
+			Ok(())
+		}
+
+		/// Lend `borrow_amount` of `asset_to_borrow` from an exchange to the `target` contract, call it with
+		/// `callback_input` then take repayment in `asset_to_repay` from it.
+		/// Repayment is the exchange's buy price of the loan (or the loan plus the trading fee when repaying in the
+		/// borrowed asset) and is paid by `target` once the callback returns, the exchange's invariant per unit of
+		/// liquidity must not fall otherwise the whole call is reverted.
+		/// The callback is called from the caller's EVM address so `target` can check who initiated the loan, the
+		/// caller must have an EVM address. Up to `gas_limit` callback gas is charged as weight, unused gas is
+		/// refunded.
+		///
+		/// `asset_to_borrow` - asset ID to borrow
+		/// `borrow_amount` - The amount of `asset_to_borrow` lent to `target`
+		/// `asset_to_repay` - asset ID to repay, the core asset or the trade asset of the exchange
+		/// `target` - The contract receiving the loan and the callback, it repays the loan
+		/// `callback_input` - The EVM input of the callback, it should encode the callback function selector
+		/// `gas_limit` - Gas limit of the callback
+		#[weight = T::WeightInfo::flash_swap().saturating_add(T::GasWeightMapping::gas_to_weight(*gas_limit))]
+		#[transactional]
+		pub fn flash_swap(
+			origin,
+			#[compact] asset_to_borrow: T::AssetId,
+			#[compact] borrow_amount: T::Balance,
+			#[compact] asset_to_repay: T::AssetId,
+			target: H160,
+			callback_input: Vec<u8>,
+			gas_limit: u64
+		) -> DispatchResultWithPostInfo {
+			let initiator = ensure_signed(origin)?;
+			let caller = T::AddressMapping::from_account_id(initiator.clone());
+			ensure!(T::AddressMapping::into_account_id(caller) == initiator, Error::<T>::BorrowerNotEvmAccount);
+
+			let mut used_gas = 0;
+			let _ = Self::execute_flash_swap(
+				&T::AddressMapping::into_account_id(target),
+				asset_to_borrow,
+				borrow_amount,
+				asset_to_repay,
+				|| {
+					used_gas = T::ContractCaller::call(&caller, &target, &callback_input, gas_limit)
+						.map_err(|_| Error::<T>::FlashSwapCallbackFailed)?;
+					Ok(())
+				},
+			)?;
+			Ok(Some(T::WeightInfo::flash_swap().saturating_add(T::GasWeightMapping::gas_to_weight(used_gas))).into())
+		}
+
 		/// Deposit core asset and trade asset at current ratio to mint liquidity
 		/// Returns amount of liquidity minted.
 		///
@@ -197,48 +389,7 @@ decl_module! {
 			#[compact] core_amount: T::Balance
 		) {
 			let from_account = ensure_signed(origin)?;
-			let core_asset_id = Self::core_asset_id();
-			ensure!(
-				!max_asset_amount.is_zero() && !core_amount.is_zero(),
-				Error::<T>::CannotAddLiquidityWithZero
-			);
-			ensure!(
-				T::MultiCurrency::free_balance(&from_account, core_asset_id) >= core_amount,
-				Error::<T>::InsufficientCoreAssetBalance
-			);
-			ensure!(
-				T::MultiCurrency::free_balance(&from_account, asset_id) >= max_asset_amount,
-				Error::<T>::InsufficientTradeAssetBalance
-			);
-			let exchange_key = (core_asset_id, asset_id);
-			let total_liquidity = <TotalLiquidity<T>>::get(&exchange_key);
-			let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
-			let core_asset_reserve = T::MultiCurrency::free_balance(&exchange_address, core_asset_id);
-
-			let (trade_asset_amount, liquidity_minted) = if total_liquidity.is_zero() || core_asset_reserve.is_zero() {
-				// new exchange pool
-				(max_asset_amount, core_amount)
-			} else {
-				let trade_asset_reserve = T::MultiCurrency::free_balance(&exchange_address, asset_id);
-				let trade_asset_amount = core_amount * trade_asset_reserve / core_asset_reserve + One::one();
-				let liquidity_minted = core_amount * total_liquidity / core_asset_reserve;
-
-				(trade_asset_amount, liquidity_minted)
-			};
-			ensure!(
-				liquidity_minted >= min_liquidity,
-				Error::<T>::MinimumLiquidityRequirementNotMet
-			);
-			ensure!(
-				max_asset_amount >= trade_asset_amount,
-				Error::<T>::MaximumTradeAssetRequirementNotMet
-			);
-
-			T::MultiCurrency::transfer(&from_account, &exchange_address, core_asset_id, core_amount, ExistenceRequirement::KeepAlive)?;
-			T::MultiCurrency::transfer(&from_account, &exchange_address, asset_id, trade_asset_amount, ExistenceRequirement::KeepAlive)?;
-
-			Self::mint_liquidity(&exchange_key, &from_account, liquidity_minted);
-			Self::deposit_event(Event::<T>::AddLiquidity(from_account, core_amount, asset_id, trade_asset_amount));
+			let _ = Self::execute_add_liquidity(&from_account, asset_id, min_liquidity, max_asset_amount, core_amount)?;
 		}
 
 		/// Burn exchange assets to withdraw core asset and trade asset at current ratio
@@ -257,45 +408,250 @@ decl_module! {
 			#[compact] min_core_withdraw: T::Balance
 		) -> DispatchResult {
 			let from_account = ensure_signed(origin)?;
+			let _ = Self::execute_remove_liquidity(
+				&from_account,
+				asset_id,
+				liquidity_to_withdraw,
+				min_asset_withdraw,
+				min_core_withdraw,
+			)?;
+			Ok(())
+		}
+
+		/// Set the spot exchange wide fee rate (root only)
+		#[weight = T::WeightInfo::set_fee_rate()]
+		pub fn set_fee_rate(origin, new_fee_rate: FeeRate<PerMillion>) -> DispatchResult {
+			ensure_root(origin)?;
+			DefaultFeeRate::mutate(|fee_rate| *fee_rate = new_fee_rate);
+			Ok(())
+		}
 
+		/// Override the fee rate of the `asset_id` exchange (root only)
+		/// `None` clears the override, reverting the exchange to the default fee rate
+		#[weight = T::WeightInfo::set_exchange_fee_rate()]
+		pub fn set_exchange_fee_rate(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			new_fee_rate: Option<FeeRate<PerMillion>>
+		) -> DispatchResult {
+			ensure_root(origin)?;
 			let core_asset_id = Self::core_asset_id();
+			ensure!(asset_id != core_asset_id, Error::<T>::InvalidAssetId);
 			let exchange_key = (core_asset_id, asset_id);
-			let account_liquidity = <LiquidityBalance<T>>::get(&exchange_key, &from_account);
-			ensure!(
-				account_liquidity >= liquidity_to_withdraw,
-				Error::<T>::InsufficientLiquidity
-			);
+			match new_fee_rate {
+				Some(fee_rate) => <ExchangeFeeRate<T>>::insert(&exchange_key, fee_rate),
+				None => <ExchangeFeeRate<T>>::remove(&exchange_key),
+			}
+			Self::deposit_event(Event::<T>::ExchangeFeeRateUpdated(asset_id, new_fee_rate));
+			Ok(())
+		}
 
-			let withdraw_value = Self::liquidity_value(asset_id, liquidity_to_withdraw);
-			let total_liquidity = <TotalLiquidity<T>>::get(&exchange_key);
+		/// Create the `asset_id` exchange priced by `curve` with its first deposit of liquidity
+		/// Returns amount of liquidity minted.
+		/// An exchange keeps its curve, `add_liquidity` into an exchange never created uses the constant product curve.
+		/// An exchange may be created again with another curve once all of its liquidity is withdrawn.
+		///
+		/// `origin`
+		/// `asset_id` - The trade asset ID
+		/// `curve` - The pricing curve of the exchange
+		/// `min_liquidity` - The minimum liquidity to add
+		/// `asset_amount` - Amount of trade asset to add
+		/// `core_amount` - Amount of core asset to add
+		#[weight = T::WeightInfo::create_exchange()]
+		#[transactional]
+		pub fn create_exchange(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			curve: ExchangeCurve,
+			#[compact] min_liquidity: T::Balance,
+			#[compact] max_asset_amount: T::Balance,
+			#[compact] core_amount: T::Balance
+		) {
+			let from_account = ensure_signed(origin)?;
+			let core_asset_id = Self::core_asset_id();
+			ensure!(asset_id != core_asset_id, Error::<T>::InvalidAssetId);
+			if let ExchangeCurve::StableSwap { amplification } = curve {
+				ensure!(
+					amplification > 0 && amplification <= MAX_AMPLIFICATION,
+					Error::<T>::InvalidAmplification
+				);
+			}
+			let exchange_key = (core_asset_id, asset_id);
+			ensure!(<TotalLiquidity<T>>::get(&exchange_key).is_zero(), Error::<T>::ExchangeExists);
+			<ExchangeCurves<T>>::insert(&exchange_key, curve);
+			Self::deposit_event(Event::<T>::ExchangeCreated(asset_id, curve));
+			let _ = Self::execute_add_liquidity(&from_account, asset_id, min_liquidity, max_asset_amount, core_amount)?;
+		}
 
+		/// Fund a reward program for the liquidity providers of the `asset_id` exchange from the treasury (root only)
+		/// `reward_per_block` of `reward_asset` is shared pro-rata between liquidity providers until `end_block`.
+		/// Funding an exchange with a running program replaces its emission rate and end block, the treasury pays or
+		/// is refunded the difference in rewards yet to be emitted.
+		#[weight = T::WeightInfo::fund_reward_program()]
+		#[transactional]
+		pub fn fund_reward_program(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			#[compact] reward_asset: T::AssetId,
+			#[compact] reward_per_block: T::Balance,
+			end_block: T::BlockNumber
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let core_asset_id = Self::core_asset_id();
+			ensure!(asset_id != core_asset_id, Error::<T>::InvalidAssetId);
+			let now = <frame_system::Pallet<T>>::block_number();
 			ensure!(
-				total_liquidity > Zero::zero(),
-				Error::<T>::EmptyExchangePool
-			);
-			ensure!(
-				withdraw_value.core >= min_core_withdraw,
-				Error::<T>::MinimumCoreAssetRequirementNotMet
-			);
-			ensure!(
-				withdraw_value.asset >= min_asset_withdraw,
-				Error::<T>::MinimumTradeAssetRequirementNotMet
+				end_block > now && !reward_per_block.is_zero(),
+				Error::<T>::InvalidRewardProgram
 			);
-			let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
-			T::MultiCurrency::transfer(&exchange_address, &from_account, core_asset_id, withdraw_value.core, ExistenceRequirement::KeepAlive)?;
-			T::MultiCurrency::transfer(&exchange_address, &from_account, asset_id, withdraw_value.asset, ExistenceRequirement::KeepAlive)?;
-			Self::burn_liquidity(&exchange_key, &from_account, liquidity_to_withdraw);
-			Self::deposit_event(Event::<T>::RemoveLiquidity(from_account, withdraw_value.core, asset_id, withdraw_value.asset));
+
+			let exchange_key = (core_asset_id, asset_id);
+			let (reward_per_share, unemitted) = match Self::update_reward_program(&exchange_key) {
+				Some(program) => {
+					ensure!(program.reward_asset == reward_asset, Error::<T>::RewardAssetMismatch);
+					let unemitted = Self::rewards_over(program.reward_per_block, program.end_block.saturating_sub(now))?;
+					(program.reward_per_share, unemitted)
+				}
+				None => (Zero::zero(), Zero::zero()),
+			};
+			let rewards = Self::rewards_over(reward_per_block, end_block - now)?;
+
+			let treasury = T::TreasuryPalletId::get().into_account();
+			let rewards_account = Self::rewards_account(&exchange_key);
+			if rewards > unemitted {
+				T::MultiCurrency::transfer(&treasury, &rewards_account, reward_asset, rewards - unemitted, ExistenceRequirement::KeepAlive)?;
+			} else if unemitted > rewards {
+				T::MultiCurrency::transfer(&rewards_account, &treasury, reward_asset, unemitted - rewards, ExistenceRequirement::AllowDeath)?;
+			}
+
+			<RewardPrograms<T>>::insert(&exchange_key, RewardProgram {
+				reward_asset,
+				reward_per_block,
+				end_block,
+				last_update_block: now,
+				reward_per_share,
+			});
+			Self::deposit_event(Event::<T>::RewardProgramFunded(asset_id, reward_asset, reward_per_block, end_block));
 			Ok(())
 		}
 
-		/// Set the spot exchange wide fee rate (root only)
-		#[weight = T::WeightInfo::set_fee_rate()]
-		pub fn set_fee_rate(origin, new_fee_rate: FeeRate<PerMillion>) -> DispatchResult {
+		/// Claim the caller's rewards from the reward program of the `asset_id` exchange
+		/// Rewards accrue on the liquidity held, they are checkpointed whenever the caller's liquidity is minted, burnt or
+		/// transferred.
+		#[weight = T::WeightInfo::claim_rewards()]
+		#[transactional]
+		pub fn claim_rewards(origin, #[compact] asset_id: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let exchange_key = (Self::core_asset_id(), asset_id);
+			Self::accrue_rewards(&exchange_key, &who);
+			Self::checkpoint_reward_stake(&exchange_key, &who);
+
+			let program = match Self::reward_program(&exchange_key) {
+				Some(program) => program,
+				None => return Ok(()),
+			};
+			let reward = <RewardStakes<T>>::mutate(&exchange_key, &who, |stake| sp_std::mem::take(&mut stake.pending));
+			if !reward.is_zero() {
+				T::MultiCurrency::transfer(
+					&Self::rewards_account(&exchange_key),
+					&who,
+					program.reward_asset,
+					reward,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				Self::deposit_event(Event::<T>::RewardsClaimed(who, asset_id, program.reward_asset, reward));
+			}
+			Ok(())
+		}
+
+		/// Set the share of trading fees paid to the treasury rather than liquidity providers (root only)
+		/// The share is a fraction of the fee (e.g. 166_667 takes 1/6th of it), `None` disables the protocol fee
+		#[weight = T::WeightInfo::set_protocol_fee_share()]
+		pub fn set_protocol_fee_share(origin, new_share: Option<FeeRate<PerMillion>>) -> DispatchResult {
 			ensure_root(origin)?;
-			DefaultFeeRate::mutate(|fee_rate| *fee_rate = new_fee_rate);
+			match new_share {
+				Some(share) => {
+					ensure!(
+						LowPrecisionUnsigned::from(share) <= LowPrecisionUnsigned::from(FeeRate::<PerMillion>::one()),
+						Error::<T>::InvalidProtocolFeeShare
+					);
+					ProtocolFeeShare::put(share);
+				}
+				None => ProtocolFeeShare::kill(),
+			}
+			Self::deposit_event(Event::<T>::ProtocolFeeShareUpdated(new_share));
+			Ok(())
+		}
+
+		/// Place a limit order to sell `sell_amount` of `asset_to_sell` for at least `minimum_buy` of `asset_to_buy`
+		/// The sell amount is reserved until the order is filled, cancelled or expires.
+		/// Once the exchange price meets the limit any keeper may fill the order with `fill_order`, otherwise
+		/// open orders are matched in full with spare block weight
+		///
+		/// `asset_to_sell` - asset ID to sell
+		/// `asset_to_buy` - asset ID to buy
+		/// `sell_amount` - The amount of `asset_to_sell` to sell
+		/// `minimum_buy` - The minimum `asset_to_buy` to receive for all of `sell_amount`
+		/// `expiry` - The last block the order may be filled
+		#[weight = T::WeightInfo::place_order()]
+		#[transactional]
+		pub fn place_order(
+			origin,
+			#[compact] asset_to_sell: T::AssetId,
+			#[compact] asset_to_buy: T::AssetId,
+			#[compact] sell_amount: T::Balance,
+			#[compact] minimum_buy: T::Balance,
+			expiry: T::BlockNumber
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(asset_to_sell != asset_to_buy, Error::<T>::AssetCannotSwapForItself);
+			ensure!(!sell_amount.is_zero() && !minimum_buy.is_zero(), Error::<T>::CannotTradeZero);
+			ensure!(expiry > <frame_system::Pallet<T>>::block_number(), Error::<T>::InvalidOrderExpiry);
+			let order_id = Self::next_order_id();
+			let next_order_id = order_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+			T::MultiCurrency::reserve(&owner, asset_to_sell, sell_amount)?;
+			NextOrderId::put(next_order_id);
+			<Orders<T>>::insert(order_id, LimitOrder {
+				owner: owner.clone(),
+				asset_to_sell,
+				asset_to_buy,
+				sell_amount,
+				minimum_buy,
+				expiry,
+			});
+			Self::deposit_event(Event::<T>::OrderPlaced(order_id, owner, asset_to_sell, asset_to_buy, sell_amount, minimum_buy, expiry));
+			Ok(())
+		}
+
+		/// Cancel a limit order, returning its unfilled sell amount to the owner (owner only)
+		#[weight = T::WeightInfo::cancel_order()]
+		#[transactional]
+		pub fn cancel_order(origin, #[compact] order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+
+			<Orders<T>>::remove(order_id);
+			let _ = T::MultiCurrency::unreserve(&order.owner, order.asset_to_sell, order.sell_amount);
+			Self::deposit_event(Event::<T>::OrderCancelled(order_id));
 			Ok(())
 		}
+
+		/// Fill `fill_amount` of a limit order at the current exchange price
+		/// The caller is paid `KeeperFeeRate` of the proceeds, the fill fails if the owner would receive less than
+		/// the order's limit price
+		///
+		/// `order_id` - The limit order to fill
+		/// `fill_amount` - The amount of the order's sell asset to sell, up to its unfilled sell amount
+		#[weight = T::WeightInfo::fill_order()]
+		#[transactional]
+		pub fn fill_order(origin, #[compact] order_id: OrderId, #[compact] fill_amount: T::Balance) -> DispatchResult {
+			let keeper = ensure_signed(origin)?;
+			let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(<frame_system::Pallet<T>>::block_number() <= order.expiry, Error::<T>::OrderExpired);
+			Self::execute_order(order_id, order, fill_amount, Some(&keeper))
+		}
 	}
 }
 
@@ -304,6 +660,7 @@ decl_event! {
 		AccountId = <T as frame_system::Config>::AccountId,
 		AssetId = <T as Config>::AssetId,
 		Balance = <T as Config>::Balance,
+		BlockNumber = <T as frame_system::Config>::BlockNumber,
 	{
 		/// Provider, core asset amount, trade asset id, trade asset amount
 		AddLiquidity(AccountId, Balance, AssetId, Balance),
@@ -313,6 +670,30 @@ decl_event! {
 		AssetBought(AssetId, AssetId, AccountId, Balance, Balance),
 		/// AssetSold, AssetBought, Buyer, SoldAmount, BoughtAmount
 		AssetSold(AssetId, AssetId, AccountId, Balance, Balance),
+		/// Trade asset id, new fee rate override (None if cleared)
+		ExchangeFeeRateUpdated(AssetId, Option<FeeRate<PerMillion>>),
+		/// New protocol fee share (None if disabled)
+		ProtocolFeeShareUpdated(Option<FeeRate<PerMillion>>),
+		/// Exchange trade asset id, fee asset id, fee amount paid to the treasury
+		ProtocolFeeCollected(AssetId, AssetId, Balance),
+		/// Exchange trade asset id, liquidity asset id
+		LiquidityAssetCreated(AssetId, AssetId),
+		/// Order id, owner, asset to sell, asset to buy, sell amount, minimum buy, expiry block
+		OrderPlaced(OrderId, AccountId, AssetId, AssetId, Balance, Balance, BlockNumber),
+		/// Order id, amount sold, amount bought for the owner, keeper fee
+		OrderFilled(OrderId, Balance, Balance, Balance),
+		/// Order id
+		OrderCancelled(OrderId),
+		/// Order id
+		OrderExpired(OrderId),
+		/// Trade asset id, exchange curve
+		ExchangeCreated(AssetId, ExchangeCurve),
+		/// Trade asset id, reward asset id, reward per block, end block
+		RewardProgramFunded(AssetId, AssetId, Balance, BlockNumber),
+		/// Provider, trade asset id, reward asset id, reward amount
+		RewardsClaimed(AccountId, AssetId, AssetId, Balance),
+		/// Borrower, asset borrowed, amount borrowed, asset repaid, amount repaid
+		FlashSwap(AccountId, AssetId, Balance, AssetId, Balance),
 	}
 }
 
@@ -323,12 +704,37 @@ decl_storage! {
 		pub CoreAssetId get(fn core_asset_id) config(): T::AssetId;
 		/// Default trading fee rate
 		pub DefaultFeeRate get(fn fee_rate) config(): FeeRate<PerMillion>;
+		/// Trading fee rate overrides per exchange, exchanges without one use `DefaultFeeRate`
+		pub ExchangeFeeRate get(fn exchange_fee_rate): map hasher(twox_64_concat) ExchangeKey<T> => Option<FeeRate<PerMillion>>;
+		/// The pricing curve of each exchange, constant product unless set at creation
+		pub ExchangeCurves get(fn exchange_curve): map hasher(twox_64_concat) ExchangeKey<T> => ExchangeCurve;
+		/// The liquidity provider reward program of each exchange
+		pub RewardPrograms get(fn reward_program): map hasher(twox_64_concat) ExchangeKey<T> => Option<RewardProgramOf<T>>;
+		/// Each liquidity provider's position in the reward program of an exchange
+		pub RewardStakes get(fn reward_stake): double_map hasher(twox_64_concat) ExchangeKey<T>, hasher(blake2_128_concat) T::AccountId => RewardStake<T::Balance>;
+		/// Share of each trading fee paid to the treasury, the remainder stays with liquidity providers
+		pub ProtocolFeeShare get(fn protocol_fee_share): Option<FeeRate<PerMillion>>;
+		/// Ring buffer of cumulative price observations per exchange.
+		/// Key: `(core_asset_id, trade_asset_id), index`
+		pub PriceObservations get(fn price_observations): double_map hasher(twox_64_concat) ExchangeKey<T>, hasher(twox_64_concat) u32 => Option<PriceObservation<T::BlockNumber>>;
+		/// Index of the latest observation in `PriceObservations` and the number of observations held, per exchange
+		pub PriceObservationCursor get(fn price_observation_cursor): map hasher(twox_64_concat) ExchangeKey<T> => (u32, u32);
 		/// Total liquidity holdings of all investors in an exchange.
-		/// ie/ total_liquidity(exchange) == sum(liquidity_balance(exchange, user)) at all times
+		/// ie/ total_liquidity(exchange) == total issuance of the exchange liquidity asset at all times
 		pub TotalLiquidity get(fn total_liquidity): map hasher(twox_64_concat) ExchangeKey<T> => T::Balance;
-		/// Liquidity holdings of a user in an exchange pool.
-		/// Key: `(core_asset_id, trade_asset_id), account_id`
-		pub LiquidityBalance get(fn liquidity_balance): double_map hasher(twox_64_concat) ExchangeKey<T>, hasher(blake2_128_concat) T::AccountId => T::Balance;
+		/// The generic asset representing liquidity holdings in an exchange pool, created with its first liquidity
+		pub LiquidityAssetId get(fn liquidity_asset_id): map hasher(twox_64_concat) ExchangeKey<T> => Option<T::AssetId>;
+		/// The exchange of each liquidity asset, the reverse of `LiquidityAssetId`
+		pub LiquidityAssetExchange get(fn liquidity_asset_exchange): map hasher(twox_64_concat) T::AssetId => Option<ExchangeKey<T>>;
+		/// The next available limit order Id
+		pub NextOrderId get(fn next_order_id): OrderId;
+		/// Open limit orders
+		pub Orders get(fn orders): map hasher(twox_64_concat) OrderId => Option<LimitOrderOf<T>>;
+		/// The `Orders` key of the last limit order checked when matching orders with spare block weight
+		/// `None` starts from the first order
+		pub OrderMatchCursor get(fn order_match_cursor): Option<Vec<u8>>;
+		/// Version of this module's storage schema
+		StorageVersion build(|_: &GenesisConfig<T>| Releases::V1 as u32): u32;
 	}
 }
 
@@ -338,21 +744,226 @@ impl<T: Config> Module<T> {
 	// Liquidity
 	//
 
+	/// Liquidity holdings of a user in an exchange pool i.e. their balance of its liquidity asset
+	/// Includes any holding yet to be migrated from the v0 `LiquidityBalance` map
+	pub fn liquidity_balance(exchange_key: ExchangeKey<T>, who: &T::AccountId) -> T::Balance {
+		let unmigrated = migration::storage_v0::LiquidityBalance::<T>::get(exchange_key, who);
+		match Self::liquidity_asset_id(exchange_key) {
+			Some(liquidity_asset_id) => {
+				T::MultiCurrency::free_balance(who, liquidity_asset_id).saturating_add(unmigrated)
+			}
+			None => unmigrated,
+		}
+	}
+
+	/// Get the liquidity asset of the `asset_id` exchange, creating it if this is the exchange's first liquidity
+	/// The exchange account owns the asset
+	pub(crate) fn get_or_create_liquidity_asset(asset_id: T::AssetId) -> Result<T::AssetId, DispatchError> {
+		let exchange_key = (Self::core_asset_id(), asset_id);
+		if let Some(liquidity_asset_id) = Self::liquidity_asset_id(exchange_key) {
+			return Ok(liquidity_asset_id);
+		}
+		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
+		let liquidity_asset_id = T::MultiCurrency::create(
+			&exchange_address,
+			Zero::zero(),
+			LIQUIDITY_ASSET_DECIMALS,
+			1,
+			Self::liquidity_asset_symbol(asset_id),
+		)?;
+		<LiquidityAssetId<T>>::insert(exchange_key, liquidity_asset_id);
+		<LiquidityAssetExchange<T>>::insert(liquidity_asset_id, exchange_key);
+		Self::deposit_event(Event::<T>::LiquidityAssetCreated(asset_id, liquidity_asset_id));
+		Ok(liquidity_asset_id)
+	}
+
+	/// The liquidity asset symbol of the `asset_id` exchange e.g. "CENNZX-LP-16000"
+	fn liquidity_asset_symbol(asset_id: T::AssetId) -> Vec<u8> {
+		let mut id: u64 = asset_id.into();
+		let mut digits = Vec::new();
+		loop {
+			digits.push(b'0' + (id % 10) as u8);
+			id /= 10;
+			if id == 0 {
+				break;
+			}
+		}
+		let mut symbol = b"CENNZX-LP-".to_vec();
+		symbol.extend(digits.iter().rev());
+		symbol
+	}
+
 	/// Mint liquidity holdings for a user in a specified exchange
-	fn mint_liquidity(exchange_key: &ExchangeKey<T>, who: &T::AccountId, increase: T::Balance) {
-		let balance = <LiquidityBalance<T>>::get(exchange_key, who);
-		let new_balance = balance.saturating_add(increase);
-		<LiquidityBalance<T>>::insert(exchange_key, who, new_balance);
+	fn mint_liquidity(exchange_key: &ExchangeKey<T>, who: &T::AccountId, increase: T::Balance) -> DispatchResult {
+		let liquidity_asset_id = Self::get_or_create_liquidity_asset(exchange_key.1)?;
+		Self::accrue_rewards(exchange_key, who);
+		// dropping the imbalance increases the liquidity asset issuance, in step with `TotalLiquidity`
+		let _ = T::MultiCurrency::deposit_creating(who, liquidity_asset_id, increase);
 		<TotalLiquidity<T>>::mutate(exchange_key, |balance| *balance = balance.saturating_add(increase));
+		Self::checkpoint_reward_stake(exchange_key, who);
+		Ok(())
 	}
 
 	/// Burn liquidity holdings from a user in a specified exchange
-	fn burn_liquidity(exchange_key: &ExchangeKey<T>, who: &T::AccountId, decrease: T::Balance) {
-		let balance = <LiquidityBalance<T>>::get(exchange_key, who);
-		let decrease = decrease.min(balance);
-		let new_balance = balance - decrease;
-		<LiquidityBalance<T>>::insert(exchange_key, who, new_balance);
+	fn burn_liquidity(exchange_key: &ExchangeKey<T>, who: &T::AccountId, decrease: T::Balance) -> DispatchResult {
+		let liquidity_asset_id = Self::liquidity_asset_id(exchange_key).ok_or(Error::<T>::InsufficientLiquidity)?;
+		Self::accrue_rewards(exchange_key, who);
+		// dropping the imbalance decreases the liquidity asset issuance, in step with `TotalLiquidity`
+		let _ = T::MultiCurrency::withdraw(
+			who,
+			liquidity_asset_id,
+			decrease,
+			WithdrawReasons::all(),
+			ExistenceRequirement::AllowDeath,
+		)?;
 		<TotalLiquidity<T>>::mutate(exchange_key, |balance| *balance = balance.saturating_sub(decrease));
+		Self::checkpoint_reward_stake(exchange_key, who);
+		Ok(())
+	}
+
+	/// Deposit core asset and trade asset at current ratio to mint liquidity for `provider`
+	/// Returns the amount of trade asset deposited and liquidity minted
+	///
+	/// `asset_id` - The trade asset ID
+	/// `min_liquidity` - The minimum liquidity to add
+	/// `max_asset_amount` - Maximum amount of trade asset to add
+	/// `core_amount` - Amount of core asset to add
+	#[transactional]
+	pub fn execute_add_liquidity(
+		provider: &T::AccountId,
+		asset_id: T::AssetId,
+		min_liquidity: T::Balance,
+		max_asset_amount: T::Balance,
+		core_amount: T::Balance,
+	) -> sp_std::result::Result<(T::Balance, T::Balance), DispatchError> {
+		let core_asset_id = Self::core_asset_id();
+		ensure!(
+			!max_asset_amount.is_zero() && !core_amount.is_zero(),
+			Error::<T>::CannotAddLiquidityWithZero
+		);
+		ensure!(
+			T::MultiCurrency::free_balance(provider, core_asset_id) >= core_amount,
+			Error::<T>::InsufficientCoreAssetBalance
+		);
+		ensure!(
+			T::MultiCurrency::free_balance(provider, asset_id) >= max_asset_amount,
+			Error::<T>::InsufficientTradeAssetBalance
+		);
+		let exchange_key = (core_asset_id, asset_id);
+		let total_liquidity = <TotalLiquidity<T>>::get(&exchange_key);
+		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
+		let core_asset_reserve = T::MultiCurrency::free_balance(&exchange_address, core_asset_id);
+		Self::update_price_observations(asset_id);
+
+		let (trade_asset_amount, liquidity_minted) = if total_liquidity.is_zero() || core_asset_reserve.is_zero() {
+			// new exchange pool
+			(
+				max_asset_amount,
+				Self::initial_liquidity(asset_id, core_amount, max_asset_amount)?,
+			)
+		} else {
+			let trade_asset_reserve = T::MultiCurrency::free_balance(&exchange_address, asset_id);
+			let trade_asset_amount = core_amount * trade_asset_reserve / core_asset_reserve + One::one();
+			let liquidity_minted = core_amount * total_liquidity / core_asset_reserve;
+
+			(trade_asset_amount, liquidity_minted)
+		};
+		ensure!(
+			liquidity_minted >= min_liquidity,
+			Error::<T>::MinimumLiquidityRequirementNotMet
+		);
+		ensure!(
+			max_asset_amount >= trade_asset_amount,
+			Error::<T>::MaximumTradeAssetRequirementNotMet
+		);
+
+		T::MultiCurrency::transfer(
+			provider,
+			&exchange_address,
+			core_asset_id,
+			core_amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		T::MultiCurrency::transfer(
+			provider,
+			&exchange_address,
+			asset_id,
+			trade_asset_amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		Self::mint_liquidity(&exchange_key, provider, liquidity_minted)?;
+		Self::deposit_event(Event::<T>::AddLiquidity(
+			provider.clone(),
+			core_amount,
+			asset_id,
+			trade_asset_amount,
+		));
+
+		Ok((trade_asset_amount, liquidity_minted))
+	}
+
+	/// Burn `provider`'s liquidity to withdraw core asset and trade asset at current ratio
+	/// Returns the value of the liquidity withdrawn
+	///
+	/// `asset_id` - The trade asset ID
+	/// `liquidity_to_withdraw` - Amount of `provider`'s liquidity to withdraw
+	/// `min_asset_withdraw` - The minimum trade asset withdrawn
+	/// `min_core_withdraw` -  The minimum core asset withdrawn
+	#[transactional]
+	pub fn execute_remove_liquidity(
+		provider: &T::AccountId,
+		asset_id: T::AssetId,
+		liquidity_to_withdraw: T::Balance,
+		min_asset_withdraw: T::Balance,
+		min_core_withdraw: T::Balance,
+	) -> sp_std::result::Result<LiquidityValue<T::Balance>, DispatchError> {
+		let core_asset_id = Self::core_asset_id();
+		let exchange_key = (core_asset_id, asset_id);
+		migration::migrate_liquidity_of::<T>(exchange_key, provider)?;
+		let account_liquidity = Self::liquidity_balance(exchange_key, provider);
+		ensure!(
+			account_liquidity >= liquidity_to_withdraw,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let withdraw_value = Self::liquidity_value(asset_id, liquidity_to_withdraw);
+		let total_liquidity = <TotalLiquidity<T>>::get(&exchange_key);
+
+		ensure!(total_liquidity > Zero::zero(), Error::<T>::EmptyExchangePool);
+		ensure!(
+			withdraw_value.core >= min_core_withdraw,
+			Error::<T>::MinimumCoreAssetRequirementNotMet
+		);
+		ensure!(
+			withdraw_value.asset >= min_asset_withdraw,
+			Error::<T>::MinimumTradeAssetRequirementNotMet
+		);
+		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
+		Self::update_price_observations(asset_id);
+		T::MultiCurrency::transfer(
+			&exchange_address,
+			provider,
+			core_asset_id,
+			withdraw_value.core,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		T::MultiCurrency::transfer(
+			&exchange_address,
+			provider,
+			asset_id,
+			withdraw_value.asset,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::burn_liquidity(&exchange_key, provider, liquidity_to_withdraw)?;
+		Self::deposit_event(Event::<T>::RemoveLiquidity(
+			provider.clone(),
+			withdraw_value.core,
+			asset_id,
+			withdraw_value.asset,
+		));
+
+		Ok(withdraw_value)
 	}
 
 	/// The Price of Liquidity for a particular `asset_id` exchange
@@ -395,7 +1006,7 @@ impl<T: Config> Module<T> {
 	pub fn account_liquidity_value(who: &T::AccountId, asset_id: T::AssetId) -> LiquidityValue<T::Balance> {
 		let core_asset_id = Self::core_asset_id();
 		let exchange_key = (core_asset_id, asset_id);
-		let account_liquidity = <LiquidityBalance<T>>::get(&exchange_key, who);
+		let account_liquidity = Self::liquidity_balance(exchange_key, who);
 		Self::liquidity_value(asset_id, account_liquidity)
 	}
 
@@ -490,7 +1101,22 @@ impl<T: Config> Module<T> {
 		ensure!(buy_amount > Zero::zero(), Error::<T>::CannotTradeZero);
 
 		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
-		Self::calculate_buy_price(buy_amount, asset_reserve, core_reserve)
+		let fee_rate = Self::effective_fee_rate(asset_id);
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => {
+				Self::calculate_buy_price(buy_amount, asset_reserve, core_reserve, fee_rate)
+			}
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				Self::calculate_stable_swap_buy_price(
+					buy_amount,
+					(asset_reserve, asset_scale),
+					(core_reserve, core_scale),
+					fee_rate,
+					amplification,
+				)
+			}
+		}
 	}
 
 	/// `asset_id` - Trade asset
@@ -503,17 +1129,34 @@ impl<T: Config> Module<T> {
 		ensure!(buy_amount > Zero::zero(), Error::<T>::CannotTradeZero);
 
 		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
-		Self::calculate_buy_price(buy_amount, core_reserve, asset_reserve)
+		let fee_rate = Self::effective_fee_rate(asset_id);
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => {
+				Self::calculate_buy_price(buy_amount, core_reserve, asset_reserve, fee_rate)
+			}
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				Self::calculate_stable_swap_buy_price(
+					buy_amount,
+					(core_reserve, core_scale),
+					(asset_reserve, asset_scale),
+					fee_rate,
+					amplification,
+				)
+			}
+		}
 	}
 
 	/// `buy_amount` - Amount to buy
 	/// `sell_reserve`- How much of the asset to sell is in the exchange
 	/// `buy_reserve` - How much of the asset to buy is in the exchange
+	/// `fee_rate` - The trading fee rate of the exchange
 	/// Returns the amount of sellable asset is required
 	fn calculate_buy_price(
 		buy_amount: T::Balance,
 		sell_reserve: T::Balance,
 		buy_reserve: T::Balance,
+		fee_rate: FeeRate<PerMillion>,
 	) -> sp_std::result::Result<T::Balance, DispatchError> {
 		ensure!(
 			!sell_reserve.is_zero() && !buy_reserve.is_zero(),
@@ -530,6 +1173,14 @@ impl<T: Config> Module<T> {
 			.checked_div(denominator_hp)
 			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
 
+		Self::apply_buy_fee(price_hp, fee_rate)
+	}
+
+	/// Round up the fee-free `price_hp` of a buy and add the exchange's trading fee
+	fn apply_buy_fee(
+		price_hp: HighPrecisionUnsigned,
+		fee_rate: FeeRate<PerMillion>,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
 		let price_lp_result: Result<LowPrecisionUnsigned, &'static str> = LowPrecisionUnsigned::try_from(price_hp);
 		ensure!(price_lp_result.is_ok(), Error::<T>::Overflow);
 
@@ -537,7 +1188,7 @@ impl<T: Config> Module<T> {
 		let price_plus_one = price_lp
 			.checked_add(One::one())
 			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
-		let fee_rate_plus_one = Self::fee_rate()
+		let fee_rate_plus_one = fee_rate
 			.checked_add(FeeRate::<PerMillion>::one())
 			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
 		let output = fee_rate_plus_one
@@ -589,7 +1240,22 @@ impl<T: Config> Module<T> {
 		ensure!(sell_amount > Zero::zero(), Error::<T>::CannotTradeZero);
 
 		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
-		Self::calculate_sell_price(sell_amount, asset_reserve, core_reserve)
+		let fee_rate = Self::effective_fee_rate(asset_id);
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => {
+				Self::calculate_sell_price(sell_amount, asset_reserve, core_reserve, fee_rate)
+			}
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				Self::calculate_stable_swap_sell_price(
+					sell_amount,
+					(asset_reserve, asset_scale),
+					(core_reserve, core_scale),
+					fee_rate,
+					amplification,
+				)
+			}
+		}
 	}
 
 	/// Returns the amount of trade asset to pay for `sell_amount` of core sold.
@@ -603,32 +1269,43 @@ impl<T: Config> Module<T> {
 		ensure!(sell_amount > Zero::zero(), Error::<T>::CannotTradeZero);
 
 		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
-		Self::calculate_sell_price(sell_amount, core_reserve, asset_reserve)
+		let fee_rate = Self::effective_fee_rate(asset_id);
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => {
+				Self::calculate_sell_price(sell_amount, core_reserve, asset_reserve, fee_rate)
+			}
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				Self::calculate_stable_swap_sell_price(
+					sell_amount,
+					(core_reserve, core_scale),
+					(asset_reserve, asset_scale),
+					fee_rate,
+					amplification,
+				)
+			}
+		}
 	}
 
 	/// `sell_amount` - Amount to sell
 	/// `sell_reserve`- How much of the asset to sell is in the exchange
 	/// `buy_reserve` - How much of the asset to buy is in the exchange
+	/// `fee_rate` - The trading fee rate of the exchange
 	/// Returns the amount of buyable asset that would be received
 	fn calculate_sell_price(
 		sell_amount: T::Balance,
 		sell_reserve: T::Balance,
 		buy_reserve: T::Balance,
+		fee_rate: FeeRate<PerMillion>,
 	) -> sp_std::result::Result<T::Balance, DispatchError> {
 		ensure!(
 			!sell_reserve.is_zero() && !buy_reserve.is_zero(),
 			Error::<T>::EmptyExchangePool
 		);
 
-		let div_rate: FeeRate<PerMillion> = Self::fee_rate()
-			.checked_add(FeeRate::<PerMillion>::one())
-			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
-		let sell_amount_scaled = FeeRate::<PerMillion>::from(sell_amount.saturated_into::<LowPrecisionUnsigned>())
-			.checked_div(div_rate)
-			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
 		let sell_reserve_hp = HighPrecisionUnsigned::from(sell_reserve.saturated_into::<LowPrecisionUnsigned>());
 		let buy_reserve_hp = HighPrecisionUnsigned::from(buy_reserve.saturated_into::<LowPrecisionUnsigned>());
-		let sell_amount_scaled_hp = HighPrecisionUnsigned::from(sell_amount_scaled);
+		let sell_amount_scaled_hp = Self::deduct_sell_fee(sell_amount, fee_rate)?;
 		let denominator_hp = sell_amount_scaled_hp + sell_reserve_hp;
 		let price_hp = buy_reserve_hp
 			.saturating_mul(sell_amount_scaled_hp)
@@ -644,16 +1321,849 @@ impl<T: Config> Module<T> {
 		Ok(price)
 	}
 
-	/// A helper for pricing functions
-	/// Fetches the reserves from an exchange for a particular `asset_id`
-	fn get_exchange_reserves(asset_id: T::AssetId) -> (T::Balance, T::Balance) {
-		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
-
-		let core_reserve = T::MultiCurrency::free_balance(&exchange_address, Self::core_asset_id());
+	/// Returns the portion of `sell_amount` which is traded after the exchange's trading fee is taken
+	fn deduct_sell_fee(
+		sell_amount: T::Balance,
+		fee_rate: FeeRate<PerMillion>,
+	) -> sp_std::result::Result<HighPrecisionUnsigned, DispatchError> {
+		let div_rate: FeeRate<PerMillion> = fee_rate
+			.checked_add(FeeRate::<PerMillion>::one())
+			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let sell_amount_scaled = FeeRate::<PerMillion>::from(sell_amount.saturated_into::<LowPrecisionUnsigned>())
+			.checked_div(div_rate)
+			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+		Ok(HighPrecisionUnsigned::from(sell_amount_scaled))
+	}
+
+	/// `buy_amount` - Amount to buy
+	/// `sell_reserve`- How much of the asset to sell is in the exchange and its scale (see `stable_swap_scales`)
+	/// `buy_reserve` - How much of the asset to buy is in the exchange and its scale (see `stable_swap_scales`)
+	/// `fee_rate` - The trading fee rate of the exchange
+	/// `amplification` - The StableSwap amplification coefficient of the exchange
+	/// Returns the amount of sellable asset is required
+	fn calculate_stable_swap_buy_price(
+		buy_amount: T::Balance,
+		(sell_reserve, sell_scale): (T::Balance, HighPrecisionUnsigned),
+		(buy_reserve, buy_scale): (T::Balance, HighPrecisionUnsigned),
+		fee_rate: FeeRate<PerMillion>,
+		amplification: u32,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		ensure!(
+			!sell_reserve.is_zero() && !buy_reserve.is_zero(),
+			Error::<T>::EmptyExchangePool
+		);
+		ensure!(buy_reserve > buy_amount, Error::<T>::InsufficientExchangePoolReserve);
+
+		let buy_amount_hp = Self::normalise(buy_amount, buy_scale)?;
+		let buy_reserve_hp = Self::normalise(buy_reserve, buy_scale)?;
+		let sell_reserve_hp = Self::normalise(sell_reserve, sell_scale)?;
+		let invariant = Self::stable_swap_invariant(sell_reserve_hp, buy_reserve_hp, amplification)?;
+		let new_sell_reserve_hp = Self::stable_swap_reserve(buy_reserve_hp - buy_amount_hp, invariant, amplification)?;
+		// round up to whole units of the asset sold, in favour of the exchange
+		let price_hp = new_sell_reserve_hp
+			.saturating_sub(sell_reserve_hp)
+			.saturating_add(sell_scale - HighPrecisionUnsigned::one())
+			/ sell_scale;
+
+		Self::apply_buy_fee(price_hp, fee_rate)
+	}
+
+	/// `sell_amount` - Amount to sell
+	/// `sell_reserve`- How much of the asset to sell is in the exchange and its scale (see `stable_swap_scales`)
+	/// `buy_reserve` - How much of the asset to buy is in the exchange and its scale (see `stable_swap_scales`)
+	/// `fee_rate` - The trading fee rate of the exchange
+	/// `amplification` - The StableSwap amplification coefficient of the exchange
+	/// Returns the amount of buyable asset that would be received
+	fn calculate_stable_swap_sell_price(
+		sell_amount: T::Balance,
+		(sell_reserve, sell_scale): (T::Balance, HighPrecisionUnsigned),
+		(buy_reserve, buy_scale): (T::Balance, HighPrecisionUnsigned),
+		fee_rate: FeeRate<PerMillion>,
+		amplification: u32,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		ensure!(
+			!sell_reserve.is_zero() && !buy_reserve.is_zero(),
+			Error::<T>::EmptyExchangePool
+		);
+
+		let sell_reserve_hp = Self::normalise(sell_reserve, sell_scale)?;
+		let buy_reserve_hp = Self::normalise(buy_reserve, buy_scale)?;
+		let sell_amount_scaled_hp = Self::deduct_sell_fee(sell_amount, fee_rate)?
+			.checked_mul(sell_scale)
+			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let invariant = Self::stable_swap_invariant(sell_reserve_hp, buy_reserve_hp, amplification)?;
+		let new_sell_reserve_hp = sell_reserve_hp
+			.checked_add(sell_amount_scaled_hp)
+			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let new_buy_reserve_hp = Self::stable_swap_reserve(new_sell_reserve_hp, invariant, amplification)?;
+		// round down to whole units of the asset bought, in favour of the exchange
+		let price_hp = buy_reserve_hp
+			.saturating_sub(new_buy_reserve_hp)
+			.saturating_sub(HighPrecisionUnsigned::one())
+			/ buy_scale;
+
+		let price_lp_result: Result<LowPrecisionUnsigned, &'static str> = LowPrecisionUnsigned::try_from(price_hp);
+		ensure!(price_lp_result.is_ok(), Error::<T>::Overflow);
+		let price: T::Balance = price_lp_result.unwrap().saturated_into();
+		ensure!(buy_reserve > price, Error::<T>::InsufficientExchangePoolReserve);
+		Ok(price)
+	}
+
+	/// Solve the StableSwap invariant `D` for reserves `x` and `y` with Newton's method
+	/// `A·n^n·(x + y) + D = A·n^n·D + D^(n+1) / (n^n·x·y)` where `n = 2`
+	fn stable_swap_invariant(
+		x: HighPrecisionUnsigned,
+		y: HighPrecisionUnsigned,
+		amplification: u32,
+	) -> sp_std::result::Result<HighPrecisionUnsigned, DispatchError> {
+		let two = HighPrecisionUnsigned::from(2u32);
+		let sum = x.checked_add(y).ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		if sum.is_zero() {
+			return Ok(Zero::zero());
+		}
+		let ann = HighPrecisionUnsigned::from(amplification) * HighPrecisionUnsigned::from(4u32);
+		let mut d = sum;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			// d_p = D^3 / (4xy)
+			let d_p = d
+				.checked_mul(d)
+				.and_then(|v| v.checked_div(x.checked_mul(two)?))
+				.and_then(|v| v.checked_mul(d))
+				.and_then(|v| v.checked_div(y.checked_mul(two)?))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+			let previous = d;
+			// D = (Ann·S + 2·d_p)·D / ((Ann - 1)·D + 3·d_p)
+			let numerator = ann
+				.checked_mul(sum)
+				.and_then(|v| v.checked_add(d_p.checked_mul(two)?))
+				.and_then(|v| v.checked_mul(d))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+			let denominator = (ann - HighPrecisionUnsigned::one())
+				.checked_mul(d)
+				.and_then(|v| v.checked_add(d_p.checked_mul(HighPrecisionUnsigned::from(3u32))?))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+			d = numerator
+				.checked_div(denominator)
+				.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+			if Self::within_one(d, previous) {
+				return Ok(d);
+			}
+		}
+		Err(Error::<T>::StableSwapNotConverged.into())
+	}
+
+	/// Solve the StableSwap invariant for the reserve `y` paired with reserve `x` under invariant `d`
+	fn stable_swap_reserve(
+		x: HighPrecisionUnsigned,
+		d: HighPrecisionUnsigned,
+		amplification: u32,
+	) -> sp_std::result::Result<HighPrecisionUnsigned, DispatchError> {
+		let two = HighPrecisionUnsigned::from(2u32);
+		let ann = HighPrecisionUnsigned::from(amplification) * HighPrecisionUnsigned::from(4u32);
+		// c = D^3 / (4x·Ann), b = x + D / Ann
+		let c = d
+			.checked_mul(d)
+			.and_then(|v| v.checked_div(x.checked_mul(two)?))
+			.and_then(|v| v.checked_mul(d))
+			.and_then(|v| v.checked_div(ann.checked_mul(two)?))
+			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let b = x.checked_add(d / ann).ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let mut y = d;
+		for _ in 0..STABLE_SWAP_MAX_ITERATIONS {
+			let previous = y;
+			// y = (y^2 + c) / (2y + b - D)
+			let numerator = y
+				.checked_mul(y)
+				.and_then(|v| v.checked_add(c))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+			let denominator = y
+				.checked_mul(two)
+				.and_then(|v| v.checked_add(b))
+				.and_then(|v| v.checked_sub(d))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+			y = numerator
+				.checked_div(denominator)
+				.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+			if Self::within_one(y, previous) {
+				return Ok(y);
+			}
+		}
+		Err(Error::<T>::StableSwapNotConverged.into())
+	}
+
+	/// Whether successive Newton's method approximations `a` and `b` have converged
+	fn within_one(a: HighPrecisionUnsigned, b: HighPrecisionUnsigned) -> bool {
+		if a > b {
+			a - b <= HighPrecisionUnsigned::one()
+		} else {
+			b - a <= HighPrecisionUnsigned::one()
+		}
+	}
+
+	/// Liquidity minted for the first deposit of `core_amount` and `asset_amount` into the `asset_id` exchange
+	/// This is the core amount for constant product exchanges and the invariant `D` for StableSwap exchanges, scaled
+	/// to the core asset's decimal places
+	fn initial_liquidity(
+		asset_id: T::AssetId,
+		core_amount: T::Balance,
+		asset_amount: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => Ok(core_amount),
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				let invariant = Self::stable_swap_invariant(
+					Self::normalise(core_amount, core_scale)?,
+					Self::normalise(asset_amount, asset_scale)?,
+					amplification,
+				)?;
+				let invariant =
+					LowPrecisionUnsigned::try_from(invariant / core_scale).map_err(|_| Error::<T>::Overflow)?;
+				Ok(invariant.saturated_into())
+			}
+		}
+	}
+
+	/// Factors scaling the (core asset, trade asset) reserves of the `asset_id` exchange to the greater of their
+	/// decimal places. StableSwap pegs whole units of both assets so their reserves are compared at one precision.
+	fn stable_swap_scales(
+		asset_id: T::AssetId,
+	) -> sp_std::result::Result<(HighPrecisionUnsigned, HighPrecisionUnsigned), DispatchError> {
+		let core_decimals = T::MultiCurrency::decimal_places(Self::core_asset_id());
+		let asset_decimals = T::MultiCurrency::decimal_places(asset_id);
+		let decimals = core_decimals.max(asset_decimals);
+		let scale = |decimal_places: u8| {
+			HighPrecisionUnsigned::from(10u32)
+				.checked_pow(HighPrecisionUnsigned::from(decimals - decimal_places))
+				.ok_or::<Error<T>>(Error::<T>::Overflow)
+		};
+		Ok((scale(core_decimals)?, scale(asset_decimals)?))
+	}
+
+	/// `amount` in the precision shared by StableSwap reserves, given its `scale` (see `stable_swap_scales`)
+	fn normalise(
+		amount: T::Balance,
+		scale: HighPrecisionUnsigned,
+	) -> sp_std::result::Result<HighPrecisionUnsigned, DispatchError> {
+		Ok(
+			HighPrecisionUnsigned::from(amount.saturated_into::<LowPrecisionUnsigned>())
+				.checked_mul(scale)
+				.ok_or::<Error<T>>(Error::<T>::Overflow)?,
+		)
+	}
+
+	/// A helper for pricing functions
+	/// Fetches the reserves from an exchange for a particular `asset_id`
+	fn get_exchange_reserves(asset_id: T::AssetId) -> (T::Balance, T::Balance) {
+		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
+
+		let core_reserve = T::MultiCurrency::free_balance(&exchange_address, Self::core_asset_id());
 		let asset_reserve = T::MultiCurrency::free_balance(&exchange_address, asset_id);
 		(core_reserve, asset_reserve)
 	}
 
+	/// The trading fee rate of the `asset_id` exchange
+	/// This is the exchange's override if set, otherwise the default fee rate
+	pub fn effective_fee_rate(asset_id: T::AssetId) -> FeeRate<PerMillion> {
+		<ExchangeFeeRate<T>>::get((Self::core_asset_id(), asset_id)).unwrap_or_else(Self::fee_rate)
+	}
+
+	/// Returns the protocol's share of the trading fee charged on `amount_in`
+	///
+	/// `amount_in` - Amount of input asset paid into the exchange, inclusive of the trading fee
+	/// `fee_rate` - The trading fee rate of the exchange
+	/// `share` - The protocol's share of the trading fee
+	fn calculate_protocol_fee(
+		amount_in: T::Balance,
+		fee_rate: FeeRate<PerMillion>,
+		share: FeeRate<PerMillion>,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		// trading fee = amount_in * fee_rate / (1 + fee_rate)
+		let fee_rate_plus_one = fee_rate
+			.checked_add(FeeRate::<PerMillion>::one())
+			.ok_or::<Error<T>>(Error::<T>::Overflow)?;
+		let amount_in_hp = HighPrecisionUnsigned::from(amount_in.saturated_into::<LowPrecisionUnsigned>());
+		let numerator_hp = amount_in_hp
+			.saturating_mul(fee_rate.into())
+			.saturating_mul(share.into());
+		let denominator_hp =
+			HighPrecisionUnsigned::from(fee_rate_plus_one).saturating_mul(FeeRate::<PerMillion>::one().into());
+		let protocol_fee_hp = numerator_hp
+			.checked_div(denominator_hp)
+			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+
+		let protocol_fee_result: Result<LowPrecisionUnsigned, &'static str> =
+			LowPrecisionUnsigned::try_from(protocol_fee_hp);
+		ensure!(protocol_fee_result.is_ok(), Error::<T>::Overflow);
+		Ok(protocol_fee_result.unwrap().saturated_into())
+	}
+
+	/// Pay the protocol's share of the trading fee on `amount_in` of `asset_in` from the `asset_id`
+	/// exchange to the treasury.
+	/// The fee is carved out of the trading fee already charged to the trader so quoted prices are unaffected,
+	/// only the liquidity providers' portion shrinks.
+	fn collect_protocol_fee(asset_id: T::AssetId, asset_in: T::AssetId, amount_in: T::Balance) -> DispatchResult {
+		let share = match Self::protocol_fee_share() {
+			Some(share) => share,
+			None => return Ok(()),
+		};
+		let protocol_fee = Self::calculate_protocol_fee(amount_in, Self::effective_fee_rate(asset_id), share)?;
+		if protocol_fee.is_zero() {
+			return Ok(());
+		}
+
+		T::MultiCurrency::transfer(
+			&T::ExchangeAddressFor::exchange_address_for(asset_id),
+			&T::TreasuryPalletId::get().into_account(),
+			asset_in,
+			protocol_fee,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::deposit_event(Event::<T>::ProtocolFeeCollected(asset_id, asset_in, protocol_fee));
+		Ok(())
+	}
+
+	//
+	// Trade paths
+	//
+
+	/// Check `path` is a tradeable route.
+	/// Every CENNZX exchange pairs the core asset with a trade asset, so one of each adjacent pair of assets must be
+	/// the core asset. Each exchange may be used once as its reserves are priced before the route is traded.
+	fn validate_path(path: &[T::AssetId]) -> DispatchResult {
+		ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+		let core_asset_id = Self::core_asset_id();
+		let mut exchanges = BTreeSet::new();
+		for hop in path.windows(2) {
+			let (asset_in, asset_out) = (hop[0], hop[1]);
+			ensure!(asset_in != asset_out, Error::<T>::AssetCannotSwapForItself);
+			let trade_asset_id = if asset_in == core_asset_id {
+				asset_out
+			} else {
+				ensure!(asset_out == core_asset_id, Error::<T>::InvalidPath);
+				asset_in
+			};
+			ensure!(exchanges.insert(trade_asset_id), Error::<T>::InvalidPath);
+		}
+		Ok(())
+	}
+
+	/// Get the amounts traded at each step of `path` to buy `amount_to_buy` of the last asset
+	/// Returns the amount of each asset in `path`, the first is the total price
+	pub fn get_buy_price_via_path(
+		path: &[T::AssetId],
+		amount_to_buy: T::Balance,
+	) -> sp_std::result::Result<Vec<T::Balance>, DispatchError> {
+		Self::validate_path(path)?;
+		let mut amounts = vec![amount_to_buy];
+		for hop in path.windows(2).rev() {
+			let amount_out = amounts[0];
+			amounts.insert(0, Self::get_buy_price(hop[1], amount_out, hop[0])?);
+		}
+		Ok(amounts)
+	}
+
+	/// Get the amounts traded at each step of `path` when selling `amount_to_sell` of the first asset
+	/// Returns the amount of each asset in `path`, the last is the total sale value
+	pub fn get_sell_price_via_path(
+		path: &[T::AssetId],
+		amount_to_sell: T::Balance,
+	) -> sp_std::result::Result<Vec<T::Balance>, DispatchError> {
+		Self::validate_path(path)?;
+		let mut amounts = vec![amount_to_sell];
+		for hop in path.windows(2) {
+			let amount_in = amounts[amounts.len() - 1];
+			amounts.push(Self::get_sell_price(hop[0], amount_in, hop[1])?);
+		}
+		Ok(amounts)
+	}
+
+	/// Candidate routes for trading `asset_in` to `asset_out`
+	/// All exchanges pair the core asset so the search space is core routed only, the route is direct if either
+	/// asset is core, otherwise via core. These are the only routes `validate_path` accepts for a distinct pair.
+	fn candidate_paths(asset_in: T::AssetId, asset_out: T::AssetId) -> Vec<Vec<T::AssetId>> {
+		let core_asset_id = Self::core_asset_id();
+		if asset_in == core_asset_id || asset_out == core_asset_id {
+			vec![vec![asset_in, asset_out]]
+		} else {
+			vec![vec![asset_in, core_asset_id, asset_out]]
+		}
+	}
+
+	/// Find the route requiring the least `asset_to_sell` to buy `amount_to_buy` of `asset_to_buy`
+	/// Only core routed paths are searched (see `candidate_paths`)
+	/// Returns the route and its price in `asset_to_sell`
+	pub fn best_buy_route(
+		asset_to_buy: T::AssetId,
+		amount_to_buy: T::Balance,
+		asset_to_sell: T::AssetId,
+	) -> sp_std::result::Result<(Vec<T::AssetId>, T::Balance), DispatchError> {
+		ensure!(asset_to_buy != asset_to_sell, Error::<T>::AssetCannotSwapForItself);
+		let mut best: Option<(Vec<T::AssetId>, T::Balance)> = None;
+		let mut last_error: DispatchError = Error::<T>::InvalidPath.into();
+		for path in Self::candidate_paths(asset_to_sell, asset_to_buy) {
+			match Self::get_buy_price_via_path(&path, amount_to_buy) {
+				Ok(amounts) => {
+					let price = amounts[0];
+					if best.as_ref().map_or(true, |(_, best_price)| price < *best_price) {
+						best = Some((path, price));
+					}
+				}
+				Err(err) => last_error = err,
+			}
+		}
+		best.ok_or(last_error)
+	}
+
+	/// Find the route paying the most `asset_to_buy` for `amount_to_sell` of `asset_to_sell`
+	/// Only core routed paths are searched (see `candidate_paths`)
+	/// Returns the route and its sale value in `asset_to_buy`
+	pub fn best_sell_route(
+		asset_to_sell: T::AssetId,
+		amount_to_sell: T::Balance,
+		asset_to_buy: T::AssetId,
+	) -> sp_std::result::Result<(Vec<T::AssetId>, T::Balance), DispatchError> {
+		ensure!(asset_to_buy != asset_to_sell, Error::<T>::AssetCannotSwapForItself);
+		let mut best: Option<(Vec<T::AssetId>, T::Balance)> = None;
+		let mut last_error: DispatchError = Error::<T>::InvalidPath.into();
+		for path in Self::candidate_paths(asset_to_sell, asset_to_buy) {
+			match Self::get_sell_price_via_path(&path, amount_to_sell) {
+				Ok(amounts) => {
+					let value = amounts[amounts.len() - 1];
+					if best.as_ref().map_or(true, |(_, best_value)| value > *best_value) {
+						best = Some((path, value));
+					}
+				}
+				Err(err) => last_error = err,
+			}
+		}
+		best.ok_or(last_error)
+	}
+
+	//
+	// Price oracle
+	//
+
+	/// The spot prices of the `asset_id` exchange scaled by `PRICE_SCALE`
+	/// Returns (trade asset price in core asset, core asset price in trade asset), zero if the exchange is empty
+	fn spot_prices(asset_id: T::AssetId) -> (HighPrecisionUnsigned, HighPrecisionUnsigned) {
+		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
+		if core_reserve.is_zero() || asset_reserve.is_zero() {
+			return (Zero::zero(), Zero::zero());
+		}
+		let core_reserve_hp = HighPrecisionUnsigned::from(core_reserve.saturated_into::<LowPrecisionUnsigned>());
+		let asset_reserve_hp = HighPrecisionUnsigned::from(asset_reserve.saturated_into::<LowPrecisionUnsigned>());
+		let scale_hp = HighPrecisionUnsigned::from(PRICE_SCALE);
+		if let ExchangeCurve::StableSwap { amplification } = Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			return Self::stable_swap_scales(asset_id)
+				.ok()
+				.and_then(|(core_scale, asset_scale)| {
+					let (asset_price, core_price) = Self::stable_swap_spot_prices(
+						asset_reserve_hp.checked_mul(asset_scale)?,
+						core_reserve_hp.checked_mul(core_scale)?,
+						amplification,
+					)?;
+					// prices per unit of each asset rather than per unit of the shared precision
+					Some((
+						asset_price.checked_mul(asset_scale)? / core_scale,
+						core_price.checked_mul(core_scale)? / asset_scale,
+					))
+				})
+				.unwrap_or((Zero::zero(), Zero::zero()));
+		}
+		(
+			core_reserve_hp.saturating_mul(scale_hp) / asset_reserve_hp,
+			asset_reserve_hp.saturating_mul(scale_hp) / core_reserve_hp,
+		)
+	}
+
+	/// The marginal prices of a StableSwap exchange with trade asset reserve `x` and core asset reserve `y`
+	/// Returns (trade asset price in core asset, core asset price in trade asset) scaled by `PRICE_SCALE`
+	fn stable_swap_spot_prices(
+		x: HighPrecisionUnsigned,
+		y: HighPrecisionUnsigned,
+		amplification: u32,
+	) -> Option<(HighPrecisionUnsigned, HighPrecisionUnsigned)> {
+		let d = Self::stable_swap_invariant(x, y, amplification).ok()?;
+		let scale_hp = HighPrecisionUnsigned::from(PRICE_SCALE);
+		let two = HighPrecisionUnsigned::from(2u32);
+		// the price is the ratio of the invariant's partial derivatives:
+		// (Ann + D^3 / (4x^2·y)) / (Ann + D^3 / (4x·y^2))
+		let ann_scaled = HighPrecisionUnsigned::from(amplification)
+			.checked_mul(HighPrecisionUnsigned::from(4u32))?
+			.checked_mul(scale_hp)?;
+		let partial = |a: HighPrecisionUnsigned, b: HighPrecisionUnsigned| -> Option<HighPrecisionUnsigned> {
+			let ratio = d.checked_mul(scale_hp)?.checked_div(a.checked_mul(two)?)?;
+			ratio
+				.checked_mul(ratio)?
+				.checked_div(scale_hp)?
+				.checked_mul(d)?
+				.checked_div(b)?
+				.checked_add(ann_scaled)
+		};
+		let dx = partial(x, y)?;
+		let dy = partial(y, x)?;
+		Some((
+			dx.checked_mul(scale_hp)?.checked_div(dy)?,
+			dy.checked_mul(scale_hp)?.checked_div(dx)?,
+		))
+	}
+
+	/// Project `observation` forward to `block_number` at the current spot prices of the `asset_id` exchange
+	/// Only valid while reserves are unchanged since `observation` was taken
+	fn extrapolate_observation(
+		asset_id: T::AssetId,
+		observation: PriceObservation<T::BlockNumber>,
+		block_number: T::BlockNumber,
+	) -> PriceObservation<T::BlockNumber> {
+		let elapsed = HighPrecisionUnsigned::from(
+			block_number
+				.saturating_sub(observation.block_number)
+				.saturated_into::<LowPrecisionUnsigned>(),
+		);
+		let (asset_price, core_price) = Self::spot_prices(asset_id);
+		PriceObservation {
+			block_number,
+			asset_price_cumulative: observation
+				.asset_price_cumulative
+				.saturating_add(asset_price.saturating_mul(elapsed)),
+			core_price_cumulative: observation
+				.core_price_cumulative
+				.saturating_add(core_price.saturating_mul(elapsed)),
+		}
+	}
+
+	/// Record the prices of the `asset_id` exchange up to the current block.
+	/// Must be called before the exchange reserves change, so only end of block prices are accumulated
+	/// and trades within the current block cannot move the average.
+	fn update_price_observations(asset_id: T::AssetId) {
+		let exchange_key = (Self::core_asset_id(), asset_id);
+		let now = <frame_system::Pallet<T>>::block_number();
+		let (latest_index, count) = <PriceObservationCursor<T>>::get(&exchange_key);
+
+		let latest = if count.is_zero() {
+			None
+		} else {
+			<PriceObservations<T>>::get(&exchange_key, latest_index)
+		};
+		let observation = match latest {
+			// already observed this block
+			Some(latest) if latest.block_number == now => return,
+			Some(latest) => Self::extrapolate_observation(asset_id, latest, now),
+			None => PriceObservation {
+				block_number: now,
+				..Default::default()
+			},
+		};
+
+		let max_observations = T::MaxPriceObservations::get().max(One::one());
+		let next_index = if count.is_zero() {
+			0
+		} else {
+			(latest_index + 1) % max_observations
+		};
+		<PriceObservations<T>>::insert(&exchange_key, next_index, observation);
+		<PriceObservationCursor<T>>::insert(&exchange_key, (next_index, (count + 1).min(max_observations)));
+	}
+
+	/// The cumulative prices of the `asset_id` exchange as at `block_number`
+	/// Returns `None` if `block_number` is older than the oldest observation held
+	fn cumulative_prices_at(
+		asset_id: T::AssetId,
+		block_number: T::BlockNumber,
+	) -> Option<PriceObservation<T::BlockNumber>> {
+		let exchange_key = (Self::core_asset_id(), asset_id);
+		let (latest_index, count) = <PriceObservationCursor<T>>::get(&exchange_key);
+		if count.is_zero() {
+			return None;
+		}
+		let latest = <PriceObservations<T>>::get(&exchange_key, latest_index)?;
+		if block_number >= latest.block_number {
+			return Some(Self::extrapolate_observation(asset_id, latest, block_number));
+		}
+
+		// the ring buffer holds `count` observations ordered by block number, starting after the latest
+		let max_observations = T::MaxPriceObservations::get().max(One::one());
+		let oldest_index = (latest_index + max_observations + 1 - count) % max_observations;
+		let observation_at = |i: u32| <PriceObservations<T>>::get(&exchange_key, (oldest_index + i) % max_observations);
+
+		let oldest = observation_at(0)?;
+		if block_number < oldest.block_number {
+			return None;
+		}
+
+		// binary search for the last observation at or before `block_number`
+		let (mut low, mut high) = (0_u32, count - 1);
+		while low < high {
+			let mid = (low + high + 1) / 2;
+			if observation_at(mid)?.block_number <= block_number {
+				low = mid;
+			} else {
+				high = mid - 1;
+			}
+		}
+		let before = observation_at(low)?;
+		if before.block_number == block_number {
+			return Some(before);
+		}
+		// prices are constant between observations so the cumulative prices can be interpolated exactly
+		let after = observation_at(low + 1)?;
+		let span = HighPrecisionUnsigned::from(
+			(after.block_number - before.block_number).saturated_into::<LowPrecisionUnsigned>(),
+		);
+		let elapsed =
+			HighPrecisionUnsigned::from((block_number - before.block_number).saturated_into::<LowPrecisionUnsigned>());
+		let interpolate = |before: HighPrecisionUnsigned, after: HighPrecisionUnsigned| {
+			before.saturating_add(after.saturating_sub(before).saturating_mul(elapsed) / span)
+		};
+		Some(PriceObservation {
+			block_number,
+			asset_price_cumulative: interpolate(before.asset_price_cumulative, after.asset_price_cumulative),
+			core_price_cumulative: interpolate(before.core_price_cumulative, after.core_price_cumulative),
+		})
+	}
+
+	/// The time-weighted average prices of the `asset_id` exchange over the last `window` blocks, scaled by `PRICE_SCALE`
+	/// Prices are sampled at the end of each block, the current block is excluded.
+	///
+	/// Returns (trade asset price in core asset, core asset price in trade asset)
+	/// or `None` if the exchange has insufficient price history for `window`
+	pub fn twap(
+		asset_id: T::AssetId,
+		window: T::BlockNumber,
+	) -> Option<(HighPrecisionUnsigned, HighPrecisionUnsigned)> {
+		if window.is_zero() {
+			return None;
+		}
+		let now = <frame_system::Pallet<T>>::block_number();
+		let start = now.checked_sub(&window)?;
+		let current = Self::cumulative_prices_at(asset_id, now)?;
+		let past = Self::cumulative_prices_at(asset_id, start)?;
+
+		let window_hp = HighPrecisionUnsigned::from(window.saturated_into::<LowPrecisionUnsigned>());
+		let asset_price = current
+			.asset_price_cumulative
+			.saturating_sub(past.asset_price_cumulative)
+			/ window_hp;
+		let core_price = current.core_price_cumulative.saturating_sub(past.core_price_cumulative) / window_hp;
+		Some((asset_price, core_price))
+	}
+
+	//
+	// Liquidity rewards
+	//
+
+	/// The account holding the funded, unclaimed rewards of the `exchange_key` reward program
+	pub fn rewards_account(exchange_key: &ExchangeKey<T>) -> T::AccountId {
+		T::TreasuryPalletId::get().into_sub_account((b"rwd", exchange_key))
+	}
+
+	/// Total rewards emitted at `reward_per_block` over `blocks`
+	fn rewards_over(reward_per_block: T::Balance, blocks: T::BlockNumber) -> Result<T::Balance, DispatchError> {
+		let blocks: T::Balance = blocks.saturated_into::<LowPrecisionUnsigned>().saturated_into();
+		reward_per_block
+			.checked_mul(&blocks)
+			.ok_or_else(|| Error::<T>::Overflow.into())
+	}
+
+	/// Bring the `exchange_key` reward program's `reward_per_share` up to the current block (or its end block)
+	/// Must be called before `TotalLiquidity` changes. Rewards emitted while the exchange has no liquidity are not
+	/// paid to anyone and stay in the rewards account.
+	fn update_reward_program(exchange_key: &ExchangeKey<T>) -> Option<RewardProgramOf<T>> {
+		let mut program = Self::reward_program(exchange_key)?;
+		let now = <frame_system::Pallet<T>>::block_number().min(program.end_block);
+		if now <= program.last_update_block {
+			return Some(program);
+		}
+
+		let total_liquidity = <TotalLiquidity<T>>::get(exchange_key);
+		if !total_liquidity.is_zero() {
+			let elapsed =
+				HighPrecisionUnsigned::from((now - program.last_update_block).saturated_into::<LowPrecisionUnsigned>());
+			let emitted =
+				HighPrecisionUnsigned::from(program.reward_per_block.saturated_into::<LowPrecisionUnsigned>())
+					.saturating_mul(elapsed);
+			program.reward_per_share = program.reward_per_share.saturating_add(
+				emitted.saturating_mul(REWARD_PER_SHARE_SCALE.into())
+					/ HighPrecisionUnsigned::from(total_liquidity.saturated_into::<LowPrecisionUnsigned>()),
+			);
+		}
+		program.last_update_block = now;
+		<RewardPrograms<T>>::insert(exchange_key, program);
+		Some(program)
+	}
+
+	/// Accrue `who`'s rewards from the `exchange_key` reward program up to the current block
+	/// Rewards accrue on `who`'s checkpointed liquidity, capped at their current liquidity in case some was reserved
+	fn accrue_rewards(exchange_key: &ExchangeKey<T>, who: &T::AccountId) {
+		let program = match Self::update_reward_program(exchange_key) {
+			Some(program) => program,
+			None => return,
+		};
+		let held = Self::liquidity_balance(*exchange_key, who);
+		<RewardStakes<T>>::mutate(exchange_key, who, |stake| {
+			let liquidity =
+				HighPrecisionUnsigned::from(stake.liquidity.min(held).saturated_into::<LowPrecisionUnsigned>());
+			let earned = liquidity.saturating_mul(program.reward_per_share.saturating_sub(stake.reward_per_share_paid))
+				/ HighPrecisionUnsigned::from(REWARD_PER_SHARE_SCALE);
+			stake.pending = stake.pending.saturating_add(
+				LowPrecisionUnsigned::try_from(earned)
+					.unwrap_or(LowPrecisionUnsigned::MAX)
+					.saturated_into(),
+			);
+			stake.reward_per_share_paid = program.reward_per_share;
+		});
+	}
+
+	/// Record `who`'s current liquidity as the amount accruing rewards in the `exchange_key` reward program
+	fn checkpoint_reward_stake(exchange_key: &ExchangeKey<T>, who: &T::AccountId) {
+		let held = Self::liquidity_balance(*exchange_key, who);
+		<RewardStakes<T>>::mutate(exchange_key, who, |stake| stake.liquidity = held);
+	}
+
+	//
+	// Limit orders
+	//
+
+	/// Sell `fill_amount` of a limit order at the current exchange price, failing if it doesn't meet the order's limit
+	/// `keeper` is paid `KeeperFeeRate` of the proceeds, orders matched without a keeper pay no fee
+	#[transactional]
+	fn execute_order(
+		order_id: OrderId,
+		mut order: LimitOrderOf<T>,
+		fill_amount: T::Balance,
+		keeper: Option<&T::AccountId>,
+	) -> DispatchResult {
+		ensure!(
+			!fill_amount.is_zero() && fill_amount <= order.sell_amount,
+			Error::<T>::InvalidFillAmount
+		);
+		let minimum_buy = Self::order_minimum_buy(&order, fill_amount)?;
+		let amount_to_buy = Self::get_sell_price(order.asset_to_sell, fill_amount, order.asset_to_buy)?;
+		let keeper_fee = match keeper {
+			Some(_) => Self::calculate_keeper_fee(amount_to_buy)?,
+			None => Zero::zero(),
+		};
+		let owner_amount = amount_to_buy.saturating_sub(keeper_fee);
+		ensure!(owner_amount >= minimum_buy, Error::<T>::MinimumBuyRequirementNotMet);
+
+		let _ = T::MultiCurrency::unreserve(&order.owner, order.asset_to_sell, fill_amount);
+		Self::execute_sell(
+			&order.owner,
+			&order.owner,
+			order.asset_to_sell,
+			order.asset_to_buy,
+			fill_amount,
+			amount_to_buy,
+		)?;
+		if let Some(keeper) = keeper {
+			if !keeper_fee.is_zero() {
+				T::MultiCurrency::transfer(
+					&order.owner,
+					keeper,
+					order.asset_to_buy,
+					keeper_fee,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+		}
+
+		order.sell_amount -= fill_amount;
+		order.minimum_buy = order.minimum_buy.saturating_sub(minimum_buy);
+		if order.sell_amount.is_zero() {
+			<Orders<T>>::remove(order_id);
+		} else {
+			<Orders<T>>::insert(order_id, order);
+		}
+		Self::deposit_event(Event::<T>::OrderFilled(order_id, fill_amount, owner_amount, keeper_fee));
+		Ok(())
+	}
+
+	/// The minimum buy for selling `fill_amount` of `order` at its limit price, rounded up in the owner's favour
+	fn order_minimum_buy(
+		order: &LimitOrderOf<T>,
+		fill_amount: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		if fill_amount == order.sell_amount {
+			return Ok(order.minimum_buy);
+		}
+		let fill_amount_hp = HighPrecisionUnsigned::from(fill_amount.saturated_into::<LowPrecisionUnsigned>());
+		let minimum_buy_hp = HighPrecisionUnsigned::from(order.minimum_buy.saturated_into::<LowPrecisionUnsigned>());
+		let sell_amount_hp = HighPrecisionUnsigned::from(order.sell_amount.saturated_into::<LowPrecisionUnsigned>());
+		ensure!(!sell_amount_hp.is_zero(), Error::<T>::DivideByZero);
+		let numerator_hp =
+			fill_amount_hp.saturating_mul(minimum_buy_hp) + sell_amount_hp - HighPrecisionUnsigned::one();
+
+		let minimum_buy_result: Result<LowPrecisionUnsigned, &'static str> =
+			LowPrecisionUnsigned::try_from(numerator_hp / sell_amount_hp);
+		ensure!(minimum_buy_result.is_ok(), Error::<T>::Overflow);
+		Ok(minimum_buy_result.unwrap().saturated_into())
+	}
+
+	/// The keeper's share of `amount` bought by filling a limit order
+	fn calculate_keeper_fee(amount: T::Balance) -> sp_std::result::Result<T::Balance, DispatchError> {
+		let amount_hp = HighPrecisionUnsigned::from(amount.saturated_into::<LowPrecisionUnsigned>());
+		let keeper_fee_hp = amount_hp
+			.saturating_mul(T::KeeperFeeRate::get().into())
+			.checked_div(FeeRate::<PerMillion>::one().into())
+			.ok_or::<Error<T>>(Error::<T>::DivideByZero)?;
+
+		let keeper_fee_result: Result<LowPrecisionUnsigned, &'static str> =
+			LowPrecisionUnsigned::try_from(keeper_fee_hp);
+		ensure!(keeper_fee_result.is_ok(), Error::<T>::Overflow);
+		Ok(keeper_fee_result.unwrap().saturated_into())
+	}
+
+	/// Remove an expired limit order, returning its unfilled sell amount to the owner
+	fn expire_order(order_id: OrderId, order: &LimitOrderOf<T>) {
+		<Orders<T>>::remove(order_id);
+		let _ = T::MultiCurrency::unreserve(&order.owner, order.asset_to_sell, order.sell_amount);
+		Self::deposit_event(Event::<T>::OrderExpired(order_id));
+	}
+
+	/// Fill or expire open limit orders while `remaining_weight` allows, continuing from the last order checked
+	/// Orders are filled in full at the current exchange price if it meets their limit, with no keeper fee
+	fn match_orders(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		// +1 read `OrderMatchCursor`, +1 write to update the cursor
+		let mut consumed_weight = T::DbWeight::get().reads_writes(1, 1);
+		let order_weight = T::WeightInfo::fill_order();
+		if consumed_weight.saturating_add(order_weight) > remaining_weight {
+			return Zero::zero();
+		}
+
+		// only open orders are visited, filled and cancelled orders have been removed
+		let mut cursor = Self::order_match_cursor();
+		let mut orders = match cursor.clone() {
+			Some(last_key) => <Orders<T>>::iter_from(last_key),
+			None => <Orders<T>>::iter(),
+		};
+		// check each order at most once per block, resuming after the last checked order next block
+		while consumed_weight.saturating_add(order_weight) <= remaining_weight {
+			let (order_id, order) = match orders.next() {
+				Some(entry) => entry,
+				None => {
+					cursor = None;
+					break;
+				}
+			};
+			consumed_weight = consumed_weight.saturating_add(order_weight);
+			if now > order.expiry {
+				Self::expire_order(order_id, &order);
+			} else {
+				// an order below its limit price is left open to check again later
+				let sell_amount = order.sell_amount;
+				let _ = Self::execute_order(order_id, order, sell_amount, None);
+			}
+			cursor = Some(<Orders<T>>::hashed_key_for(order_id));
+		}
+		match cursor {
+			Some(last_key) => OrderMatchCursor::put(last_key),
+			None => OrderMatchCursor::kill(),
+		}
+
+		consumed_weight
+	}
+
 	//
 	// Trade functions
 	//
@@ -750,6 +2260,231 @@ impl<T: Config> Module<T> {
 		Ok(amount_to_buy)
 	}
 
+	/// Buy `amount_to_buy` of the last asset in `path` with the first, trading through each exchange along `path`.
+	///
+	/// `trader` - Account selling the first asset in `path`
+	/// `recipient` - Account to receive the last asset in `path`
+	/// `path` - The assets to trade through
+	/// `amount_to_buy` - The amount of the last asset in `path` to buy
+	/// `maximum_sell` - Maximum acceptable amount of the first asset in `path` the trader will sell
+	#[transactional]
+	pub fn execute_buy_via_path(
+		trader: &T::AccountId,
+		recipient: &T::AccountId,
+		path: &[T::AssetId],
+		amount_to_buy: T::Balance,
+		maximum_sell: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		// Check the sell amount meets the maximum requirement
+		let amounts = Self::get_buy_price_via_path(path, amount_to_buy)?;
+		let amount_to_sell = amounts[0];
+		ensure!(amount_to_sell <= maximum_sell, Error::<T>::MaximumSellRequirementNotMet);
+
+		let (asset_to_sell, asset_to_buy) = (path[0], path[path.len() - 1]);
+		ensure!(
+			T::MultiCurrency::free_balance(trader, asset_to_sell) >= amount_to_sell,
+			Error::<T>::InsufficientBalance
+		);
+
+		Self::execute_path(trader, recipient, path, &amounts)?;
+
+		Self::deposit_event(Event::<T>::AssetBought(
+			asset_to_sell,
+			asset_to_buy,
+			trader.clone(),
+			amount_to_sell,
+			amount_to_buy,
+		));
+
+		Ok(amount_to_sell)
+	}
+
+	/// Sell `amount_to_sell` of the first asset in `path` for the last, trading through each exchange along `path`.
+	///
+	/// `trader` - Account selling the first asset in `path`
+	/// `recipient` - Account to receive the last asset in `path`
+	/// `path` - The assets to trade through
+	/// `amount_to_sell` - The amount of the first asset in `path` to sell
+	/// `minimum_buy` - The minimum acceptable amount of the last asset in `path` to receive
+	#[transactional]
+	pub fn execute_sell_via_path(
+		trader: &T::AccountId,
+		recipient: &T::AccountId,
+		path: &[T::AssetId],
+		amount_to_sell: T::Balance,
+		minimum_buy: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		Self::validate_path(path)?;
+		let (asset_to_sell, asset_to_buy) = (path[0], path[path.len() - 1]);
+		ensure!(
+			T::MultiCurrency::free_balance(trader, asset_to_sell) >= amount_to_sell,
+			Error::<T>::InsufficientBalance
+		);
+
+		// Check the buy amount meets the minimum requirement
+		let amounts = Self::get_sell_price_via_path(path, amount_to_sell)?;
+		let amount_to_buy = amounts[amounts.len() - 1];
+		ensure!(amount_to_buy >= minimum_buy, Error::<T>::MinimumBuyRequirementNotMet);
+
+		Self::execute_path(trader, recipient, path, &amounts)?;
+
+		Self::deposit_event(Event::<T>::AssetSold(
+			asset_to_sell,
+			asset_to_buy,
+			trader.clone(),
+			amount_to_sell,
+			amount_to_buy,
+		));
+
+		Ok(amount_to_buy)
+	}
+
+	/// Trade `amounts` through each exchange along `path`, intermediate assets pass through `trader`
+	fn execute_path(
+		trader: &T::AccountId,
+		recipient: &T::AccountId,
+		path: &[T::AssetId],
+		amounts: &[T::Balance],
+	) -> DispatchResult {
+		let last_hop = path.len() - 2;
+		for (i, hop) in path.windows(2).enumerate() {
+			let hop_recipient = if i == last_hop { recipient } else { trader };
+			Self::execute_trade(trader, hop_recipient, hop[0], hop[1], amounts[i], amounts[i + 1])?;
+		}
+		Ok(())
+	}
+
+	/// Lend `borrow_amount` of `asset_to_borrow` to `borrower`, run `callback` then take repayment in
+	/// `asset_to_repay` from `borrower`
+	/// Returns the amount of `asset_to_repay` paid
+	///
+	/// `borrower` - Account receiving the loan and repaying it
+	/// `asset_to_borrow` - asset ID to borrow
+	/// `borrow_amount` - The amount of `asset_to_borrow` to lend
+	/// `asset_to_repay` - asset ID to repay, the core asset or the trade asset of the exchange
+	/// `callback` - Runs once the loan is made, e.g. calling the borrower's contract
+	#[transactional]
+	pub fn execute_flash_swap(
+		borrower: &T::AccountId,
+		asset_to_borrow: T::AssetId,
+		borrow_amount: T::Balance,
+		asset_to_repay: T::AssetId,
+		callback: impl FnOnce() -> DispatchResult,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		ensure!(!borrow_amount.is_zero(), Error::<T>::CannotTradeZero);
+		let core_asset_id = Self::core_asset_id();
+		let asset_id = if asset_to_borrow == core_asset_id {
+			asset_to_repay
+		} else {
+			asset_to_borrow
+		};
+		ensure!(
+			asset_id != core_asset_id && (asset_to_repay == core_asset_id || asset_to_repay == asset_id),
+			Error::<T>::InvalidFlashSwap
+		);
+
+		// the repayment is priced before the callback can move the exchange reserves
+		let repay_amount = if asset_to_repay == asset_to_borrow {
+			let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
+			let reserve = if asset_to_borrow == core_asset_id {
+				core_reserve
+			} else {
+				asset_reserve
+			};
+			ensure!(reserve > borrow_amount, Error::<T>::InsufficientExchangePoolReserve);
+			Self::calculate_flash_loan_repayment(borrow_amount, Self::effective_fee_rate(asset_id))?
+		} else {
+			Self::get_buy_price(asset_to_borrow, borrow_amount, asset_to_repay)?
+		};
+
+		let exchange_key = (core_asset_id, asset_id);
+		let exchange_address = T::ExchangeAddressFor::exchange_address_for(asset_id);
+		let liquidity_before = <TotalLiquidity<T>>::get(&exchange_key);
+		let invariant_before = Self::exchange_invariant(asset_id)?;
+		Self::update_price_observations(asset_id);
+
+		T::MultiCurrency::transfer(
+			&exchange_address,
+			borrower,
+			asset_to_borrow,
+			borrow_amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+
+		callback()?;
+
+		T::MultiCurrency::transfer(
+			borrower,
+			&exchange_address,
+			asset_to_repay,
+			repay_amount,
+			ExistenceRequirement::KeepAlive,
+		)?;
+		Self::collect_protocol_fee(asset_id, asset_to_repay, repay_amount)?;
+
+		// the callback may have traded with the exchange or changed its liquidity, so compare the invariant per unit
+		// of liquidity
+		let liquidity_after = <TotalLiquidity<T>>::get(&exchange_key);
+		let invariant_after = Self::exchange_invariant(asset_id)?;
+		let repaid = if liquidity_before.is_zero() || liquidity_after.is_zero() {
+			invariant_after >= invariant_before
+		} else {
+			let liquidity_before =
+				HighPrecisionUnsigned::from(liquidity_before.saturated_into::<LowPrecisionUnsigned>());
+			let liquidity_after = HighPrecisionUnsigned::from(liquidity_after.saturated_into::<LowPrecisionUnsigned>());
+			invariant_after.saturating_mul(liquidity_before) >= invariant_before.saturating_mul(liquidity_after)
+		};
+		ensure!(repaid, Error::<T>::FlashSwapNotRepaid);
+
+		Self::deposit_event(Event::<T>::FlashSwap(
+			borrower.clone(),
+			asset_to_borrow,
+			borrow_amount,
+			asset_to_repay,
+			repay_amount,
+		));
+
+		Ok(repay_amount)
+	}
+
+	/// Returns `borrow_amount` plus the trading fee, rounded up
+	fn calculate_flash_loan_repayment(
+		borrow_amount: T::Balance,
+		fee_rate: FeeRate<PerMillion>,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		let scale = HighPrecisionUnsigned::from(FeeRate::<PerMillion>::one());
+		let rate_plus_one = HighPrecisionUnsigned::from(fee_rate).saturating_add(scale);
+		let repay_hp = HighPrecisionUnsigned::from(borrow_amount.saturated_into::<LowPrecisionUnsigned>())
+			.saturating_mul(rate_plus_one)
+			.saturating_add(scale - HighPrecisionUnsigned::one())
+			/ scale;
+		let repay = LowPrecisionUnsigned::try_from(repay_hp).map_err(|_| Error::<T>::Overflow)?;
+		Ok(repay.saturated_into())
+	}
+
+	/// The invariant of the `asset_id` exchange at its current reserves
+	/// This is `sqrt(x·y)` for constant product exchanges so it scales with liquidity like StableSwap's `D`
+	fn exchange_invariant(asset_id: T::AssetId) -> sp_std::result::Result<HighPrecisionUnsigned, DispatchError> {
+		let (core_reserve, asset_reserve) = Self::get_exchange_reserves(asset_id);
+		let core_reserve_hp = HighPrecisionUnsigned::from(core_reserve.saturated_into::<LowPrecisionUnsigned>());
+		let asset_reserve_hp = HighPrecisionUnsigned::from(asset_reserve.saturated_into::<LowPrecisionUnsigned>());
+		match Self::exchange_curve((Self::core_asset_id(), asset_id)) {
+			ExchangeCurve::ConstantProduct => Ok(core_reserve_hp.saturating_mul(asset_reserve_hp).integer_sqrt()),
+			ExchangeCurve::StableSwap { amplification } => {
+				let (core_scale, asset_scale) = Self::stable_swap_scales(asset_id)?;
+				Self::stable_swap_invariant(
+					asset_reserve_hp
+						.checked_mul(asset_scale)
+						.ok_or::<Error<T>>(Error::<T>::Overflow)?,
+					core_reserve_hp
+						.checked_mul(core_scale)
+						.ok_or::<Error<T>>(Error::<T>::Overflow)?,
+					amplification,
+				)
+			}
+		}
+	}
+
 	/// Perform the transfer of funds between `trader`/`recipient` and the target exchange pools.
 	/// Note: this operation is atomic, if one intermediate transfer fails, then the entire trade will be rolled back and return error.
 	#[transactional]
@@ -766,11 +2501,13 @@ impl<T: Config> Module<T> {
 		// If either asset is core, we only need to make one exchange
 		// otherwise, we make two exchanges
 		if asset_to_sell == core_asset_id || asset_to_buy == core_asset_id {
-			let exchange_address = if asset_to_buy == core_asset_id {
-				T::ExchangeAddressFor::exchange_address_for(asset_to_sell)
+			let trade_asset_id = if asset_to_buy == core_asset_id {
+				asset_to_sell
 			} else {
-				T::ExchangeAddressFor::exchange_address_for(asset_to_buy)
+				asset_to_buy
 			};
+			let exchange_address = T::ExchangeAddressFor::exchange_address_for(trade_asset_id);
+			Self::update_price_observations(trade_asset_id);
 
 			T::MultiCurrency::transfer(
 				trader,
@@ -786,9 +2523,12 @@ impl<T: Config> Module<T> {
 				amount_to_buy,
 				ExistenceRequirement::KeepAlive,
 			))
+			.and_then(|_| Self::collect_protocol_fee(trade_asset_id, asset_to_sell, amount_to_sell))
 		} else {
 			let exchange_address_a = T::ExchangeAddressFor::exchange_address_for(asset_to_sell);
 			let exchange_address_b = T::ExchangeAddressFor::exchange_address_for(asset_to_buy);
+			Self::update_price_observations(asset_to_sell);
+			Self::update_price_observations(asset_to_buy);
 
 			Self::get_asset_to_core_sell_price(asset_to_sell, amount_to_sell).and_then(|core_amount| {
 				T::MultiCurrency::transfer(
@@ -812,6 +2552,8 @@ impl<T: Config> Module<T> {
 					amount_to_buy,
 					ExistenceRequirement::KeepAlive,
 				))
+				.and_then(|_| Self::collect_protocol_fee(asset_to_sell, asset_to_sell, amount_to_sell))
+				.and_then(|_| Self::collect_protocol_fee(asset_to_buy, core_asset_id, core_amount))
 			})
 		}
 	}
@@ -0,0 +1,85 @@
+/* Copyright 2019-2021 Centrality Investments Limited
+*
+* Licensed under the LGPL, Version 3.0 (the "License");
+* you may not use this file except in compliance with the License.
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific language governing permissions and
+* limitations under the License.
+* You may obtain a copy of the License at the root of this project source code,
+* or at:
+*     https://centrality.ai/licenses/gplv3.txt
+*     https://centrality.ai/licenses/lgplv3.txt
+*/
+//!
+//! CENNZX storage migrations
+//!
+use super::*;
+use frame_support::IterableStorageDoubleMap;
+
+/// V0 storage entries to migrate
+pub(crate) mod storage_v0 {
+	use super::*;
+	pub struct Module<T>(sp_std::marker::PhantomData<T>);
+	decl_storage! {
+		trait Store for Module<T: Config> as Cennzx {
+			/// Liquidity holdings of a user in an exchange pool.
+			/// Key: `(core_asset_id, trade_asset_id), account_id`
+			pub LiquidityBalance get(fn liquidity_balance): double_map hasher(twox_64_concat) ExchangeKey<T>, hasher(blake2_128_concat) T::AccountId => T::Balance;
+		}
+	}
+}
+
+/// The maximum number of `LiquidityBalance` holdings to migrate in a single block
+pub const MAX_LIQUIDITY_MIGRATIONS_PER_BLOCK: u32 = 100;
+
+/// Migrate liquidity from v0 to v1
+/// `LiquidityBalance` entries are minted as the liquidity asset of their exchange, the total issuance of which
+/// matches `TotalLiquidity`
+/// At most `MAX_LIQUIDITY_MIGRATIONS_PER_BLOCK` holdings are migrated per call, the storage version stays at V0,
+/// continuing next block, until every holding has migrated. Until then `liquidity_balance` includes unmigrated
+/// holdings.
+pub fn do_v0_to_v1<T: Config>() -> Weight {
+	// +1 read for the remaining holdings check, +1 write for the storage version
+	let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+	let holdings: Vec<(ExchangeKey<T>, T::AccountId, T::Balance)> = storage_v0::LiquidityBalance::<T>::iter()
+		.take(MAX_LIQUIDITY_MIGRATIONS_PER_BLOCK as usize)
+		.collect();
+	// +1 read & write to remove the holding, +3 reads & 2 writes to mint it, +6 writes to create the asset (worst case)
+	weight += T::DbWeight::get().reads_writes(4, 9) * holdings.len() as Weight;
+
+	for (exchange_key, who, liquidity) in holdings {
+		// holdings are only left in place if the liquidity asset can't be created i.e. asset ids are exhausted
+		let _ = migrate_holding::<T>(exchange_key, &who, liquidity);
+	}
+
+	if storage_v0::LiquidityBalance::<T>::iter().next().is_none() {
+		StorageVersion::put(Releases::V1 as u32);
+	}
+
+	weight
+}
+
+/// Migrate `who`'s `LiquidityBalance` holding in the `exchange_key` exchange, if any, to its liquidity asset
+/// Liquidity is migrated ahead of the block migration when it is withdrawn
+pub(crate) fn migrate_liquidity_of<T: Config>(exchange_key: ExchangeKey<T>, who: &T::AccountId) -> DispatchResult {
+	let liquidity = storage_v0::LiquidityBalance::<T>::get(exchange_key, who);
+	if liquidity.is_zero() {
+		return Ok(());
+	}
+	migrate_holding::<T>(exchange_key, who, liquidity)
+}
+
+/// Mint `liquidity` of the `exchange_key` liquidity asset to `who` and remove their `LiquidityBalance` holding
+fn migrate_holding<T: Config>(
+	exchange_key: ExchangeKey<T>,
+	who: &T::AccountId,
+	liquidity: T::Balance,
+) -> DispatchResult {
+	let liquidity_asset_id = Module::<T>::get_or_create_liquidity_asset(exchange_key.1)?;
+	let _ = T::MultiCurrency::deposit_creating(who, liquidity_asset_id, liquidity);
+	storage_v0::LiquidityBalance::<T>::remove(exchange_key, who);
+	Ok(())
+}
@@ -32,9 +32,10 @@ use crate::{
 	Config,
 };
 pub(crate) use cennznet_primitives::types::{AccountId, AssetId, Balance};
-use core::convert::TryFrom;
+use core::{cell::RefCell, convert::TryFrom};
 use crml_generic_asset::impls::TransferDustImbalance;
-use frame_support::{parameter_types, PalletId};
+use crml_support::{ContractCaller, PrefixedAddressMapping, H160};
+use frame_support::{dispatch::DispatchError, parameter_types, weights::Weight, PalletId};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -87,6 +88,10 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 		pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+		pub const MaxVestingSchedules: u32 = 4;
+		pub const MinVestedTransfer: Balance = 1;
+		pub const MaxPriceObservations: u32 = 4;
+		pub KeeperFeeRate: FeeRate<PerMillion> = FeeRate::<PerMillion>::from(10_000u128);
 }
 impl crml_generic_asset::Config for Test {
 	type AssetId = AssetId;
@@ -94,6 +99,9 @@ impl crml_generic_asset::Config for Test {
 	type Event = Event;
 	type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = Cennzx;
 }
 
 impl Config for Test {
@@ -102,9 +110,54 @@ impl Config for Test {
 	type AssetId = AssetId;
 	type ExchangeAddressFor = ExchangeAddressGenerator<Self>;
 	type MultiCurrency = GenericAsset;
+	type TreasuryPalletId = TreasuryPalletId;
+	type MaxPriceObservations = MaxPriceObservations;
+	type KeeperFeeRate = KeeperFeeRate;
+	type AddressMapping = PrefixedAddressMapping<AccountId>;
+	type ContractCaller = MockContractCaller;
+	type GasWeightMapping = MockGasWeightMapping;
 	type WeightInfo = ();
 }
 
+pub struct MockGasWeightMapping;
+impl pallet_evm::GasWeightMapping for MockGasWeightMapping {
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas
+	}
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight
+	}
+}
+
+thread_local! {
+	/// Runs in place of the EVM when a contract callback is executed
+	static CONTRACT_CALLBACK: RefCell<Option<ContractCallback>> = RefCell::new(None);
+}
+
+/// Logic run in place of a contract call, given the caller, target and input, returning the gas used
+pub type ContractCallback = fn(&H160, &H160, &[u8]) -> Result<u64, DispatchError>;
+
+/// Set the logic run by contract callbacks
+pub fn set_contract_callback(callback: ContractCallback) {
+	CONTRACT_CALLBACK.with(|c| *c.borrow_mut() = Some(callback));
+}
+
+pub struct MockContractCaller;
+impl ContractCaller for MockContractCaller {
+	type Address = H160;
+	fn call(
+		caller: &Self::Address,
+		target: &Self::Address,
+		input: &[u8],
+		_gas_limit: u64,
+	) -> Result<u64, DispatchError> {
+		match CONTRACT_CALLBACK.with(|c| *c.borrow()) {
+			Some(callback) => callback(caller, target, input),
+			None => Ok(0),
+		}
+	}
+}
+
 pub struct ExtBuilder {
 	core_asset_id: u32,
 	fee_rate: FeeRate<PerMillion>,
@@ -19,17 +19,30 @@
 
 use crate::{
 	assert_balance_eq, assert_exchange_balance_eq,
+	migration::{storage_v0, MAX_LIQUIDITY_MIGRATIONS_PER_BLOCK},
 	mock::{
-		last_event, AccountId, Cennzx, Event, ExtBuilder, Origin, Test, CORE_ASSET_ID, TRADE_ASSET_A_ID,
-		TRADE_ASSET_B_ID,
+		last_event, set_contract_callback, AccountId, AssetId, Balance, Cennzx, Event, ExtBuilder, GenericAsset,
+		Origin, System, Test, TreasuryPalletId, CORE_ASSET_ID, FEE_ASSET_ID, TRADE_ASSET_A_ID, TRADE_ASSET_B_ID,
 	},
-	types::{FeeRate, LowPrecisionUnsigned, PerMillion, PerThousand},
-	with_account, with_exchange, Error, ExchangeAddressFor, RawEvent,
+	types::{
+		ExchangeCurve, FeeRate, HighPrecisionUnsigned, LimitOrder, LowPrecisionUnsigned, PerMillion, PerThousand,
+		Releases, RewardProgram, MAX_AMPLIFICATION, PRICE_SCALE,
+	},
+	weights::WeightInfo,
+	with_account, with_exchange, Error, ExchangeAddressFor, RawEvent, StorageVersion, TotalLiquidity,
 };
 use core::convert::TryFrom;
-use crml_support::MultiCurrency;
+use crml_generic_asset::AssetInfo;
+use crml_support::{MultiCurrency, PrefixedAddressMapping, H160};
 use frame_support::traits::{LockableCurrency, WithdrawReasons};
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	traits::{Get, OnIdle, OnInitialize, OnRuntimeUpgrade},
+	weights::Weight,
+	IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use pallet_evm::AddressMapping;
+use sp_runtime::{traits::AccountIdConversion, DispatchError};
 
 #[test]
 fn investor_can_add_liquidity() {
@@ -191,12 +204,12 @@ fn remove_liquidity_fails_with_empty_pool() {
 fn calculate_buy_price_zero_cases() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_err!(
-			Cennzx::calculate_buy_price(100, 0, 10),
+			Cennzx::calculate_buy_price(100, 0, 10, Cennzx::fee_rate()),
 			Error::<Test>::EmptyExchangePool
 		);
 
 		assert_err!(
-			Cennzx::calculate_buy_price(100, 10, 0),
+			Cennzx::calculate_buy_price(100, 10, 0, Cennzx::fee_rate()),
 			Error::<Test>::EmptyExchangePool
 		);
 	});
@@ -207,10 +220,15 @@ fn calculate_buy_price_zero_cases() {
 #[test]
 fn calculate_buy_price_for_valid_data() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Cennzx::calculate_buy_price(123, 1000, 1000), 141);
+		assert_ok!(Cennzx::calculate_buy_price(123, 1000, 1000, Cennzx::fee_rate()), 141);
 
 		assert_ok!(
-			Cennzx::calculate_buy_price(100_000_000_000_000, 120_627_710_511_649_660, 20_627_710_511_649_660,),
+			Cennzx::calculate_buy_price(
+				100_000_000_000_000,
+				120_627_710_511_649_660,
+				20_627_710_511_649_660,
+				Cennzx::fee_rate()
+			),
 			589396433540516
 		);
 	});
@@ -226,6 +244,7 @@ fn calculate_buy_price_for_max_reserve_balance() {
 				LowPrecisionUnsigned::max_value() / 2,
 				LowPrecisionUnsigned::max_value() / 2,
 				LowPrecisionUnsigned::max_value(),
+				Cennzx::fee_rate(),
 			),
 			170651607010850639426882365627031758044
 		);
@@ -243,6 +262,7 @@ fn calculate_buy_price_should_fail_with_max_reserve_and_max_amount() {
 				LowPrecisionUnsigned::max_value() - 100,
 				LowPrecisionUnsigned::max_value(),
 				LowPrecisionUnsigned::max_value(),
+				Cennzx::fee_rate(),
 			),
 			Error::<Test>::Overflow
 		);
@@ -257,12 +277,12 @@ fn calculate_buy_price_max_withdrawal() {
 		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
 
 		assert_err!(
-			Cennzx::calculate_buy_price(1000, 1000, 1000),
+			Cennzx::calculate_buy_price(1000, 1000, 1000, Cennzx::fee_rate()),
 			Error::<Test>::InsufficientExchangePoolReserve
 		);
 
 		assert_err!(
-			Cennzx::calculate_buy_price(1_000_000, 1000, 1000),
+			Cennzx::calculate_buy_price(1_000_000, 1000, 1000, Cennzx::fee_rate()),
 			Error::<Test>::InsufficientExchangePoolReserve
 		);
 	});
@@ -1019,16 +1039,21 @@ fn core_to_asset_transfer_buy_10_to_1000() {
 #[test]
 fn calculate_sell_price_for_valid_data() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(Cennzx::calculate_sell_price(123, 1000, 1000), 108);
+		assert_ok!(Cennzx::calculate_sell_price(123, 1000, 1000, Cennzx::fee_rate()), 108);
 
 		// No f32/f64 types, so we use large values to test precision
 		assert_ok!(
-			Cennzx::calculate_sell_price(123_000_000, 1_000_000_000, 1_000_000_000),
+			Cennzx::calculate_sell_price(123_000_000, 1_000_000_000, 1_000_000_000, Cennzx::fee_rate()),
 			109236233
 		);
 
 		assert_ok!(
-			Cennzx::calculate_sell_price(100_000_000_000_000, 120_627_710_511_649_660, 4_999_727_416_279_531_363),
+			Cennzx::calculate_sell_price(
+				100_000_000_000_000,
+				120_627_710_511_649_660,
+				4_999_727_416_279_531_363,
+				Cennzx::fee_rate()
+			),
 			4128948876492407
 		);
 
@@ -1036,7 +1061,8 @@ fn calculate_sell_price_for_valid_data() {
 			Cennzx::calculate_sell_price(
 				100_000_000_000_000,
 				120_627_710_511_649_660,
-				LowPrecisionUnsigned::max_value()
+				LowPrecisionUnsigned::max_value(),
+				Cennzx::fee_rate()
 			),
 			281017019450612581324176880746747822
 		);
@@ -1053,7 +1079,8 @@ fn calculate_sell_price_for_max_reserve_balance() {
 			Cennzx::calculate_sell_price(
 				LowPrecisionUnsigned::max_value() / 2,
 				LowPrecisionUnsigned::max_value() / 2,
-				LowPrecisionUnsigned::max_value()
+				LowPrecisionUnsigned::max_value(),
+				Cennzx::fee_rate()
 			),
 			169886353929574869427545984738775941814
 		);
@@ -1833,3 +1860,1643 @@ fn execute_trade_calculate_sale_price_fails() {
 		assert_exchange_balance_eq!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
 	});
 }
+
+#[test]
+fn set_exchange_fee_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+
+		let fee_rate: FeeRate<PerMillion> = 100_000.into();
+		assert_ok!(Cennzx::set_exchange_fee_rate(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			Some(fee_rate)
+		));
+		assert_eq!(
+			Cennzx::exchange_fee_rate((CORE_ASSET_ID, TRADE_ASSET_A_ID)),
+			Some(fee_rate)
+		);
+		assert_eq!(Cennzx::effective_fee_rate(TRADE_ASSET_A_ID), fee_rate);
+		assert_eq!(Cennzx::effective_fee_rate(TRADE_ASSET_B_ID), Cennzx::fee_rate());
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::ExchangeFeeRateUpdated(TRADE_ASSET_A_ID, Some(fee_rate)))
+		);
+
+		// the override applies to its exchange only
+		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID), Ok(123));
+		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_B_ID), Ok(112));
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_A_ID, 100, CORE_ASSET_ID), Ok(82));
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_B_ID, 100, CORE_ASSET_ID), Ok(90));
+
+		// clearing the override reverts to the default fee rate
+		assert_ok!(Cennzx::set_exchange_fee_rate(Origin::root(), TRADE_ASSET_A_ID, None));
+		assert_eq!(Cennzx::exchange_fee_rate((CORE_ASSET_ID, TRADE_ASSET_A_ID)), None);
+		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID), Ok(112));
+	});
+}
+
+#[test]
+fn set_exchange_fee_rate_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let trader = with_account!(CORE_ASSET_ID => 10, TRADE_ASSET_A_ID => 10);
+		assert_noop!(
+			Cennzx::set_exchange_fee_rate(Origin::signed(trader), TRADE_ASSET_A_ID, Some(0.into())),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cennzx::set_exchange_fee_rate(Origin::root(), CORE_ASSET_ID, Some(0.into())),
+			Error::<Test>::InvalidAssetId
+		);
+	});
+}
+
+#[test]
+fn set_protocol_fee_share() {
+	ExtBuilder::default().build().execute_with(|| {
+		let share: FeeRate<PerMillion> = 500_000.into();
+		assert_ok!(Cennzx::set_protocol_fee_share(Origin::root(), Some(share)));
+		assert_eq!(Cennzx::protocol_fee_share(), Some(share));
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::ProtocolFeeShareUpdated(Some(share)))
+		);
+
+		assert_noop!(
+			Cennzx::set_protocol_fee_share(Origin::root(), Some(1_000_001.into())),
+			Error::<Test>::InvalidProtocolFeeShare
+		);
+
+		assert_ok!(Cennzx::set_protocol_fee_share(Origin::root(), None));
+		assert_eq!(Cennzx::protocol_fee_share(), None);
+	});
+}
+
+#[test]
+fn protocol_fee_is_paid_to_treasury() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let trader = with_account!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = Cennzx::set_fee_rate(Origin::root(), 100_000.into());
+		assert_ok!(Cennzx::set_protocol_fee_share(Origin::root(), Some(500_000.into())));
+
+		// the protocol share does not change the quoted price
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_A_ID, 110, CORE_ASSET_ID), Ok(90));
+		assert_ok!(Cennzx::sell_asset(
+			Origin::signed(trader.clone()),
+			None,
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			110,
+			90,
+		));
+
+		// trading fee = 110 * 0.1 / 1.1 = 10, the treasury takes half
+		assert_balance_eq!(treasury, TRADE_ASSET_A_ID => 5);
+		assert_balance_eq!(trader, TRADE_ASSET_A_ID => 890);
+		assert_balance_eq!(trader, CORE_ASSET_ID => 1090);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 910, TRADE_ASSET_A_ID => 1105);
+		assert!(System::events().iter().any(|record| record.event
+			== Event::Cennzx(RawEvent::ProtocolFeeCollected(TRADE_ASSET_A_ID, TRADE_ASSET_A_ID, 5))));
+	});
+}
+
+#[test]
+fn protocol_fee_is_paid_on_each_exchange_of_an_asset_to_asset_trade() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader = with_account!(TRADE_ASSET_A_ID => 1000, TRADE_ASSET_B_ID => 0);
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = Cennzx::set_fee_rate(Origin::root(), 100_000.into());
+		assert_ok!(Cennzx::set_protocol_fee_share(Origin::root(), Some(1_000_000.into())));
+
+		assert_ok!(
+			Cennzx::execute_sell(&trader, &trader, TRADE_ASSET_A_ID, TRADE_ASSET_B_ID, 110, 1),
+			74
+		);
+
+		// exchange A is paid 110 A and sends 90 core to exchange B
+		// fees: 110 * 0.1 / 1.1 = 10 A and 90 * 0.1 / 1.1 = 8 core
+		assert_balance_eq!(treasury, TRADE_ASSET_A_ID => 10);
+		assert_balance_eq!(treasury, CORE_ASSET_ID => 8);
+		assert_balance_eq!(trader, TRADE_ASSET_B_ID => 74);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 910, TRADE_ASSET_A_ID => 1100);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1082, TRADE_ASSET_B_ID => 926);
+	});
+}
+
+#[test]
+fn twap_accumulates_end_of_block_prices() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let trader = with_account!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let _ = Cennzx::set_fee_rate(Origin::root(), 0.into());
+
+		// block 1: exchange moves to 910 core / 1100 asset
+		assert_ok!(
+			Cennzx::execute_sell(&trader, &trader, TRADE_ASSET_A_ID, CORE_ASSET_ID, 100, 1),
+			90
+		);
+		assert_eq!(Cennzx::twap(TRADE_ASSET_A_ID, 1), None);
+
+		System::set_block_number(11);
+		assert_eq!(
+			Cennzx::twap(TRADE_ASSET_A_ID, 10),
+			Some((
+				827_272_727_272_727_272_u128.into(),
+				1_208_791_208_791_208_791_u128.into()
+			))
+		);
+
+		// block 11: exchange moves to 1000 core / 1001 asset
+		assert_ok!(
+			Cennzx::execute_sell(&trader, &trader, CORE_ASSET_ID, TRADE_ASSET_A_ID, 90, 1),
+			99
+		);
+		// trades in the current block are excluded
+		assert_eq!(
+			Cennzx::twap(TRADE_ASSET_A_ID, 10),
+			Some((
+				827_272_727_272_727_272_u128.into(),
+				1_208_791_208_791_208_791_u128.into()
+			))
+		);
+
+		System::set_block_number(21);
+		assert_eq!(
+			Cennzx::twap(TRADE_ASSET_A_ID, 10),
+			Some((
+				999_000_999_000_999_000_u128.into(),
+				1_001_000_000_000_000_000_u128.into()
+			))
+		);
+		assert_eq!(
+			Cennzx::twap(TRADE_ASSET_A_ID, 20),
+			Some((
+				913_136_863_136_863_136_u128.into(),
+				1_104_895_604_395_604_395_u128.into()
+			))
+		);
+		// window starts between observations
+		assert_eq!(
+			Cennzx::twap(TRADE_ASSET_A_ID, 15),
+			Some((
+				941_758_241_758_241_757_u128.into(),
+				1_070_263_736_263_736_263_u128.into()
+			))
+		);
+		assert_eq!(Cennzx::twap(TRADE_ASSET_A_ID, 21), None);
+		assert_eq!(Cennzx::twap(TRADE_ASSET_A_ID, 0), None);
+		assert_eq!(Cennzx::twap(TRADE_ASSET_B_ID, 10), None);
+	});
+}
+
+#[test]
+fn twap_observations_are_bounded() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let investor = with_account!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+
+		for block_number in 1..=6 {
+			System::set_block_number(block_number);
+			assert_ok!(Cennzx::add_liquidity(
+				Origin::signed(investor.clone()),
+				TRADE_ASSET_A_ID,
+				0,
+				100,
+				10
+			));
+		}
+
+		// `MaxPriceObservations` is 4, observations for blocks 3 to 6 are retained
+		assert_eq!(
+			Cennzx::price_observation_cursor((CORE_ASSET_ID, TRADE_ASSET_A_ID)),
+			(1, 4)
+		);
+		System::set_block_number(7);
+		assert!(Cennzx::twap(TRADE_ASSET_A_ID, 4).is_some());
+		assert_eq!(Cennzx::twap(TRADE_ASSET_A_ID, 5), None);
+	});
+}
+
+#[test]
+fn buy_asset_via_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader: AccountId = with_account!(CORE_ASSET_ID => 2200, TRADE_ASSET_A_ID => 2200);
+		let path = vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID];
+
+		assert_eq!(Cennzx::get_buy_price_via_path(&path, 150), Ok(vec![216, 176, 150]));
+		assert_ok!(Cennzx::buy_asset_via_path(
+			Origin::signed(trader.clone()),
+			None,
+			path,
+			150,
+			216,
+		));
+
+		// matches a direct asset to asset trade
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 824, TRADE_ASSET_A_ID => 1216);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1176, TRADE_ASSET_B_ID => 850);
+		assert_balance_eq!(trader, TRADE_ASSET_A_ID => 1984);
+		assert_balance_eq!(trader, TRADE_ASSET_B_ID => 150);
+		assert_balance_eq!(trader, CORE_ASSET_ID => 2200);
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::AssetBought(
+				TRADE_ASSET_A_ID,
+				TRADE_ASSET_B_ID,
+				trader,
+				216,
+				150
+			))
+		);
+	});
+}
+
+#[test]
+fn buy_asset_via_path_respects_maximum_sell() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader: AccountId = with_account!(CORE_ASSET_ID => 2200, TRADE_ASSET_A_ID => 2200);
+
+		assert_noop!(
+			Cennzx::buy_asset_via_path(
+				Origin::signed(trader),
+				None,
+				vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID],
+				150,
+				215,
+			),
+			Error::<Test>::MaximumSellRequirementNotMet
+		);
+	});
+}
+
+#[test]
+fn sell_asset_via_path() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader: AccountId = with_account!(CORE_ASSET_ID => 2200, TRADE_ASSET_A_ID => 2200);
+		let recipient: AccountId = with_account!("bob", CORE_ASSET_ID => 100, TRADE_ASSET_B_ID => 100);
+
+		assert_ok!(Cennzx::sell_asset_via_path(
+			Origin::signed(trader.clone()),
+			Some(recipient.clone()),
+			vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID],
+			150,
+			113,
+		));
+
+		// matches a direct asset to asset trade
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 871, TRADE_ASSET_A_ID => 1150);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1129, TRADE_ASSET_B_ID => 887);
+		assert_balance_eq!(trader, TRADE_ASSET_A_ID => 2050);
+		assert_balance_eq!(trader, CORE_ASSET_ID => 2200);
+		assert_balance_eq!(recipient, TRADE_ASSET_B_ID => 213);
+		assert_balance_eq!(recipient, CORE_ASSET_ID => 100);
+	});
+}
+
+#[test]
+fn sell_asset_via_path_respects_minimum_buy() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader: AccountId = with_account!(CORE_ASSET_ID => 2200, TRADE_ASSET_A_ID => 2200);
+
+		assert_noop!(
+			Cennzx::sell_asset_via_path(
+				Origin::signed(trader),
+				None,
+				vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID],
+				150,
+				114,
+			),
+			Error::<Test>::MinimumBuyRequirementNotMet
+		);
+	});
+}
+
+#[test]
+fn trade_via_path_rejects_invalid_paths() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+		let trader: AccountId = with_account!(CORE_ASSET_ID => 2200, TRADE_ASSET_A_ID => 2200);
+
+		for path in vec![
+			vec![],
+			vec![TRADE_ASSET_A_ID],
+			// no exchange between trade assets
+			vec![TRADE_ASSET_A_ID, TRADE_ASSET_B_ID],
+			// exchange A is used twice
+			vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_A_ID],
+			vec![CORE_ASSET_ID, TRADE_ASSET_A_ID, CORE_ASSET_ID],
+		] {
+			assert_noop!(
+				Cennzx::sell_asset_via_path(Origin::signed(trader.clone()), None, path, 100, 1),
+				Error::<Test>::InvalidPath
+			);
+		}
+		assert_noop!(
+			Cennzx::buy_asset_via_path(
+				Origin::signed(trader),
+				None,
+				vec![TRADE_ASSET_A_ID, TRADE_ASSET_A_ID],
+				100,
+				1000
+			),
+			Error::<Test>::AssetCannotSwapForItself
+		);
+	});
+}
+
+#[test]
+fn best_route() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+
+		assert_eq!(
+			Cennzx::best_buy_route(TRADE_ASSET_B_ID, 150, TRADE_ASSET_A_ID),
+			Ok((vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID], 216))
+		);
+		assert_eq!(
+			Cennzx::best_sell_route(TRADE_ASSET_A_ID, 150, TRADE_ASSET_B_ID),
+			Ok((vec![TRADE_ASSET_A_ID, CORE_ASSET_ID, TRADE_ASSET_B_ID], 113))
+		);
+		assert_eq!(
+			Cennzx::best_sell_route(CORE_ASSET_ID, 100, TRADE_ASSET_B_ID),
+			Ok((
+				vec![CORE_ASSET_ID, TRADE_ASSET_B_ID],
+				Cennzx::get_sell_price(CORE_ASSET_ID, 100, TRADE_ASSET_B_ID).unwrap()
+			))
+		);
+		assert_err!(
+			Cennzx::best_buy_route(TRADE_ASSET_B_ID, 1000, TRADE_ASSET_A_ID),
+			Error::<Test>::InsufficientExchangePoolReserve
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_mints_liquidity_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor: AccountId = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A_ID => 100);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		assert_eq!(Cennzx::liquidity_asset_id(exchange_key), None);
+
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			2,
+			15,
+			10
+		));
+		let liquidity_asset_id = Cennzx::liquidity_asset_id(exchange_key).expect("liquidity asset created");
+		assert!(System::events().iter().any(|record| record.event
+			== Event::Cennzx(RawEvent::LiquidityAssetCreated(TRADE_ASSET_A_ID, liquidity_asset_id))));
+		assert_eq!(
+			GenericAsset::asset_meta(liquidity_asset_id).symbol(),
+			b"CENNZX-LP-2".to_vec()
+		);
+
+		// the existing liquidity asset is reused
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			2,
+			16,
+			10
+		));
+		assert_eq!(Cennzx::liquidity_asset_id(exchange_key), Some(liquidity_asset_id));
+		assert_balance_eq!(investor, liquidity_asset_id => 20);
+		assert_eq!(GenericAsset::total_issuance(liquidity_asset_id), 20);
+		assert_eq!(Cennzx::total_liquidity(exchange_key), 20);
+
+		assert_ok!(Cennzx::remove_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			5,
+			1,
+			1
+		));
+		assert_balance_eq!(investor, liquidity_asset_id => 15);
+		assert_eq!(GenericAsset::total_issuance(liquidity_asset_id), 15);
+		assert_eq!(Cennzx::total_liquidity(exchange_key), 15);
+	});
+}
+
+#[test]
+fn liquidity_asset_is_transferable() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor: AccountId = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A_ID => 100);
+		let bob: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			2,
+			15,
+			10
+		));
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		let liquidity_asset_id = Cennzx::liquidity_asset_id(exchange_key).unwrap();
+
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(investor.clone()),
+			liquidity_asset_id,
+			bob.clone(),
+			4
+		));
+		assert_eq!(Cennzx::liquidity_balance(exchange_key, &investor), 6);
+		assert_eq!(Cennzx::liquidity_balance(exchange_key, &bob), 4);
+
+		// the new holder can withdraw, the previous holder can't exceed their remaining liquidity
+		assert_noop!(
+			Cennzx::remove_liquidity(Origin::signed(investor.clone()), TRADE_ASSET_A_ID, 7, 1, 1),
+			Error::<Test>::InsufficientLiquidity
+		);
+		assert_ok!(Cennzx::remove_liquidity(
+			Origin::signed(bob.clone()),
+			TRADE_ASSET_A_ID,
+			4,
+			1,
+			1
+		));
+		assert_balance_eq!(bob, CORE_ASSET_ID => 4);
+		assert_balance_eq!(bob, TRADE_ASSET_A_ID => 6);
+		assert_eq!(GenericAsset::total_issuance(liquidity_asset_id), 6);
+		assert_eq!(Cennzx::total_liquidity(exchange_key), 6);
+	});
+}
+
+#[test]
+fn migrate_liquidity_balances_to_liquidity_assets() {
+	ExtBuilder::default().build().execute_with(|| {
+		let andrea: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let bob: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let exchange_a = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		let exchange_b = (CORE_ASSET_ID, TRADE_ASSET_B_ID);
+		StorageVersion::put(Releases::V0 as u32);
+		storage_v0::LiquidityBalance::<Test>::insert(exchange_a, &andrea, 100);
+		storage_v0::LiquidityBalance::<Test>::insert(exchange_a, &bob, 50);
+		storage_v0::LiquidityBalance::<Test>::insert(exchange_b, &bob, 30);
+		<TotalLiquidity<Test>>::insert(exchange_a, 150);
+		<TotalLiquidity<Test>>::insert(exchange_b, 30);
+
+		<Cennzx as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V1 as u32);
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::iter().count(), 0);
+		let liquidity_asset_a = Cennzx::liquidity_asset_id(exchange_a).unwrap();
+		let liquidity_asset_b = Cennzx::liquidity_asset_id(exchange_b).unwrap();
+		assert_ne!(liquidity_asset_a, liquidity_asset_b);
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &andrea), 100);
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &bob), 50);
+		assert_eq!(Cennzx::liquidity_balance(exchange_b, &bob), 30);
+		assert_eq!(
+			GenericAsset::total_issuance(liquidity_asset_a),
+			Cennzx::total_liquidity(exchange_a)
+		);
+		assert_eq!(
+			GenericAsset::total_issuance(liquidity_asset_b),
+			Cennzx::total_liquidity(exchange_b)
+		);
+
+		// runs once
+		<Cennzx as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &andrea), 100);
+		assert_eq!(GenericAsset::total_issuance(liquidity_asset_a), 150);
+	});
+}
+
+#[test]
+fn migrate_liquidity_balances_retries_when_liquidity_asset_unavailable() {
+	ExtBuilder::default().build().execute_with(|| {
+		let andrea: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let exchange_a = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		StorageVersion::put(Releases::V0 as u32);
+		storage_v0::LiquidityBalance::<Test>::insert(exchange_a, &andrea, 100);
+		<TotalLiquidity<Test>>::insert(exchange_a, 100);
+
+		// asset ids are exhausted, the liquidity asset can't be created
+		let next_asset_id = GenericAsset::next_asset_id();
+		crml_generic_asset::NextAssetId::<Test>::put(AssetId::MAX);
+		<Cennzx as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V0 as u32);
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::get(exchange_a, &andrea), 100);
+
+		// the next upgrade completes the migration
+		crml_generic_asset::NextAssetId::<Test>::put(next_asset_id);
+		<Cennzx as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V1 as u32);
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::iter().count(), 0);
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &andrea), 100);
+	});
+}
+
+#[test]
+fn migrate_liquidity_balances_over_multiple_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let exchange_a = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		let holding_count = MAX_LIQUIDITY_MIGRATIONS_PER_BLOCK + 2;
+		let total_liquidity = 10 * holding_count as Balance;
+		with_exchange!(CORE_ASSET_ID => total_liquidity, TRADE_ASSET_A_ID => total_liquidity);
+		StorageVersion::put(Releases::V0 as u32);
+		for i in 0..holding_count {
+			storage_v0::LiquidityBalance::<Test>::insert(exchange_a, AccountId::new([i as u8; 32]), 10);
+		}
+		<TotalLiquidity<Test>>::insert(exchange_a, total_liquidity);
+
+		// only the maximum is migrated by the upgrade
+		<Cennzx as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(StorageVersion::get(), Releases::V0 as u32);
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::iter().count(), 2);
+
+		// unmigrated holdings are counted and may be withdrawn
+		let (_, unmigrated, _) = storage_v0::LiquidityBalance::<Test>::iter().next().unwrap();
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &unmigrated), 10);
+		assert_eq!(
+			Cennzx::account_liquidity_value(&unmigrated, TRADE_ASSET_A_ID).liquidity,
+			10
+		);
+		assert_ok!(Cennzx::remove_liquidity(
+			Origin::signed(unmigrated.clone()),
+			TRADE_ASSET_A_ID,
+			10,
+			1,
+			1
+		));
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::get(exchange_a, &unmigrated), 0);
+		assert_eq!(Cennzx::liquidity_balance(exchange_a, &unmigrated), 0);
+		assert_balance_eq!(unmigrated, CORE_ASSET_ID => 10);
+
+		// the remainder is migrated next block
+		Cennzx::on_initialize(2);
+		assert_eq!(StorageVersion::get(), Releases::V1 as u32);
+		assert_eq!(storage_v0::LiquidityBalance::<Test>::iter().count(), 0);
+		let liquidity_asset_a = Cennzx::liquidity_asset_id(exchange_a).unwrap();
+		assert_eq!(
+			GenericAsset::total_issuance(liquidity_asset_a),
+			Cennzx::total_liquidity(exchange_a)
+		);
+		for i in 0..holding_count {
+			let who = AccountId::new([i as u8; 32]);
+			if who != unmigrated {
+				assert_eq!(Cennzx::liquidity_balance(exchange_a, &who), 10);
+			}
+		}
+	});
+}
+
+#[test]
+fn place_order_reserves_sell_amount() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+
+		assert_ok!(Cennzx::place_order(
+			Origin::signed(owner.clone()),
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			95,
+			10
+		));
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::OrderPlaced(
+				0,
+				owner.clone(),
+				TRADE_ASSET_A_ID,
+				CORE_ASSET_ID,
+				100,
+				95,
+				10
+			))
+		);
+		assert_eq!(
+			Cennzx::orders(0),
+			Some(LimitOrder {
+				owner: owner.clone(),
+				asset_to_sell: TRADE_ASSET_A_ID,
+				asset_to_buy: CORE_ASSET_ID,
+				sell_amount: 100,
+				minimum_buy: 95,
+				expiry: 10,
+			})
+		);
+		assert_eq!(Cennzx::next_order_id(), 1);
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 400);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 100);
+	});
+}
+
+#[test]
+fn place_order_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 50);
+		let place = |sell, buy, sell_amount, minimum_buy, expiry| {
+			Cennzx::place_order(
+				Origin::signed(owner.clone()),
+				sell,
+				buy,
+				sell_amount,
+				minimum_buy,
+				expiry,
+			)
+		};
+
+		assert_noop!(
+			place(TRADE_ASSET_A_ID, TRADE_ASSET_A_ID, 10, 10, 10),
+			Error::<Test>::AssetCannotSwapForItself
+		);
+		assert_noop!(
+			place(TRADE_ASSET_A_ID, CORE_ASSET_ID, 0, 10, 10),
+			Error::<Test>::CannotTradeZero
+		);
+		assert_noop!(
+			place(TRADE_ASSET_A_ID, CORE_ASSET_ID, 10, 0, 10),
+			Error::<Test>::CannotTradeZero
+		);
+		assert_noop!(
+			place(TRADE_ASSET_A_ID, CORE_ASSET_ID, 10, 10, 1),
+			Error::<Test>::InvalidOrderExpiry
+		);
+		assert!(place(TRADE_ASSET_A_ID, CORE_ASSET_ID, 51, 10, 10).is_err());
+		assert_eq!(Cennzx::next_order_id(), 0);
+	});
+}
+
+#[test]
+fn cancel_order() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		let bob: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		assert_ok!(Cennzx::place_order(
+			Origin::signed(owner.clone()),
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			95,
+			10
+		));
+
+		assert_noop!(
+			Cennzx::cancel_order(Origin::signed(bob), 0),
+			Error::<Test>::NotOrderOwner
+		);
+		assert_noop!(
+			Cennzx::cancel_order(Origin::signed(owner.clone()), 1),
+			Error::<Test>::OrderNotFound
+		);
+		assert_ok!(Cennzx::cancel_order(Origin::signed(owner.clone()), 0));
+		assert_eq!(last_event(), Event::Cennzx(RawEvent::OrderCancelled(0)));
+		assert_eq!(Cennzx::orders(0), None);
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 500);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 0);
+	});
+}
+
+#[test]
+fn fill_order_pays_keeper() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		let keeper: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let trader: AccountId = with_account!("charlie", CORE_ASSET_ID => 200, TRADE_ASSET_A_ID => 0);
+		assert_ok!(Cennzx::place_order(
+			Origin::signed(owner.clone()),
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			95,
+			10
+		));
+
+		// 100 A sells for 90 core, below the limit
+		assert_noop!(
+			Cennzx::fill_order(Origin::signed(keeper.clone()), 0, 100),
+			Error::<Test>::MinimumBuyRequirementNotMet
+		);
+		assert_noop!(
+			Cennzx::fill_order(Origin::signed(keeper.clone()), 0, 101),
+			Error::<Test>::InvalidFillAmount
+		);
+
+		// the price of A rises
+		assert_ok!(Cennzx::sell_asset(
+			Origin::signed(trader),
+			None,
+			CORE_ASSET_ID,
+			TRADE_ASSET_A_ID,
+			200,
+			1
+		));
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1200, TRADE_ASSET_A_ID => 835);
+
+		// 100 A sells for 127 core, 1% to the keeper
+		assert_ok!(Cennzx::fill_order(Origin::signed(keeper.clone()), 0, 100));
+		assert_eq!(last_event(), Event::Cennzx(RawEvent::OrderFilled(0, 100, 126, 1)));
+		assert_eq!(Cennzx::orders(0), None);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1073, TRADE_ASSET_A_ID => 935);
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 400);
+		assert_balance_eq!(owner, CORE_ASSET_ID => 126);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 0);
+		assert_balance_eq!(keeper, CORE_ASSET_ID => 1);
+
+		assert_noop!(
+			Cennzx::fill_order(Origin::signed(keeper), 0, 100),
+			Error::<Test>::OrderNotFound
+		);
+	});
+}
+
+#[test]
+fn fill_order_fails_after_expiry() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		assert_ok!(Cennzx::place_order(
+			Origin::signed(owner.clone()),
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			50,
+			10
+		));
+
+		System::set_block_number(11);
+		assert_noop!(
+			Cennzx::fill_order(Origin::signed(owner), 0, 100),
+			Error::<Test>::OrderExpired
+		);
+	});
+}
+
+#[test]
+fn partially_filled_order_is_matched_on_idle() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		let keeper: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let trader: AccountId = with_account!("charlie", CORE_ASSET_ID => 200, TRADE_ASSET_A_ID => 0);
+		assert_ok!(Cennzx::place_order(
+			Origin::signed(owner.clone()),
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			95,
+			10
+		));
+		// below the limit price, the order stays open
+		Cennzx::on_idle(1, Weight::max_value());
+		assert!(Cennzx::orders(0).is_some());
+
+		assert_ok!(Cennzx::sell_asset(
+			Origin::signed(trader),
+			None,
+			CORE_ASSET_ID,
+			TRADE_ASSET_A_ID,
+			200,
+			1
+		));
+
+		// 40 A sells for 53 core, the limit for 40 is 38 (rounded up)
+		assert_ok!(Cennzx::fill_order(Origin::signed(keeper.clone()), 0, 40));
+		assert_eq!(
+			Cennzx::orders(0).map(|order| (order.sell_amount, order.minimum_buy)),
+			Some((60, 57))
+		);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 60);
+		assert_balance_eq!(owner, CORE_ASSET_ID => 53);
+
+		// no weight to spare
+		Cennzx::on_idle(2, 0);
+		assert!(Cennzx::orders(0).is_some());
+
+		// the remaining 60 A sell for 72 core, without a keeper fee
+		Cennzx::on_idle(2, Weight::max_value());
+		assert_eq!(last_event(), Event::Cennzx(RawEvent::OrderFilled(0, 60, 72, 0)));
+		assert_eq!(Cennzx::orders(0), None);
+		assert_balance_eq!(owner, CORE_ASSET_ID => 125);
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 400);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 0);
+	});
+}
+
+#[test]
+fn expired_orders_are_removed_on_idle() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		for expiry in [5, 10] {
+			assert_ok!(Cennzx::place_order(
+				Origin::signed(owner.clone()),
+				TRADE_ASSET_A_ID,
+				CORE_ASSET_ID,
+				100,
+				95,
+				expiry
+			));
+		}
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 300);
+
+		Cennzx::on_idle(6, Weight::max_value());
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::Cennzx(RawEvent::OrderExpired(0))));
+		assert_eq!(Cennzx::orders(0), None);
+		assert!(Cennzx::orders(1).is_some());
+		assert_balance_eq!(owner, TRADE_ASSET_A_ID => 400);
+		assert_eq!(GenericAsset::reserved_balance(TRADE_ASSET_A_ID, &owner), 100);
+	});
+}
+
+#[test]
+fn order_matching_resumes_from_cursor() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let owner: AccountId = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 500);
+		for _ in 0..3 {
+			assert_ok!(Cennzx::place_order(
+				Origin::signed(owner.clone()),
+				TRADE_ASSET_A_ID,
+				CORE_ASSET_ID,
+				100,
+				95,
+				5
+			));
+		}
+		// cancelled orders are not visited
+		assert_ok!(Cennzx::cancel_order(Origin::signed(owner.clone()), 1));
+
+		// spare weight for a single order
+		let order_weight = <Test as crate::Config>::WeightInfo::fill_order();
+		let cursor_weight = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1);
+		let consumed = Cennzx::on_idle(6, cursor_weight + order_weight);
+		assert_eq!(consumed, cursor_weight + order_weight);
+		assert_eq!(Cennzx::orders(0).is_some() as u8 + Cennzx::orders(2).is_some() as u8, 1);
+		assert!(Cennzx::order_match_cursor().is_some());
+
+		// the next block resumes with the remaining open order, then starts over
+		assert_eq!(
+			Cennzx::on_idle(7, cursor_weight + order_weight),
+			cursor_weight + order_weight
+		);
+		assert_eq!(Cennzx::orders(0), None);
+		assert_eq!(Cennzx::orders(2), None);
+		Cennzx::on_idle(8, Weight::max_value());
+		assert_eq!(Cennzx::order_match_cursor(), None);
+	});
+}
+
+#[test]
+fn create_exchange() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor: AccountId = with_account!(CORE_ASSET_ID => 2000, TRADE_ASSET_A_ID => 3000);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+		assert_eq!(Cennzx::exchange_curve(exchange_key), ExchangeCurve::ConstantProduct);
+
+		// the first deposit mints the StableSwap invariant as liquidity
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			curve,
+			2999,
+			2000,
+			1000
+		));
+		assert_eq!(Cennzx::exchange_curve(exchange_key), curve);
+		assert_eq!(Cennzx::total_liquidity(exchange_key), 2999);
+		assert_eq!(Cennzx::liquidity_balance(exchange_key, &investor), 2999);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 2000);
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::Cennzx(RawEvent::ExchangeCreated(TRADE_ASSET_A_ID, curve))));
+
+		// the exchange keeps its curve when more liquidity is added
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			1,
+			1000,
+			400
+		));
+		assert_eq!(Cennzx::exchange_curve(exchange_key), curve);
+
+		// an emptied exchange may be created again with another curve
+		assert_ok!(Cennzx::remove_liquidity(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			Cennzx::liquidity_balance(exchange_key, &investor),
+			1,
+			1
+		));
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor),
+			TRADE_ASSET_A_ID,
+			ExchangeCurve::ConstantProduct,
+			1,
+			100,
+			100
+		));
+		assert_eq!(Cennzx::exchange_curve(exchange_key), ExchangeCurve::ConstantProduct);
+	});
+}
+
+#[test]
+fn create_exchange_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let trader = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A_ID => 100);
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+		assert_noop!(
+			Cennzx::create_exchange(Origin::root(), TRADE_ASSET_A_ID, curve, 1, 10, 10),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cennzx::create_exchange(Origin::signed(trader.clone()), CORE_ASSET_ID, curve, 1, 10, 10),
+			Error::<Test>::InvalidAssetId
+		);
+		assert_noop!(
+			Cennzx::create_exchange(
+				Origin::signed(trader.clone()),
+				TRADE_ASSET_A_ID,
+				ExchangeCurve::StableSwap { amplification: 0 },
+				1,
+				10,
+				10
+			),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Cennzx::create_exchange(
+				Origin::signed(trader.clone()),
+				TRADE_ASSET_A_ID,
+				ExchangeCurve::StableSwap {
+					amplification: MAX_AMPLIFICATION + 1
+				},
+				1,
+				10,
+				10
+			),
+			Error::<Test>::InvalidAmplification
+		);
+		assert_noop!(
+			Cennzx::create_exchange(Origin::signed(trader.clone()), TRADE_ASSET_A_ID, curve, 1, 0, 10),
+			Error::<Test>::CannotAddLiquidityWithZero
+		);
+
+		// the curve is fixed once the exchange has liquidity
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(trader.clone()),
+			TRADE_ASSET_A_ID,
+			1,
+			10,
+			10
+		));
+		assert_noop!(
+			Cennzx::create_exchange(Origin::signed(trader), TRADE_ASSET_A_ID, curve, 1, 10, 10),
+			Error::<Test>::ExchangeExists
+		);
+	});
+}
+
+#[test]
+fn stable_swap_prices() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor = with_account!("bob", CORE_ASSET_ID => 2000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&investor, FEE_ASSET_ID, 1000);
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			curve,
+			1,
+			1000,
+			1000
+		));
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_B_ID => 1000);
+
+		// slippage on the stable exchange is much lower than on the constant product exchange
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_A_ID, 100, CORE_ASSET_ID), Ok(98));
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_B_ID, 100, CORE_ASSET_ID), Ok(90));
+		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_A_ID), Ok(101));
+		assert_eq!(Cennzx::get_buy_price(CORE_ASSET_ID, 100, TRADE_ASSET_B_ID), Ok(112));
+		assert_noop!(
+			Cennzx::get_buy_price(CORE_ASSET_ID, 1000, TRADE_ASSET_A_ID),
+			Error::<Test>::InsufficientExchangePoolReserve
+		);
+
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor),
+			FEE_ASSET_ID,
+			curve,
+			1,
+			1000,
+			1000
+		));
+		assert_eq!(Cennzx::get_sell_price(TRADE_ASSET_A_ID, 100, FEE_ASSET_ID), Ok(96));
+	});
+}
+
+#[test]
+fn stable_swap_sell_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor = with_account!("bob", CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor),
+			TRADE_ASSET_A_ID,
+			ExchangeCurve::StableSwap { amplification: 100 },
+			1,
+			1000,
+			1000
+		));
+		let trader = with_account!(CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 200);
+
+		assert_ok!(Cennzx::sell_asset(
+			Origin::signed(trader.clone()),
+			None,
+			TRADE_ASSET_A_ID,
+			CORE_ASSET_ID,
+			100,
+			98,
+		));
+		assert_balance_eq!(trader, CORE_ASSET_ID => 98);
+		assert_balance_eq!(trader, TRADE_ASSET_A_ID => 100);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 902, TRADE_ASSET_A_ID => 1100);
+	});
+}
+
+#[test]
+fn stable_swap_normalises_decimal_places() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the trade asset has 6 decimal places, the core asset has the default 4
+		crml_generic_asset::AssetMeta::<Test>::insert(TRADE_ASSET_B_ID, AssetInfo::new(b"B".to_vec(), 6, 1));
+		let investor = with_account!("bob", CORE_ASSET_ID => 10_000_000, TRADE_ASSET_B_ID => 1_000_000_000);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_B_ID);
+
+		// 1000 units of each asset
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor),
+			TRADE_ASSET_B_ID,
+			ExchangeCurve::StableSwap { amplification: 100 },
+			1,
+			1_000_000_000,
+			10_000_000
+		));
+		// liquidity is minted in the core asset's decimal places
+		assert_eq!(Cennzx::total_liquidity(exchange_key), 20_000_000);
+
+		// a unit of either asset trades for close to a unit of the other
+		assert_eq!(
+			Cennzx::get_sell_price(TRADE_ASSET_B_ID, 100_000_000, CORE_ASSET_ID),
+			Ok(996_509)
+		);
+		assert_eq!(
+			Cennzx::get_sell_price(CORE_ASSET_ID, 1_000_000, TRADE_ASSET_B_ID),
+			Ok(99_650_879)
+		);
+		assert_eq!(
+			Cennzx::get_buy_price(CORE_ASSET_ID, 1_000_000, TRADE_ASSET_B_ID),
+			Ok(100_350_425)
+		);
+		assert_eq!(
+			Cennzx::get_buy_price(TRADE_ASSET_B_ID, 100_000_000, CORE_ASSET_ID),
+			Ok(1_003_505)
+		);
+
+		// spot prices are per base unit of each asset
+		assert_eq!(
+			Cennzx::spot_prices(TRADE_ASSET_B_ID),
+			(
+				HighPrecisionUnsigned::from(PRICE_SCALE / 100),
+				HighPrecisionUnsigned::from(PRICE_SCALE * 100)
+			)
+		);
+	});
+}
+
+#[test]
+fn stable_swap_spot_prices() {
+	ExtBuilder::default().build().execute_with(|| {
+		let investor = with_account!("bob", CORE_ASSET_ID => 2000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&investor, TRADE_ASSET_B_ID, 2000);
+		let curve = ExchangeCurve::StableSwap { amplification: 100 };
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor.clone()),
+			TRADE_ASSET_A_ID,
+			curve,
+			1,
+			1000,
+			1000
+		));
+		assert_ok!(Cennzx::create_exchange(
+			Origin::signed(investor),
+			TRADE_ASSET_B_ID,
+			curve,
+			1,
+			2000,
+			1000
+		));
+
+		let scale = HighPrecisionUnsigned::from(PRICE_SCALE);
+		assert_eq!(Cennzx::spot_prices(TRADE_ASSET_A_ID), (scale, scale));
+		// the more plentiful trade asset trades slightly below the core asset
+		assert_eq!(
+			Cennzx::spot_prices(TRADE_ASSET_B_ID),
+			(
+				HighPrecisionUnsigned::from(995_820_694_978_888_749_u128),
+				HighPrecisionUnsigned::from(1_004_196_844_916_142_108_u128)
+			)
+		);
+	});
+}
+
+#[test]
+fn fund_reward_program() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = GenericAsset::deposit_creating(&treasury, TRADE_ASSET_B_ID, 10_000);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+		let rewards_account = Cennzx::rewards_account(&exchange_key);
+
+		assert_ok!(Cennzx::fund_reward_program(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			TRADE_ASSET_B_ID,
+			10,
+			101
+		));
+		assert_eq!(
+			Cennzx::reward_program(exchange_key),
+			Some(RewardProgram {
+				reward_asset: TRADE_ASSET_B_ID,
+				reward_per_block: 10,
+				end_block: 101,
+				last_update_block: 1,
+				reward_per_share: HighPrecisionUnsigned::zero(),
+			})
+		);
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::RewardProgramFunded(
+				TRADE_ASSET_A_ID,
+				TRADE_ASSET_B_ID,
+				10,
+				101
+			))
+		);
+		assert_balance_eq!(treasury, TRADE_ASSET_B_ID => 9_000);
+		assert_balance_eq!(rewards_account, TRADE_ASSET_B_ID => 1_000);
+
+		// shortening the program refunds the treasury for rewards no longer emitted
+		System::set_block_number(51);
+		assert_ok!(Cennzx::fund_reward_program(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			TRADE_ASSET_B_ID,
+			5,
+			61
+		));
+		assert_balance_eq!(treasury, TRADE_ASSET_B_ID => 9_450);
+		assert_balance_eq!(rewards_account, TRADE_ASSET_B_ID => 550);
+	});
+}
+
+#[test]
+fn fund_reward_program_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = GenericAsset::deposit_creating(&treasury, TRADE_ASSET_B_ID, 10_000);
+		let investor = with_account!(CORE_ASSET_ID => 100, TRADE_ASSET_A_ID => 100);
+
+		assert_noop!(
+			Cennzx::fund_reward_program(Origin::signed(investor), TRADE_ASSET_A_ID, TRADE_ASSET_B_ID, 10, 101),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cennzx::fund_reward_program(Origin::root(), CORE_ASSET_ID, TRADE_ASSET_B_ID, 10, 101),
+			Error::<Test>::InvalidAssetId
+		);
+		assert_noop!(
+			Cennzx::fund_reward_program(Origin::root(), TRADE_ASSET_A_ID, TRADE_ASSET_B_ID, 10, 1),
+			Error::<Test>::InvalidRewardProgram
+		);
+		assert_noop!(
+			Cennzx::fund_reward_program(Origin::root(), TRADE_ASSET_A_ID, TRADE_ASSET_B_ID, 0, 101),
+			Error::<Test>::InvalidRewardProgram
+		);
+
+		assert_ok!(Cennzx::fund_reward_program(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			TRADE_ASSET_B_ID,
+			10,
+			101
+		));
+		assert_noop!(
+			Cennzx::fund_reward_program(Origin::root(), TRADE_ASSET_A_ID, CORE_ASSET_ID, 10, 101),
+			Error::<Test>::RewardAssetMismatch
+		);
+	});
+}
+
+#[test]
+fn rewards_accrue_pro_rata_to_liquidity() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = GenericAsset::deposit_creating(&treasury, TRADE_ASSET_B_ID, 10_000);
+		let alice: AccountId = with_account!("andrea", CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let bob: AccountId = with_account!("bob", CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(alice.clone()),
+			TRADE_ASSET_A_ID,
+			1,
+			100,
+			100
+		));
+		assert_ok!(Cennzx::fund_reward_program(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			TRADE_ASSET_B_ID,
+			10,
+			101
+		));
+
+		// alice earns all rewards until bob provides an equal amount of liquidity
+		System::set_block_number(11);
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(bob.clone()),
+			TRADE_ASSET_A_ID,
+			1,
+			101,
+			100
+		));
+		assert_eq!(Cennzx::reward_stake(exchange_key, &alice).liquidity, 100);
+		assert_eq!(Cennzx::reward_stake(exchange_key, &bob).liquidity, 100);
+
+		System::set_block_number(21);
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::RewardsClaimed(
+				alice.clone(),
+				TRADE_ASSET_A_ID,
+				TRADE_ASSET_B_ID,
+				150
+			))
+		);
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(bob.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 150);
+		assert_balance_eq!(bob, TRADE_ASSET_B_ID => 50);
+
+		// bob's share falls after removing liquidity
+		System::set_block_number(31);
+		assert_ok!(Cennzx::remove_liquidity(
+			Origin::signed(bob.clone()),
+			TRADE_ASSET_A_ID,
+			50,
+			1,
+			1
+		));
+		assert_eq!(Cennzx::reward_stake(exchange_key, &bob).pending, 50);
+		assert_eq!(Cennzx::reward_stake(exchange_key, &bob).liquidity, 50);
+
+		// rewards stop at the end block
+		System::set_block_number(111);
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(bob.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 666);
+		assert_balance_eq!(bob, TRADE_ASSET_B_ID => 333);
+		assert_balance_eq!(Cennzx::rewards_account(&exchange_key), TRADE_ASSET_B_ID => 1);
+
+		// nothing more to claim
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 666);
+	});
+}
+
+#[test]
+fn transferred_liquidity_earns_rewards_from_the_transfer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let treasury: AccountId = TreasuryPalletId::get().into_account();
+		let _ = GenericAsset::deposit_creating(&treasury, TRADE_ASSET_B_ID, 10_000);
+		let alice: AccountId = with_account!("andrea", CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let bob: AccountId = with_account!("bob", CORE_ASSET_ID => 0, TRADE_ASSET_A_ID => 0);
+		let exchange_key = (CORE_ASSET_ID, TRADE_ASSET_A_ID);
+
+		assert_ok!(Cennzx::add_liquidity(
+			Origin::signed(alice.clone()),
+			TRADE_ASSET_A_ID,
+			1,
+			100,
+			100
+		));
+		assert_ok!(Cennzx::fund_reward_program(
+			Origin::root(),
+			TRADE_ASSET_A_ID,
+			TRADE_ASSET_B_ID,
+			10,
+			101
+		));
+
+		System::set_block_number(11);
+		let liquidity_asset_id = Cennzx::liquidity_asset_id(exchange_key).unwrap();
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(alice.clone()),
+			liquidity_asset_id,
+			bob.clone(),
+			50
+		));
+
+		// both sides are checkpointed by the transfer
+		assert_eq!(Cennzx::reward_stake(exchange_key, &alice).pending, 100);
+		assert_eq!(Cennzx::reward_stake(exchange_key, &alice).liquidity, 50);
+		assert_eq!(Cennzx::reward_stake(exchange_key, &bob).liquidity, 50);
+
+		System::set_block_number(21);
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(bob.clone()), TRADE_ASSET_A_ID));
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 150);
+		assert_balance_eq!(bob, TRADE_ASSET_B_ID => 50);
+
+		// claimed liquidity can't be claimed again by passing it back
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(bob.clone()),
+			liquidity_asset_id,
+			alice.clone(),
+			50
+		));
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 150);
+
+		System::set_block_number(31);
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(bob.clone()), TRADE_ASSET_A_ID));
+		assert_ok!(Cennzx::claim_rewards(Origin::signed(alice.clone()), TRADE_ASSET_A_ID));
+		assert_balance_eq!(alice, TRADE_ASSET_B_ID => 250);
+		assert_balance_eq!(bob, TRADE_ASSET_B_ID => 50);
+	});
+}
+
+/// The account of an EVM address
+fn evm_account(address: u64) -> AccountId {
+	PrefixedAddressMapping::<AccountId>::into_account_id(H160::from_low_u64_be(address))
+}
+
+#[test]
+fn flash_swap_repaid_in_core_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let target = H160::from_low_u64_be(5_555);
+		let target_account = evm_account(5_555);
+		let _ = GenericAsset::deposit_creating(&target_account, CORE_ASSET_ID, 200);
+
+		// the target is called from the initiator's EVM address
+		set_contract_callback(|caller, target, input| {
+			assert_eq!(*caller, H160::from_low_u64_be(1_111));
+			assert_eq!(*target, H160::from_low_u64_be(5_555));
+			assert_eq!(input, &[1, 2, 3]);
+			Ok(0)
+		});
+
+		// repays the buy price of 100 A
+		assert_ok!(Cennzx::flash_swap(
+			Origin::signed(evm_account(1_111)),
+			TRADE_ASSET_A_ID,
+			100,
+			CORE_ASSET_ID,
+			target,
+			vec![1, 2, 3],
+			50,
+		));
+		assert_eq!(
+			last_event(),
+			Event::Cennzx(RawEvent::FlashSwap(
+				target_account.clone(),
+				TRADE_ASSET_A_ID,
+				100,
+				CORE_ASSET_ID,
+				112
+			))
+		);
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1112, TRADE_ASSET_A_ID => 900);
+		assert_balance_eq!(target_account, CORE_ASSET_ID => 88);
+		assert_balance_eq!(target_account, TRADE_ASSET_A_ID => 100);
+	});
+}
+
+#[test]
+fn flash_swap_charges_used_gas() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&evm_account(5_555), CORE_ASSET_ID, 200);
+
+		// the callback uses 20 of its 50 gas
+		set_contract_callback(|_caller, _target, _input| Ok(20));
+
+		let post_info = Cennzx::flash_swap(
+			Origin::signed(evm_account(1_111)),
+			TRADE_ASSET_A_ID,
+			100,
+			CORE_ASSET_ID,
+			H160::from_low_u64_be(5_555),
+			vec![],
+			50,
+		)
+		.unwrap();
+		let flash_swap_weight = <Test as crate::Config>::WeightInfo::flash_swap();
+		assert_eq!(post_info.actual_weight, Some(flash_swap_weight + 20));
+	});
+}
+
+#[test]
+fn flash_swap_repaid_in_borrowed_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let target_account = evm_account(5_555);
+
+		// the callback makes a profit with the loan
+		set_contract_callback(|_caller, target, _input| {
+			let target_account = PrefixedAddressMapping::<AccountId>::into_account_id(*target);
+			let _ = GenericAsset::deposit_creating(&target_account, TRADE_ASSET_A_ID, 10);
+			Ok(0)
+		});
+
+		// repays the loan plus the 0.3% fee, rounded up
+		assert_ok!(Cennzx::flash_swap(
+			Origin::signed(evm_account(1_111)),
+			TRADE_ASSET_A_ID,
+			500,
+			TRADE_ASSET_A_ID,
+			H160::from_low_u64_be(5_555),
+			vec![],
+			0,
+		));
+		assert_exchange_balance_eq!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1002);
+		assert_balance_eq!(target_account, TRADE_ASSET_A_ID => 8);
+	});
+}
+
+#[test]
+fn flash_swap_not_repaid() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&evm_account(5_555), CORE_ASSET_ID, 100);
+
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(evm_account(1_111)),
+				TRADE_ASSET_A_ID,
+				100,
+				CORE_ASSET_ID,
+				H160::from_low_u64_be(5_555),
+				vec![],
+				0,
+			),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn flash_swap_fails_when_callback_trades_against_the_loan() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&evm_account(5_555), TRADE_ASSET_A_ID, 200);
+
+		// selling into the exchange while its reserves are lent out gets a better price than the loan is repaid at
+		set_contract_callback(|_caller, target, _input| {
+			let target_account = PrefixedAddressMapping::<AccountId>::into_account_id(*target);
+			assert_ok!(Cennzx::sell_asset(
+				Origin::signed(target_account),
+				None,
+				TRADE_ASSET_A_ID,
+				CORE_ASSET_ID,
+				100,
+				1
+			));
+			Ok(0)
+		});
+
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(evm_account(1_111)),
+				TRADE_ASSET_A_ID,
+				500,
+				TRADE_ASSET_A_ID,
+				H160::from_low_u64_be(5_555),
+				vec![],
+				0,
+			),
+			Error::<Test>::FlashSwapNotRepaid
+		);
+	});
+}
+
+#[test]
+fn flash_swap_fails_when_callback_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let _ = GenericAsset::deposit_creating(&evm_account(5_555), CORE_ASSET_ID, 200);
+
+		// a reverted callback fails the flash swap rather than being repaid from the target's own funds
+		set_contract_callback(|_caller, _target, _input| Err(DispatchError::Other("reverted")));
+
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(evm_account(1_111)),
+				TRADE_ASSET_A_ID,
+				100,
+				CORE_ASSET_ID,
+				H160::from_low_u64_be(5_555),
+				vec![],
+				0,
+			),
+			Error::<Test>::FlashSwapCallbackFailed
+		);
+	});
+}
+
+#[test]
+fn flash_swap_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		with_exchange!(CORE_ASSET_ID => 1000, TRADE_ASSET_A_ID => 1000);
+		let borrower = evm_account(1_111);
+		let target = H160::from_low_u64_be(5_555);
+		let _ = GenericAsset::deposit_creating(&evm_account(5_555), CORE_ASSET_ID, 10_000);
+
+		// the callback would be called from an address the initiator does not own
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(sp_keyring::AccountKeyring::Alice.into()),
+				TRADE_ASSET_A_ID,
+				100,
+				CORE_ASSET_ID,
+				target,
+				vec![],
+				0
+			),
+			Error::<Test>::BorrowerNotEvmAccount
+		);
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(borrower.clone()),
+				TRADE_ASSET_A_ID,
+				0,
+				CORE_ASSET_ID,
+				target,
+				vec![],
+				0
+			),
+			Error::<Test>::CannotTradeZero
+		);
+		// the repayment must be in an asset of the borrowed asset's exchange
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(borrower.clone()),
+				TRADE_ASSET_A_ID,
+				100,
+				TRADE_ASSET_B_ID,
+				target,
+				vec![],
+				0
+			),
+			Error::<Test>::InvalidFlashSwap
+		);
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(borrower.clone()),
+				CORE_ASSET_ID,
+				100,
+				CORE_ASSET_ID,
+				target,
+				vec![],
+				0
+			),
+			Error::<Test>::InvalidFlashSwap
+		);
+		assert_noop!(
+			Cennzx::flash_swap(
+				Origin::signed(borrower),
+				TRADE_ASSET_A_ID,
+				1000,
+				TRADE_ASSET_A_ID,
+				target,
+				vec![],
+				0
+			),
+			Error::<Test>::InsufficientExchangePoolReserve
+		);
+	});
+}
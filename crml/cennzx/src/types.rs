@@ -28,20 +28,113 @@ use serde::{Deserialize, Serialize};
 pub use sp_core::U256 as HighPrecisionUnsigned;
 pub use u128 as LowPrecisionUnsigned;
 
+/// Fixed point scale of prices tracked by the TWAP oracle i.e. a price of `PRICE_SCALE` is 1:1
+pub const PRICE_SCALE: LowPrecisionUnsigned = 1_000_000_000_000_000_000;
+
+/// A snapshot of an exchange's cumulative prices, taken at most once per block
+/// The average price between two observations is their difference in cumulative price over the blocks elapsed
+#[derive(Encode, Decode, Copy, Clone, Debug, Default, PartialEq, Eq, TypeInfo)]
+pub struct PriceObservation<BlockNumber> {
+	/// The block the observation was taken, before any trades in that block
+	pub block_number: BlockNumber,
+	/// Sum over blocks of the trade asset price in core asset at the end of each block, scaled by `PRICE_SCALE`
+	pub asset_price_cumulative: HighPrecisionUnsigned,
+	/// Sum over blocks of the core asset price in trade asset at the end of each block, scaled by `PRICE_SCALE`
+	pub core_price_cumulative: HighPrecisionUnsigned,
+}
+
+/// The largest StableSwap amplification coefficient an exchange may use
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The pricing invariant of an exchange
+#[derive(Encode, Decode, Copy, Clone, Debug, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ExchangeCurve {
+	/// `x * y = k`, for assets with independent prices
+	ConstantProduct,
+	/// Curve's StableSwap invariant for assets which should trade near 1:1
+	/// A higher `amplification` flattens the curve around the balanced price, giving less slippage there
+	StableSwap { amplification: u32 },
+}
+
+impl Default for ExchangeCurve {
+	fn default() -> Self {
+		ExchangeCurve::ConstantProduct
+	}
+}
+
+/// Unique Id of a limit order
+pub type OrderId = u64;
+
+/// A standing order to sell an asset once the exchange price meets a limit
+#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct LimitOrder<AccountId, AssetId, Balance, BlockNumber> {
+	/// The account which placed the order, the unfilled `sell_amount` is reserved from it
+	pub owner: AccountId,
+	/// The asset to sell
+	pub asset_to_sell: AssetId,
+	/// The asset to buy
+	pub asset_to_buy: AssetId,
+	/// The amount of `asset_to_sell` yet to be sold
+	pub sell_amount: Balance,
+	/// The minimum `asset_to_buy` to receive for the remaining `sell_amount`, this sets the order's limit price
+	pub minimum_buy: Balance,
+	/// The last block the order may be filled
+	pub expiry: BlockNumber,
+}
+
+/// Fixed point scale of the rewards accrued per unit of liquidity by a reward program
+pub const REWARD_PER_SHARE_SCALE: LowPrecisionUnsigned = 1_000_000_000_000_000_000;
+
+/// A stream of rewards paid to the liquidity providers of an exchange
+#[derive(Encode, Decode, Copy, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct RewardProgram<AssetId, Balance, BlockNumber> {
+	/// The asset rewards are paid in
+	pub reward_asset: AssetId,
+	/// Rewards emitted each block, shared pro-rata between liquidity providers
+	pub reward_per_block: Balance,
+	/// The block emission stops
+	pub end_block: BlockNumber,
+	/// The block `reward_per_share` was last brought up to date
+	pub last_update_block: BlockNumber,
+	/// Rewards emitted per unit of liquidity since the exchange's first program, scaled by `REWARD_PER_SHARE_SCALE`
+	pub reward_per_share: HighPrecisionUnsigned,
+}
+
+/// A liquidity provider's position in an exchange's reward program
+#[derive(Encode, Decode, Copy, Clone, Debug, Default, PartialEq, Eq, TypeInfo)]
+pub struct RewardStake<Balance> {
+	/// The provider's liquidity when last checkpointed, rewards accrue on this or their current balance if lower
+	/// Checkpointed on every mint, burn and transfer of the provider's liquidity
+	pub liquidity: Balance,
+	/// The program's `reward_per_share` when the provider's rewards were last accrued
+	pub reward_per_share_paid: HighPrecisionUnsigned,
+	/// Rewards accrued and not yet claimed
+	pub pending: Balance,
+}
+
+/// Storage releases
+pub enum Releases {
+	/// Liquidity held in the `LiquidityBalance` storage map
+	V0 = 0,
+	/// Liquidity held as a generic asset per exchange
+	V1 = 1,
+}
+
 /// A trait for values which hold an implicit scale factor that needs to be taken into account in calculations
 pub trait Scaled {
 	const SCALE: LowPrecisionUnsigned;
 }
 
 /// Per millionth of unit price
-#[derive(Debug, Clone, Copy, PartialEq, TypeInfo)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
 pub enum PerMillion {}
 impl Scaled for PerMillion {
 	const SCALE: LowPrecisionUnsigned = 1_000_000;
 }
 
 /// Per thousandth of unit price
-#[derive(Debug, Clone, Copy, PartialEq, TypeInfo)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
 pub enum PerThousand {}
 impl Scaled for PerThousand {
 	const SCALE: LowPrecisionUnsigned = 1_000;
@@ -62,7 +155,7 @@ impl Into<&'static str> for FeeRateError {
 
 /// Inner type is `LowPrecisionUnsigned` in order to support compatibility with `crml_generic_asset::Balance` type
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Copy, Clone, Debug, PartialEq, TypeInfo)]
+#[derive(Encode, Decode, Copy, Clone, Debug, PartialEq, Eq, TypeInfo)]
 pub struct FeeRate<S: Scaled>(LowPrecisionUnsigned, PhantomData<S>);
 
 impl<S: Scaled> Default for FeeRate<S> {
@@ -26,32 +26,108 @@ pub trait WeightInfo {
 	fn add_liquidity() -> Weight;
 	fn remove_liquidity() -> Weight;
 	fn set_fee_rate() -> Weight;
+	fn set_exchange_fee_rate() -> Weight;
+	fn create_exchange() -> Weight;
+	fn buy_asset_stable_swap() -> Weight;
+	fn sell_asset_stable_swap() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn buy_asset_via_path() -> Weight;
+	fn sell_asset_via_path() -> Weight;
+	fn place_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn fill_order() -> Weight;
+	fn fund_reward_program() -> Weight;
+	fn claim_rewards() -> Weight;
+	fn flash_swap() -> Weight;
 }
 
 impl WeightInfo for () {
 	fn buy_asset() -> Weight {
 		(297_000_000 as Weight)
-			.saturating_add(DbWeight::get().reads(9 as Weight))
-			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().reads(15 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
 	}
 	fn sell_asset() -> Weight {
 		(290_000_000 as Weight)
-			.saturating_add(DbWeight::get().reads(9 as Weight))
-			.saturating_add(DbWeight::get().writes(6 as Weight))
+			.saturating_add(DbWeight::get().reads(15 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
 	}
 	fn add_liquidity() -> Weight {
-		(207_000_000 as Weight)
-			.saturating_add(DbWeight::get().reads(8 as Weight))
-			.saturating_add(DbWeight::get().writes(6 as Weight))
+		(219_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(16 as Weight))
 	}
 	fn remove_liquidity() -> Weight {
-		(214_000_000 as Weight)
-			.saturating_add(DbWeight::get().reads(8 as Weight))
-			.saturating_add(DbWeight::get().writes(6 as Weight))
+		(226_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
 	}
 	fn set_fee_rate() -> Weight {
 		(14_000_000 as Weight)
 			.saturating_add(DbWeight::get().reads(1 as Weight))
 			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
+	fn set_exchange_fee_rate() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn create_exchange() -> Weight {
+		(236_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(17 as Weight))
+			.saturating_add(DbWeight::get().writes(17 as Weight))
+	}
+	fn buy_asset_stable_swap() -> Weight {
+		(341_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(18 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn sell_asset_stable_swap() -> Weight {
+		(334_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(18 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn buy_asset_via_path() -> Weight {
+		(300_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(15 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn sell_asset_via_path() -> Weight {
+		(293_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(15 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(14_000_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn place_order() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_order() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn fill_order() -> Weight {
+		(356_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(19 as Weight))
+			.saturating_add(DbWeight::get().writes(17 as Weight))
+	}
+	fn fund_reward_program() -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn claim_rewards() -> Weight {
+		(83_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(241_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().writes(10 as Weight))
+	}
 }
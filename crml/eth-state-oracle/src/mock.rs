@@ -54,6 +54,8 @@ frame_support::construct_runtime!(
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
+	pub const MaxVestingSchedules: u32 = 4;
+	pub const MinVestedTransfer: Balance = 1;
 }
 impl frame_system::Config for TestRuntime {
 	type BlockWeights = ();
@@ -87,6 +89,9 @@ impl crml_generic_asset::Config for TestRuntime {
 	type Event = Event;
 	type OnDustImbalance = ();
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = ();
 }
 
 /// Lifted from runtime/src/lib.rs
@@ -131,6 +136,7 @@ pub struct MockBuyFeeAsset;
 
 impl BuyFeeAsset for MockBuyFeeAsset {
 	type AccountId = AccountId;
+	type AssetId = AssetId;
 	type Balance = Balance;
 	type FeeExchange = FeeExchange<AssetId, Self::Balance>;
 
@@ -139,15 +145,39 @@ impl BuyFeeAsset for MockBuyFeeAsset {
 		amount: Self::Balance,
 		fee_exchange: &Self::FeeExchange,
 	) -> Result<Self::Balance, DispatchError> {
-		let new_balance = GenericAsset::free_balance(fee_exchange.asset_id(), &who)
+		let asset_id = fee_exchange.asset_id().ok_or(DispatchError::Other("No Asset"))?;
+		let new_balance = GenericAsset::free_balance(asset_id, &who)
 			.checked_sub(amount)
 			.ok_or(DispatchError::Other("No Balance"))?;
-		GenericAsset::make_free_balance_be(&who, fee_exchange.asset_id(), new_balance);
+		GenericAsset::make_free_balance_be(&who, asset_id, new_balance);
 		let _ = GenericAsset::deposit_into_existing(&who, GenericAsset::fee_currency(), amount)?;
 
 		Ok(amount)
 	}
 
+	fn sell_fee_asset(
+		who: &Self::AccountId,
+		amount: Self::Balance,
+		asset_id: Self::AssetId,
+		_min_buy: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		let new_balance = GenericAsset::free_balance(GenericAsset::fee_currency(), &who)
+			.checked_sub(amount)
+			.ok_or(DispatchError::Other("No Balance"))?;
+		GenericAsset::make_free_balance_be(&who, GenericAsset::fee_currency(), new_balance);
+		let _ = GenericAsset::deposit_into_existing(&who, asset_id, amount)?;
+
+		Ok(amount)
+	}
+
+	fn buy_fee_asset_price(_asset_id: Self::AssetId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		Ok(amount)
+	}
+
+	fn fee_asset_id() -> Self::AssetId {
+		GenericAsset::fee_currency()
+	}
+
 	fn buy_fee_weight() -> Weight {
 		unimplemented!()
 	}
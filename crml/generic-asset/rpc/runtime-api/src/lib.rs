@@ -30,6 +30,7 @@ use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
 	/// The API to query asset meta information.
+	#[api_version(2)]
 	pub trait GenericAssetRuntimeApi<AssetId, Balance, AccountId> where
 		AssetId: Codec,
 		Balance: Codec,
@@ -37,7 +38,7 @@ sp_api::decl_runtime_apis! {
 	{
 		/// Get all assets data paired with their ids.
 		fn asset_meta() -> Vec<(AssetId, AssetInfo)>;
-		/// Get total balance of an account including free, locked and reserved
+		/// Get total balance of an account including free, locked, vesting and reserved
 		fn get_balance(account: AccountId, asset_id: AssetId) -> AllBalances<Balance>;
 	}
 }
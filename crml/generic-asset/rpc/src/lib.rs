@@ -72,6 +72,10 @@ pub struct BalanceInformation<Balance> {
 	staked: Balance,
 	#[serde(with = "serde_balance")]
 	available: Balance,
+	#[serde(with = "serde_balance")]
+	vesting_locked: Balance,
+	#[serde(with = "serde_balance")]
+	vestable: Balance,
 }
 
 mod serde_balance {
@@ -140,6 +144,8 @@ where
 			reserved: result.reserved,
 			staked: result.staked,
 			available: result.available,
+			vesting_locked: result.vesting_locked,
+			vestable: result.vestable,
 		})
 	}
 }
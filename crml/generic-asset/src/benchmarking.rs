@@ -178,14 +178,13 @@ benchmarks! {
 		let initial_balance = T::Balance::from(5_000_000u32);
 		let permissions = PermissionLatest {
 			update: Owner::Address(caller.clone()),
-			mint: Owner::None,
-			burn: Owner::None,
+			..Default::default()
 		};
 
 		let new_permission = PermissionLatest {
 			update: Owner::Address(caller.clone()),
 			mint: Owner::Address(caller.clone()),
-			burn: Owner::None,
+			..Default::default()
 		};
 		let asset_id = GenericAsset::<T>::next_asset_id();
 		let asset_options :AssetOptions<T::Balance, T::AccountId> = AssetOptions {
@@ -226,6 +225,131 @@ benchmarks! {
 		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &T::AccountId::default()), total_issuance);
 		assert_eq!(asset_id, T::AssetId::from(1000u32));
 	}
+
+	// Benchmark `vested_transfer` to a recipient which already has `s - 1` schedules.
+	vested_transfer {
+		let s in 1 .. T::MaxVestingSchedules::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = GenericAsset::<T>::spending_asset_id();
+		GenericAsset::<T>::set_free_balance(asset_id, &caller, T::Balance::from(5_000_000u32));
+
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let locked = T::MinVestedTransfer::get().max(T::Balance::from(1_000u32));
+		let schedule = VestingSchedule::new(locked, T::Balance::from(10u32), T::BlockNumber::from(10u32), T::BlockNumber::from(5u32));
+		for _ in 1..s {
+			let _ = GenericAsset::<T>::vested_transfer(RawOrigin::Signed(caller.clone()).into(), asset_id, recipient.clone(), schedule);
+		}
+	}: vested_transfer(RawOrigin::Signed(caller.clone()), asset_id, recipient.clone(), schedule)
+	verify {
+		assert_eq!(GenericAsset::<T>::vesting(asset_id, &recipient).len() as u32, s);
+	}
+
+	// Benchmark `vest` with `s` schedules, one of which has completed.
+	vest {
+		let s in 1 .. T::MaxVestingSchedules::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = GenericAsset::<T>::spending_asset_id();
+		GenericAsset::<T>::set_free_balance(asset_id, &caller, T::Balance::from(5_000_000u32));
+		let mut schedules = vec![VestingSchedule::new(T::Balance::from(10u32), T::Balance::from(10u32), Zero::zero(), Zero::zero())];
+		for _ in 1..s {
+			schedules.push(VestingSchedule::new(T::Balance::from(1_000u32), T::Balance::from(10u32), Zero::zero(), Zero::zero()));
+		}
+		<Vesting<T>>::insert(asset_id, &caller, schedules);
+		frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(1u32));
+	}: vest(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert_eq!(GenericAsset::<T>::vesting(asset_id, &caller).len() as u32, s - 1);
+	}
+
+	// Benchmark `merge_schedules` with `s` schedules.
+	merge_schedules {
+		let s in 2 .. T::MaxVestingSchedules::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = GenericAsset::<T>::spending_asset_id();
+		GenericAsset::<T>::set_free_balance(asset_id, &caller, T::Balance::from(5_000_000u32));
+		let schedules = (0..s)
+			.map(|_| VestingSchedule::new(T::Balance::from(1_000u32), T::Balance::from(10u32), Zero::zero(), Zero::zero()))
+			.collect::<Vec<_>>();
+		<Vesting<T>>::insert(asset_id, &caller, schedules);
+	}: merge_schedules(RawOrigin::Signed(caller.clone()), asset_id, 0, 1)
+	verify {
+		assert_eq!(GenericAsset::<T>::vesting(asset_id, &caller).len() as u32, s - 1);
+	}
+
+	// Benchmark `freeze_asset`, create asset from ROOT account with 'freeze' permission.
+	freeze_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_regulated_asset::<T>(caller.clone());
+	}: freeze_asset(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert!(GenericAsset::<T>::asset_frozen(asset_id));
+	}
+
+	// Benchmark `thaw_asset`, thaw a frozen asset.
+	thaw_asset {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_regulated_asset::<T>(caller.clone());
+		<FrozenAssets<T>>::insert(asset_id, true);
+	}: thaw_asset(RawOrigin::Signed(caller.clone()), asset_id)
+	verify {
+		assert!(!GenericAsset::<T>::asset_frozen(asset_id));
+	}
+
+	// Benchmark `freeze_account`, create asset from ROOT account with 'freeze' permission.
+	freeze_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_regulated_asset::<T>(caller.clone());
+		let account: T::AccountId = account("bob", 0, SEED);
+	}: freeze_account(RawOrigin::Signed(caller.clone()), asset_id, account.clone())
+	verify {
+		assert!(GenericAsset::<T>::account_frozen(asset_id, &account));
+	}
+
+	// Benchmark `thaw_account`, thaw a frozen account.
+	thaw_account {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_regulated_asset::<T>(caller.clone());
+		let account: T::AccountId = account("bob", 0, SEED);
+		<FrozenAccounts<T>>::insert(asset_id, &account, true);
+	}: thaw_account(RawOrigin::Signed(caller.clone()), asset_id, account.clone())
+	verify {
+		assert!(!GenericAsset::<T>::account_frozen(asset_id, &account));
+	}
+
+	// Benchmark `force_transfer` with the worst possible conditions:
+	// Transfer will kill the frozen sender account and create the recipient account.
+	force_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let asset_id = create_regulated_asset::<T>(caller.clone());
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let target: T::AccountId = account("bob", 0, SEED);
+		let transfer_amount = T::Balance::from(5_000_000u32);
+		GenericAsset::<T>::set_free_balance(asset_id, &target, transfer_amount);
+		<FrozenAccounts<T>>::insert(asset_id, &target, true);
+	}: force_transfer(RawOrigin::Signed(caller.clone()), asset_id, target.clone(), recipient.clone(), transfer_amount)
+	verify {
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &target), Zero::zero());
+		assert_eq!(GenericAsset::<T>::free_balance(asset_id, &recipient), transfer_amount);
+	}
+}
+
+// Create an asset with every permission assigned to `owner`
+fn create_regulated_asset<T: Config>(owner: T::AccountId) -> T::AssetId {
+	let asset_id = GenericAsset::<T>::next_asset_id();
+	let permissions = PermissionLatest {
+		update: Owner::Address(owner.clone()),
+		mint: Owner::Address(owner.clone()),
+		burn: Owner::Address(owner.clone()),
+		freeze: Owner::Address(owner.clone()),
+		thaw: Owner::Address(owner.clone()),
+		force_transfer: Owner::Address(owner.clone()),
+	};
+	let asset_options: AssetOptions<T::Balance, T::AccountId> = AssetOptions {
+		initial_issuance: T::Balance::from(5_000_000u32),
+		permissions,
+	};
+	let _ = GenericAsset::<T>::create(RawOrigin::Root.into(), owner, asset_options, AssetInfo::default());
+	asset_id
 }
 
 impl_benchmark_test_suite!(
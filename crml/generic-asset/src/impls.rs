@@ -52,6 +52,10 @@ impl<T: Config> MultiCurrency for Module<T> {
 			.unique_saturated_into()
 	}
 
+	fn decimal_places(currency: Self::CurrencyId) -> u8 {
+		<Module<T>>::asset_meta(currency).decimal_places()
+	}
+
 	fn total_balance(who: &T::AccountId, currency: Self::CurrencyId) -> Self::Balance {
 		<Module<T>>::total_balance(currency, who)
 	}
@@ -189,11 +193,7 @@ impl<T: Config> MultiCurrency for Module<T> {
 			Some(owner.clone()),
 			AssetOptions {
 				initial_issuance: initial_supply,
-				permissions: crate::types::PermissionLatest {
-					update: crate::types::Owner::Address(owner.clone()),
-					mint: crate::types::Owner::Address(owner.clone()),
-					burn: crate::types::Owner::Address(owner.clone()),
-				},
+				permissions: crate::types::PermissionLatest::new(owner.clone()),
 			},
 			crate::types::AssetInfo::new(symbol, decimal_places, minimum_balance),
 		)?;
@@ -52,6 +52,8 @@
 //! simply dropped, it should automatically maintain any book-keeping such as total issuance.)
 //! - **Lock:** A freeze on a specified amount of an account's free balance until a specified block number. Multiple
 //! locks always operate over the same funds, so they "overlay" rather than "stack".
+//! - **Vesting Schedule:** A linear release of locked balance, starting after a cliff. Vested balance is held by
+//! a single vesting lock per account and asset, which is updated by calling `vest`.
 //!
 //! ### Implementations
 //!
@@ -81,6 +83,15 @@
 //! - `mint`: Mint an asset, increases its total issuance. The origin of this call must have mint permissions.
 //! - `burn`: Burn an asset, decreases its total issuance. The origin of this call must have burn permissions.
 //! - `create_reserved`: Create a new kind of reserved asset. The origin of this call must be root.
+//! - `vested_transfer`: Transfer an amount to another account, locked under a new vesting schedule.
+//! - `vest`: Unlock any vested balance of the caller.
+//! - `merge_schedules`: Merge two vesting schedules of the caller into one.
+//! - `freeze_asset`/`thaw_asset`: Halt or resume all transfers of an asset. The origin of these calls must have
+//! freeze or thaw permissions.
+//! - `freeze_account`/`thaw_account`: Halt or resume withdrawals of an account's balance of an asset. The origin
+//! of these calls must have freeze or thaw permissions.
+//! - `force_transfer`: Transfer an asset out of any account. The origin of this call must have force transfer
+//! permissions.
 //!
 //! ### Public Functions
 //!
@@ -101,14 +112,14 @@
 //! - `slash_reserved`: Deduct up to an amount from reserved balance of an account. This function cannot fail.
 //! - `repatriate_reserved`: Move up to an amount from reserved balance of an account to free balance of another
 //! account.
-//! - `check_permission`: Check permission to perform burn, mint or update.
+//! - `check_permission`: Check permission to perform burn, mint, update, freeze, thaw or force transfer.
 //! - `ensure_can_withdraw`: Check if the account is able to make a withdrawal of the given amount
 //!	for the given reason.
 //!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crml_support::AssetIdAuthority;
+use crml_support::{AssetIdAuthority, OnAssetTransferSubscriber};
 use frame_support::pallet_prelude::*;
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
@@ -123,7 +134,7 @@ use frame_system::pallet_prelude::*;
 use sp_runtime::{
 	traits::{
 		AtLeast32BitUnsigned, Bounded, CheckedAdd, CheckedMul, CheckedSub, MaybeSerializeDeserialize, Member, One,
-		UniqueSaturatedInto, Zero,
+		Saturating, UniqueSaturatedInto, Zero,
 	},
 	DispatchError, DispatchResult, RuntimeDebug, SaturatedConversion,
 };
@@ -153,8 +164,17 @@ pub trait Config: frame_system::Config {
 	type OnDustImbalance: OnUnbalanced<NegativeImbalance<Self>>;
 	/// Weight information for extrinsics in this module.
 	type WeightInfo: WeightInfo;
+	/// The maximum number of vesting schedules an account may have per asset.
+	type MaxVestingSchedules: Get<u32>;
+	/// The minimum amount which may be transferred under a vesting schedule.
+	type MinVestedTransfer: Get<Self::Balance>;
+	/// Notified when free balance moves between accounts, transfer weights include its notification weight.
+	type OnTransferSubscription: OnAssetTransferSubscriber<Self::AccountId, Self::AssetId>;
 }
 
+/// Identifier of the lock holding vesting balances.
+pub const VESTING_ID: LockIdentifier = *b"vesting ";
+
 decl_error! {
 	/// Error for the generic-asset module.
 	pub enum Error for Module<T: Config> {
@@ -194,6 +214,28 @@ decl_error! {
 		DecimalTooLarge,
 		/// The integer for initial issuance is too large for conversion into u128.
 		InitialIssuanceTooLarge,
+		/// The account has no vesting schedules for the asset.
+		NotVesting,
+		/// The account already has the maximum number of vesting schedules for the asset.
+		TooManyVestingSchedules,
+		/// A vesting schedule must lock and release a non-zero amount.
+		InvalidVestingSchedule,
+		/// The amount locked under a vesting schedule is below `MinVestedTransfer`.
+		AmountLow,
+		/// No vesting schedule exists at the given index.
+		ScheduleIndexOutOfBounds,
+		/// The origin does not have permission to freeze an asset or account.
+		NoFreezePermission,
+		/// The origin does not have permission to thaw an asset or account.
+		NoThawPermission,
+		/// The origin does not have permission to force transfer an asset.
+		NoForceTransferPermission,
+		/// Only root may change the freeze, thaw or force transfer roles of an asset.
+		NoRegulatoryRolePermission,
+		/// The asset is frozen and can't be transferred.
+		AssetIsFrozen,
+		/// The account's balance of the asset is frozen and can't be withdrawn.
+		AccountIsFrozen,
 	}
 }
 
@@ -204,36 +246,49 @@ decl_module! {
 		fn deposit_event() = default;
 
 		fn on_runtime_upgrade() -> Weight {
-			if StorageVersion::get() != Releases::V1 as u32 {
-				return Zero::zero();
-			}
-
-			// Update to v2
-			StorageVersion::put(Releases::V2 as u32);
-			// `TokenLocks` migrating from `bool` to `TokenLockReason`
-			#[allow(dead_code)]
-			mod old_storage {
-				use super::{Config, BalanceLockOld};
-				use sp_std::prelude::*;
-				pub struct Module<T>(sp_std::marker::PhantomData<T>);
-				frame_support::decl_storage! {
-					trait Store for Module<T: Config> as GenericAsset {
-						/// Any liquidity locks on some account balances.
-						pub Locks get(fn locks): double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => Vec<BalanceLockOld<T::Balance>>;
+			let mut weight: Weight = Zero::zero();
+
+			if StorageVersion::get() == Releases::V1 as u32 {
+				// Update to v2
+				StorageVersion::put(Releases::V2 as u32);
+				// `TokenLocks` migrating from `bool` to `TokenLockReason`
+				#[allow(dead_code)]
+				mod old_storage {
+					use super::{Config, BalanceLockOld};
+					use sp_std::prelude::*;
+					pub struct Module<T>(sp_std::marker::PhantomData<T>);
+					frame_support::decl_storage! {
+						trait Store for Module<T: Config> as GenericAsset {
+							/// Any liquidity locks on some account balances.
+							pub Locks get(fn locks): double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => Vec<BalanceLockOld<T::Balance>>;
+						}
 					}
 				}
+
+				// original migration here:
+				// https://github.com/paritytech/substrate/pull/4649/files
+				let locks: Vec<(T::AssetId, T::AccountId, Vec<BalanceLockOld<T::Balance>>)> = old_storage::Locks::<T>::iter().collect();
+				weight += locks.len() as Weight * 100_000;
+				for (asset_id, address, old_locks) in &locks {
+					Locks::<T>::insert(
+						asset_id,
+						address,
+						old_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<T::Balance>>>()
+					);
+				}
 			}
 
-			// original migration here:
-			// https://github.com/paritytech/substrate/pull/4649/files
-			let locks: Vec<(T::AssetId, T::AccountId, Vec<BalanceLockOld<T::Balance>>)> = old_storage::Locks::<T>::iter().collect();
-			let weight = locks.len() as Weight * 100_000;
-			for (asset_id, address, old_locks) in &locks {
-				Locks::<T>::insert(
-					asset_id,
-					address,
-					old_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<T::Balance>>>()
-				);
+			if StorageVersion::get() == Releases::V2 as u32 {
+				// Update to v3
+				StorageVersion::put(Releases::V3 as u32);
+				// `Permissions` migrating from `PermissionsV1` to `PermissionsV2`
+				let permissions: Vec<(T::AssetId, PermissionVersions<T::AccountId>)> = <Permissions<T>>::iter().collect();
+				weight += permissions.len() as Weight * 100_000;
+				for (asset_id, permission) in permissions {
+					let latest: PermissionLatest<T::AccountId> = permission.into();
+					let permission: PermissionVersions<T::AccountId> = latest.into();
+					<Permissions<T>>::insert(asset_id, permission);
+				}
 			}
 
 			weight
@@ -275,7 +330,7 @@ decl_module! {
 		/// - It contains a limited number of reads and writes internally and no complex computation.
 		///
 		/// # </weight>
-		#[weight = T::WeightInfo::transfer()]
+		#[weight = T::WeightInfo::transfer().saturating_add(T::OnTransferSubscription::on_asset_transfer_weight())]
 		pub fn transfer(origin, #[compact] asset_id: T::AssetId, to: T::AccountId, #[compact] amount: T::Balance) {
 			let origin = ensure_signed(origin)?;
 			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
@@ -283,7 +338,7 @@ decl_module! {
 		}
 
 		/// Transfer all of the free balance of `asset_id` to another account.
-		#[weight = T::WeightInfo::transfer()]
+		#[weight = T::WeightInfo::transfer().saturating_add(T::OnTransferSubscription::on_asset_transfer_weight())]
 		pub fn transfer_all(origin, #[compact] asset_id: T::AssetId, to: T::AccountId) {
 			let origin = ensure_signed(origin)?;
 			Self::make_transfer_with_event(asset_id, &origin, &to, Self::free_balance(asset_id, &origin), ExistenceRequirement::AllowDeath)?;
@@ -291,7 +346,8 @@ decl_module! {
 
 		/// Updates permissions(mint/burn/change permission) for a given `asset_id` and an account.
 		///
-		/// The `origin` must have `update` permission.
+		/// The `origin` must have `update` permission, or be root.
+		/// The freeze, thaw and force transfer roles may only be changed by root.
 		///
 		/// weights:
 		/// O(1) limited number of read and writes
@@ -302,19 +358,21 @@ decl_module! {
 			#[compact] asset_id: T::AssetId,
 			new_permission: PermissionLatest<T::AccountId>
 		) -> DispatchResult {
-			let origin = ensure_signed(origin)?;
+			if let Ok(origin) = ensure_signed(origin.clone()) {
+				ensure!(Self::check_permission(asset_id, &origin, &PermissionType::Update), Error::<T>::NoUpdatePermission);
+				let current: PermissionLatest<T::AccountId> = Self::get_permission(asset_id).into();
+				ensure!(current.same_regulatory_roles(&new_permission), Error::<T>::NoRegulatoryRolePermission);
+			} else {
+				ensure_root(origin)?;
+				ensure!(<TotalIssuance<T>>::contains_key(asset_id), Error::<T>::AssetIdNotExist);
+			}
 
 			let permissions: PermissionVersions<T::AccountId> = new_permission.into();
+			<Permissions<T>>::insert(asset_id, &permissions);
 
-			if Self::check_permission(asset_id, &origin, &PermissionType::Update) {
-				<Permissions<T>>::insert(asset_id, &permissions);
-
-				Self::deposit_event(Event::<T>::PermissionUpdated(asset_id, permissions.into()));
+			Self::deposit_event(Event::<T>::PermissionUpdated(asset_id, permissions.into()));
 
-				Ok(())
-			} else {
-				Err(Error::<T>::NoUpdatePermission)?
-			}
+			Ok(())
 		}
 
 		/// Updates asset info for a given `asset_id`.
@@ -385,6 +443,176 @@ decl_module! {
 			ensure_root(origin)?;
 			Self::create_asset(Some(asset_id), None, options, info)
 		}
+
+		/// Transfer `schedule.locked` of `asset_id` to `target`, locked under the given vesting schedule.
+		/// At least `MinVestedTransfer` must be locked.
+		///
+		/// The dispatch origin for this call must be `Signed` by the transactor.
+		///
+		/// Weights:
+		/// O(S) where S is the number of vesting schedules of `target`, charged at its `MaxVestingSchedules` bound.
+		#[weight = T::WeightInfo::vested_transfer(T::MaxVestingSchedules::get())
+			.saturating_add(T::OnTransferSubscription::on_asset_transfer_weight())]
+		fn vested_transfer(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			target: T::AccountId,
+			schedule: VestingSchedule<T::Balance, T::BlockNumber>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(schedule.is_valid(), Error::<T>::InvalidVestingSchedule);
+			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+			let mut schedules = Self::vesting(asset_id, &target);
+			ensure!((schedules.len() as u32) < T::MaxVestingSchedules::get(), Error::<T>::TooManyVestingSchedules);
+
+			Self::make_transfer_with_event(asset_id, &origin, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+			schedules.push(schedule);
+			<Vesting<T>>::insert(asset_id, &target, schedules);
+			Self::deposit_event(Event::<T>::VestingScheduleAdded(asset_id, origin, target.clone(), schedule));
+			Self::update_vesting(asset_id, &target);
+
+			Ok(())
+		}
+
+		/// Unlock any balance of `asset_id` which has vested for the caller.
+		///
+		/// Weights:
+		/// O(S) where S is the number of vesting schedules of the caller, charged at its `MaxVestingSchedules` bound.
+		#[weight = T::WeightInfo::vest(T::MaxVestingSchedules::get())]
+		fn vest(origin, #[compact] asset_id: T::AssetId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(<Vesting<T>>::contains_key(asset_id, &who), Error::<T>::NotVesting);
+			Self::update_vesting(asset_id, &who);
+			Ok(())
+		}
+
+		/// Merge two vesting schedules of the caller for `asset_id` into one.
+		///
+		/// The merged schedule locks the sum of what remains locked in both, starts at the later of the
+		/// current block and either start, and ends at the later ending block.
+		/// Any vested balance is unlocked first.
+		///
+		/// Weights:
+		/// O(S) where S is the number of vesting schedules of the caller, charged at its `MaxVestingSchedules` bound.
+		#[weight = T::WeightInfo::merge_schedules(T::MaxVestingSchedules::get())]
+		fn merge_schedules(origin, #[compact] asset_id: T::AssetId, schedule1_index: u32, schedule2_index: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut schedules = Self::vesting(asset_id, &who);
+			ensure!(!schedules.is_empty(), Error::<T>::NotVesting);
+			let (schedule1_index, schedule2_index) = (schedule1_index as usize, schedule2_index as usize);
+			ensure!(
+				schedule1_index != schedule2_index
+					&& schedule1_index < schedules.len()
+					&& schedule2_index < schedules.len(),
+				Error::<T>::ScheduleIndexOutOfBounds
+			);
+
+			// remove the higher index first so the lower index stays valid
+			let schedule2 = schedules.remove(schedule1_index.max(schedule2_index));
+			let schedule1 = schedules.remove(schedule1_index.min(schedule2_index));
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some(merged) = Self::merge_vesting_schedules(now, schedule1, schedule2) {
+				schedules.push(merged);
+			}
+			<Vesting<T>>::insert(asset_id, &who, schedules);
+			Self::update_vesting(asset_id, &who);
+
+			Ok(())
+		}
+
+		/// Freeze `asset_id`, halting all transfers and withdrawals of it.
+		///
+		/// The `origin` must have `freeze` permission.
+		///
+		/// Weights:
+		/// O(1) limited number of read and writes
+		#[weight = T::WeightInfo::freeze_asset()]
+		fn freeze_asset(origin, #[compact] asset_id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(<TotalIssuance<T>>::contains_key(asset_id), Error::<T>::AssetIdNotExist);
+			ensure!(Self::check_permission(asset_id, &origin, &PermissionType::Freeze), Error::<T>::NoFreezePermission);
+
+			<FrozenAssets<T>>::insert(asset_id, true);
+			Self::deposit_event(Event::<T>::AssetFrozen(asset_id));
+			Ok(())
+		}
+
+		/// Thaw a frozen `asset_id`, allowing transfers of it again.
+		///
+		/// The `origin` must have `thaw` permission.
+		///
+		/// Weights:
+		/// O(1) limited number of read and writes
+		#[weight = T::WeightInfo::thaw_asset()]
+		fn thaw_asset(origin, #[compact] asset_id: T::AssetId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::check_permission(asset_id, &origin, &PermissionType::Thaw), Error::<T>::NoThawPermission);
+
+			<FrozenAssets<T>>::remove(asset_id);
+			Self::deposit_event(Event::<T>::AssetThawed(asset_id));
+			Ok(())
+		}
+
+		/// Freeze `who`'s balance of `asset_id`, preventing it from being withdrawn.
+		/// `who` may still receive the asset.
+		///
+		/// The `origin` must have `freeze` permission.
+		///
+		/// Weights:
+		/// O(1) limited number of read and writes
+		#[weight = T::WeightInfo::freeze_account()]
+		fn freeze_account(origin, #[compact] asset_id: T::AssetId, who: T::AccountId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(<TotalIssuance<T>>::contains_key(asset_id), Error::<T>::AssetIdNotExist);
+			ensure!(Self::check_permission(asset_id, &origin, &PermissionType::Freeze), Error::<T>::NoFreezePermission);
+
+			<FrozenAccounts<T>>::insert(asset_id, &who, true);
+			Self::deposit_event(Event::<T>::AccountFrozen(asset_id, who));
+			Ok(())
+		}
+
+		/// Thaw `who`'s frozen balance of `asset_id`.
+		///
+		/// The `origin` must have `thaw` permission.
+		///
+		/// Weights:
+		/// O(1) limited number of read and writes
+		#[weight = T::WeightInfo::thaw_account()]
+		fn thaw_account(origin, #[compact] asset_id: T::AssetId, who: T::AccountId) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::check_permission(asset_id, &origin, &PermissionType::Thaw), Error::<T>::NoThawPermission);
+
+			<FrozenAccounts<T>>::remove(asset_id, &who);
+			Self::deposit_event(Event::<T>::AccountThawed(asset_id, who));
+			Ok(())
+		}
+
+		/// Transfer `amount` of `asset_id` from any account to another, for compliance.
+		/// This ignores any freeze or lock on the `from` account.
+		///
+		/// The `origin` must have `force_transfer` permission.
+		///
+		/// Weights:
+		/// O(1) limited number of read and writes
+		#[weight = T::WeightInfo::force_transfer().saturating_add(T::OnTransferSubscription::on_asset_transfer_weight())]
+		fn force_transfer(
+			origin,
+			#[compact] asset_id: T::AssetId,
+			from: T::AccountId,
+			to: T::AccountId,
+			#[compact] amount: T::Balance,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(
+				Self::check_permission(asset_id, &origin, &PermissionType::ForceTransfer),
+				Error::<T>::NoForceTransferPermission
+			);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			Self::move_free_balance(asset_id, &from, &to, amount)?;
+			Self::deposit_event(Event::<T>::ForceTransferred(asset_id, from, to, amount));
+			Ok(())
+		}
 	}
 }
 
@@ -398,6 +626,8 @@ enum Releases {
 	V1 = 1,
 	/// Storage version as of runtime version 47
 	V2 = 2,
+	/// Storage version as of runtime version 65, with `PermissionsV2`
+	V3 = 3,
 }
 
 impl Default for Releases {
@@ -437,7 +667,7 @@ decl_storage! {
 		pub Permissions get(fn get_permission) build(|config: &GenesisConfig<T>| {
 			config.permissions
 				.iter()
-				.map(|(asset, owner)| (*asset, PermissionsV1::new(owner.clone()).into())).collect::<Vec<_>>()
+				.map(|(asset, owner)| (*asset, PermissionLatest::new(owner.clone()).into())).collect::<Vec<_>>()
 		}): map hasher(twox_64_concat) T::AssetId => PermissionVersions<T::AccountId>;
 
 		/// Any liquidity locks on some account balances.
@@ -453,6 +683,23 @@ decl_storage! {
 		/// The info for assets
 		pub AssetMeta get(fn asset_meta) config(): map hasher(twox_64_concat) T::AssetId => AssetInfo;
 
+		/// Vesting schedules of an account's balance of an asset.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted.
+		pub Vesting get(fn vesting):
+			double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => Vec<VestingSchedule<T::Balance, T::BlockNumber>>;
+
+		/// Assets which are frozen, halting all transfers.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted.
+		pub FrozenAssets get(fn asset_frozen): map hasher(twox_64_concat) T::AssetId => bool;
+
+		/// Account balances of an asset which are frozen, preventing withdrawal.
+		///
+		/// TWOX-NOTE: `AssetId` is trusted.
+		pub FrozenAccounts get(fn account_frozen):
+			double_map hasher(twox_64_concat) T::AssetId, hasher(blake2_128_concat) T::AccountId => bool;
+
 		/// Storage version of the pallet.
 		///
 		/// This is set to v1 for new networks.
@@ -482,7 +729,8 @@ decl_event! {
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::AssetId,
 		<T as Config>::Balance,
-		AssetOptions = AssetOptions<<T as Config>::Balance, <T as frame_system::Config>::AccountId>
+		AssetOptions = AssetOptions<<T as Config>::Balance, <T as frame_system::Config>::AccountId>,
+		VestingSchedule = VestingSchedule<<T as Config>::Balance, <T as frame_system::Config>::BlockNumber>
 	{
 		/// Asset created (asset_id, creator, asset_options).
 		Created(AssetId, AccountId, AssetOptions),
@@ -498,6 +746,22 @@ decl_event! {
 		Burned(AssetId, AccountId, Balance),
 		/// Asset balance storage has been reclaimed due to falling below the existential deposit
 		DustReclaimed(AssetId, AccountId, Balance),
+		/// A vesting schedule was added (asset_id, from, to, schedule).
+		VestingScheduleAdded(AssetId, AccountId, AccountId, VestingSchedule),
+		/// The vesting lock of an account was updated (asset_id, account, amount still locked).
+		VestingUpdated(AssetId, AccountId, Balance),
+		/// An account has fully vested (asset_id, account).
+		VestingCompleted(AssetId, AccountId),
+		/// Asset frozen (asset_id).
+		AssetFrozen(AssetId),
+		/// Asset thawed (asset_id).
+		AssetThawed(AssetId),
+		/// An account's balance of an asset frozen (asset_id, account).
+		AccountFrozen(AssetId, AccountId),
+		/// An account's balance of an asset thawed (asset_id, account).
+		AccountThawed(AssetId, AccountId),
+		/// Asset transferred by force (asset_id, from, to, amount).
+		ForceTransferred(AssetId, AccountId, AccountId, Balance),
 	}
 }
 
@@ -629,6 +893,23 @@ impl<T: Config> Module<T> {
 		to: &T::AccountId,
 		amount: T::Balance,
 		_req: ExistenceRequirement,
+	) -> DispatchResult {
+		ensure!(!Self::asset_frozen(asset_id), Error::<T>::AssetIsFrozen);
+		let new_from_balance = Self::free_balance(asset_id, from)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::InsufficientBalance)?;
+
+		Self::ensure_can_withdraw(asset_id, from, amount, WithdrawReasons::TRANSFER, new_from_balance)?;
+
+		Self::move_free_balance(asset_id, from, to, amount)
+	}
+
+	/// Move some free balance from one account to another, without checking freezes or locks.
+	fn move_free_balance(
+		asset_id: T::AssetId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: T::Balance,
 	) -> DispatchResult {
 		let new_from_balance = Self::free_balance(asset_id, from)
 			.checked_sub(&amount)
@@ -637,12 +918,11 @@ impl<T: Config> Module<T> {
 			.checked_add(&amount)
 			.ok_or(Error::<T>::TransferOverflow)?;
 
-		Self::ensure_can_withdraw(asset_id, from, amount, WithdrawReasons::TRANSFER, new_from_balance)?;
-
 		if from == to {
 			return Ok(());
 		}
 
+		T::OnTransferSubscription::on_before_asset_transfer(asset_id, from, to);
 		Self::set_free_balance(asset_id, to, new_to_balance);
 		Self::set_free_balance(asset_id, from, new_from_balance);
 
@@ -650,6 +930,7 @@ impl<T: Config> Module<T> {
 		if new_from_balance < existential_deposit.saturated_into() {
 			Self::reclaim_free_balance(asset_id, from);
 		}
+		T::OnTransferSubscription::on_asset_transfer(asset_id, from, to);
 
 		Ok(())
 	}
@@ -677,6 +958,8 @@ impl<T: Config> Module<T> {
 	/// If the free balance is lower than `amount`, then no funds will be moved and an `Err` will
 	/// be returned. This is different behavior than `unreserve`.
 	pub fn reserve(asset_id: T::AssetId, who: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		ensure!(!Self::asset_frozen(asset_id), Error::<T>::AssetIsFrozen);
+		ensure!(!Self::account_frozen(asset_id, who), Error::<T>::AccountIsFrozen);
 		// Do we need to consider that this is an atomic transaction?
 		let original_reserve_balance = Self::reserved_balance(asset_id, who);
 		let original_free_balance = Self::free_balance(asset_id, who);
@@ -763,6 +1046,8 @@ impl<T: Config> Module<T> {
 		beneficiary: &T::AccountId,
 		amount: T::Balance,
 	) -> Result<T::Balance, DispatchError> {
+		ensure!(!Self::asset_frozen(asset_id), Error::<T>::AssetIsFrozen);
+		ensure!(!Self::account_frozen(asset_id, payee), Error::<T>::AccountIsFrozen);
 		if amount.is_zero() {
 			return Ok(Zero::zero());
 		}
@@ -776,13 +1061,15 @@ impl<T: Config> Module<T> {
 		// Intentionally allowing `beneficiary` to receive dust amounts
 		// `repatriate_reserved` is an internal function likely called by protocol operations
 		// this will allow an account to accumulate without being reaped too early
+		T::OnTransferSubscription::on_before_asset_transfer(asset_id, payee, beneficiary);
 		<FreeBalance<T>>::insert(asset_id, &beneficiary, &new_beneficiary_free_balance);
 		Self::set_reserved_balance(asset_id, payee, new_payee_reserve_balance);
+		T::OnTransferSubscription::on_asset_transfer(asset_id, payee, beneficiary);
 
 		Ok(amount - repatriated_amount)
 	}
 
-	/// Check permission to perform burn, mint or update.
+	/// Check permission to perform burn, mint, update, freeze, thaw or force transfer.
 	///
 	/// # Arguments
 	/// * `asset_id`:  A `T::AssetId` type that contains the `asset_id`, which has the permission embedded.
@@ -815,6 +1102,27 @@ impl<T: Config> Module<T> {
 					..
 				},
 			) => account == *who,
+			(
+				PermissionType::Freeze,
+				PermissionLatest {
+					freeze: Owner::Address(account),
+					..
+				},
+			) => account == *who,
+			(
+				PermissionType::Thaw,
+				PermissionLatest {
+					thaw: Owner::Address(account),
+					..
+				},
+			) => account == *who,
+			(
+				PermissionType::ForceTransfer,
+				PermissionLatest {
+					force_transfer: Owner::Address(account),
+					..
+				},
+			) => account == *who,
 			_ => false,
 		}
 	}
@@ -830,6 +1138,8 @@ impl<T: Config> Module<T> {
 		reasons: WithdrawReasons,
 		new_balance: T::Balance,
 	) -> DispatchResult {
+		ensure!(!Self::asset_frozen(asset_id), Error::<T>::AssetIsFrozen);
+		ensure!(!Self::account_frozen(asset_id, who), Error::<T>::AccountIsFrozen);
 		if amount.is_zero() {
 			return Ok(());
 		}
@@ -860,23 +1170,92 @@ impl<T: Config> Module<T> {
 	}
 
 	/// Return all types of balance stored under an account
-	/// Include reserved, locked and free balance
+	/// Include reserved, locked, vesting and free balance
 	pub fn get_all_balances(account_id: &T::AccountId, asset_id: T::AssetId) -> AllBalances<T::Balance>
 	where
 		T::Balance: Sum,
 	{
 		let reserved = <ReservedBalance<T>>::get(&asset_id, &account_id);
-		let locks = Self::locks(&asset_id, &account_id);
+		let (vesting_locks, locks): (Vec<_>, Vec<_>) = Self::locks(&asset_id, &account_id)
+			.into_iter()
+			.partition(|l| l.id == VESTING_ID);
 		let staked: T::Balance = locks.iter().map(|l| l.amount).sum();
-		let available = <FreeBalance<T>>::get(&asset_id, &account_id) - staked;
+		let vesting_locked: T::Balance = vesting_locks.iter().map(|l| l.amount).sum();
+		let vestable = vesting_locked.saturating_sub(Self::vesting_locked_balance(asset_id, account_id));
+		let available = (<FreeBalance<T>>::get(&asset_id, &account_id) - staked).saturating_sub(vesting_locked);
 
 		AllBalances {
 			reserved,
 			staked,
 			available,
+			vesting_locked,
+			vestable,
+		}
+	}
+
+	/// The balance of `asset_id` still locked by `who`'s vesting schedules at the current block
+	pub fn vesting_locked_balance(asset_id: T::AssetId, who: &T::AccountId) -> T::Balance {
+		let now = <frame_system::Pallet<T>>::block_number();
+		Self::vesting(asset_id, who)
+			.iter()
+			.fold(Zero::zero(), |total: T::Balance, s| {
+				total.saturating_add(s.locked_at(now))
+			})
+	}
+
+	/// Drop any completed vesting schedules of `who` and set the vesting lock to the balance still locked.
+	fn update_vesting(asset_id: T::AssetId, who: &T::AccountId) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut schedules = Self::vesting(asset_id, who);
+		schedules.retain(|s| !s.locked_at(now).is_zero());
+
+		if schedules.is_empty() {
+			<Vesting<T>>::remove(asset_id, who);
+			Self::remove_lock(VESTING_ID, asset_id, who);
+			Self::deposit_event(Event::<T>::VestingCompleted(asset_id, who.clone()));
+		} else {
+			<Vesting<T>>::insert(asset_id, who, schedules);
+			let locked = Self::vesting_locked_balance(asset_id, who);
+			Self::set_lock(VESTING_ID, asset_id, who, locked, WithdrawReasons::all());
+			Self::deposit_event(Event::<T>::VestingUpdated(asset_id, who.clone(), locked));
 		}
 	}
 
+	/// Merge two vesting schedules at block `now`.
+	///
+	/// Returns `None` if both schedules have completed, or the unfinished schedule if only one has.
+	fn merge_vesting_schedules(
+		now: T::BlockNumber,
+		schedule1: VestingSchedule<T::Balance, T::BlockNumber>,
+		schedule2: VestingSchedule<T::Balance, T::BlockNumber>,
+	) -> Option<VestingSchedule<T::Balance, T::BlockNumber>> {
+		let locked1 = schedule1.locked_at(now);
+		let locked2 = schedule2.locked_at(now);
+		match (locked1.is_zero(), locked2.is_zero()) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			_ => (),
+		}
+
+		let locked = locked1.saturating_add(locked2);
+		let start = now.max(schedule1.start).max(schedule2.start);
+		let ending = schedule1.ending_block().max(schedule2.ending_block());
+		let cliff_end = schedule1
+			.start
+			.saturating_add(schedule1.cliff)
+			.max(schedule2.start.saturating_add(schedule2.cliff));
+		let duration: T::Balance = ending.saturating_sub(start).saturated_into::<u128>().saturated_into();
+		let per_block = (locked / duration.max(One::one())).max(One::one());
+
+		Some(VestingSchedule::new(
+			locked,
+			per_block,
+			start,
+			cliff_end.saturating_sub(start),
+		))
+	}
+
 	/// Reclaim asset storage items for an account
 	/// Any dust imbalance from free balance is passed to a dust imbalance handler.
 	fn reclaim_free_balance(asset_id: T::AssetId, who: &T::AccountId) {
@@ -101,6 +101,8 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxVestingSchedules: u32 = 4;
+	pub const MinVestedTransfer: u64 = 5;
 }
 impl Config for Test {
 	type Balance = u64;
@@ -108,6 +110,9 @@ impl Config for Test {
 	type Event = Event;
 	type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = ();
 }
 
 // Build storage for generic asset with some default values
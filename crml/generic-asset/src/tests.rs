@@ -23,9 +23,9 @@
 use super::*;
 use crate::mock::{
 	new_test_ext_with_balance, new_test_ext_with_default, new_test_ext_with_next_asset_id,
-	new_test_ext_with_permissions, Event as TestEvent, GenericAsset, NegativeImbalanceOf, Origin, PositiveImbalanceOf,
-	System, Test, TreasuryPalletId, ALICE, ASSET_ID, BOB, CHARLIE, ID_1, ID_2, INITIAL_BALANCE, INITIAL_ISSUANCE,
-	SPENDING_ASSET_ID, STAKING_ASSET_ID, TEST1_ASSET_ID, TEST2_ASSET_ID,
+	new_test_ext_with_permissions, Event as TestEvent, GenericAsset, MaxVestingSchedules, MinVestedTransfer,
+	NegativeImbalanceOf, Origin, PositiveImbalanceOf, System, Test, TreasuryPalletId, ALICE, ASSET_ID, BOB, CHARLIE,
+	ID_1, ID_2, INITIAL_BALANCE, INITIAL_ISSUANCE, SPENDING_ASSET_ID, STAKING_ASSET_ID, TEST1_ASSET_ID, TEST2_ASSET_ID,
 };
 use frame_support::{
 	assert_noop, assert_ok,
@@ -560,7 +560,7 @@ fn migrate_locks_on_runtime_upgrade() {
 		assert_eq!(<Locks<Test>>::iter().count(), 2);
 		assert_eq!(<Locks<Test>>::get(STAKING_ASSET_ID, ALICE), alice_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<u64>>>());
 		assert_eq!(<Locks<Test>>::get(STAKING_ASSET_ID, BOB), bob_locks.iter().map(|l| l.clone().upgrade()).collect::<Vec<BalanceLock<u64>>>());
-		assert_eq!(StorageVersion::get(), Releases::V2 as u32);
+		assert_eq!(StorageVersion::get(), Releases::V3 as u32);
 
 	});
 }
@@ -1296,12 +1296,14 @@ fn update_permission_should_change_permission() {
 			update: Owner::Address(ALICE),
 			mint: Owner::None,
 			burn: Owner::None,
+			..Default::default()
 		};
 
 		let new_permission = PermissionLatest {
 			update: Owner::Address(ALICE),
 			mint: Owner::Address(ALICE),
 			burn: Owner::None,
+			..Default::default()
 		};
 		let asset_info = AssetInfo::default();
 
@@ -1336,6 +1338,7 @@ fn update_permission_should_throw_error_when_lack_of_permissions() {
 			update: Owner::Address(ALICE),
 			mint: Owner::Address(ALICE),
 			burn: Owner::None,
+			..Default::default()
 		};
 		let asset_info = AssetInfo::default();
 
@@ -1369,7 +1372,7 @@ fn create_asset_works_with_given_asset_id_and_from_account() {
 	new_test_ext_with_next_asset_id(1001).execute_with(|| {
 		let from_account: Option<<Test as frame_system::Config>::AccountId> = Some(ALICE);
 		let permissions = PermissionLatest::new(ALICE);
-		let expected_permission = PermissionVersions::V1(permissions.clone());
+		let expected_permission = PermissionVersions::V2(permissions.clone());
 		let asset_info = AssetInfo::default();
 
 		assert_ok!(GenericAsset::create_asset(
@@ -1707,7 +1710,7 @@ fn burn_should_raise_event() {
 #[test]
 fn can_set_asset_owner_permissions_in_genesis() {
 	new_test_ext_with_permissions(vec![(ASSET_ID, ALICE)]).execute_with(|| {
-		let expected: PermissionVersions<_> = PermissionsV1::new(ALICE).into();
+		let expected: PermissionVersions<_> = PermissionLatest::new(ALICE).into();
 		let actual = GenericAsset::get_permission(ASSET_ID);
 		assert_eq!(expected, actual);
 	});
@@ -1831,6 +1834,7 @@ fn non_owner_permitted_update_asset_info() {
 			update: Owner::Address(BOB),
 			mint: Owner::None,
 			burn: Owner::None,
+			..Default::default()
 		};
 		assert_ok!(GenericAsset::update_permission(
 			Origin::signed(ALICE),
@@ -1848,3 +1852,470 @@ fn non_owner_permitted_update_asset_info() {
 		assert_eq!(<AssetMeta<Test>>::get(ASSET_ID), web3_asset_info);
 	});
 }
+
+#[test]
+fn vesting_schedule_releases_linearly_after_cliff() {
+	let schedule = VestingSchedule::<u64, u64>::new(50, 5, 10, 4);
+	assert_eq!(schedule.locked_at(0), 50);
+	assert_eq!(schedule.locked_at(13), 50);
+	assert_eq!(schedule.locked_at(14), 30);
+	assert_eq!(schedule.locked_at(19), 5);
+	assert_eq!(schedule.locked_at(20), 0);
+	assert_eq!(schedule.ending_block(), 20);
+
+	// the cliff outlasts the release
+	assert_eq!(VestingSchedule::<u64, u64>::new(10, 3, 0, 8).ending_block(), 8);
+	// a partial final block
+	assert_eq!(VestingSchedule::<u64, u64>::new(10, 3, 0, 0).ending_block(), 4);
+}
+
+#[test]
+fn vested_transfer_locks_balance_until_vested() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		let schedule = VestingSchedule::new(50, 5, 10, 4);
+		assert_ok!(GenericAsset::vested_transfer(
+			Origin::signed(ALICE),
+			STAKING_ASSET_ID,
+			BOB,
+			schedule
+		));
+
+		assert_eq!(GenericAsset::free_balance(STAKING_ASSET_ID, &BOB), 50);
+		assert_eq!(GenericAsset::vesting(STAKING_ASSET_ID, &BOB), vec![schedule]);
+		assert_eq!(GenericAsset::locks(STAKING_ASSET_ID, &BOB)[0].amount, 50);
+		let expected_event =
+			TestEvent::GenericAsset(RawEvent::VestingScheduleAdded(STAKING_ASSET_ID, ALICE, BOB, schedule));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(BOB), STAKING_ASSET_ID, CHARLIE, 1),
+			Error::<Test>::LiquidityRestrictions
+		);
+
+		// nothing is released before the cliff
+		System::set_block_number(13);
+		assert_ok!(GenericAsset::vest(Origin::signed(BOB), STAKING_ASSET_ID));
+		assert_eq!(GenericAsset::locks(STAKING_ASSET_ID, &BOB)[0].amount, 50);
+
+		// everything accrued since the start is released at the cliff
+		System::set_block_number(14);
+		assert_ok!(GenericAsset::vest(Origin::signed(BOB), STAKING_ASSET_ID));
+		assert_eq!(GenericAsset::locks(STAKING_ASSET_ID, &BOB)[0].amount, 30);
+		let expected_event = TestEvent::GenericAsset(RawEvent::VestingUpdated(STAKING_ASSET_ID, BOB, 30));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(BOB),
+			STAKING_ASSET_ID,
+			CHARLIE,
+			20
+		));
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(BOB), STAKING_ASSET_ID, CHARLIE, 1),
+			Error::<Test>::LiquidityRestrictions
+		);
+
+		System::set_block_number(20);
+		assert_ok!(GenericAsset::vest(Origin::signed(BOB), STAKING_ASSET_ID));
+		assert!(!<Vesting<Test>>::contains_key(STAKING_ASSET_ID, &BOB));
+		assert!(!<Locks<Test>>::contains_key(STAKING_ASSET_ID, &BOB));
+		let expected_event = TestEvent::GenericAsset(RawEvent::VestingCompleted(STAKING_ASSET_ID, BOB));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_ok!(GenericAsset::transfer(
+			Origin::signed(BOB),
+			STAKING_ASSET_ID,
+			CHARLIE,
+			30
+		));
+	});
+}
+
+#[test]
+fn vested_transfer_checks_schedule_and_limit() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		assert_noop!(
+			GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				VestingSchedule::new(10, 0, 0, 0)
+			),
+			Error::<Test>::InvalidVestingSchedule
+		);
+		assert_noop!(
+			GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				VestingSchedule::new(0, 1, 0, 0)
+			),
+			Error::<Test>::InvalidVestingSchedule
+		);
+		assert_noop!(
+			GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				VestingSchedule::new(MinVestedTransfer::get() - 1, 1, 0, 0)
+			),
+			Error::<Test>::AmountLow
+		);
+		assert_noop!(
+			GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				VestingSchedule::new(INITIAL_BALANCE + 1, 1, 0, 0)
+			),
+			Error::<Test>::InsufficientBalance
+		);
+
+		let schedule = VestingSchedule::new(10, 1, 5, 0);
+		for _ in 0..MaxVestingSchedules::get() {
+			assert_ok!(GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				schedule
+			));
+		}
+		assert_noop!(
+			GenericAsset::vested_transfer(Origin::signed(ALICE), STAKING_ASSET_ID, BOB, schedule),
+			Error::<Test>::TooManyVestingSchedules
+		);
+		assert_noop!(
+			GenericAsset::vest(Origin::signed(CHARLIE), STAKING_ASSET_ID),
+			Error::<Test>::NotVesting
+		);
+	});
+}
+
+#[test]
+fn get_all_balances_reports_vesting() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(GenericAsset::vested_transfer(
+			Origin::signed(ALICE),
+			STAKING_ASSET_ID,
+			BOB,
+			VestingSchedule::new(20, 2, 0, 0)
+		));
+		assert_ok!(GenericAsset::vested_transfer(
+			Origin::signed(ALICE),
+			STAKING_ASSET_ID,
+			BOB,
+			VestingSchedule::new(30, 1, 5, 0)
+		));
+		GenericAsset::set_lock(ID_1, STAKING_ASSET_ID, &BOB, 1, WithdrawReasons::all());
+
+		// 18 + 30 locked when the second schedule was added
+		System::set_block_number(6);
+		let balances = GenericAsset::get_all_balances(&BOB, STAKING_ASSET_ID);
+		assert_eq!(balances.staked, 1);
+		assert_eq!(balances.vesting_locked, 48);
+		// 8 + 29 remain locked
+		assert_eq!(balances.vestable, 11);
+		assert_eq!(balances.available, 1);
+
+		assert_ok!(GenericAsset::vest(Origin::signed(BOB), STAKING_ASSET_ID));
+		let balances = GenericAsset::get_all_balances(&BOB, STAKING_ASSET_ID);
+		assert_eq!(balances.vesting_locked, 37);
+		assert_eq!(balances.vestable, 0);
+		assert_eq!(balances.available, 12);
+	});
+}
+
+#[test]
+fn merge_schedules_combines_remaining_locked_balance() {
+	new_test_ext_with_balance(STAKING_ASSET_ID, ALICE, INITIAL_BALANCE).execute_with(|| {
+		System::set_block_number(1);
+		let schedule1 = VestingSchedule::new(20, 2, 0, 0);
+		let schedule2 = VestingSchedule::new(30, 1, 5, 10);
+		let schedule3 = VestingSchedule::new(10, 1, 0, 0);
+		for schedule in vec![schedule1, schedule2, schedule3] {
+			assert_ok!(GenericAsset::vested_transfer(
+				Origin::signed(ALICE),
+				STAKING_ASSET_ID,
+				BOB,
+				schedule
+			));
+		}
+
+		assert_noop!(
+			GenericAsset::merge_schedules(Origin::signed(BOB), STAKING_ASSET_ID, 1, 1),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		assert_noop!(
+			GenericAsset::merge_schedules(Origin::signed(BOB), STAKING_ASSET_ID, 0, 3),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		assert_noop!(
+			GenericAsset::merge_schedules(Origin::signed(CHARLIE), STAKING_ASSET_ID, 0, 1),
+			Error::<Test>::NotVesting
+		);
+
+		// 10 remains of the first schedule and the second is still before its cliff
+		System::set_block_number(5);
+		assert_ok!(GenericAsset::merge_schedules(
+			Origin::signed(BOB),
+			STAKING_ASSET_ID,
+			1,
+			0
+		));
+		let merged = VestingSchedule::new(40, 1, 5, 10);
+		assert_eq!(GenericAsset::vesting(STAKING_ASSET_ID, &BOB), vec![schedule3, merged]);
+		assert_eq!(GenericAsset::vesting_locked_balance(STAKING_ASSET_ID, &BOB), 5 + 40);
+		assert_eq!(GenericAsset::locks(STAKING_ASSET_ID, &BOB)[0].amount, 45);
+
+		// merging a completed schedule keeps the other one
+		System::set_block_number(10);
+		assert_ok!(GenericAsset::merge_schedules(
+			Origin::signed(BOB),
+			STAKING_ASSET_ID,
+			0,
+			1
+		));
+		assert_eq!(GenericAsset::vesting(STAKING_ASSET_ID, &BOB), vec![merged]);
+		assert_eq!(GenericAsset::locks(STAKING_ASSET_ID, &BOB)[0].amount, 40);
+	});
+}
+
+// Create an asset with every permission assigned to ALICE
+fn create_regulated_asset() {
+	let permissions = PermissionLatest {
+		update: Owner::Address(ALICE),
+		mint: Owner::Address(ALICE),
+		burn: Owner::Address(ALICE),
+		freeze: Owner::Address(ALICE),
+		thaw: Owner::Address(ALICE),
+		force_transfer: Owner::Address(ALICE),
+	};
+	let asset_info = AssetInfo::default();
+	assert_ok!(GenericAsset::create(
+		Origin::root(),
+		ALICE,
+		asset_options(permissions, asset_info.decimal_places()),
+		asset_info
+	));
+	assert_ok!(GenericAsset::transfer(
+		Origin::signed(ALICE),
+		ASSET_ID,
+		BOB,
+		INITIAL_BALANCE
+	));
+}
+
+#[test]
+fn migrate_permissions_on_runtime_upgrade() {
+	new_test_ext_with_default().execute_with(|| {
+		StorageVersion::put(Releases::V2 as u32);
+		let v1 = PermissionsV1 {
+			update: Owner::Address(ALICE),
+			mint: Owner::None,
+			burn: Owner::Address(BOB),
+		};
+		<Permissions<Test>>::insert(ASSET_ID, PermissionVersions::V1(v1.clone()));
+
+		let _ = GenericAsset::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get(), Releases::V3 as u32);
+		assert_eq!(
+			<Permissions<Test>>::get(ASSET_ID),
+			PermissionVersions::V2(PermissionsV2 {
+				update: Owner::Address(ALICE),
+				mint: Owner::None,
+				burn: Owner::Address(BOB),
+				freeze: Owner::None,
+				thaw: Owner::None,
+				force_transfer: Owner::None,
+			})
+		);
+		assert!(GenericAsset::check_permission(ASSET_ID, &BOB, &PermissionType::Burn));
+		assert!(!GenericAsset::check_permission(
+			ASSET_ID,
+			&ALICE,
+			&PermissionType::Freeze
+		));
+	});
+}
+
+#[test]
+fn freeze_asset_halts_transfers() {
+	new_test_ext_with_default().execute_with(|| {
+		System::set_block_number(1);
+		create_regulated_asset();
+
+		assert_noop!(
+			GenericAsset::freeze_asset(Origin::signed(BOB), ASSET_ID),
+			Error::<Test>::NoFreezePermission
+		);
+		assert_noop!(
+			GenericAsset::freeze_asset(Origin::signed(ALICE), ASSET_ID + 1),
+			Error::<Test>::AssetIdNotExist
+		);
+		assert_ok!(GenericAsset::freeze_asset(Origin::signed(ALICE), ASSET_ID));
+		assert!(GenericAsset::asset_frozen(ASSET_ID));
+		let expected_event = TestEvent::GenericAsset(RawEvent::AssetFrozen(ASSET_ID));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(BOB), ASSET_ID, CHARLIE, 1),
+			Error::<Test>::AssetIsFrozen
+		);
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(ALICE), ASSET_ID, BOB, 1),
+			Error::<Test>::AssetIsFrozen
+		);
+
+		assert_noop!(
+			GenericAsset::thaw_asset(Origin::signed(BOB), ASSET_ID),
+			Error::<Test>::NoThawPermission
+		);
+		assert_ok!(GenericAsset::thaw_asset(Origin::signed(ALICE), ASSET_ID));
+		assert!(!GenericAsset::asset_frozen(ASSET_ID));
+		assert_ok!(GenericAsset::transfer(Origin::signed(BOB), ASSET_ID, CHARLIE, 1));
+	});
+}
+
+#[test]
+fn freeze_account_prevents_withdrawal() {
+	new_test_ext_with_default().execute_with(|| {
+		System::set_block_number(1);
+		create_regulated_asset();
+
+		assert_noop!(
+			GenericAsset::freeze_account(Origin::signed(BOB), ASSET_ID, BOB),
+			Error::<Test>::NoFreezePermission
+		);
+		assert_ok!(GenericAsset::freeze_account(Origin::signed(ALICE), ASSET_ID, BOB));
+		assert!(GenericAsset::account_frozen(ASSET_ID, &BOB));
+		let expected_event = TestEvent::GenericAsset(RawEvent::AccountFrozen(ASSET_ID, BOB));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(
+			GenericAsset::transfer(Origin::signed(BOB), ASSET_ID, CHARLIE, 1),
+			Error::<Test>::AccountIsFrozen
+		);
+		assert_noop!(
+			GenericAsset::ensure_can_withdraw(
+				ASSET_ID,
+				&BOB,
+				1,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				INITIAL_BALANCE - 1
+			),
+			Error::<Test>::AccountIsFrozen
+		);
+		// a frozen account may still receive the asset
+		assert_ok!(GenericAsset::transfer(Origin::signed(ALICE), ASSET_ID, BOB, 1));
+		// other accounts are unaffected
+		assert_ok!(GenericAsset::transfer(Origin::signed(ALICE), ASSET_ID, CHARLIE, 1));
+
+		assert_noop!(
+			GenericAsset::thaw_account(Origin::signed(BOB), ASSET_ID, BOB),
+			Error::<Test>::NoThawPermission
+		);
+		assert_ok!(GenericAsset::thaw_account(Origin::signed(ALICE), ASSET_ID, BOB));
+		assert!(!GenericAsset::account_frozen(ASSET_ID, &BOB));
+		assert_ok!(GenericAsset::transfer(Origin::signed(BOB), ASSET_ID, CHARLIE, 1));
+	});
+}
+
+#[test]
+fn freeze_prevents_reserving_and_repatriating() {
+	new_test_ext_with_default().execute_with(|| {
+		create_regulated_asset();
+		assert_ok!(GenericAsset::reserve(ASSET_ID, &BOB, 10));
+
+		assert_ok!(GenericAsset::freeze_account(Origin::signed(ALICE), ASSET_ID, BOB));
+		assert_noop!(GenericAsset::reserve(ASSET_ID, &BOB, 1), Error::<Test>::AccountIsFrozen);
+		assert_noop!(
+			GenericAsset::repatriate_reserved(ASSET_ID, &BOB, &CHARLIE, 10),
+			Error::<Test>::AccountIsFrozen
+		);
+		assert_ok!(GenericAsset::thaw_account(Origin::signed(ALICE), ASSET_ID, BOB));
+
+		assert_ok!(GenericAsset::freeze_asset(Origin::signed(ALICE), ASSET_ID));
+		assert_noop!(GenericAsset::reserve(ASSET_ID, &BOB, 1), Error::<Test>::AssetIsFrozen);
+		assert_noop!(
+			GenericAsset::repatriate_reserved(ASSET_ID, &BOB, &CHARLIE, 10),
+			Error::<Test>::AssetIsFrozen
+		);
+		assert_ok!(GenericAsset::thaw_asset(Origin::signed(ALICE), ASSET_ID));
+
+		assert_ok!(GenericAsset::repatriate_reserved(ASSET_ID, &BOB, &CHARLIE, 10), 0);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &CHARLIE), 10);
+	});
+}
+
+#[test]
+fn only_root_changes_regulatory_roles() {
+	new_test_ext_with_default().execute_with(|| {
+		create_regulated_asset();
+		let mut permissions = PermissionLatest {
+			update: Owner::Address(ALICE),
+			mint: Owner::Address(BOB),
+			burn: Owner::Address(ALICE),
+			freeze: Owner::Address(ALICE),
+			thaw: Owner::Address(ALICE),
+			force_transfer: Owner::Address(ALICE),
+		};
+		// the owner may change other roles
+		assert_ok!(GenericAsset::update_permission(
+			Origin::signed(ALICE),
+			ASSET_ID,
+			permissions.clone()
+		));
+		assert!(GenericAsset::check_permission(ASSET_ID, &BOB, &PermissionType::Mint));
+
+		permissions.force_transfer = Owner::Address(BOB);
+		assert_noop!(
+			GenericAsset::update_permission(Origin::signed(ALICE), ASSET_ID, permissions.clone()),
+			Error::<Test>::NoRegulatoryRolePermission
+		);
+		assert_noop!(
+			GenericAsset::update_permission(Origin::root(), ASSET_ID + 1, permissions.clone()),
+			Error::<Test>::AssetIdNotExist
+		);
+		assert_ok!(GenericAsset::update_permission(Origin::root(), ASSET_ID, permissions));
+		assert!(GenericAsset::check_permission(
+			ASSET_ID,
+			&BOB,
+			&PermissionType::ForceTransfer
+		));
+	});
+}
+
+#[test]
+fn force_transfer_moves_frozen_balance() {
+	new_test_ext_with_default().execute_with(|| {
+		System::set_block_number(1);
+		create_regulated_asset();
+		assert_ok!(GenericAsset::freeze_account(Origin::signed(ALICE), ASSET_ID, BOB));
+
+		assert_noop!(
+			GenericAsset::force_transfer(Origin::signed(BOB), ASSET_ID, BOB, CHARLIE, 1),
+			Error::<Test>::NoForceTransferPermission
+		);
+		assert_noop!(
+			GenericAsset::force_transfer(Origin::signed(ALICE), ASSET_ID, BOB, CHARLIE, 0),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			GenericAsset::force_transfer(Origin::signed(ALICE), ASSET_ID, BOB, CHARLIE, INITIAL_BALANCE + 1),
+			Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(GenericAsset::force_transfer(
+			Origin::signed(ALICE),
+			ASSET_ID,
+			BOB,
+			CHARLIE,
+			INITIAL_BALANCE
+		));
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &BOB), 0);
+		assert_eq!(GenericAsset::free_balance(ASSET_ID, &CHARLIE), INITIAL_BALANCE);
+		let expected_event =
+			TestEvent::GenericAsset(RawEvent::ForceTransferred(ASSET_ID, BOB, CHARLIE, INITIAL_BALANCE));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+	});
+}
@@ -3,7 +3,10 @@
 use codec::{Decode, Encode, Error as CodecError, HasCompact, Input, MaxEncodedLen, Output};
 use frame_support::traits::{LockIdentifier, WithdrawReasons};
 use scale_info::{Type, TypeDefPrimitive, TypeInfo};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, One, Saturating, Zero},
+	RuntimeDebug, SaturatedConversion,
+};
 use sp_std::{ops::BitOr, prelude::*, vec};
 
 #[cfg(feature = "std")]
@@ -154,8 +157,70 @@ pub struct AllBalances<Balance> {
 	pub reserved: Balance,
 	///Staked balance (Locked)
 	pub staked: Balance,
-	/// Available balance (Free - staked)
+	/// Available balance (Free - staked - vesting_locked)
 	pub available: Balance,
+	/// Balance held by the vesting lock
+	pub vesting_locked: Balance,
+	/// Vesting locked balance which has vested and may be unlocked by calling `vest`
+	pub vestable: Balance,
+}
+
+/// A linear vesting schedule over an account's balance of some asset.
+///
+/// The full `locked` amount is held until `start + cliff`, after which `per_block` is released for every
+/// block since `start`.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// Amount locked at the start of the schedule
+	pub locked: Balance,
+	/// Amount released per block once the cliff has passed
+	pub per_block: Balance,
+	/// Block number at which the schedule starts accruing
+	pub start: BlockNumber,
+	/// Number of blocks after `start` before any balance is released
+	pub cliff: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Create a new vesting schedule
+	pub fn new(locked: Balance, per_block: Balance, start: BlockNumber, cliff: BlockNumber) -> Self {
+		Self {
+			locked,
+			per_block,
+			start,
+			cliff,
+		}
+	}
+
+	/// A schedule must lock and release some balance
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
+	/// Amount still locked by the schedule at block `n`
+	pub fn locked_at(&self, n: BlockNumber) -> Balance {
+		if n < self.start.saturating_add(self.cliff) {
+			return self.locked;
+		}
+		let elapsed: Balance = n.saturating_sub(self.start).saturated_into::<u128>().saturated_into();
+		self.locked.saturating_sub(self.per_block.saturating_mul(elapsed))
+	}
+
+	/// The first block at which the schedule has nothing locked
+	pub fn ending_block(&self) -> BlockNumber {
+		let per_block = self.per_block.max(One::one());
+		let mut duration = self.locked / per_block;
+		if !(self.locked % per_block).is_zero() {
+			duration = duration.saturating_add(One::one());
+		}
+		let duration: BlockNumber = duration.saturated_into::<u128>().saturated_into();
+		self.start.saturating_add(duration.max(self.cliff))
+	}
 }
 
 /// Asset permissions
@@ -180,16 +245,63 @@ impl<AccountId: Clone> PermissionsV1<AccountId> {
 	}
 }
 
+/// Asset permissions, with roles for regulated assets
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PermissionsV2<AccountId> {
+	/// Who have permission to update asset permission
+	pub update: Owner<AccountId>,
+	/// Who have permission to mint new asset
+	pub mint: Owner<AccountId>,
+	/// Who have permission to burn asset
+	pub burn: Owner<AccountId>,
+	/// Who have permission to freeze the asset, or an account's balance of it
+	pub freeze: Owner<AccountId>,
+	/// Who have permission to thaw the asset, or an account's balance of it
+	pub thaw: Owner<AccountId>,
+	/// Who have permission to transfer asset out of any account
+	pub force_transfer: Owner<AccountId>,
+}
+
+impl<AccountId: Clone> PermissionsV2<AccountId> {
+	/// Create a new `PermissionV2` with update, mint and burn permission to the given `owner`.
+	/// The freeze, thaw and force transfer roles are left unassigned.
+	pub fn new(owner: AccountId) -> Self {
+		PermissionsV1::new(owner).into()
+	}
+}
+
+impl<AccountId: PartialEq> PermissionsV2<AccountId> {
+	/// Whether `other` assigns the same freeze, thaw and force transfer roles
+	pub fn same_regulatory_roles(&self, other: &Self) -> bool {
+		self.freeze == other.freeze && self.thaw == other.thaw && self.force_transfer == other.force_transfer
+	}
+}
+
+impl<AccountId> From<PermissionsV1<AccountId>> for PermissionsV2<AccountId> {
+	fn from(v1: PermissionsV1<AccountId>) -> Self {
+		Self {
+			update: v1.update,
+			mint: v1.mint,
+			burn: v1.burn,
+			freeze: Owner::None,
+			thaw: Owner::None,
+			force_transfer: Owner::None,
+		}
+	}
+}
+
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 #[repr(u8)]
 enum PermissionVersionNumber {
 	V1 = 0,
+	V2 = 1,
 }
 
 /// Versioned asset permission
 #[derive(Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum PermissionVersions<AccountId> {
 	V1(PermissionsV1<AccountId>),
+	V2(PermissionsV2<AccountId>),
 }
 
 /// Asset permission types
@@ -200,14 +312,20 @@ pub enum PermissionType {
 	Mint,
 	/// Permission to update asset
 	Update,
+	/// Permission to freeze the asset or an account's balance
+	Freeze,
+	/// Permission to thaw the asset or an account's balance
+	Thaw,
+	/// Permission to transfer asset out of any account
+	ForceTransfer,
 }
 
 /// Alias to latest asset permissions
-pub type PermissionLatest<AccountId> = PermissionsV1<AccountId>;
+pub type PermissionLatest<AccountId> = PermissionsV2<AccountId>;
 
 impl<AccountId> Default for PermissionVersions<AccountId> {
 	fn default() -> Self {
-		PermissionVersions::V1(Default::default())
+		PermissionVersions::V2(Default::default())
 	}
 }
 
@@ -218,6 +336,10 @@ impl<AccountId: Encode> Encode for PermissionVersions<AccountId> {
 				dest.write(&PermissionVersionNumber::V1.encode());
 				dest.write(&payload.encode());
 			}
+			PermissionVersions::V2(payload) => {
+				dest.write(&PermissionVersionNumber::V2.encode());
+				dest.write(&payload.encode());
+			}
 		}
 	}
 }
@@ -229,6 +351,7 @@ impl<AccountId: Decode> Decode for PermissionVersions<AccountId> {
 		let version = PermissionVersionNumber::decode(input)?;
 		Ok(match version {
 			PermissionVersionNumber::V1 => PermissionVersions::V1(Decode::decode(input)?),
+			PermissionVersionNumber::V2 => PermissionVersions::V2(Decode::decode(input)?),
 		})
 	}
 }
@@ -243,10 +366,17 @@ impl<AccountId> Default for PermissionsV1<AccountId> {
 	}
 }
 
+impl<AccountId> Default for PermissionsV2<AccountId> {
+	fn default() -> Self {
+		PermissionsV1::default().into()
+	}
+}
+
 impl<AccountId> Into<PermissionLatest<AccountId>> for PermissionVersions<AccountId> {
 	fn into(self) -> PermissionLatest<AccountId> {
 		match self {
-			PermissionVersions::V1(v1) => v1,
+			PermissionVersions::V1(v1) => v1.into(),
+			PermissionVersions::V2(v2) => v2,
 		}
 	}
 }
@@ -254,6 +384,6 @@ impl<AccountId> Into<PermissionLatest<AccountId>> for PermissionVersions<Account
 /// Converts the latest permission to other version.
 impl<AccountId> Into<PermissionVersions<AccountId>> for PermissionLatest<AccountId> {
 	fn into(self) -> PermissionVersions<AccountId> {
-		PermissionVersions::V1(self)
+		PermissionVersions::V2(self)
 	}
 }
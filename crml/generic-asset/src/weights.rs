@@ -30,6 +30,14 @@ pub trait WeightInfo {
 	fn transfer_keep_alive() -> Weight;
 	fn update_asset_info() -> Weight;
 	fn update_permission() -> Weight;
+	fn vest(s: u32) -> Weight;
+	fn vested_transfer(s: u32) -> Weight;
+	fn merge_schedules(s: u32) -> Weight;
+	fn freeze_asset() -> Weight;
+	fn thaw_asset() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
+	fn force_transfer() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -73,4 +81,47 @@ impl WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(7 as Weight))
 			.saturating_add(DbWeight::get().writes(5 as Weight))
 	}
+	fn vest(s: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((800_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vested_transfer(s: u32) -> Weight {
+		(195_000_000 as Weight)
+			.saturating_add((700_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(10 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn merge_schedules(s: u32) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((800_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn freeze_asset() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn thaw_asset() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn freeze_account() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn thaw_account() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn force_transfer() -> Weight {
+		(160_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
 }
@@ -77,6 +77,8 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxVestingSchedules: u32 = 4;
+	pub const MinVestedTransfer: Balance = 1;
 }
 impl crml_generic_asset::Config for Test {
 	type AssetId = AssetId;
@@ -84,6 +86,9 @@ impl crml_generic_asset::Config for Test {
 	type Event = Event;
 	type OnDustImbalance = ();
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = ();
 }
 
 parameter_types! {
@@ -20,11 +20,12 @@
 
 use cennznet_primitives::types::{CollectionId, SerialNumber, SeriesId, TokenId};
 use codec::Codec;
-use crml_nft::{CollectionInfo, Config, Listing, ListingId, TokenInfo};
+use crml_nft::{CollectionInfo, Config, Listing, ListingId, TokenInfo, TokenRental};
 use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
 	/// The RPC API to interact with NFT module
+	#[api_version(2)]
 	pub trait NftApi<AccountId, T> where
 		AccountId: Codec,
 		T: Config,
@@ -53,5 +54,8 @@ sp_api::decl_runtime_apis! {
 			offset: u128,
 			limit: u16,
 		) -> (Option<u128>, Vec<(ListingId, Listing<T>)>);
+
+		/// Get the active rental of a given token i.e. its current user and rental expiry
+		fn token_user(token_id: TokenId) -> Option<TokenRental<AccountId, T::BlockNumber>>;
 	}
 }
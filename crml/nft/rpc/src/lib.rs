@@ -20,12 +20,15 @@ use std::sync::Arc;
 
 use cennznet_primitives::types::{BlockNumber, CollectionId, SerialNumber, SeriesId, TokenId};
 use codec::Codec;
-use crml_nft::{CollectionInfo, Config, Listing, ListingResponse, ListingResponseWrapper, TokenInfo};
+use crml_nft::{CollectionInfo, Config, Listing, ListingResponse, ListingResponseWrapper, TokenInfo, TokenRental};
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, UniqueSaturatedInto},
+};
 
 pub use self::gen_client::Client as NftClient;
 pub use crml_nft_rpc_runtime_api::{self as runtime_api, NftApi as NftRuntimeApi};
@@ -57,6 +60,9 @@ pub trait NftApi<AccountId> {
 		cursor: u128,
 		limit: u16,
 	) -> Result<ListingResponseWrapper<AccountId>>;
+
+	#[rpc(name = "nft_getTokenUser")]
+	fn token_user(&self, token_id: TokenId) -> Result<Option<TokenRental<AccountId, BlockNumber>>>;
 }
 
 /// Error type of this RPC api.
@@ -155,8 +161,9 @@ where
 		limit: u16,
 	) -> Result<ListingResponseWrapper<AccountId>> {
 		let api = self.client.runtime_api();
-		let best = self.client.info().best_hash;
-		let at = BlockId::hash(best);
+		let info = self.client.info();
+		let best_number: BlockNumber = info.best_number.unique_saturated_into();
+		let at = BlockId::hash(info.best_hash);
 
 		let result = api
 			.collection_listings(&at, collection_id, offset, limit)
@@ -193,6 +200,17 @@ where
 					token_ids: auction.tokens,
 					royalties: auction.royalties_schedule.entitlements,
 				},
+				Listing::Dutch(auction) => ListingResponse {
+					id: listing_id,
+					listing_type: "dutchAuction".as_bytes().to_vec(),
+					payment_asset: auction.payment_asset,
+					price: auction.current_price(best_number),
+					end_block: auction.close,
+					buyer: None,
+					seller: auction.seller,
+					token_ids: auction.tokens,
+					royalties: auction.royalties_schedule.entitlements,
+				},
 			})
 			.collect();
 
@@ -201,4 +219,15 @@ where
 			new_cursor,
 		})
 	}
+
+	fn token_user(&self, token_id: TokenId) -> Result<Option<TokenRental<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let best = self.client.info().best_hash;
+		let at = BlockId::hash(best);
+		api.token_user(&at, token_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query token user.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
@@ -23,7 +23,7 @@ use frame_system::RawOrigin;
 use sp_runtime::Permill;
 
 use crate::types::MAX_ENTITLEMENTS;
-use crate::{Module as Nft, MAX_COLLECTION_NAME_LENGTH};
+use crate::{Module as Nft, MAX_COLLECTION_NAME_LENGTH, MAX_SCHEMA_FIELDS};
 
 /// payment asset
 const PAYMENT_ASSET: u32 = 16_000;
@@ -64,6 +64,8 @@ fn setup_token<T: Config>(owner: T::AccountId) -> CollectionId {
 		Some(owner.clone()),
 		MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
 		None,
+		None,
+		None,
 	)
 	.expect("created token");
 
@@ -102,7 +104,7 @@ benchmarks! {
 		let serial_number = <Nft<T>>::next_serial_number(collection_id, series_id);
 		let final_token_id = (collection_id, series_id, serial_number);
 		let _ = <Nft<T>>::create_collection(RawOrigin::Signed(creator.clone()).into(), b"test-collection".to_vec(), Some(royalties.clone())).expect("created collection");
-		let _ = <Nft<T>>::mint_series(RawOrigin::Signed(creator.clone()).into(), collection_id, 1, Some(owner.clone()), MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()), None).expect("minted series");
+		let _ = <Nft<T>>::mint_series(RawOrigin::Signed(creator.clone()).into(), collection_id, 1, Some(owner.clone()), MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()), None, None, None).expect("minted series");
 
 	}: _(RawOrigin::Signed(creator.clone()), collection_id, series_id, q.into(), Some(owner.clone()))
 	verify {
@@ -126,7 +128,7 @@ benchmarks! {
 				.collect::<Vec<(T::AccountId, Permill)>>(),
 		};
 
-	}: _(RawOrigin::Signed(creator.clone()), collection_id, q.into(), Some(owner.clone()), MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()), Some(royalties))
+	}: _(RawOrigin::Signed(creator.clone()), collection_id, q.into(), Some(owner.clone()), MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()), Some(royalties), None, None)
 	verify {
 		// the last token id in
 		assert_eq!(<Nft<T>>::token_owner((collection_id, series_id), <Nft<T>>::next_serial_number(collection_id, series_id) - 1), owner);
@@ -189,7 +191,7 @@ benchmarks! {
 		let _ = T::MultiCurrency::deposit_creating(&owner, PAYMENT_ASSET, PRICE);
 		let _ = T::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, PRICE + 1);
 		let listing_id = <Nft<T>>::next_listing_id();
-		let _ = <Nft<T>>::auction(RawOrigin::Signed(owner.clone()).into(), token_id, PAYMENT_ASSET, PRICE, Some(duration), None).expect("listed ok");
+		let _ = <Nft<T>>::auction(RawOrigin::Signed(owner.clone()).into(), token_id, PAYMENT_ASSET, PRICE, Some(duration), None, None).expect("listed ok");
 		// worst case path is to replace an existing bid
 		let _ = <Nft<T>>::bid(RawOrigin::Signed(owner.clone()).into(), listing_id, PRICE);
 
@@ -205,12 +207,148 @@ benchmarks! {
 		let listing_id = <Nft<T>>::next_listing_id();
 		let duration = T::BlockNumber::from(100_u32);
 		let listing_id = <Nft<T>>::next_listing_id();
-		let _ = <Nft<T>>::auction(RawOrigin::Signed(owner.clone()).into(), token_id, PAYMENT_ASSET, PRICE, Some(duration), None).expect("listed ok");
+		let _ = <Nft<T>>::auction(RawOrigin::Signed(owner.clone()).into(), token_id, PAYMENT_ASSET, PRICE, Some(duration), None, None).expect("listed ok");
 
 	}: _(RawOrigin::Signed(owner.clone()), listing_id)
 	verify {
 		assert!(<Nft<T>>::listings(listing_id).is_none());
 	}
+
+	// worst case path replaces an already scheduled update
+	set_series_royalties {
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+		let (_, royalties) = setup_collection::<T>(creator.clone());
+		let _ = <Nft<T>>::set_series_royalties(RawOrigin::Signed(creator.clone()).into(), collection_id, 0, None)
+			.expect("scheduled update");
+
+	}: _(RawOrigin::Signed(creator.clone()), collection_id, 0, Some(royalties.clone()))
+	verify {
+		assert_eq!(<Nft<T>>::pending_series_royalties(collection_id, 0).and_then(|(_, r)| r), Some(royalties));
+	}
+
+	// worst case path replaces an already scheduled update
+	set_token_royalties {
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+		let token_id = (collection_id, 0, 0);
+		let (_, royalties) = setup_collection::<T>(creator.clone());
+		let _ = <Nft<T>>::set_token_royalties(RawOrigin::Signed(creator.clone()).into(), token_id, None)
+			.expect("scheduled update");
+
+	}: _(RawOrigin::Signed(creator.clone()), token_id, Some(royalties.clone()))
+	verify {
+		assert_eq!(<Nft<T>>::pending_token_royalties(token_id).and_then(|(_, r)| r), Some(royalties));
+	}
+
+	set_public_mint {
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+		let public_mint = PublicMint {
+			start: T::BlockNumber::from(0_u32),
+			end: Some(T::BlockNumber::from(100_u32)),
+			payment_asset: PAYMENT_ASSET,
+			price: PRICE,
+			max_per_account: Some(QUANTITY),
+		};
+
+	}: _(RawOrigin::Signed(creator.clone()), collection_id, 0, Some(public_mint.clone()))
+	verify {
+		assert_eq!(<Nft<T>>::series_public_mint(collection_id, 0), Some(public_mint));
+	}
+
+	public_mint {
+		let q in 1 .. 10;
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let minter: T::AccountId = account("minter", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+		let public_mint = PublicMint {
+			start: T::BlockNumber::from(0_u32),
+			end: None,
+			payment_asset: PAYMENT_ASSET,
+			price: PRICE,
+			max_per_account: Some(QUANTITY),
+		};
+		let _ = <Nft<T>>::set_public_mint(RawOrigin::Signed(creator.clone()).into(), collection_id, 0, Some(public_mint)).expect("public mint set");
+		let _ = T::MultiCurrency::deposit_creating(&minter, PAYMENT_ASSET, PRICE * q as Balance);
+
+	}: _(RawOrigin::Signed(minter.clone()), collection_id, 0, q)
+	verify {
+		assert_eq!(<Nft<T>>::next_serial_number(collection_id, 0), QUANTITY + q);
+		assert_eq!(<Nft<T>>::public_mint_count((collection_id, 0), &minter), q);
+	}
+
+	set_attribute_policy {
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+
+	}: _(RawOrigin::Signed(creator.clone()), collection_id, 0, AttributePolicy::OwnerOnly)
+	verify {
+		assert_eq!(<Nft<T>>::series_attribute_policy(collection_id, 0), AttributePolicy::OwnerOnly);
+	}
+
+	update_token_attributes {
+		let n in 1 .. MAX_SCHEMA_FIELDS;
+		let creator: T::AccountId = whitelisted_caller();
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = setup_token::<T>(owner);
+		let token_id = (collection_id, 0, 0);
+		// distinct keys and values of the max. attribute length
+		let max_length = T::MaxAttributeLength::get() as usize;
+		let updates = (0..n)
+			.map(|i| {
+				let mut key = vec![b'k'; max_length - 1];
+				key.push(b'a' + i as u8);
+				(key, Some(NFTAttributeValue::String(vec![b'v'; max_length])))
+			})
+			.collect::<Vec<_>>();
+
+	}: _(RawOrigin::Signed(creator.clone()), token_id, updates)
+	verify {
+		assert_eq!(<Nft<T>>::token_attributes(token_id).len() as u32, n);
+	}
+
+	lend {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let user: T::AccountId = account("user", 0, 0);
+		let collection_id = setup_token::<T>(owner.clone());
+		let token_id = (collection_id, 0, 0);
+
+	}: _(RawOrigin::Signed(owner.clone()), token_id, user.clone(), T::BlockNumber::from(100_u32), None)
+	verify {
+		assert!(<Nft<T>>::token_rentals(token_id).is_some());
+	}
+
+	accept_rental {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let user: T::AccountId = account("user", 0, 0);
+		let collection_id = setup_token::<T>(owner.clone());
+		let token_id = (collection_id, 0, 0);
+		let _ = T::MultiCurrency::deposit_creating(&user, PAYMENT_ASSET, PRICE);
+		let _ = <Nft<T>>::lend(RawOrigin::Signed(owner.clone()).into(), token_id, user.clone(), T::BlockNumber::from(100_u32), Some((PAYMENT_ASSET, PRICE))).expect("offered ok");
+
+	}: _(RawOrigin::Signed(user.clone()), token_id)
+	verify {
+		assert!(<Nft<T>>::rental_offers(token_id).is_none());
+		assert!(<Nft<T>>::token_rentals(token_id).is_some());
+	}
+
+	cancel_rental_offer {
+		let owner: T::AccountId = account("owner", 0, 0);
+		let user: T::AccountId = account("user", 0, 0);
+		let collection_id = setup_token::<T>(owner.clone());
+		let token_id = (collection_id, 0, 0);
+		let _ = <Nft<T>>::lend(RawOrigin::Signed(owner.clone()).into(), token_id, user, T::BlockNumber::from(100_u32), Some((PAYMENT_ASSET, PRICE))).expect("offered ok");
+
+	}: _(RawOrigin::Signed(owner.clone()), token_id)
+	verify {
+		assert!(<Nft<T>>::rental_offers(token_id).is_none());
+	}
 }
 
 #[cfg(test)]
@@ -288,4 +426,67 @@ mod tests {
 			assert_ok!(test_benchmark_cancel_sale::<Test>());
 		});
 	}
+
+	#[test]
+	fn set_series_royalties() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_series_royalties::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_token_royalties() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_token_royalties::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_public_mint() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_public_mint::<Test>());
+		});
+	}
+
+	#[test]
+	fn public_mint() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_public_mint::<Test>());
+		});
+	}
+
+	#[test]
+	fn set_attribute_policy() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_attribute_policy::<Test>());
+		});
+	}
+
+	#[test]
+	fn update_token_attributes() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_update_token_attributes::<Test>());
+		});
+	}
+
+	#[test]
+	fn lend() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_lend::<Test>());
+		});
+	}
+
+	#[test]
+	fn accept_rental() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_accept_rental::<Test>());
+		});
+	}
+
+	#[test]
+	fn cancel_rental_offer() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_rental_offer::<Test>());
+		});
+	}
 }
@@ -66,6 +66,12 @@ pub const MAX_SCHEMA_FIELDS: u32 = 16;
 pub const MAX_COLLECTION_NAME_LENGTH: u8 = 32;
 /// The maximum amount of listings to return
 pub const MAX_COLLECTION_LISTING_LIMIT: u16 = 100;
+/// The maximum number of offers to expire in a single block
+pub const MAX_OFFER_EXPIRIES_PER_BLOCK: u32 = 50;
+/// The maximum number of rentals to end in a single block
+pub const MAX_RENTAL_ENDS_PER_BLOCK: u32 = 50;
+/// The maximum number of royalties updates to apply in a single block
+pub const MAX_ROYALTIES_UPDATES_PER_BLOCK: u32 = 50;
 /// The logging target for this module
 pub(crate) const LOG_TARGET: &str = "nft";
 
@@ -83,6 +89,8 @@ pub trait Config: frame_system::Config {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
 	/// Default auction / sale length in blocks
 	type DefaultListingDuration: Get<Self::BlockNumber>;
+	/// Delay in blocks before a series royalties update takes effect
+	type RoyaltiesUpdateDelay: Get<Self::BlockNumber>;
 	/// Maximum byte length of an NFT attribute
 	type MaxAttributeLength: Get<u8>;
 	/// Handles a multi-currency fungible asset system
@@ -97,6 +105,7 @@ decl_event!(
 	pub enum Event<T> where
 		CollectionId = CollectionId,
 		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
 		AssetId = AssetId,
 		Balance = Balance,
 		Reason = AuctionClosureReason,
@@ -132,8 +141,14 @@ decl_event!(
 		AuctionSold(CollectionId, ListingId, AssetId, Balance, AccountId),
 		/// An auction has closed without selling (collection, listing, reason)
 		AuctionClosed(CollectionId, ListingId, Reason),
+		/// An auction was extended by a late bid (listing, new closing block)
+		ListingExtended(ListingId, BlockNumber),
 		/// A new highest bid was placed (collection, listing, amount)
 		Bid(CollectionId, ListingId, Balance),
+		/// A dutch auction has opened (collection, listing, marketplace_id)
+		DutchAuctionOpen(CollectionId, ListingId, Option<MarketplaceId>),
+		/// A dutch auction has sold (collection, listing, payment asset, price, new owner)
+		DutchAuctionSold(CollectionId, ListingId, AssetId, Balance, AccountId),
 		/// An account has been registered as a marketplace (account, entitlement, marketplace_id)
 		RegisteredMarketplace(AccountId, Permill, MarketplaceId),
 		/// An offer has been made on an NFT (offer_id, amount, asset_id, marketplace_id, buyer)
@@ -144,8 +159,34 @@ decl_event!(
 		OfferRejected(OfferId),
 		/// An offer has been cancelled (offer_id)
 		OfferCancelled(OfferId),
+		/// An offer has expired and its funds were released (offer_id)
+		OfferExpired(OfferId),
 		/// The token balances for a collection have been updated (collection_id, series_id)
 		TokenBalancesUpdated(CollectionId, SeriesId),
+		/// A token rental has been offered (token, user, payment asset, price)
+		RentalOffered(TokenId, AccountId, AssetId, Balance),
+		/// A token rental offer has been cancelled (token)
+		RentalOfferCancelled(TokenId),
+		/// A token has been rented (token, user, expiry block)
+		TokenRented(TokenId, AccountId, BlockNumber),
+		/// A token rental has ended, usage rights returned to the owner (token, user)
+		RentalEnded(TokenId, AccountId),
+		/// The attribute policy of a series has been set (collection, series, policy)
+		AttributePolicySet(CollectionId, SeriesId, AttributePolicy),
+		/// A token attribute has been set (token, key, value)
+		TokenAttributeSet(TokenId, AttributeKey, NFTAttributeValue),
+		/// A token attribute has been removed (token, key)
+		TokenAttributeRemoved(TokenId, AttributeKey),
+		/// The public mint of a series has been set or removed (collection, series)
+		PublicMintUpdated(CollectionId, SeriesId),
+		/// A series royalties update has been scheduled (collection, series, effective block)
+		SeriesRoyaltiesUpdateScheduled(CollectionId, SeriesId, BlockNumber),
+		/// A scheduled series royalties update has taken effect (collection, series)
+		SeriesRoyaltiesUpdated(CollectionId, SeriesId),
+		/// A token royalties update has been scheduled (token, effective block)
+		TokenRoyaltiesUpdateScheduled(TokenId, BlockNumber),
+		/// The royalties of an individual token have been set or removed (token)
+		TokenRoyaltiesUpdated(TokenId),
 	}
 );
 
@@ -172,7 +213,7 @@ decl_error! {
 		NotForFixedPriceSale,
 		/// The token is not listed for auction sale
 		NotForAuction,
-		/// Cannot operate on a listed NFT
+		/// Cannot operate on a listed or rented NFT
 		TokenListingProtection,
 		/// Internal error during payment
 		InternalPayment,
@@ -180,6 +221,8 @@ decl_error! {
 		RoyaltiesInvalid,
 		/// Auction bid was lower than reserve or current highest bid
 		BidTooLow,
+		/// Auction rules are invalid i.e. soft close window without an extension period
+		InvalidAuctionRules,
 		/// Selling tokens from different collections is not allowed
 		MixedBundleSale,
 		/// Tokens with different individual royalties cannot be sold together
@@ -202,6 +245,36 @@ decl_error! {
 		ZeroOffer,
 		/// Cannot make an offer on a token up for auction
 		TokenOnAuction,
+		/// Dutch auction start price must not be lower than the reserve price
+		DutchAuctionPriceInvalid,
+		/// The token does not meet the requirements of the offer
+		OfferTokenMismatch,
+		/// The offer expiry block must be in the future
+		OfferExpiryInvalid,
+		/// Rental duration must be greater than 0
+		RentalDurationInvalid,
+		/// No rental has been offered to the caller for the token
+		NoRentalOffer,
+		/// The series transfer policy does not allow the token to change owner
+		TokenNotTransferable,
+		/// The series transfer policy does not allow the token owner to burn the token
+		TokenNotBurnable,
+		/// The series transfer policy does not allow the token to be revoked
+		TokenNotRevocable,
+		/// The series token attributes are frozen
+		AttributesFrozen,
+		/// The attribute key is invalid (non-utf8 or empty)
+		InvalidAttributeKey,
+		/// Max issuance must be greater than 0 and cover the initial quantity
+		InvalidMaxIssuance,
+		/// Minting would exceed the max issuance of the series
+		MaxIssuanceReached,
+		/// The public mint window is invalid i.e. it closes before it opens
+		InvalidPublicMint,
+		/// The series has no public mint open
+		PublicMintNotOpen,
+		/// Minting would exceed the public mint limit for the account
+		PublicMintLimitReached,
 	}
 }
 
@@ -230,6 +303,32 @@ decl_storage! {
 		pub SeriesName get(fn series_name): map hasher(twox_64_concat) (CollectionId, SeriesId) => CollectionNameType;
 		/// Map from (collection, series) to configured royalties schedule
 		pub SeriesRoyalties get(fn series_royalties): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => Option<RoyaltiesSchedule<T::AccountId>>;
+		/// Map from (collection, series) to a scheduled royalties update (effective block, new schedule)
+		/// A `None` schedule removes the series royalties
+		pub PendingSeriesRoyalties get(fn pending_series_royalties): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => Option<(T::BlockNumber, Option<RoyaltiesSchedule<T::AccountId>>)>;
+		/// Map from block to the series royalties updates taking effect at that block
+		pub SeriesRoyaltiesUpdateSchedule get(fn series_royalties_update_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) (CollectionId, SeriesId) => bool;
+		/// Map from token to its individual royalties schedule, overriding the series and collection schedules
+		pub TokenRoyalties get(fn token_royalties): map hasher(twox_64_concat) TokenId => Option<RoyaltiesSchedule<T::AccountId>>;
+		/// Map from token to a scheduled royalties update (effective block, new schedule)
+		/// A `None` schedule removes the token royalties
+		pub PendingTokenRoyalties get(fn pending_token_royalties): map hasher(twox_64_concat) TokenId => Option<(T::BlockNumber, Option<RoyaltiesSchedule<T::AccountId>>)>;
+		/// Map from block to the token royalties updates taking effect at that block
+		pub TokenRoyaltiesUpdateSchedule get(fn token_royalties_update_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) TokenId => bool;
+		/// Block numbers with royalties updates yet to be applied, oldest first
+		pub RoyaltiesUpdateBacklog get(fn royalties_update_backlog): Vec<T::BlockNumber>;
+		/// Map from a (collection, series) to its token attribute update policy
+		pub SeriesAttributePolicy get(fn series_attribute_policy): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => AttributePolicy;
+		/// Map from a token to its own attributes, keyed by name
+		pub TokenAttributes get(fn token_attributes): map hasher(twox_64_concat) TokenId => BTreeMap<AttributeKey, NFTAttributeValue>;
+		/// Map from a (collection, series) to its transfer policy
+		pub SeriesTransferPolicy get(fn series_transfer_policy): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => TransferPolicy;
+		/// Map from series to its max. issuance, if any. Immutable once set
+		pub SeriesMaxIssuance get(fn series_max_issuance): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => Option<TokenCount>;
+		/// Map from series to its public mint config
+		pub SeriesPublicMint get(fn series_public_mint): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId => Option<PublicMint<T::BlockNumber>>;
+		/// Map from series to the number of tokens minted by an account in its public mint
+		pub PublicMintCount get(fn public_mint_count): double_map hasher(twox_64_concat) (CollectionId, SeriesId), hasher(blake2_128_concat) T::AccountId => TokenCount;
 		/// Map from a (collection, series) to its total issuance
 		pub SeriesIssuance get(fn series_issuance): double_map hasher(twox_64_concat) CollectionId, hasher(twox_64_concat) SeriesId =>  TokenCount;
 		/// Map from a token series to its metadata reference scheme
@@ -253,14 +352,30 @@ decl_storage! {
 		pub ListingWinningBid get(fn listing_winning_bid): map hasher(twox_64_concat) ListingId => Option<(T::AccountId, Balance)>;
 		/// Block numbers where listings will close. Value is `true` if at block number `listing_id` is scheduled to close.
 		pub ListingEndSchedule get(fn listing_end_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) ListingId => bool;
+		/// Map from auction listing_id to its bidding rules
+		pub ListingAuctionRules get(fn listing_auction_rules): map hasher(twox_64_concat) ListingId => AuctionRules<T::BlockNumber>;
 		/// Map from offer_id to the information related to the offer
-		pub Offers get(fn offers): map hasher(twox_64_concat) OfferId => Option<OfferType<T::AccountId>>;
+		pub Offers get(fn offers): map hasher(twox_64_concat) OfferId => Option<OfferType<T::AccountId, T::BlockNumber>>;
 		/// Maps from token_id to a vector of offer_ids on that token
 		pub TokenOffers get(fn token_offers): map hasher(twox_64_concat) TokenId => Vec<OfferId>;
+		/// Maps from collection_id to a vector of collection and attribute offer_ids on that collection
+		pub CollectionOffers get(fn collection_offers): map hasher(twox_64_concat) CollectionId => Vec<OfferId>;
 		/// The next available offer_id
 		pub NextOfferId get(fn next_offer_id): OfferId;
+		/// Block numbers where offers will expire. Value is `true` if at block number `offer_id` is scheduled to expire.
+		pub OfferEndSchedule get(fn offer_end_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) OfferId => bool;
+		/// Block numbers with offer expiries yet to be processed, oldest first
+		pub OfferExpiryBacklog get(fn offer_expiry_backlog): Vec<T::BlockNumber>;
+		/// Map from a token to its active rental, if any
+		pub TokenRentals get(fn token_rentals): map hasher(twox_64_concat) TokenId => Option<TokenRental<T::AccountId, T::BlockNumber>>;
+		/// Map from a token to a rental offered by its owner, awaiting acceptance
+		pub RentalOffers get(fn rental_offers): map hasher(twox_64_concat) TokenId => Option<RentalOffer<T::AccountId, T::BlockNumber>>;
+		/// Block numbers where rentals will end. Value is `true` if at block number `token_id` rental is scheduled to end.
+		pub RentalEndSchedule get(fn rental_end_schedule): double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) TokenId => bool;
+		/// Block numbers with rental endings yet to be processed, oldest first
+		pub RentalEndBacklog get(fn rental_end_backlog): Vec<T::BlockNumber>;
 		/// Version of this module's storage schema
-		StorageVersion build(|_: &GenesisConfig| Releases::V2 as u32): u32;
+		StorageVersion build(|_: &GenesisConfig| Releases::V3 as u32): u32;
 	}
 }
 
@@ -271,7 +386,7 @@ decl_module! {
 		fn deposit_event() = default;
 
 		fn on_runtime_upgrade() -> Weight {
-			use migration::v1_storage;
+			use migration::{v1_storage, v2_storage};
 			use frame_support::IterableStorageMap;
 
 			if StorageVersion::get() == Releases::V1 as u32 {
@@ -323,18 +438,89 @@ decl_module! {
 
 				log!(warn, "🃏 listings migrated");
 				return 6_000_000 as Weight + weight * 100_000;
+			} else if StorageVersion::get() == Releases::V2 as u32 {
+				StorageVersion::put(Releases::V3 as u32);
+
+				let offers: Vec<(OfferId, v2_storage::OfferType<T::AccountId>)> = v2_storage::Offers::<T>::iter().collect();
+				let weight = offers.len() as Weight;
+				for (offer_id, offer) in offers {
+					let offer_migrated = match offer {
+						v2_storage::OfferType::Simple(v2_storage::SimpleOffer {
+							token_id,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+						}) => OfferType::Simple(SimpleOffer {
+							token_id,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+							expiry: None,
+						}),
+						v2_storage::OfferType::Collection(v2_storage::CollectionOffer {
+							collection_id,
+							series_id,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+						}) => OfferType::Collection(CollectionOffer {
+							collection_id,
+							series_id,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+							expiry: None,
+						}),
+						v2_storage::OfferType::Attribute(v2_storage::AttributeOffer {
+							collection_id,
+							attribute,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+						}) => OfferType::Attribute(AttributeOffer {
+							collection_id,
+							attribute,
+							asset_id,
+							amount,
+							buyer,
+							marketplace_id,
+							expiry: None,
+						}),
+					};
+					Offers::<T>::insert(offer_id, offer_migrated);
+				}
+
+				log!(warn, "🃏 offers migrated");
+				return 6_000_000 as Weight + weight * 100_000;
 			} else {
 				Zero::zero()
 			}
 		}
 
-		/// Check and close all expired listings
+		/// Check and close all expired listings, offers and rentals
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			// TODO: this is unbounded and could become costly
 			// https://github.com/cennznet/cennznet/issues/444
 			let removed_count = Self::close_listings_at(now);
+			// royalties updates are bounded by `MAX_ROYALTIES_UPDATES_PER_BLOCK`
+			let royalties_updates = Self::update_series_royalties_at(now);
+			// rental endings are bounded by `MAX_RENTAL_ENDS_PER_BLOCK`
+			let ended_rentals = Self::end_rentals_at(now);
+			// offer expiry is bounded by `MAX_OFFER_EXPIRIES_PER_BLOCK`
+			let expired_count = Self::expire_offers_at(now);
 			// 'buy' weight is comparable to successful closure of an auction
+			// 'cancel_offer' weight is comparable to an offer expiry
 			T::WeightInfo::buy() * removed_count as Weight
+				+ T::WeightInfo::cancel_offer() * expired_count as Weight
+				// 'cancel_sale' weight is comparable to a rental ending
+				+ T::WeightInfo::cancel_sale() * ended_rentals as Weight
+				+ T::DbWeight::get().reads_writes(2, 2) * royalties_updates as Weight
+				+ T::DbWeight::get().reads_writes(4, 1)
 		}
 
 		/// Set the owner of a collection
@@ -454,6 +640,8 @@ decl_module! {
 		/// `quantity` - number of tokens to mint now
 		/// `owner` - the token owner, defaults to the caller
 		/// `metadata_scheme` - The off-chain metadata referencing scheme for tokens in this series
+		/// `transfer_policy` - How tokens in this series may change hands, defaults to transferable
+		/// `max_issuance` - The max. number of tokens that can ever be minted in this series, unlimited if unspecified
 		/// Caller must be the collection owner
 		#[weight = T::WeightInfo::mint_series(*quantity)]
 		#[transactional]
@@ -464,9 +652,11 @@ decl_module! {
 			owner: Option<T::AccountId>,
 			metadata_scheme: MetadataScheme,
 			royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+			transfer_policy: Option<TransferPolicy>,
+			max_issuance: Option<TokenCount>,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-			Self::do_mint_series(origin, collection_id, quantity, owner, metadata_scheme, royalties_schedule)?;
+			Self::do_mint_series(origin, collection_id, quantity, owner, metadata_scheme, royalties_schedule, transfer_policy, max_issuance)?;
 			Ok(())
 		}
 
@@ -510,6 +700,133 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Set or remove the royalties schedule of a series
+		/// The change takes effect after `RoyaltiesUpdateDelay` blocks, replacing any update already scheduled
+		/// Removing the series schedule falls back to the collection schedule
+		///
+		/// Caller must be the collection owner
+		#[weight = T::WeightInfo::set_series_royalties()]
+		fn set_series_royalties(
+			origin,
+			collection_id: CollectionId,
+			series_id: SeriesId,
+			royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+		) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission);
+			ensure!(Self::series_exists(collection_id, series_id), Error::<T>::NoSeries);
+			if let Some(ref royalties_schedule) = royalties_schedule {
+				ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
+			}
+
+			let delay = T::RoyaltiesUpdateDelay::get();
+			if delay.is_zero() {
+				Self::clear_pending_series_royalties(collection_id, series_id);
+				Self::apply_series_royalties(collection_id, series_id, royalties_schedule);
+				return Ok(());
+			}
+
+			let effective_block = <frame_system::Pallet<T>>::block_number().saturating_add(delay);
+			Self::clear_pending_series_royalties(collection_id, series_id);
+			<PendingSeriesRoyalties<T>>::insert(collection_id, series_id, (effective_block, royalties_schedule));
+			<SeriesRoyaltiesUpdateSchedule<T>>::insert(effective_block, (collection_id, series_id), true);
+			Self::deposit_event(RawEvent::SeriesRoyaltiesUpdateScheduled(collection_id, series_id, effective_block));
+		}
+
+		/// Set or remove the individual royalties schedule of a token
+		/// The token schedule takes priority over the series and collection schedules
+		/// The change takes effect after `RoyaltiesUpdateDelay` blocks, replacing any update already scheduled
+		///
+		/// Caller must be the collection owner
+		#[weight = T::WeightInfo::set_token_royalties()]
+		fn set_token_royalties(
+			origin,
+			token_id: TokenId,
+			royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+		) {
+			let origin = ensure_signed(origin)?;
+			let (collection_id, series_id, serial_number) = token_id;
+			ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission);
+			ensure!(<TokenOwner<T>>::contains_key((collection_id, series_id), serial_number), Error::<T>::NoToken);
+			// listings have already settled their royalties
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenListingProtection);
+
+			if let Some(ref royalties_schedule) = royalties_schedule {
+				ensure!(royalties_schedule.validate(), Error::<T>::RoyaltiesInvalid);
+			}
+
+			let delay = T::RoyaltiesUpdateDelay::get();
+			if delay.is_zero() {
+				Self::clear_pending_token_royalties(token_id);
+				Self::apply_token_royalties(token_id, royalties_schedule);
+				return Ok(());
+			}
+
+			let effective_block = <frame_system::Pallet<T>>::block_number().saturating_add(delay);
+			Self::clear_pending_token_royalties(token_id);
+			<PendingTokenRoyalties<T>>::insert(token_id, (effective_block, royalties_schedule));
+			<TokenRoyaltiesUpdateSchedule<T>>::insert(effective_block, token_id, true);
+			Self::deposit_event(RawEvent::TokenRoyaltiesUpdateScheduled(token_id, effective_block));
+		}
+
+		/// Set or remove the public mint of a series
+		/// While open, any account may mint tokens of the series by paying the mint price to the collection owner
+		///
+		/// Caller must be the collection owner
+		#[weight = T::WeightInfo::set_public_mint()]
+		fn set_public_mint(origin, collection_id: CollectionId, series_id: SeriesId, public_mint: Option<PublicMint<T::BlockNumber>>) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission);
+			ensure!(NextSerialNumber::contains_key(collection_id, series_id), Error::<T>::NoSeries);
+
+			match public_mint {
+				Some(public_mint) => {
+					ensure!(public_mint.validate(), Error::<T>::InvalidPublicMint);
+					<SeriesPublicMint<T>>::insert(collection_id, series_id, public_mint);
+				},
+				None => <SeriesPublicMint<T>>::remove(collection_id, series_id),
+			}
+			Self::deposit_event(RawEvent::PublicMintUpdated(collection_id, series_id));
+		}
+
+		/// Mint tokens from a series with an open public mint
+		///
+		/// `quantity` - how many tokens to mint, the caller pays the mint price for each
+		/// -----------
+		/// Weight is O(N) where N is `quantity`
+		#[weight = T::WeightInfo::public_mint(*quantity)]
+		#[transactional]
+		fn public_mint(origin, collection_id: CollectionId, series_id: SeriesId, quantity: TokenCount) {
+			let origin = ensure_signed(origin)?;
+			ensure!(quantity > Zero::zero(), Error::<T>::NoToken);
+
+			let public_mint = Self::series_public_mint(collection_id, series_id).ok_or(Error::<T>::PublicMintNotOpen)?;
+			ensure!(public_mint.is_open(<frame_system::Pallet<T>>::block_number()), Error::<T>::PublicMintNotOpen);
+
+			let minted = Self::public_mint_count((collection_id, series_id), &origin)
+				.checked_add(quantity)
+				.ok_or(Error::<T>::PublicMintLimitReached)?;
+			if let Some(max_per_account) = public_mint.max_per_account {
+				ensure!(minted <= max_per_account, Error::<T>::PublicMintLimitReached);
+			}
+
+			let serial_number = Self::next_serial_number(collection_id, series_id);
+			ensure!(
+				serial_number.checked_add(quantity).is_some(),
+				Error::<T>::NoAvailableIds
+			);
+
+			if !public_mint.price.is_zero() {
+				let collection_owner = Self::collection_owner(collection_id).ok_or(Error::<T>::NoCollection)?;
+				let total_price = public_mint.price.checked_mul(quantity.into()).ok_or(Error::<T>::InternalPayment)?;
+				T::MultiCurrency::transfer(&origin, &collection_owner, public_mint.payment_asset, total_price, ExistenceRequirement::AllowDeath)?;
+			}
+
+			Self::do_mint(&origin, collection_id, series_id, serial_number, quantity)?;
+			<PublicMintCount<T>>::insert((collection_id, series_id), &origin, minted);
+			Self::deposit_event(RawEvent::CreateTokens(collection_id, series_id, quantity, origin));
+		}
+
 		/// Transfer ownership of an NFT
 		/// Caller must be the token owner
 		#[weight = T::WeightInfo::transfer()]
@@ -566,42 +883,42 @@ decl_module! {
 			let origin = ensure_signed(origin)?;
 
 			ensure!(!serial_numbers.is_empty(), Error::<T>::NoToken);
+			ensure!(Self::series_transfer_policy(collection_id, series_id).is_burnable(), Error::<T>::TokenNotBurnable);
 
 			for serial_number in serial_numbers.iter() {
 				ensure!(!TokenLocks::contains_key((collection_id, series_id, serial_number)), Error::<T>::TokenListingProtection);
 				ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
-				<TokenOwner<T>>::remove((collection_id, series_id), serial_number);
 			}
 
-			let quantity = serial_numbers.len() as TokenCount;
-			let _ = <TokenBalance<T>>::try_mutate::<_, (), Error<T>, _>(&origin, |balances| {
-				match (*balances).get_mut(&(collection_id, series_id)) {
-					Some(balance) => {
-						let new_balance = balance.saturating_sub(quantity);
-						if new_balance.is_zero() {
-							balances.remove(&(collection_id, series_id));
-						} else {
-							*balance = new_balance;
-						}
-						Ok(())
-					},
-					None => Err(Error::<T>::NoToken.into()),
-				}
-			})?;
-
-			if Self::series_issuance(collection_id, series_id).saturating_sub(serial_numbers.len() as TokenCount).is_zero() {
-				// this is the last of the tokens
-				SeriesAttributes::remove(collection_id, series_id);
-				SeriesIssuance::remove(collection_id, series_id);
-				SeriesMetadataScheme::remove(collection_id, series_id);
-				<SeriesRoyalties<T>>::remove(collection_id, series_id);
-			} else {
-				SeriesIssuance::mutate(collection_id, series_id, |q| *q = q.saturating_sub(quantity));
-			}
+			Self::do_burn_unchecked(&origin, collection_id, series_id, &serial_numbers)?;
 
 			Self::deposit_event(RawEvent::Burn(collection_id, series_id, serial_numbers));
 		}
 
+		/// Revoke a token, burning it on behalf of its owner 🔥
+		/// The token's series must have the `IssuerRevocable` transfer policy
+		///
+		/// Caller must be the collection owner
+		#[weight = T::WeightInfo::burn()]
+		#[transactional]
+		fn revoke(origin, token_id: TokenId) {
+			let origin = ensure_signed(origin)?;
+			let (collection_id, series_id, serial_number) = token_id;
+
+			ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission);
+			ensure!(
+				Self::series_transfer_policy(collection_id, series_id) == TransferPolicy::IssuerRevocable,
+				Error::<T>::TokenNotRevocable
+			);
+			ensure!(<TokenOwner<T>>::contains_key((collection_id, series_id), serial_number), Error::<T>::NoToken);
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenListingProtection);
+
+			let owner = Self::token_owner((collection_id, series_id), serial_number);
+			Self::do_burn_unchecked(&owner, collection_id, series_id, &[serial_number])?;
+
+			Self::deposit_event(RawEvent::Burn(collection_id, series_id, vec![serial_number]));
+		}
+
 		/// Sell a single token at a fixed price
 		///
 		/// `buyer` optionally, the account to receive the NFT. If unspecified, then any account may purchase
@@ -671,6 +988,7 @@ decl_module! {
 			// use the first token's collection as representative of the bundle
 			let (bundle_collection_id, _series_id, _serial_number) = tokens[0];
 			for (collection_id, series_id, serial_number) in tokens.iter() {
+				ensure!(Self::series_transfer_policy(collection_id, series_id).is_transferable(), Error::<T>::TokenNotTransferable);
 				ensure!(!TokenLocks::contains_key((collection_id, series_id, serial_number)), Error::<T>::TokenListingProtection);
 				ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
 				TokenLocks::insert((collection_id, series_id, serial_number), TokenLockReason::Listed(listing_id));
@@ -699,55 +1017,81 @@ decl_module! {
 		}
 
 		/// Buy a token listing for its specified price
+		/// Dutch auction listings are bought at their current asking price
 		#[weight = T::WeightInfo::buy()]
 		#[transactional]
 		fn buy(origin, listing_id: ListingId) {
 			let origin = ensure_signed(origin)?;
 			ensure!(<Listings<T>>::contains_key(listing_id), Error::<T>::NotForFixedPriceSale);
 
-			if let Some(Listing::FixedPrice(listing)) = Self::listings(listing_id) {
-
-				// if buyer is specified in the listing, then `origin` must be buyer
-				if let Some(buyer) = &listing.buyer {
-					ensure!(&origin == buyer, Error::<T>::NoPermission);
-				}
+			match Self::listings(listing_id) {
+				Some(Listing::FixedPrice(listing)) => {
+					// if buyer is specified in the listing, then `origin` must be buyer
+					if let Some(buyer) = &listing.buyer {
+						ensure!(&origin == buyer, Error::<T>::NoPermission);
+					}
 
-				let (collection_id, series_id, _serial_number) = listing.tokens.get(0).ok_or_else(|| Error::<T>::NoToken)?;
+					let (collection_id, series_id, _serial_number) = listing.tokens.get(0).ok_or_else(|| Error::<T>::NoToken)?;
 
-				let royalty_fees = listing.royalties_schedule.calculate_total_entitlement();
-				if royalty_fees.is_zero() {
-					// full proceeds to seller/`current_owner`
-					T::MultiCurrency::transfer(&origin, &listing.seller, listing.payment_asset, listing.fixed_price, ExistenceRequirement::AllowDeath)?;
-				} else {
-					// withdraw funds from buyer, split between royalty payments and seller
-					let mut for_seller = listing.fixed_price;
-					let mut imbalance = T::MultiCurrency::withdraw(&origin, listing.payment_asset, listing.fixed_price, WithdrawReasons::TRANSFER, ExistenceRequirement::AllowDeath)?;
-					for (who, entitlement) in listing.royalties_schedule.entitlements.into_iter() {
-						let royalty = entitlement * listing.fixed_price;
-						for_seller -= royalty;
-						imbalance = match imbalance.offset(T::MultiCurrency::deposit_into_existing(&who, listing.payment_asset, royalty)?) {
-							SameOrOther::Same(value) => value,
+					let royalty_fees = listing.royalties_schedule.calculate_total_entitlement();
+					if royalty_fees.is_zero() {
+						// full proceeds to seller/`current_owner`
+						T::MultiCurrency::transfer(&origin, &listing.seller, listing.payment_asset, listing.fixed_price, ExistenceRequirement::AllowDeath)?;
+					} else {
+						// withdraw funds from buyer, split between royalty payments and seller
+						let mut for_seller = listing.fixed_price;
+						let mut imbalance = T::MultiCurrency::withdraw(&origin, listing.payment_asset, listing.fixed_price, WithdrawReasons::TRANSFER, ExistenceRequirement::AllowDeath)?;
+						for (who, entitlement) in listing.royalties_schedule.entitlements.into_iter() {
+							let royalty = entitlement * listing.fixed_price;
+							for_seller -= royalty;
+							imbalance = match imbalance.offset(T::MultiCurrency::deposit_into_existing(&who, listing.payment_asset, royalty)?) {
+								SameOrOther::Same(value) => value,
+								SameOrOther::Other(_) | SameOrOther::None => return Err(Error::<T>::InternalPayment.into()),
+							}
+						}
+						match imbalance.offset(T::MultiCurrency::deposit_into_existing(&listing.seller, listing.payment_asset, for_seller)?) {
+							SameOrOther::Same(_) => (),
 							SameOrOther::Other(_) | SameOrOther::None => return Err(Error::<T>::InternalPayment.into()),
 						}
 					}
-					match imbalance.offset(T::MultiCurrency::deposit_into_existing(&listing.seller, listing.payment_asset, for_seller)?) {
-						SameOrOther::Same(_) => (),
-						SameOrOther::Other(_) | SameOrOther::None => return Err(Error::<T>::InternalPayment.into()),
-					}
-				}
 
-				let serial_numbers: Vec<SerialNumber> = listing.tokens.iter().map(|token_id| {
-					TokenLocks::remove(token_id);
-					token_id.2
-				}).collect();
-				OpenCollectionListings::remove(collection_id, listing_id);
+					let serial_numbers: Vec<SerialNumber> = listing.tokens.iter().map(|token_id| {
+						TokenLocks::remove(token_id);
+						token_id.2
+					}).collect();
+					OpenCollectionListings::remove(collection_id, listing_id);
 
-				let _ = Self::do_transfer_unchecked(*collection_id, *series_id, &serial_numbers, &listing.seller, &origin)?;
-				Self::remove_fixed_price_listing(listing_id);
+					let _ = Self::do_transfer_unchecked(*collection_id, *series_id, &serial_numbers, &listing.seller, &origin)?;
+					Self::remove_listing(listing_id);
 
-				Self::deposit_event(RawEvent::FixedPriceSaleComplete(*collection_id, listing_id, origin));
-			} else {
-				return Err(Error::<T>::NotForFixedPriceSale.into());
+					Self::deposit_event(RawEvent::FixedPriceSaleComplete(*collection_id, listing_id, origin));
+				},
+				Some(Listing::Dutch(listing)) => {
+					let (collection_id, _series_id, _serial_number) = listing.tokens.get(0).ok_or_else(|| Error::<T>::NoToken)?;
+					let collection_id = *collection_id;
+					let price = listing.current_price(<frame_system::Pallet<T>>::block_number());
+
+					// lock funds for settlement
+					T::MultiCurrency::reserve(&origin, listing.payment_asset, price)?;
+
+					for token_id in listing.tokens.iter() {
+						TokenLocks::remove(token_id);
+					}
+					OpenCollectionListings::remove(collection_id, listing_id);
+
+					Self::process_payment_and_transfer(
+						&origin,
+						&listing.seller,
+						listing.payment_asset,
+						listing.tokens,
+						price,
+						listing.royalties_schedule,
+					)?;
+					Self::remove_listing(listing_id);
+
+					Self::deposit_event(RawEvent::DutchAuctionSold(collection_id, listing_id, listing.payment_asset, price, origin));
+				},
+				_ => return Err(Error::<T>::NotForFixedPriceSale.into()),
 			}
 		}
 
@@ -757,6 +1101,7 @@ decl_module! {
 		/// - `payment_asset` fungible asset Id to receive payment with
 		/// - `reserve_price` winning bid must be over this threshold
 		/// - `duration` length of the auction (in blocks), uses default duration if unspecified
+		/// - `rules` minimum bid increment and soft close extension, uses default rules if unspecified
 		#[weight = T::WeightInfo::sell()]
 		fn auction(
 			origin,
@@ -764,7 +1109,8 @@ decl_module! {
 			payment_asset: AssetId,
 			reserve_price: Balance,
 			duration: Option<T::BlockNumber>,
-			marketplace_id: Option<MarketplaceId>
+			marketplace_id: Option<MarketplaceId>,
+			rules: Option<AuctionRules<T::BlockNumber>>
 		) -> DispatchResult {
 			Self::auction_bundle(
 				origin,
@@ -772,7 +1118,8 @@ decl_module! {
 				payment_asset,
 				reserve_price,
 				duration,
-				marketplace_id
+				marketplace_id,
+				rules
 			)
 		}
 
@@ -784,6 +1131,7 @@ decl_module! {
 		/// - `payment_asset` fungible asset Id to receive payment with
 		/// - `reserve_price` winning bid must be over this threshold
 		/// - `duration` length of the auction (in blocks), uses default duration if unspecified
+		/// - `rules` minimum bid increment and soft close extension, uses default rules if unspecified
 		#[weight = {
 			T::WeightInfo::sell()
 				.saturating_add(
@@ -797,7 +1145,8 @@ decl_module! {
 			payment_asset: AssetId,
 			reserve_price: Balance,
 			duration: Option<T::BlockNumber>,
-			marketplace_id: Option<MarketplaceId>
+			marketplace_id: Option<MarketplaceId>,
+			rules: Option<AuctionRules<T::BlockNumber>>
 		) {
 			let origin = ensure_signed(origin)?;
 
@@ -805,6 +1154,18 @@ decl_module! {
 				return Err(Error::<T>::NoToken.into());
 			}
 
+			if let Some(ref rules) = rules {
+				ensure!(
+					rules.soft_close_window.is_zero() || !rules.extension_period.is_zero(),
+					Error::<T>::InvalidAuctionRules
+				);
+				ensure!(
+					rules.soft_close_window <= MAX_AUCTION_EXTENSION_PERIOD.into()
+						&& rules.extension_period <= MAX_AUCTION_EXTENSION_PERIOD.into(),
+					Error::<T>::InvalidAuctionRules
+				);
+			}
+
 			let royalties_schedule = Self::check_bundle_royalties(&tokens, marketplace_id)?;
 
 			let listing_id = Self::next_listing_id();
@@ -813,6 +1174,7 @@ decl_module! {
 			// use the first token's collection as representative of the bundle
 			let (bundle_collection_id, _series_id, _serial_number) = tokens[0];
 			for (collection_id, series_id, serial_number) in tokens.iter() {
+				ensure!(Self::series_transfer_policy(collection_id, series_id).is_transferable(), Error::<T>::TokenNotTransferable);
 				ensure!(!TokenLocks::contains_key((collection_id, series_id, serial_number)), Error::<T>::TokenListingProtection);
 				ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
 				TokenLocks::insert((collection_id, series_id, serial_number), TokenLockReason::Listed(listing_id));
@@ -834,6 +1196,9 @@ decl_module! {
 
 			OpenCollectionListings::insert(bundle_collection_id, listing_id, true);
 			Listings::insert(listing_id, listing);
+			if let Some(rules) = rules {
+				ListingAuctionRules::<T>::insert(listing_id, rules);
+			}
 			NextListingId::mutate(|i| *i += 1);
 
 			Self::deposit_event(RawEvent::AuctionOpen(bundle_collection_id, listing_id, marketplace_id));
@@ -847,8 +1212,9 @@ decl_module! {
 			let origin = ensure_signed(origin)?;
 
 			if let Some(Listing::Auction(mut listing)) = Self::listings(listing_id) {
+				let rules = Self::listing_auction_rules(listing_id);
 				if let Some(current_bid) = Self::listing_winning_bid(listing_id) {
-					ensure!(amount > current_bid.1, Error::<T>::BidTooLow);
+					ensure!(amount >= rules.min_next_bid(current_bid.1), Error::<T>::BidTooLow);
 				} else {
 					// first bid
 					ensure!(amount >= listing.reserve_price, Error::<T>::BidTooLow);
@@ -875,16 +1241,17 @@ decl_module! {
 					*maybe_current_bid = Some((origin, amount))
 				});
 
-				// Auto extend auction if bid is made within certain amount of time of auction duration
+				// Auto extend auction if bid is made within the listing's soft close window
 				let listing_end_block = listing.close;
 				let current_block = <frame_system::Pallet<T>>::block_number();
 				let blocks_till_close = listing_end_block - current_block;
-				let new_closing_block = current_block + T::BlockNumber::from(AUCTION_EXTENSION_PERIOD);
-				if blocks_till_close <= T::BlockNumber::from(AUCTION_EXTENSION_PERIOD) {
+				let new_closing_block = current_block.saturating_add(rules.extension_period);
+				if blocks_till_close <= rules.soft_close_window && new_closing_block > listing_end_block {
 					ListingEndSchedule::<T>::remove(listing_end_block, listing_id);
 					ListingEndSchedule::<T>::insert(new_closing_block, listing_id, true);
 					listing.close = new_closing_block;
 					Listings::<T>::insert(listing_id, Listing::Auction(listing.clone()));
+					Self::deposit_event(RawEvent::ListingExtended(listing_id, new_closing_block));
 				}
 
 				let listing_collection_id = listing.tokens[0].0;
@@ -894,6 +1261,105 @@ decl_module! {
 			}
 		}
 
+		/// Sell a token by dutch auction, the asking price falls linearly over the listing duration
+		///
+		/// Caller must be the token owner
+		/// - `payment_asset` fungible asset Id to receive payment with
+		/// - `start_price` asking price when the listing opens
+		/// - `reserve_price` asking price in the last block before the listing closes
+		/// - `duration` length of the auction (in blocks), uses default duration if unspecified
+		#[weight = T::WeightInfo::sell()]
+		fn dutch_auction(
+			origin,
+			token_id: TokenId,
+			payment_asset: AssetId,
+			start_price: Balance,
+			reserve_price: Balance,
+			duration: Option<T::BlockNumber>,
+			marketplace_id: Option<MarketplaceId>
+		) -> DispatchResult {
+			Self::dutch_auction_bundle(
+				origin,
+				vec![token_id],
+				payment_asset,
+				start_price,
+				reserve_price,
+				duration,
+				marketplace_id
+			)
+		}
+
+		/// Sell a bundle of tokens by dutch auction, the asking price falls linearly over the listing duration
+		/// The first buyer at the current asking price wins
+		/// - Tokens must be from the same series
+		/// - Tokens with individual royalties schedules cannot be sold in bundles
+		///
+		/// Caller must be the token owner
+		/// - `payment_asset` fungible asset Id to receive payment with
+		/// - `start_price` asking price when the listing opens
+		/// - `reserve_price` asking price in the last block before the listing closes
+		/// - `duration` length of the auction (in blocks), uses default duration if unspecified
+		#[weight = {
+			T::WeightInfo::sell()
+				.saturating_add(
+					T::DbWeight::get().reads_writes(2, 1).saturating_mul(tokens.len() as Weight)
+				)
+		}]
+		#[transactional]
+		fn dutch_auction_bundle(
+			origin,
+			tokens: Vec<TokenId>,
+			payment_asset: AssetId,
+			start_price: Balance,
+			reserve_price: Balance,
+			duration: Option<T::BlockNumber>,
+			marketplace_id: Option<MarketplaceId>
+		) {
+			let origin = ensure_signed(origin)?;
+
+			if tokens.is_empty() {
+				return Err(Error::<T>::NoToken.into());
+			}
+			ensure!(start_price >= reserve_price, Error::<T>::DutchAuctionPriceInvalid);
+
+			let royalties_schedule = Self::check_bundle_royalties(&tokens, marketplace_id)?;
+
+			let listing_id = Self::next_listing_id();
+			ensure!(listing_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
+
+			// use the first token's collection as representative of the bundle
+			let (bundle_collection_id, _series_id, _serial_number) = tokens[0];
+			for (collection_id, series_id, serial_number) in tokens.iter() {
+				ensure!(Self::series_transfer_policy(collection_id, series_id).is_transferable(), Error::<T>::TokenNotTransferable);
+				ensure!(!TokenLocks::contains_key((collection_id, series_id, serial_number)), Error::<T>::TokenListingProtection);
+				ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
+				TokenLocks::insert((collection_id, series_id, serial_number), TokenLockReason::Listed(listing_id));
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let listing_end_block = now.saturating_add(duration.unwrap_or_else(T::DefaultListingDuration::get));
+			ListingEndSchedule::<T>::insert(listing_end_block, listing_id, true);
+			let listing = Listing::<T>::Dutch(
+				DutchAuctionListing::<T> {
+					payment_asset,
+					start_price,
+					reserve_price,
+					open: now,
+					close: listing_end_block,
+					tokens: tokens.clone(),
+					seller: origin.clone(),
+					royalties_schedule,
+					marketplace_id,
+				}
+			);
+
+			OpenCollectionListings::insert(bundle_collection_id, listing_id, true);
+			Listings::insert(listing_id, listing);
+			NextListingId::mutate(|i| *i += 1);
+
+			Self::deposit_event(RawEvent::DutchAuctionOpen(bundle_collection_id, listing_id, marketplace_id));
+		}
+
 		/// Close a sale or auction returning tokens
 		/// Requires no successful bids have been made for an auction.
 		/// Caller must be the listed seller
@@ -918,6 +1384,19 @@ decl_module! {
 					ensure!(auction.seller == origin, Error::<T>::NoPermission);
 					ensure!(Self::listing_winning_bid(listing_id).is_none(), Error::<T>::TokenListingProtection);
 					Listings::<T>::remove(listing_id);
+					ListingAuctionRules::<T>::remove(listing_id);
+					ListingEndSchedule::<T>::remove(auction.close, listing_id);
+					for token_id in auction.tokens.iter() {
+						TokenLocks::remove(token_id);
+					}
+					let collection_id = auction.tokens[0].0;
+					OpenCollectionListings::remove(collection_id, listing_id);
+
+					Self::deposit_event(RawEvent::AuctionClosed(collection_id, listing_id, AuctionClosureReason::VendorCancelled));
+				},
+				Some(Listing::<T>::Dutch(auction)) => {
+					ensure!(auction.seller == origin, Error::<T>::NoPermission);
+					Listings::<T>::remove(listing_id);
 					ListingEndSchedule::<T>::remove(auction.close, listing_id);
 					for token_id in auction.tokens.iter() {
 						TokenLocks::remove(token_id);
@@ -955,13 +1434,14 @@ decl_module! {
 					Self::deposit_event(RawEvent::FixedPriceSalePriceUpdated(collection_id, listing_id));
 					Ok(())
 				},
-				Some(Listing::<T>::Auction(_)) => Err(Error::<T>::NotForFixedPriceSale.into()),
+				Some(Listing::<T>::Auction(_)) | Some(Listing::<T>::Dutch(_)) => Err(Error::<T>::NotForFixedPriceSale.into()),
 				None => Err(Error::<T>::NotForFixedPriceSale.into()),
 			}
 		}
 
 		/// Create an offer on a token
-		/// Locks funds until offer is accepted, rejected or cancelled
+		/// Locks funds until offer is accepted, rejected, cancelled or expired
+		/// `expiry` optionally, the block at which the offer is withdrawn and funds released
 		#[weight = T::WeightInfo::make_simple_offer()]
 		#[transactional]
 		fn make_simple_offer (
@@ -970,18 +1450,20 @@ decl_module! {
 			amount: Balance,
 			asset_id: AssetId,
 			marketplace_id: Option<MarketplaceId>,
+			expiry: Option<T::BlockNumber>,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 			ensure!(!amount.is_zero(), Error::<T>::ZeroOffer);
 			ensure!(Self::token_owner((token_id.0, token_id.1), token_id.2) != origin, Error::<T>::IsTokenOwner);
 			let offer_id = Self::next_offer_id();
 			ensure!(offer_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
+			Self::schedule_offer_expiry(offer_id, expiry)?;
 
 			// ensure the token_id is not currently in an auction
 			if let Some(TokenLockReason::Listed(listing_id)) = Self::token_locks(token_id) {
 				match Self::listings(listing_id) {
 					Some(Listing::<T>::Auction(_)) => return Err(Error::<T>::TokenOnAuction.into()),
-					None | Some(Listing::<T>::FixedPrice(_)) => (),
+					None | Some(Listing::<T>::FixedPrice(_)) | Some(Listing::<T>::Dutch(_)) => (),
 				}
 			}
 			// check user has the required funds to make this offer
@@ -997,13 +1479,14 @@ decl_module! {
 			// try lock funds
 			T::MultiCurrency::reserve(&origin, asset_id, amount)?;
 			TokenOffers::append(token_id, offer_id);
-			let new_offer = OfferType::<T::AccountId>::Simple(
+			let new_offer = OfferType::<T::AccountId, T::BlockNumber>::Simple(
 				SimpleOffer{
 					token_id,
 					asset_id,
 					amount,
 					buyer: origin.clone(),
 					marketplace_id,
+					expiry,
 				}
 			);
 			Offers::<T>::insert(offer_id, new_offer);
@@ -1013,6 +1496,68 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Create an offer on any token in a collection, or in a single series of the collection
+		/// Locks funds until offer is accepted, cancelled or expired
+		#[weight = T::WeightInfo::make_simple_offer()]
+		#[transactional]
+		fn make_collection_offer (
+			origin,
+			collection_id: CollectionId,
+			series_id: Option<SeriesId>,
+			amount: Balance,
+			asset_id: AssetId,
+			marketplace_id: Option<MarketplaceId>,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(<CollectionOwner<T>>::contains_key(collection_id), Error::<T>::NoCollection);
+			if let Some(series_id) = series_id {
+				ensure!(Self::series_exists(collection_id, series_id), Error::<T>::NoSeries);
+			}
+			let new_offer = OfferType::<T::AccountId, T::BlockNumber>::Collection(
+				CollectionOffer {
+					collection_id,
+					series_id,
+					asset_id,
+					amount,
+					buyer: origin.clone(),
+					marketplace_id,
+					expiry,
+				}
+			);
+			Self::do_make_collection_offer(origin, collection_id, new_offer)
+		}
+
+		/// Create an offer on any token in a collection whose series attributes include `attribute`
+		/// Locks funds until offer is accepted, cancelled or expired
+		#[weight = T::WeightInfo::make_simple_offer()]
+		#[transactional]
+		fn make_attribute_offer (
+			origin,
+			collection_id: CollectionId,
+			attribute: NFTAttributeValue,
+			amount: Balance,
+			asset_id: AssetId,
+			marketplace_id: Option<MarketplaceId>,
+			expiry: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			ensure!(<CollectionOwner<T>>::contains_key(collection_id), Error::<T>::NoCollection);
+			ensure!(attribute.len() <= T::MaxAttributeLength::get() as usize, Error::<T>::MaxAttributeLength);
+			let new_offer = OfferType::<T::AccountId, T::BlockNumber>::Attribute(
+				AttributeOffer {
+					collection_id,
+					attribute,
+					asset_id,
+					amount,
+					buyer: origin.clone(),
+					marketplace_id,
+					expiry,
+				}
+			);
+			Self::do_make_collection_offer(origin, collection_id, new_offer)
+		}
+
 		/// Cancels an offer on a token
 		/// Caller must be the offer buyer
 		#[weight = T::WeightInfo::cancel_offer()]
@@ -1022,16 +1567,12 @@ decl_module! {
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 			if let Some(offer_type) = Self::offers(offer_id) {
-				match offer_type {
-					OfferType::Simple(offer) => {
-						ensure!(offer.buyer == origin, Error::<T>::NotBuyer);
-						T::MultiCurrency::unreserve(&origin, offer.asset_id, offer.amount);
-						Offers::<T>::remove(offer_id);
-						TokenOffers::mutate(offer.token_id, |offers| offers.binary_search(&offer_id).map(|idx| offers.remove(idx)).unwrap());
-						Self::deposit_event(RawEvent::OfferCancelled(offer_id));
-						Ok(())
-					}
-				}
+				ensure!(offer_type.buyer() == &origin, Error::<T>::NotBuyer);
+				let (asset_id, amount) = offer_type.payment();
+				T::MultiCurrency::unreserve(&origin, asset_id, amount);
+				Self::remove_offer(offer_id, &offer_type);
+				Self::deposit_event(RawEvent::OfferCancelled(offer_id));
+				Ok(())
 			} else {
 				Err(Error::<T>::InvalidOffer.into())
 			}
@@ -1046,34 +1587,209 @@ decl_module! {
 			offer_id: OfferId,
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
-			if let Some(offer_type) = Self::offers(offer_id) {
-				match offer_type {
-					OfferType::Simple(offer) => {
-						let token_id = offer.token_id;
-						ensure!(Self::token_owner((token_id.0, token_id.1), token_id.2) == origin, Error::<T>::NoPermission);
-
-						let royalties_schedule = Self::check_bundle_royalties(&vec![token_id], offer.marketplace_id)?;
-						Self::process_payment_and_transfer(
-							&offer.buyer,
-							&origin,
-							offer.asset_id,
-							vec![offer.token_id],
-							offer.amount,
-							royalties_schedule,
-						)?;
+			if let Some(OfferType::Simple(offer)) = Self::offers(offer_id) {
+				ensure!(!Self::is_offer_expired(offer.expiry), Error::<T>::InvalidOffer);
+				let token_id = offer.token_id;
+				ensure!(Self::token_owner((token_id.0, token_id.1), token_id.2) == origin, Error::<T>::NoPermission);
+				ensure!(Self::token_locks(token_id) != Some(TokenLockReason::Rented), Error::<T>::TokenListingProtection);
+
+				let royalties_schedule = Self::check_bundle_royalties(&vec![token_id], offer.marketplace_id)?;
+				Self::process_payment_and_transfer(
+					&offer.buyer,
+					&origin,
+					offer.asset_id,
+					vec![offer.token_id],
+					offer.amount,
+					royalties_schedule,
+				)?;
 
-						// Clean storage
-						Offers::<T>::remove(offer_id);
-						TokenOffers::mutate(token_id, |offers| offers.binary_search(&offer_id).map(|idx| offers.remove(idx)).unwrap());
-						Self::deposit_event(RawEvent::OfferAccepted(offer_id));
-						Ok(())
-					}
-				}
+				// Clean storage
+				Self::remove_offer(offer_id, &OfferType::Simple(offer));
+				Self::deposit_event(RawEvent::OfferAccepted(offer_id));
+				Ok(())
 			} else {
 				Err(Error::<T>::InvalidOffer.into())
 			}
 		}
 
+		/// Accepts a collection or attribute offer, selling `token_id` to the buyer
+		/// Caller must be token owner and the token must meet the offer's requirements
+		#[weight = T::WeightInfo::accept_offer()]
+		#[transactional]
+		fn accept_collection_offer (
+			origin,
+			offer_id: OfferId,
+			token_id: TokenId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let offer_type = Self::offers(offer_id).ok_or(Error::<T>::InvalidOffer)?;
+			ensure!(!Self::is_offer_expired(offer_type.expiry()), Error::<T>::InvalidOffer);
+			let (collection_id, series_id, serial_number) = token_id;
+			let marketplace_id = match &offer_type {
+				OfferType::Collection(offer) => {
+					ensure!(
+						collection_id == offer.collection_id && offer.series_id.map_or(true, |id| id == series_id),
+						Error::<T>::OfferTokenMismatch
+					);
+					offer.marketplace_id
+				},
+				OfferType::Attribute(offer) => {
+					ensure!(
+						collection_id == offer.collection_id && Self::series_attributes(collection_id, series_id).contains(&offer.attribute),
+						Error::<T>::OfferTokenMismatch
+					);
+					offer.marketplace_id
+				},
+				OfferType::Simple(_) => return Err(Error::<T>::InvalidOffer.into()),
+			};
+			ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission);
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenListingProtection);
+
+			let royalties_schedule = Self::check_bundle_royalties(&vec![token_id], marketplace_id)?;
+			let (asset_id, amount) = offer_type.payment();
+			Self::process_payment_and_transfer(
+				offer_type.buyer(),
+				&origin,
+				asset_id,
+				vec![token_id],
+				amount,
+				royalties_schedule,
+			)?;
+
+			// Clean storage
+			Self::remove_offer(offer_id, &offer_type);
+			Self::deposit_event(RawEvent::OfferAccepted(offer_id));
+			Ok(())
+		}
+
+		/// Set who may update the per-token attributes of a series
+		/// Once `Frozen` the policy can not be changed
+		///
+		/// Caller must be the collection owner
+		#[weight = T::WeightInfo::set_attribute_policy()]
+		fn set_attribute_policy(origin, collection_id: CollectionId, series_id: SeriesId, policy: AttributePolicy) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission);
+			ensure!(Self::series_exists(collection_id, series_id), Error::<T>::NoSeries);
+			ensure!(Self::series_attribute_policy(collection_id, series_id) != AttributePolicy::Frozen, Error::<T>::AttributesFrozen);
+
+			SeriesAttributePolicy::insert(collection_id, series_id, policy);
+			Self::deposit_event(RawEvent::AttributePolicySet(collection_id, series_id, policy));
+		}
+
+		/// Set or remove attributes of a token
+		/// - `updates` the attributes to change, a `None` value removes the attribute
+		///
+		/// Caller must be permitted by the series attribute policy, the collection owner by default
+		/// -----------
+		/// Weight is O(N) where N is the number of `updates`
+		#[weight = T::WeightInfo::update_token_attributes(updates.len() as u32)]
+		#[transactional]
+		fn update_token_attributes(origin, token_id: TokenId, updates: Vec<(AttributeKey, Option<NFTAttributeValue>)>) {
+			let origin = ensure_signed(origin)?;
+			let (collection_id, series_id, serial_number) = token_id;
+			ensure!(<TokenOwner<T>>::contains_key((collection_id, series_id), serial_number), Error::<T>::NoToken);
+
+			match Self::series_attribute_policy(collection_id, series_id) {
+				AttributePolicy::IssuerOnly => ensure!(Self::collection_owner(collection_id) == Some(origin), Error::<T>::NoPermission),
+				AttributePolicy::OwnerOnly => ensure!(Self::token_owner((collection_id, series_id), serial_number) == origin, Error::<T>::NoPermission),
+				AttributePolicy::Frozen => return Err(Error::<T>::AttributesFrozen.into()),
+			}
+
+			let mut attributes = Self::token_attributes(token_id);
+			for (key, value) in updates.into_iter() {
+				ensure!(!key.is_empty() && core::str::from_utf8(&key).is_ok(), Error::<T>::InvalidAttributeKey);
+				ensure!(key.len() <= T::MaxAttributeLength::get() as usize, Error::<T>::MaxAttributeLength);
+				match value {
+					Some(value) => {
+						ensure!(value.len() <= T::MaxAttributeLength::get() as usize, Error::<T>::MaxAttributeLength);
+						attributes.insert(key.clone(), value.clone());
+						Self::deposit_event(RawEvent::TokenAttributeSet(token_id, key, value));
+					},
+					None => {
+						if attributes.remove(&key).is_some() {
+							Self::deposit_event(RawEvent::TokenAttributeRemoved(token_id, key));
+						}
+					},
+				}
+			}
+			ensure!(attributes.len() <= MAX_SCHEMA_FIELDS as usize, Error::<T>::SchemaMaxAttributes);
+
+			if attributes.is_empty() {
+				TokenAttributes::remove(token_id);
+			} else {
+				TokenAttributes::insert(token_id, attributes);
+			}
+		}
+
+		/// Lend a token to `user` for `duration` blocks without transferring ownership
+		/// The token can't be transferred or listed until the rental ends
+		///
+		/// Caller must be the token owner
+		/// - `user` the account to receive usage rights of the token
+		/// - `duration` length of the rental (in blocks)
+		/// - `price` optionally, the (asset, amount) `user` must pay to accept the rental
+		/// if unspecified the rental starts immediately
+		#[weight = T::WeightInfo::lend()]
+		#[transactional]
+		fn lend(
+			origin,
+			token_id: TokenId,
+			user: T::AccountId,
+			duration: T::BlockNumber,
+			price: Option<(AssetId, Balance)>,
+		) {
+			let origin = ensure_signed(origin)?;
+			ensure!(Self::token_owner((token_id.0, token_id.1), token_id.2) == origin, Error::<T>::NoPermission);
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenListingProtection);
+			ensure!(user != origin, Error::<T>::IsTokenOwner);
+			ensure!(!duration.is_zero(), Error::<T>::RentalDurationInvalid);
+
+			match price {
+				Some((payment_asset, price)) if !price.is_zero() => {
+					RentalOffers::<T>::insert(
+						token_id,
+						RentalOffer {
+							lender: origin,
+							user: user.clone(),
+							duration,
+							payment_asset,
+							price,
+						}
+					);
+					Self::deposit_event(RawEvent::RentalOffered(token_id, user, payment_asset, price));
+				},
+				_ => Self::start_rental(token_id, user, duration),
+			}
+		}
+
+		/// Accept a rental offered to the caller, paying the rental price to the token owner
+		#[weight = T::WeightInfo::accept_rental()]
+		#[transactional]
+		fn accept_rental(origin, token_id: TokenId) {
+			let origin = ensure_signed(origin)?;
+			let offer = Self::rental_offers(token_id).ok_or(Error::<T>::NoRentalOffer)?;
+			ensure!(offer.user == origin, Error::<T>::NoRentalOffer);
+			// the token may have changed hands since the rental was offered
+			ensure!(Self::token_owner((token_id.0, token_id.1), token_id.2) == offer.lender, Error::<T>::NoPermission);
+			ensure!(!TokenLocks::contains_key(token_id), Error::<T>::TokenListingProtection);
+
+			T::MultiCurrency::transfer(&origin, &offer.lender, offer.payment_asset, offer.price, ExistenceRequirement::AllowDeath)?;
+			RentalOffers::<T>::remove(token_id);
+			Self::start_rental(token_id, origin, offer.duration);
+		}
+
+		/// Cancel a rental offer
+		/// Caller must be the lender
+		#[weight = T::WeightInfo::cancel_rental_offer()]
+		fn cancel_rental_offer(origin, token_id: TokenId) {
+			let origin = ensure_signed(origin)?;
+			let offer = Self::rental_offers(token_id).ok_or(Error::<T>::NoRentalOffer)?;
+			ensure!(offer.lender == origin, Error::<T>::NoPermission);
+			RentalOffers::<T>::remove(token_id);
+			Self::deposit_event(RawEvent::RentalOfferCancelled(token_id));
+		}
+
 		/// Runs through an NFT series and fixes the token balance storage for that series
 		/// Only required for NFT series created prior to the EVM update
 		#[weight = 100_000_000]
@@ -1119,6 +1835,8 @@ impl<T: Config> Module<T> {
 		owner: Option<T::AccountId>,
 		metadata_scheme: MetadataScheme,
 		royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+		transfer_policy: Option<TransferPolicy>,
+		max_issuance: Option<TokenCount>,
 	) -> Result<SeriesId, DispatchError> {
 		// Permission and existence check
 		if let Some(collection_owner) = Self::collection_owner(collection_id) {
@@ -1142,6 +1860,18 @@ impl<T: Config> Module<T> {
 			<SeriesRoyalties<T>>::insert(collection_id, series_id, royalties_schedule);
 		}
 
+		if let Some(transfer_policy) = transfer_policy {
+			SeriesTransferPolicy::insert(collection_id, series_id, transfer_policy);
+		}
+
+		if let Some(max_issuance) = max_issuance {
+			ensure!(
+				!max_issuance.is_zero() && quantity <= max_issuance,
+				Error::<T>::InvalidMaxIssuance
+			);
+			SeriesMaxIssuance::insert(collection_id, series_id, max_issuance);
+		}
+
 		// Now mint the series tokens
 		let owner = owner.unwrap_or(origin);
 		Self::do_mint(&owner, collection_id, series_id, 0 as SerialNumber, quantity)?;
@@ -1200,6 +1930,7 @@ impl<T: Config> Module<T> {
 	/// We're ok iff, all tokens in the bundle are from the:
 	/// 1) same collection and same series
 	/// 2) same collection and different series, no series royalties set (could extend to iff royalties equal)
+	/// and all tokens share the same individual royalties, if any
 	/// Although possible, we do not support:
 	/// 3) different collections, no royalties allowed
 	fn check_bundle_royalties(
@@ -1208,8 +1939,10 @@ impl<T: Config> Module<T> {
 	) -> Result<RoyaltiesSchedule<T::AccountId>, Error<T>> {
 		// use the first token's collection as representative of the bundle
 		let (bundle_collection_id, bundle_series_id, _serial_number) = tokens[0];
+		let bundle_token_royalties = Self::token_royalties(tokens[0]);
 
-		for (collection_id, series_id, _serial_number) in tokens.iter() {
+		for token_id in tokens.iter() {
+			let (collection_id, series_id, _serial_number) = token_id;
 			ensure!(*collection_id == bundle_collection_id, Error::<T>::MixedBundleSale);
 			if *series_id != bundle_series_id {
 				ensure!(
@@ -1217,10 +1950,12 @@ impl<T: Config> Module<T> {
 					Error::<T>::RoyaltiesProtection
 				);
 			}
+			ensure!(
+				Self::token_royalties(token_id) == bundle_token_royalties,
+				Error::<T>::RoyaltiesProtection
+			);
 		}
-		// series schedule takes priority if it exists
-		let mut royalties = Self::series_royalties(bundle_collection_id, bundle_series_id)
-			.unwrap_or_else(|| Self::collection_royalties(bundle_collection_id).unwrap_or_else(Default::default));
+		let mut royalties = Self::royalties_for(tokens[0]).unwrap_or_else(Default::default);
 		let royalties = match marketplace_id {
 			Some(marketplace_id) => {
 				ensure!(
@@ -1238,6 +1973,14 @@ impl<T: Config> Module<T> {
 		};
 		Ok(royalties)
 	}
+	/// The royalties schedule applying to sales of `token_id`, if any
+	/// The token schedule takes priority, then the series schedule, then the collection schedule
+	pub fn royalties_for(token_id: TokenId) -> Option<RoyaltiesSchedule<T::AccountId>> {
+		let (collection_id, series_id, _serial_number) = token_id;
+		Self::token_royalties(token_id)
+			.or_else(|| Self::series_royalties(collection_id, series_id))
+			.or_else(|| Self::collection_royalties(collection_id))
+	}
 	/// Transfer the given tokens from `current_owner` to `new_owner`
 	/// Does no verification
 	fn do_transfer_unchecked(
@@ -1247,6 +1990,10 @@ impl<T: Config> Module<T> {
 		current_owner: &T::AccountId,
 		new_owner: &T::AccountId,
 	) -> DispatchResult {
+		ensure!(
+			Self::series_transfer_policy(collection_id, series_id).is_transferable(),
+			Error::<T>::TokenNotTransferable
+		);
 		for serial_number in serial_numbers.iter() {
 			<TokenOwner<T>>::insert((collection_id, series_id), serial_number, new_owner);
 			let token_id: TokenId = (collection_id, series_id, *serial_number);
@@ -1273,6 +2020,55 @@ impl<T: Config> Module<T> {
 
 		Ok(())
 	}
+	/// Burn tokens from `owner`, removing the series metadata when its last token is burned
+	/// Caller must ensure the burn is permitted
+	fn do_burn_unchecked(
+		owner: &T::AccountId,
+		collection_id: CollectionId,
+		series_id: SeriesId,
+		serial_numbers: &[SerialNumber],
+	) -> DispatchResult {
+		for serial_number in serial_numbers.iter() {
+			<TokenOwner<T>>::remove((collection_id, series_id), serial_number);
+			TokenAttributes::remove((collection_id, series_id, serial_number));
+			<TokenRoyalties<T>>::remove((collection_id, series_id, serial_number));
+			Self::clear_pending_token_royalties((collection_id, series_id, serial_number));
+		}
+
+		let quantity = serial_numbers.len() as TokenCount;
+		let _ = <TokenBalance<T>>::try_mutate::<_, (), Error<T>, _>(owner, |balances| {
+			match (*balances).get_mut(&(collection_id, series_id)) {
+				Some(balance) => {
+					let new_balance = balance.saturating_sub(quantity);
+					if new_balance.is_zero() {
+						balances.remove(&(collection_id, series_id));
+					} else {
+						*balance = new_balance;
+					}
+					Ok(())
+				}
+				None => Err(Error::<T>::NoToken.into()),
+			}
+		})?;
+
+		if Self::series_issuance(collection_id, series_id)
+			.saturating_sub(quantity)
+			.is_zero()
+		{
+			// this is the last of the tokens
+			// the transfer and attribute policies are kept, like `SeriesMaxIssuance`, so they still bind
+			// tokens from `mint_additional`
+			SeriesAttributes::remove(collection_id, series_id);
+			SeriesIssuance::remove(collection_id, series_id);
+			SeriesMetadataScheme::remove(collection_id, series_id);
+			<SeriesRoyalties<T>>::remove(collection_id, series_id);
+			Self::clear_pending_series_royalties(collection_id, series_id);
+		} else {
+			SeriesIssuance::mutate(collection_id, series_id, |q| *q = q.saturating_sub(quantity));
+		}
+
+		Ok(())
+	}
 	/// Mint additional tokens in a series
 	fn do_mint(
 		owner: &T::AccountId,
@@ -1281,6 +2077,14 @@ impl<T: Config> Module<T> {
 		serial_number: SerialNumber,
 		quantity: TokenCount,
 	) -> DispatchResult {
+		if let Some(max_issuance) = Self::series_max_issuance(collection_id, series_id) {
+			// burned tokens still count towards the max issuance
+			ensure!(
+				serial_number.saturating_add(quantity) <= max_issuance,
+				Error::<T>::MaxIssuanceReached
+			);
+		}
+
 		if quantity > Zero::zero() {
 			// Mint the set tokens
 			for serial_number in serial_number..serial_number + quantity {
@@ -1324,12 +2128,240 @@ impl<T: Config> Module<T> {
 
 		return owned_tokens;
 	}
-	/// Remove a single fixed price listing and all it's metadata
-	fn remove_fixed_price_listing(listing_id: ListingId) {
+	/// Reserve the buyer's funds and store a new collection or attribute offer
+	fn do_make_collection_offer(
+		origin: T::AccountId,
+		collection_id: CollectionId,
+		new_offer: OfferType<T::AccountId, T::BlockNumber>,
+	) -> DispatchResult {
+		let (asset_id, amount) = new_offer.payment();
+		ensure!(!amount.is_zero(), Error::<T>::ZeroOffer);
+		let offer_id = Self::next_offer_id();
+		ensure!(offer_id.checked_add(One::one()).is_some(), Error::<T>::NoAvailableIds);
+		Self::schedule_offer_expiry(offer_id, new_offer.expiry())?;
+
+		// try lock funds
+		T::MultiCurrency::reserve(&origin, asset_id, amount)?;
+		CollectionOffers::append(collection_id, offer_id);
+		let marketplace_id = match &new_offer {
+			OfferType::Simple(offer) => offer.marketplace_id,
+			OfferType::Collection(offer) => offer.marketplace_id,
+			OfferType::Attribute(offer) => offer.marketplace_id,
+		};
+		Offers::<T>::insert(offer_id, new_offer);
+		NextOfferId::mutate(|i| *i += 1);
+
+		Self::deposit_event(RawEvent::OfferMade(offer_id, amount, asset_id, marketplace_id, origin));
+		Ok(())
+	}
+	/// Remove an offer and its index entries from storage
+	/// Does not release any reserved funds
+	fn remove_offer(offer_id: OfferId, offer_type: &OfferType<T::AccountId, T::BlockNumber>) {
+		Offers::<T>::remove(offer_id);
+		if let Some(expiry) = offer_type.expiry() {
+			OfferEndSchedule::<T>::remove(expiry, offer_id);
+		}
+		let remove_id = |offers: &mut Vec<OfferId>| {
+			if let Ok(idx) = offers.binary_search(&offer_id) {
+				offers.remove(idx);
+			}
+		};
+		match offer_type {
+			OfferType::Simple(offer) => TokenOffers::mutate(offer.token_id, remove_id),
+			OfferType::Collection(offer) => CollectionOffers::mutate(offer.collection_id, remove_id),
+			OfferType::Attribute(offer) => CollectionOffers::mutate(offer.collection_id, remove_id),
+		}
+	}
+	/// Grant `user` usage rights of `token_id` for `duration` blocks, the token is locked until the rental ends
+	fn start_rental(token_id: TokenId, user: T::AccountId, duration: T::BlockNumber) {
+		let expiry = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+		TokenLocks::insert(token_id, TokenLockReason::Rented);
+		RentalEndSchedule::<T>::insert(expiry, token_id, true);
+		TokenRentals::<T>::insert(
+			token_id,
+			TokenRental {
+				user: user.clone(),
+				expiry,
+			},
+		);
+		Self::deposit_event(RawEvent::TokenRented(token_id, user, expiry));
+	}
+	/// End all rentals scheduled to end at this block `now`, returning usage rights to the token owners
+	/// At most `MAX_RENTAL_ENDS_PER_BLOCK` rentals are ended, any remainder is carried over to the next block
+	/// Returns the number of rentals ended
+	fn end_rentals_at(now: T::BlockNumber) -> u32 {
+		let initial_backlog = Self::rental_end_backlog();
+		let mut backlog = initial_backlog.clone();
+		if RentalEndSchedule::<T>::iter_prefix(now).next().is_some() {
+			backlog.push(now);
+		}
+
+		let mut ended = 0_u32;
+		while let Some(block) = backlog.first().copied() {
+			let budget = MAX_RENTAL_ENDS_PER_BLOCK.saturating_sub(ended);
+			for (token_id, _) in RentalEndSchedule::<T>::drain_prefix(block).take(budget as usize) {
+				if let Some(rental) = TokenRentals::<T>::take(token_id) {
+					TokenLocks::remove(token_id);
+					Self::deposit_event(RawEvent::RentalEnded(token_id, rental.user));
+				}
+				ended += 1;
+			}
+			if RentalEndSchedule::<T>::iter_prefix(block).next().is_some() {
+				// budget exhausted, continue from here next block
+				break;
+			}
+			backlog.remove(0);
+		}
+
+		if backlog != initial_backlog {
+			RentalEndBacklog::<T>::put(backlog);
+		}
+
+		ended
+	}
+	/// Apply the series and token royalties updates scheduled for block `now`
+	/// At most `MAX_ROYALTIES_UPDATES_PER_BLOCK` updates are applied, any remainder is carried over to the next block
+	/// Returns the number of updates applied
+	fn update_series_royalties_at(now: T::BlockNumber) -> u32 {
+		let initial_backlog = Self::royalties_update_backlog();
+		let mut backlog = initial_backlog.clone();
+		if Self::has_royalties_updates_at(now) {
+			backlog.push(now);
+		}
+
+		let mut updated = 0_u32;
+		while let Some(block) = backlog.first().copied() {
+			let budget = MAX_ROYALTIES_UPDATES_PER_BLOCK.saturating_sub(updated);
+			for ((collection_id, series_id), _) in
+				<SeriesRoyaltiesUpdateSchedule<T>>::drain_prefix(block).take(budget as usize)
+			{
+				if let Some((_, royalties_schedule)) = <PendingSeriesRoyalties<T>>::take(collection_id, series_id) {
+					Self::apply_series_royalties(collection_id, series_id, royalties_schedule);
+				}
+				updated += 1;
+			}
+			let budget = MAX_ROYALTIES_UPDATES_PER_BLOCK.saturating_sub(updated);
+			for (token_id, _) in <TokenRoyaltiesUpdateSchedule<T>>::drain_prefix(block).take(budget as usize) {
+				if let Some((_, royalties_schedule)) = <PendingTokenRoyalties<T>>::take(token_id) {
+					Self::apply_token_royalties(token_id, royalties_schedule);
+				}
+				updated += 1;
+			}
+			if Self::has_royalties_updates_at(block) {
+				// budget exhausted, continue from here next block
+				break;
+			}
+			backlog.remove(0);
+		}
+
+		if backlog != initial_backlog {
+			RoyaltiesUpdateBacklog::<T>::put(backlog);
+		}
+
+		updated
+	}
+	/// Whether any series or token royalties updates are scheduled for `block`
+	fn has_royalties_updates_at(block: T::BlockNumber) -> bool {
+		<SeriesRoyaltiesUpdateSchedule<T>>::iter_prefix(block).next().is_some()
+			|| <TokenRoyaltiesUpdateSchedule<T>>::iter_prefix(block).next().is_some()
+	}
+	/// Set the royalties schedule of a series, a `None` schedule removes it
+	fn apply_series_royalties(
+		collection_id: CollectionId,
+		series_id: SeriesId,
+		royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>,
+	) {
+		match royalties_schedule {
+			Some(royalties_schedule) => <SeriesRoyalties<T>>::insert(collection_id, series_id, royalties_schedule),
+			None => <SeriesRoyalties<T>>::remove(collection_id, series_id),
+		}
+		Self::deposit_event(RawEvent::SeriesRoyaltiesUpdated(collection_id, series_id));
+	}
+	/// Remove any scheduled royalties update of a series
+	fn clear_pending_series_royalties(collection_id: CollectionId, series_id: SeriesId) {
+		if let Some((effective_block, _)) = <PendingSeriesRoyalties<T>>::take(collection_id, series_id) {
+			<SeriesRoyaltiesUpdateSchedule<T>>::remove(effective_block, (collection_id, series_id));
+		}
+	}
+	/// Set the royalties schedule of a token, a `None` schedule removes it
+	fn apply_token_royalties(token_id: TokenId, royalties_schedule: Option<RoyaltiesSchedule<T::AccountId>>) {
+		match royalties_schedule {
+			Some(royalties_schedule) => <TokenRoyalties<T>>::insert(token_id, royalties_schedule),
+			None => <TokenRoyalties<T>>::remove(token_id),
+		}
+		Self::deposit_event(RawEvent::TokenRoyaltiesUpdated(token_id));
+	}
+	/// Remove any scheduled royalties update of a token
+	fn clear_pending_token_royalties(token_id: TokenId) {
+		if let Some((effective_block, _)) = <PendingTokenRoyalties<T>>::take(token_id) {
+			<TokenRoyaltiesUpdateSchedule<T>>::remove(effective_block, token_id);
+		}
+	}
+	/// Returns the active rental of `token_id`, if any
+	/// The rental's `user` holds usage rights of the token until `expiry`
+	pub fn token_user(token_id: TokenId) -> Option<TokenRental<T::AccountId, T::BlockNumber>> {
+		Self::token_rentals(token_id).filter(|rental| rental.expiry > <frame_system::Pallet<T>>::block_number())
+	}
+	/// Schedule `offer_id` to expire at block `expiry`, if set
+	fn schedule_offer_expiry(offer_id: OfferId, expiry: Option<T::BlockNumber>) -> DispatchResult {
+		if let Some(expiry) = expiry {
+			ensure!(
+				expiry > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::OfferExpiryInvalid
+			);
+			OfferEndSchedule::<T>::insert(expiry, offer_id, true);
+		}
+		Ok(())
+	}
+	/// True if an offer with the given `expiry` can no longer be accepted
+	fn is_offer_expired(expiry: Option<T::BlockNumber>) -> bool {
+		expiry.map_or(false, |expiry| expiry <= <frame_system::Pallet<T>>::block_number())
+	}
+	/// Expire offers scheduled to end at this block `now` or earlier, releasing the buyer's reserved funds
+	/// At most `MAX_OFFER_EXPIRIES_PER_BLOCK` offers are expired, any remainder is carried over to the next block
+	/// Returns the number of offers expired
+	fn expire_offers_at(now: T::BlockNumber) -> u32 {
+		let initial_backlog = Self::offer_expiry_backlog();
+		let mut backlog = initial_backlog.clone();
+		if OfferEndSchedule::<T>::iter_prefix(now).next().is_some() {
+			backlog.push(now);
+		}
+
+		let mut expired = 0_u32;
+		while let Some(block) = backlog.first().copied() {
+			let budget = MAX_OFFER_EXPIRIES_PER_BLOCK.saturating_sub(expired);
+			for (offer_id, _) in OfferEndSchedule::<T>::drain_prefix(block).take(budget as usize) {
+				if let Some(offer_type) = Offers::<T>::get(offer_id) {
+					let (asset_id, amount) = offer_type.payment();
+					T::MultiCurrency::unreserve(offer_type.buyer(), asset_id, amount);
+					Self::remove_offer(offer_id, &offer_type);
+					Self::deposit_event(RawEvent::OfferExpired(offer_id));
+				}
+				expired += 1;
+			}
+			if OfferEndSchedule::<T>::iter_prefix(block).next().is_some() {
+				// budget exhausted, continue from here next block
+				break;
+			}
+			backlog.remove(0);
+		}
+
+		if backlog != initial_backlog {
+			OfferExpiryBacklog::<T>::put(backlog);
+		}
+
+		expired
+	}
+	/// Remove a single listing and all it's metadata
+	fn remove_listing(listing_id: ListingId) {
 		let listing_type = Listings::<T>::take(listing_id);
 		ListingWinningBid::<T>::remove(listing_id);
-		if let Some(Listing::<T>::FixedPrice(listing)) = listing_type {
-			ListingEndSchedule::<T>::remove(listing.close, listing_id);
+		ListingAuctionRules::<T>::remove(listing_id);
+		match listing_type {
+			Some(Listing::<T>::FixedPrice(listing)) => ListingEndSchedule::<T>::remove(listing.close, listing_id),
+			Some(Listing::<T>::Auction(listing)) => ListingEndSchedule::<T>::remove(listing.close, listing_id),
+			Some(Listing::<T>::Dutch(listing)) => ListingEndSchedule::<T>::remove(listing.close, listing_id),
+			None => (),
 		}
 	}
 	/// Close all listings scheduled to close at this block `now`, ensuring payments and ownerships changes are made for winning bids
@@ -1349,6 +2381,20 @@ impl<T: Config> Module<T> {
 
 					Self::deposit_event(RawEvent::FixedPriceSaleClosed(listing_collection_id, listing_id));
 				}
+				Some(Listing::Dutch(listing)) => {
+					// release listed tokens
+					for token_id in listing.tokens.iter() {
+						TokenLocks::remove(token_id);
+					}
+					let listing_collection_id = listing.tokens[0].0;
+					OpenCollectionListings::remove(listing_collection_id, listing_id);
+
+					Self::deposit_event(RawEvent::AuctionClosed(
+						listing_collection_id,
+						listing_id,
+						AuctionClosureReason::ExpiredNoBids,
+					));
+				}
 				Some(Listing::Auction(listing)) => {
 					// release listed tokens
 					for token_id in listing.tokens.iter() {
@@ -1356,6 +2402,7 @@ impl<T: Config> Module<T> {
 					}
 					let listing_collection_id = listing.tokens[0].0;
 					OpenCollectionListings::remove(listing_collection_id, listing_id);
+					ListingAuctionRules::<T>::remove(listing_id);
 
 					if let Some((winner, hammer_price)) = ListingWinningBid::<T>::take(listing_id) {
 						if let Err(err) = Self::process_payment_and_transfer(
@@ -1456,7 +2503,19 @@ impl<T: Config> Module<T> {
 				Some(r) => r.entitlements,
 				None => Vec::new(),
 			};
-			Some(CollectionInfo { name, owner, royalties })
+			let remaining_supply = (0..Self::next_series_id(collection_id))
+				.filter_map(|series_id| {
+					let max_issuance = Self::series_max_issuance(collection_id, series_id)?;
+					let minted = Self::next_serial_number(collection_id, series_id);
+					Some((series_id, max_issuance.saturating_sub(minted)))
+				})
+				.collect();
+			Some(CollectionInfo {
+				name,
+				owner,
+				royalties,
+				remaining_supply,
+			})
 		}
 	}
 	/// Find the attributes and owner from a series
@@ -1466,18 +2525,19 @@ impl<T: Config> Module<T> {
 		serial_number: SerialNumber,
 	) -> TokenInfo<T::AccountId> {
 		let attributes = Self::series_attributes(collection_id, series_id);
+		let token_attributes = Self::token_attributes((collection_id, series_id, serial_number))
+			.into_iter()
+			.collect();
 		let owner = Self::token_owner((collection_id, series_id), serial_number);
-		let royalties = match <SeriesRoyalties<T>>::get(collection_id, series_id) {
+		let royalties = match Self::royalties_for((collection_id, series_id, serial_number)) {
 			Some(r) => r.entitlements,
-			None => match <CollectionRoyalties<T>>::get(&collection_id) {
-				Some(r) => r.entitlements,
-				None => Vec::new(),
-			},
+			None => Vec::new(),
 		};
 		TokenInfo {
 			attributes,
 			owner,
 			royalties,
+			token_attributes,
 		}
 	}
 	/// Get list of all NFT listings within a range
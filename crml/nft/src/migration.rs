@@ -67,3 +67,57 @@ pub mod v1_storage {
 		}
 	}
 }
+
+#[allow(dead_code)]
+pub mod v2_storage {
+	use crate::{AssetId, Balance, CollectionId, Config, MarketplaceId, NFTAttributeValue, OfferId, SeriesId, TokenId};
+	use codec::{Decode, Encode};
+	use scale_info::TypeInfo;
+	use sp_std::prelude::*;
+
+	/// Information about a simple offer v2
+	#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+	pub struct SimpleOffer<AccountId> {
+		pub token_id: TokenId,
+		pub asset_id: AssetId,
+		pub amount: Balance,
+		pub buyer: AccountId,
+		pub marketplace_id: Option<MarketplaceId>,
+	}
+
+	/// Information about a collection offer v2
+	#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+	pub struct CollectionOffer<AccountId> {
+		pub collection_id: CollectionId,
+		pub series_id: Option<SeriesId>,
+		pub asset_id: AssetId,
+		pub amount: Balance,
+		pub buyer: AccountId,
+		pub marketplace_id: Option<MarketplaceId>,
+	}
+
+	/// Information about an attribute offer v2
+	#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+	pub struct AttributeOffer<AccountId> {
+		pub collection_id: CollectionId,
+		pub attribute: NFTAttributeValue,
+		pub asset_id: AssetId,
+		pub amount: Balance,
+		pub buyer: AccountId,
+		pub marketplace_id: Option<MarketplaceId>,
+	}
+
+	#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+	pub enum OfferType<AccountId> {
+		Simple(SimpleOffer<AccountId>),
+		Collection(CollectionOffer<AccountId>),
+		Attribute(AttributeOffer<AccountId>),
+	}
+
+	pub struct Module<T>(sp_std::marker::PhantomData<T>);
+	frame_support::decl_storage! {
+		trait Store for Module<T: Config> as Nft {
+			pub Offers get(fn offers): map hasher(twox_64_concat) OfferId => Option<OfferType<T::AccountId>>;
+		}
+	}
+}
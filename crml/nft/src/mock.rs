@@ -72,6 +72,8 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxVestingSchedules: u32 = 4;
+	pub const MinVestedTransfer: Balance = 1;
 }
 impl crml_generic_asset::Config for Test {
 	type AssetId = AssetId;
@@ -79,6 +81,9 @@ impl crml_generic_asset::Config for Test {
 	type Event = Event;
 	type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = ();
 }
 
 pub struct MockTransferSubscriber;
@@ -89,12 +94,14 @@ impl OnTransferSubscriber for MockTransferSubscriber {
 parameter_types! {
 	pub const DefaultListingDuration: u64 = 5;
 	pub const MaxAttributeLength: u8 = 140;
+	pub const RoyaltiesUpdateDelay: u64 = 10;
 }
 impl crate::Config for Test {
 	type Event = Event;
 	type MultiCurrency = GenericAsset;
 	type MaxAttributeLength = MaxAttributeLength;
 	type DefaultListingDuration = DefaultListingDuration;
+	type RoyaltiesUpdateDelay = RoyaltiesUpdateDelay;
 	type WeightInfo = ();
 	type OnTransferSubscription = MockTransferSubscriber;
 }
@@ -14,7 +14,7 @@
 */
 
 use super::*;
-use crate::mock::{AccountId, Event, ExtBuilder, GenericAsset, Nft, System, Test};
+use crate::mock::{AccountId, Event, ExtBuilder, GenericAsset, Nft, RoyaltiesUpdateDelay, System, Test};
 use cennznet_primitives::types::{CollectionId, SerialNumber, SeriesId, TokenCount, TokenId};
 use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use sp_runtime::Permill;
@@ -28,6 +28,7 @@ fn has_event(
 	event: RawEvent<
 		CollectionId,
 		AccountId,
+		u64,
 		AssetId,
 		Balance,
 		AuctionClosureReason,
@@ -74,6 +75,8 @@ fn setup_token() -> (CollectionId, TokenId, <Test as frame_system::Config>::Acco
 		Some(token_owner),
 		MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 		None,
+		None,
+		None,
 	));
 
 	(collection_id, token_id, token_owner)
@@ -97,6 +100,8 @@ fn setup_token_with_royalties(
 		Some(token_owner),
 		MetadataScheme::Https(b"example.com/metadata".to_vec()),
 		None,
+		None,
+		None,
 	));
 
 	(collection_id, token_id, token_owner)
@@ -108,7 +113,7 @@ fn make_new_simple_offer(
 	token_id: TokenId,
 	buyer: AccountId,
 	marketplace_id: Option<MarketplaceId>,
-) -> (OfferId, SimpleOffer<AccountId>) {
+) -> (OfferId, SimpleOffer<AccountId, u64>) {
 	let next_offer_id = Nft::next_offer_id();
 
 	assert_ok!(Nft::make_simple_offer(
@@ -116,7 +121,8 @@ fn make_new_simple_offer(
 		token_id,
 		offer_amount,
 		PAYMENT_ASSET,
-		marketplace_id
+		marketplace_id,
+		None
 	));
 	let offer = SimpleOffer {
 		token_id,
@@ -124,6 +130,7 @@ fn make_new_simple_offer(
 		amount: offer_amount,
 		buyer,
 		marketplace_id,
+		expiry: None,
 	};
 
 	// Check storage has been updated
@@ -177,6 +184,8 @@ fn migrate_to_metadata_scheme() {
 			Some(token_owner),
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 		SeriesMetadataScheme::remove(collection_id, series_id + 1);
 
@@ -264,6 +273,66 @@ fn migration_v1_to_v2() {
 	});
 }
 
+#[test]
+fn migration_v2_to_v3() {
+	use frame_support::traits::OnRuntimeUpgrade;
+	use migration::v2_storage;
+
+	ExtBuilder::default().build().execute_with(|| {
+		// setup old values
+		v2_storage::Offers::<Test>::insert(
+			1,
+			v2_storage::OfferType::Simple(v2_storage::SimpleOffer {
+				token_id: (55, 2, 2),
+				asset_id: PAYMENT_ASSET,
+				amount: 1_000,
+				buyer: 5,
+				marketplace_id: Some(1),
+			}),
+		);
+		v2_storage::Offers::<Test>::insert(
+			2,
+			v2_storage::OfferType::Collection(v2_storage::CollectionOffer {
+				collection_id: 55,
+				series_id: Some(2),
+				asset_id: PAYMENT_ASSET,
+				amount: 2_000,
+				buyer: 5,
+				marketplace_id: None,
+			}),
+		);
+
+		// run upgrade
+		StorageVersion::put(Releases::V2 as u32); // rollback to v2
+		<Module<Test> as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(
+			Nft::offers(1).expect("offer exists"),
+			OfferType::Simple(SimpleOffer {
+				token_id: (55, 2, 2),
+				asset_id: PAYMENT_ASSET,
+				amount: 1_000,
+				buyer: 5,
+				marketplace_id: Some(1),
+				expiry: None,
+			}),
+		);
+		assert_eq!(
+			Nft::offers(2).expect("offer exists"),
+			OfferType::Collection(CollectionOffer {
+				collection_id: 55,
+				series_id: Some(2),
+				asset_id: PAYMENT_ASSET,
+				amount: 2_000,
+				buyer: 5,
+				marketplace_id: None,
+				expiry: None,
+			}),
+		);
+		assert_eq!(StorageVersion::get(), Releases::V3 as u32);
+	});
+}
+
 #[test]
 fn set_owner() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -373,6 +442,8 @@ fn transfer() {
 			Some(token_owner),
 			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		// test
@@ -420,6 +491,8 @@ fn transfer_fails_prechecks() {
 			Some(token_owner),
 			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		let not_the_owner = 3_u64;
@@ -463,6 +536,8 @@ fn burn() {
 			Some(token_owner),
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		// test
@@ -519,6 +594,8 @@ fn burn_fails_prechecks() {
 			Some(token_owner),
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		// Not owner
@@ -565,6 +642,8 @@ fn sell_bundle() {
 			None,
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		let tokens = vec![(collection_id, 0, 1), (collection_id, 0, 3), (collection_id, 0, 4)];
@@ -752,7 +831,15 @@ fn sell_fails() {
 
 		// can't auction, listed for fixed price sale
 		assert_noop!(
-			Nft::auction(Some(token_owner).into(), token_id, PAYMENT_ASSET, 1_000, None, None),
+			Nft::auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				None
+			),
 			Error::<Test>::TokenListingProtection
 		);
 	});
@@ -877,6 +964,7 @@ fn update_fixed_price_fails() {
 			reserve_price,
 			Some(System::block_number() + 1),
 			None,
+			None,
 		));
 
 		// can't update, listed for auction
@@ -1286,6 +1374,7 @@ fn cancel_auction() {
 			reserve_price,
 			Some(System::block_number() + 1),
 			None,
+			Some(AuctionRules::default()),
 		));
 
 		assert_noop!(
@@ -1304,6 +1393,7 @@ fn cancel_auction() {
 		// storage cleared up
 		assert!(Nft::listings(listing_id).is_none());
 		assert!(!Nft::listing_end_schedule(System::block_number() + 1, listing_id));
+		assert!(!ListingAuctionRules::<Test>::contains_key(listing_id));
 
 		// it should be free to operate on the token
 		assert_ok!(Nft::transfer(Some(token_owner).into(), token_id, token_owner + 1,));
@@ -1325,6 +1415,8 @@ fn auction_bundle() {
 			None,
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
 		let tokens = vec![(collection_id, 0, 1), (collection_id, 0, 3), (collection_id, 0, 4)];
@@ -1337,6 +1429,7 @@ fn auction_bundle() {
 			1_000,
 			Some(1),
 			None,
+			None,
 		));
 
 		assert!(Nft::open_collection_listings(collection_id, listing_id));
@@ -1375,7 +1468,15 @@ fn auction_bundle_fails() {
 
 		// empty tokens fails
 		assert_noop!(
-			Nft::auction_bundle(Some(collection_owner).into(), vec![], PAYMENT_ASSET, 1_000, None, None),
+			Nft::auction_bundle(
+				Some(collection_owner).into(),
+				vec![],
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				None
+			),
 			Error::<Test>::NoToken
 		);
 
@@ -1387,6 +1488,7 @@ fn auction_bundle_fails() {
 				PAYMENT_ASSET,
 				1_000,
 				None,
+				None,
 				None
 			),
 			Error::<Test>::MixedBundleSale
@@ -1402,6 +1504,7 @@ fn auction_bundle_fails() {
 				PAYMENT_ASSET,
 				1_000,
 				None,
+				None,
 				None
 			),
 			Error::<Test>::RoyaltiesProtection
@@ -1425,6 +1528,7 @@ fn auction() {
 			reserve_price,
 			Some(1),
 			None,
+			None,
 		));
 		assert_eq!(
 			Nft::token_locks(&token_id).unwrap(),
@@ -1492,6 +1596,7 @@ fn bid_auto_extends() {
 			reserve_price,
 			Some(2),
 			None,
+			None,
 		));
 
 		// Place bid
@@ -1509,6 +1614,125 @@ fn bid_auto_extends() {
 	});
 }
 
+#[test]
+fn bid_soft_close_extends() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let payment_asset = PAYMENT_ASSET;
+		let reserve_price = 100_000;
+		let close = System::block_number() + 20;
+		let rules = AuctionRules {
+			min_bid_increment: None,
+			soft_close_window: 5,
+			extension_period: 10,
+		};
+
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::auction(
+			Some(token_owner).into(),
+			token_id,
+			payment_asset,
+			reserve_price,
+			Some(20),
+			None,
+			Some(rules.clone()),
+		));
+		assert_eq!(Nft::listing_auction_rules(listing_id), rules);
+
+		let bidder_1 = 10;
+		let bidder_2 = 11;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&bidder_1, payment_asset, reserve_price);
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&bidder_2, payment_asset, reserve_price + 1);
+
+		// outside the soft close window
+		assert_ok!(Nft::bid(Some(bidder_1).into(), listing_id, reserve_price));
+		assert!(Nft::listing_end_schedule(close, listing_id));
+
+		// inside the soft close window
+		System::set_block_number(close - 4);
+		assert_ok!(Nft::bid(Some(bidder_2).into(), listing_id, reserve_price + 1));
+		let new_close = close - 4 + 10;
+		if let Some(Listing::Auction(listing)) = Nft::listings(listing_id) {
+			assert_eq!(listing.close, new_close);
+		}
+		assert!(!Nft::listing_end_schedule(close, listing_id));
+		assert!(Nft::listing_end_schedule(new_close, listing_id));
+		assert!(has_event(RawEvent::ListingExtended(listing_id, new_close)));
+
+		// rules are removed with the listing
+		let _ = Nft::on_initialize(new_close);
+		assert!(Nft::listings(listing_id).is_none());
+		assert!(!ListingAuctionRules::<Test>::contains_key(listing_id));
+	});
+}
+
+#[test]
+fn bid_min_increment() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1;
+		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2;
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner).into(),
+			collection_id,
+			2,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+		let payment_asset = PAYMENT_ASSET;
+		let reserve_price = 1_000;
+		let bidder_1 = 10;
+		let bidder_2 = 11;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&bidder_1, payment_asset, 10_000);
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&bidder_2, payment_asset, 10_000);
+
+		// absolute increment
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::auction(
+			Some(token_owner).into(),
+			(collection_id, 0, 0),
+			payment_asset,
+			reserve_price,
+			None,
+			None,
+			Some(AuctionRules {
+				min_bid_increment: Some(BidIncrement::Absolute(50)),
+				..Default::default()
+			}),
+		));
+		assert_ok!(Nft::bid(Some(bidder_1).into(), listing_id, reserve_price));
+		assert_noop!(
+			Nft::bid(Some(bidder_2).into(), listing_id, reserve_price + 49),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Nft::bid(Some(bidder_2).into(), listing_id, reserve_price + 50));
+
+		// percentage increment
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::auction(
+			Some(token_owner).into(),
+			(collection_id, 0, 1),
+			payment_asset,
+			reserve_price,
+			None,
+			None,
+			Some(AuctionRules {
+				min_bid_increment: Some(BidIncrement::Percentage(Permill::from_percent(10))),
+				..Default::default()
+			}),
+		));
+		assert_ok!(Nft::bid(Some(bidder_1).into(), listing_id, reserve_price));
+		assert_noop!(
+			Nft::bid(Some(bidder_2).into(), listing_id, reserve_price + 99),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Nft::bid(Some(bidder_2).into(), listing_id, reserve_price + 100));
+	});
+}
+
 #[test]
 fn auction_royalty_payments() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1534,6 +1758,7 @@ fn auction_royalty_payments() {
 			reserve_price,
 			Some(1),
 			None,
+			None,
 		));
 
 		// first bidder at reserve price
@@ -1685,6 +1910,7 @@ fn auction_fails_prechecks() {
 				reserve_price,
 				Some(1),
 				None,
+				None,
 			),
 			Error::<Test>::NoPermission
 		);
@@ -1698,6 +1924,7 @@ fn auction_fails_prechecks() {
 				reserve_price,
 				Some(1),
 				None,
+				None,
 			),
 			Error::<Test>::NoPermission
 		);
@@ -1710,6 +1937,7 @@ fn auction_fails_prechecks() {
 			reserve_price,
 			Some(1),
 			None,
+			None,
 		));
 		// already listed
 		assert_noop!(
@@ -1720,6 +1948,7 @@ fn auction_fails_prechecks() {
 				reserve_price,
 				Some(1),
 				None,
+				None,
 			),
 			Error::<Test>::TokenListingProtection
 		);
@@ -1740,6 +1969,64 @@ fn auction_fails_prechecks() {
 	});
 }
 
+#[test]
+fn auction_invalid_rules() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_collection_id, token_id, token_owner) = setup_token();
+
+		assert_noop!(
+			Nft::auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				Some(AuctionRules {
+					min_bid_increment: None,
+					soft_close_window: 5,
+					extension_period: 0,
+				}),
+			),
+			Error::<Test>::InvalidAuctionRules
+		);
+
+		// extensions can't hold bids reserved indefinitely
+		assert_noop!(
+			Nft::auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				Some(AuctionRules {
+					min_bid_increment: None,
+					soft_close_window: 5,
+					extension_period: MAX_AUCTION_EXTENSION_PERIOD as u64 + 1,
+				}),
+			),
+			Error::<Test>::InvalidAuctionRules
+		);
+		assert_noop!(
+			Nft::auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				Some(AuctionRules {
+					min_bid_increment: None,
+					soft_close_window: MAX_AUCTION_EXTENSION_PERIOD as u64 + 1,
+					extension_period: 5,
+				}),
+			),
+			Error::<Test>::InvalidAuctionRules
+		);
+	});
+}
+
 #[test]
 fn bid_fails_prechecks() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -1761,6 +2048,7 @@ fn bid_fails_prechecks() {
 			reserve_price,
 			Some(1),
 			None,
+			None,
 		));
 
 		let bidder = 5;
@@ -1799,1232 +2087,2980 @@ fn bid_fails_prechecks() {
 }
 
 #[test]
-fn transfer_batch() {
+fn dutch_auction() {
 	ExtBuilder::default().build().execute_with(|| {
-		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
-		let token_owner = 2_u64;
-		let token_1_quantity = 3;
-		let series_id = Nft::next_series_id(collection_id);
-
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner).into(),
-			collection_id,
-			token_1_quantity,
-			Some(token_owner),
-			MetadataScheme::Https(b"example.com/metadata".to_vec()),
-			None,
-		));
+		let (collection_id, token_id, token_owner) = setup_token();
+		let payment_asset = PAYMENT_ASSET;
+		let start_price = 1_000;
+		let reserve_price = 100;
+		let listing_id = Nft::next_listing_id();
 
-		// test
-		let tokens = vec![0, 1, 2];
-		let new_owner = 3_u64;
-		assert_ok!(Nft::transfer_batch(
+		assert_ok!(Nft::dutch_auction(
 			Some(token_owner).into(),
-			collection_id,
-			series_id,
-			tokens.clone(),
-			new_owner,
+			token_id,
+			payment_asset,
+			start_price,
+			reserve_price,
+			Some(10),
+			None,
 		));
-		assert!(has_event(RawEvent::Transfer(
-			token_owner,
-			collection_id,
-			series_id,
-			tokens.clone(),
-			new_owner
-		)));
-
 		assert_eq!(
-			Nft::collected_tokens(collection_id, &new_owner),
-			tokens
-				.iter()
-				.map(|sn| (collection_id, series_id, *sn))
-				.collect::<Vec<TokenId>>()
+			Nft::token_locks(&token_id).unwrap(),
+			TokenLockReason::Listed(listing_id)
 		);
-		assert!(Nft::collected_tokens(collection_id, &token_owner).is_empty());
-	});
-}
+		assert_eq!(Nft::next_listing_id(), listing_id + 1);
+		assert!(Nft::open_collection_listings(collection_id, listing_id));
+		assert!(Nft::listing_end_schedule(System::block_number() + 10, listing_id));
+		assert!(has_event(RawEvent::DutchAuctionOpen(collection_id, listing_id, None)));
 
-#[test]
-fn transfer_batch_fails() {
-	ExtBuilder::default().build().execute_with(|| {
-		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
-		let token_owner = 2_u64;
-		let series_id = Nft::next_series_id(collection_id);
+		// price falls linearly to the reserve, reached in the last block before the close
+		let listing = match Nft::listings(listing_id) {
+			Some(Listing::Dutch(listing)) => listing,
+			_ => panic!("expected dutch auction listing"),
+		};
+		assert_eq!(listing.current_price(System::block_number()), start_price);
+		assert_eq!(listing.current_price(System::block_number() + 5), 500);
+		assert_eq!(listing.current_price(System::block_number() + 9), reserve_price);
+		assert_eq!(listing.current_price(System::block_number() + 10), reserve_price);
 
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner).into(),
-			collection_id,
-			3,
-			Some(token_owner),
-			MetadataScheme::Https(b"example.com/metadata".to_vec()),
-			None,
-		));
+		// first buyer at the current price wins
+		System::set_block_number(System::block_number() + 5);
+		let buyer = 5;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, payment_asset, start_price);
+		assert_ok!(Nft::buy(Some(buyer).into(), listing_id));
 
-		// token 3 doesn't exist
-		let new_owner = 3_u64;
-		assert_noop!(
-			Nft::transfer_batch(
-				Some(token_owner).into(),
-				collection_id,
-				series_id,
-				vec![0, 3, 1],
-				new_owner,
-			),
-			Error::<Test>::NoPermission
-		);
+		// no royalties, all proceeds to token owner
+		assert_eq!(GenericAsset::free_balance(payment_asset, &token_owner), 500);
+		assert_eq!(GenericAsset::free_balance(payment_asset, &buyer), start_price - 500);
+		assert!(GenericAsset::reserved_balance(payment_asset, &buyer).is_zero());
 
-		// not owner
-		assert_noop!(
-			Nft::transfer_batch(
-				Some(token_owner + 1).into(),
-				collection_id,
-				series_id,
-				vec![0, 1, 2],
-				new_owner
-			),
-			Error::<Test>::NoPermission
-		);
+		// listing removed
+		assert!(Nft::listings(listing_id).is_none());
+		assert!(!Nft::listing_end_schedule(System::block_number() + 5, listing_id));
+		assert!(!Nft::open_collection_listings(collection_id, listing_id));
 
-		// transfer empty ids should fail
+		// ownership changed
+		assert!(Nft::token_locks(&token_id).is_none());
+		assert_eq!(Nft::collected_tokens(collection_id, &buyer), vec![token_id]);
+		assert!(has_event(RawEvent::DutchAuctionSold(
+			collection_id,
+			listing_id,
+			payment_asset,
+			500,
+			buyer
+		)));
+
+		// second buyer is too late
 		assert_noop!(
-			Nft::transfer_batch(Some(token_owner).into(), collection_id, series_id, vec![], new_owner),
-			Error::<Test>::NoToken
+			Nft::buy(Some(buyer + 1).into(), listing_id),
+			Error::<Test>::NotForFixedPriceSale
 		);
 	});
 }
 
 #[test]
-fn mint_series() {
+fn dutch_auction_royalty_payments() {
 	ExtBuilder::default().build().execute_with(|| {
-		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
-		let token_owner = 2_u64;
-		let quantity = 5;
-		let series_id = Nft::next_series_id(collection_id);
+		let payment_asset = PAYMENT_ASSET;
+		let beneficiary_1 = 11;
+		let collection_owner = 1;
 		let royalties_schedule = RoyaltiesSchedule {
-			entitlements: vec![(collection_owner, Permill::one())],
+			entitlements: vec![
+				(collection_owner, Permill::from_float(0.1111)),
+				(beneficiary_1, Permill::from_float(0.1111)),
+			],
 		};
+		let (collection_id, token_id, token_owner) = setup_token_with_royalties(royalties_schedule.clone(), 1);
+		let marketplace_account = 20;
+		let marketplace_entitlement = Permill::from_float(0.05);
+		let marketplace_id = Nft::next_marketplace_id();
+		assert_ok!(Nft::register_marketplace(
+			Some(marketplace_account).into(),
+			None,
+			marketplace_entitlement
+		));
+		let listing_id = Nft::next_listing_id();
+		let start_price = 200_008;
+		let reserve_price = 100_004;
 
-		// mint token Ids 0-4
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner).into(),
-			collection_id,
-			quantity,
-			Some(token_owner),
-			MetadataScheme::Https(b"example.com/metadata".to_vec()),
-			Some(royalties_schedule.clone()),
+		assert_ok!(Nft::dutch_auction(
+			Some(token_owner).into(),
+			token_id,
+			payment_asset,
+			start_price,
+			reserve_price,
+			Some(4),
+			Some(marketplace_id),
 		));
 
-		assert!(has_event(RawEvent::CreateSeries(
-			collection_id,
-			series_id,
-			quantity,
-			token_owner
-		)));
+		// buy at the floor price in the last block before the listing closes
+		let last_block = System::block_number() + 3;
+		System::set_block_number(last_block);
+		let _ = Nft::on_initialize(last_block);
+		let buyer = 10;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, payment_asset, reserve_price);
+		let presale_issuance = GenericAsset::total_issuance(payment_asset);
+		assert_ok!(Nft::buy(Some(buyer).into(), listing_id));
 
-		// check token ownership
-		assert_eq!(Nft::series_issuance(collection_id, series_id), quantity);
-		assert_eq!(
-			Nft::series_royalties(collection_id, series_id).expect("royalties set"),
-			royalties_schedule
-		);
-		// We minted collection token 0, next collection token id is 1
-		assert_eq!(Nft::next_series_id(collection_id), 1);
+		// royalties distributed according to `entitlements` map
 		assert_eq!(
-			Nft::collected_tokens(collection_id, &token_owner),
-			vec![0, 1, 2, 3, 4]
-				.into_iter()
-				.map(|t| (collection_id, series_id, t))
-				.collect::<Vec<TokenId>>(),
+			GenericAsset::free_balance(payment_asset, &collection_owner),
+			royalties_schedule.entitlements[0].1 * reserve_price
 		);
 		assert_eq!(
-			Nft::token_balance(&token_owner).get(&(collection_id, series_id)),
-			Some(&(5))
+			GenericAsset::free_balance(payment_asset, &beneficiary_1),
+			royalties_schedule.entitlements[1].1 * reserve_price
 		);
-
-		// check we can mint some more
-		// mint token Ids 5-7
-		let additional_quantity = 3;
-		assert_ok!(Nft::mint_additional(
-			Some(collection_owner).into(),
-			collection_id,
-			series_id,
-			additional_quantity,
-			Some(token_owner + 1), // new owner this time
-		));
 		assert_eq!(
-			Nft::token_balance(&token_owner + 1).get(&(collection_id, series_id)),
-			Some(&(3))
+			GenericAsset::free_balance(payment_asset, &marketplace_account),
+			marketplace_entitlement * reserve_price
 		);
+		// token owner gets sale price less royalties
 		assert_eq!(
-			Nft::next_serial_number(collection_id, series_id),
-			quantity + additional_quantity
+			GenericAsset::free_balance(payment_asset, &token_owner),
+			reserve_price
+				- royalties_schedule
+					.entitlements
+					.into_iter()
+					.map(|(_, e)| e * reserve_price)
+					.sum::<Balance>()
+				- marketplace_entitlement * reserve_price
 		);
+		assert!(GenericAsset::free_balance(payment_asset, &buyer).is_zero());
+		assert!(GenericAsset::reserved_balance(payment_asset, &buyer).is_zero());
+		assert_eq!(GenericAsset::total_issuance(payment_asset), presale_issuance);
 
-		assert_eq!(
-			Nft::collected_tokens(collection_id, &token_owner),
-			vec![0, 1, 2, 3, 4]
-				.into_iter()
-				.map(|t| (collection_id, series_id, t))
-				.collect::<Vec<TokenId>>()
-		);
-		assert_eq!(
-			Nft::collected_tokens(collection_id, &(token_owner + 1)),
-			vec![5, 6, 7]
-				.into_iter()
-				.map(|t| (collection_id, series_id, t))
-				.collect::<Vec<TokenId>>()
-		);
-		assert_eq!(
-			Nft::series_issuance(collection_id, series_id),
-			quantity + additional_quantity
-		);
+		// ownership changed
+		assert_eq!(Nft::collected_tokens(collection_id, &buyer), vec![token_id]);
 	});
 }
 
 #[test]
-fn mint_series_zero_quantity() {
+fn dutch_auction_fails_prechecks() {
 	ExtBuilder::default().build().execute_with(|| {
-		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
-		let quantity = 0; // Initial quantity of 0 should work
-		let series_id = Nft::next_series_id(collection_id);
-
-		// mint new series
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner).into(),
-			collection_id,
-			quantity,
-			None,
-			MetadataScheme::Https(b"example.com/metadata".to_vec()),
-			None,
-		));
-
-		assert!(has_event(RawEvent::CreateSeries(
-			collection_id,
-			series_id,
-			quantity,
-			collection_owner
-		)));
-
-		// check issuance
-		assert_eq!(Nft::series_issuance(collection_id, series_id), quantity);
-	});
-}
+		let (_, token_id, token_owner) = setup_token();
+		let payment_asset = PAYMENT_ASSET;
+		let start_price = 1_000;
+		let reserve_price = 100;
 
-#[test]
-fn mint_series_fails_prechecks() {
-	ExtBuilder::default().build().execute_with(|| {
-		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
+		// start price below reserve
+		assert_noop!(
+			Nft::dutch_auction(
+				Some(token_owner).into(),
+				token_id,
+				payment_asset,
+				reserve_price,
+				start_price,
+				None,
+				None,
+			),
+			Error::<Test>::DutchAuctionPriceInvalid
+		);
 
+		// not owner
 		assert_noop!(
-			Nft::mint_series(
-				Some(2_u64).into(),
-				collection_id,
-				3,
-				Some(collection_owner),
-				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			Nft::dutch_auction(
+				Some(token_owner + 1).into(),
+				token_id,
+				payment_asset,
+				start_price,
+				reserve_price,
+				None,
 				None,
 			),
 			Error::<Test>::NoPermission
 		);
 
+		// token listed already
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::dutch_auction(
+			Some(token_owner).into(),
+			token_id,
+			payment_asset,
+			start_price,
+			reserve_price,
+			None,
+			None,
+		));
 		assert_noop!(
-			Nft::mint_series(
-				Some(collection_owner).into(),
-				collection_id + 1, // collection doesn't exist
-				3,
+			Nft::sell(
+				Some(token_owner).into(),
+				token_id,
 				None,
-				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+				payment_asset,
+				start_price,
 				None,
+				None
 			),
-			Error::<Test>::NoCollection
+			Error::<Test>::TokenListingProtection
+		);
+		assert_noop!(
+			Nft::update_fixed_price(Some(token_owner).into(), listing_id, start_price),
+			Error::<Test>::NotForFixedPriceSale
+		);
+
+		// buyer can't afford the current price
+		let buyer = 5;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, payment_asset, start_price - 1);
+		assert_noop!(
+			Nft::buy(Some(buyer).into(), listing_id),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
 		);
 	});
 }
 
 #[test]
-fn mint_series_royalties_invalid() {
+fn dutch_auction_cancel_and_close() {
 	ExtBuilder::default().build().execute_with(|| {
-		let token_owner = 1_u64;
-		let collection_id = setup_collection(token_owner);
-		let quantity = 5;
+		let (collection_id, token_id, token_owner) = setup_token();
+		let payment_asset = PAYMENT_ASSET;
+		let listing_id = Nft::next_listing_id();
 
-		// Create with empty royalty vec should fail
+		assert_ok!(Nft::dutch_auction(
+			Some(token_owner).into(),
+			token_id,
+			payment_asset,
+			1_000,
+			100,
+			Some(1),
+			None,
+		));
 		assert_noop!(
-			Nft::mint_series(
-				Some(token_owner).into(),
-				collection_id,
-				quantity,
-				Some(token_owner),
-				MetadataScheme::Https(b"example.com/metadata".to_vec()),
-				Some(RoyaltiesSchedule::<AccountId> { entitlements: vec![] }),
-			),
-			Error::<Test>::RoyaltiesInvalid
+			Nft::cancel_sale(Some(token_owner + 1).into(), listing_id),
+			Error::<Test>::NoPermission
 		);
+		assert_ok!(Nft::cancel_sale(Some(token_owner).into(), listing_id));
+		assert!(has_event(RawEvent::AuctionClosed(
+			collection_id,
+			listing_id,
+			AuctionClosureReason::VendorCancelled
+		)));
+		assert!(Nft::listings(listing_id).is_none());
+		assert!(!Nft::listing_end_schedule(System::block_number() + 1, listing_id));
+		assert!(Nft::token_locks(&token_id).is_none());
 
-		// Too big royalties should fail
-		assert_noop!(
-			Nft::mint_series(
-				Some(token_owner).into(),
-				collection_id,
-				quantity,
-				Some(token_owner),
-				MetadataScheme::Https(b"example.com/metadata".to_vec()),
-				Some(RoyaltiesSchedule::<AccountId> {
-					entitlements: vec![(3_u64, Permill::from_float(1.2)), (4_u64, Permill::from_float(3.3))]
-				}),
-			),
-			Error::<Test>::RoyaltiesInvalid
-		);
-	})
+		// listing closes on schedule with no buyer
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::dutch_auction(
+			Some(token_owner).into(),
+			token_id,
+			payment_asset,
+			1_000,
+			100,
+			Some(1),
+			None,
+		));
+		let _ = Nft::on_initialize(System::block_number() + 1);
+		assert!(has_event(RawEvent::AuctionClosed(
+			collection_id,
+			listing_id,
+			AuctionClosureReason::ExpiredNoBids
+		)));
+		assert!(Nft::listings(listing_id).is_none());
+		assert!(!Nft::open_collection_listings(collection_id, listing_id));
+		assert!(Nft::token_locks(&token_id).is_none());
+		assert_eq!(Nft::token_owner((collection_id, 0), 0), token_owner);
+	});
 }
 
 #[test]
-fn mint_additional_fails() {
+fn transfer_batch() {
 	ExtBuilder::default().build().execute_with(|| {
 		let collection_owner = 1_u64;
 		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2_u64;
+		let token_1_quantity = 3;
 		let series_id = Nft::next_series_id(collection_id);
 
-		// mint token Ids 0-4
 		assert_ok!(Nft::mint_series(
 			Some(collection_owner).into(),
 			collection_id,
-			5,
-			None,
+			token_1_quantity,
+			Some(token_owner),
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
-		// add 0 additional fails
-		assert_noop!(
-			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id, 0, None),
-			Error::<Test>::NoToken
-		);
+		// test
+		let tokens = vec![0, 1, 2];
+		let new_owner = 3_u64;
+		assert_ok!(Nft::transfer_batch(
+			Some(token_owner).into(),
+			collection_id,
+			series_id,
+			tokens.clone(),
+			new_owner,
+		));
+		assert!(has_event(RawEvent::Transfer(
+			token_owner,
+			collection_id,
+			series_id,
+			tokens.clone(),
+			new_owner
+		)));
 
-		// add to non-existing series fails
-		assert_noop!(
-			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id + 1, 5, None),
-			Error::<Test>::NoToken
+		assert_eq!(
+			Nft::collected_tokens(collection_id, &new_owner),
+			tokens
+				.iter()
+				.map(|sn| (collection_id, series_id, *sn))
+				.collect::<Vec<TokenId>>()
 		);
+		assert!(Nft::collected_tokens(collection_id, &token_owner).is_empty());
+	});
+}
 
-		// not collection owner
-		assert_noop!(
-			Nft::mint_additional(Some(collection_owner + 1).into(), collection_id, series_id, 5, None),
-			Error::<Test>::NoPermission
-		);
+#[test]
+fn transfer_batch_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2_u64;
+		let series_id = Nft::next_series_id(collection_id);
 
 		assert_ok!(Nft::mint_series(
 			Some(collection_owner).into(),
 			collection_id,
-			1,
+			3,
+			Some(token_owner),
+			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			None,
 			None,
-			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
 		));
-	});
-}
-
-#[test]
-fn get_collection_info() {
-	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let name = b"test-collection".to_vec();
-		assert!(has_event(RawEvent::CreateCollection(
-			collection_id,
-			name.clone(),
-			owner
-		)));
-
-		let collection_info = CollectionInfo {
-			name,
-			owner,
-			royalties: vec![],
-		};
-		assert_eq!(Nft::collection_info::<AccountId>(collection_id), Some(collection_info));
-	});
-}
 
-#[test]
-fn get_collection_listings_on_no_active_listings() {
-	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let cursor: u128 = 0;
-		let limit: u16 = 100;
+		// token 3 doesn't exist
+		let new_owner = 3_u64;
+		assert_noop!(
+			Nft::transfer_batch(
+				Some(token_owner).into(),
+				collection_id,
+				series_id,
+				vec![0, 3, 1],
+				new_owner,
+			),
+			Error::<Test>::NoPermission
+		);
 
-		// Should return an empty array as no NFTs have been listed
-		let response = Nft::collection_listings(collection_id, cursor, limit);
+		// not owner
+		assert_noop!(
+			Nft::transfer_batch(
+				Some(token_owner + 1).into(),
+				collection_id,
+				series_id,
+				vec![0, 1, 2],
+				new_owner
+			),
+			Error::<Test>::NoPermission
+		);
 
-		assert_eq!(response.0, None);
-		assert_eq!(response.1, vec![]);
+		// transfer empty ids should fail
+		assert_noop!(
+			Nft::transfer_batch(Some(token_owner).into(), collection_id, series_id, vec![], new_owner),
+			Error::<Test>::NoToken
+		);
 	});
 }
 
 #[test]
-fn get_collection_listings() {
+fn mint_series() {
 	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let cursor: u128 = 0;
-		let limit: u16 = 100;
-		let quantity = 200;
-
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2_u64;
+		let quantity = 5;
 		let series_id = Nft::next_series_id(collection_id);
-		// mint token Ids
+		let royalties_schedule = RoyaltiesSchedule {
+			entitlements: vec![(collection_owner, Permill::one())],
+		};
+
+		// mint token Ids 0-4
 		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
+			Some(collection_owner).into(),
 			collection_id,
 			quantity,
-			None,
+			Some(token_owner),
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			Some(royalties_schedule.clone()),
+			None,
 			None,
 		));
+
 		assert!(has_event(RawEvent::CreateSeries(
 			collection_id,
 			series_id,
 			quantity,
-			owner
+			token_owner
 		)));
 
-		let payment_asset = PAYMENT_ASSET;
-		let price = 1_000;
-		let close = 10;
-		// List tokens for sale
-		for serial_number in 0..quantity {
-			let token_id: TokenId = (collection_id, series_id, serial_number);
-			assert_ok!(Nft::sell(
-				Some(owner).into(),
-				token_id,
-				None,
-				payment_asset,
-				price,
-				Some(close),
-				None,
-			));
-		}
-
-		// Should return an empty array as no NFTs have been listed
-		let (new_cursor, listings) = Nft::collection_listings(collection_id, cursor, limit);
-		let royalties_schedule = RoyaltiesSchedule { entitlements: vec![] };
-		assert_eq!(new_cursor, Some(limit as u128));
+		// check token ownership
+		assert_eq!(Nft::series_issuance(collection_id, series_id), quantity);
+		assert_eq!(
+			Nft::series_royalties(collection_id, series_id).expect("royalties set"),
+			royalties_schedule
+		);
+		// We minted collection token 0, next collection token id is 1
+		assert_eq!(Nft::next_series_id(collection_id), 1);
+		assert_eq!(
+			Nft::collected_tokens(collection_id, &token_owner),
+			vec![0, 1, 2, 3, 4]
+				.into_iter()
+				.map(|t| (collection_id, series_id, t))
+				.collect::<Vec<TokenId>>(),
+		);
+		assert_eq!(
+			Nft::token_balance(&token_owner).get(&(collection_id, series_id)),
+			Some(&(5))
+		);
 
-		// Check the response is as expected
-		for id in 0..limit {
-			let token_id: Vec<TokenId> = vec![(collection_id, series_id, id as u32)];
-			let expected_listing = FixedPriceListing {
-				payment_asset,
-				fixed_price: price,
-				close: close + 1,
-				buyer: None,
-				seller: owner,
-				tokens: token_id,
-				royalties_schedule: royalties_schedule.clone(),
-				marketplace_id: None,
-			};
-			let expected_listing = Listing::FixedPrice(expected_listing);
-			assert_eq!(listings[id as usize], (id as u128, expected_listing));
-		}
+		// check we can mint some more
+		// mint token Ids 5-7
+		let additional_quantity = 3;
+		assert_ok!(Nft::mint_additional(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			additional_quantity,
+			Some(token_owner + 1), // new owner this time
+		));
+		assert_eq!(
+			Nft::token_balance(&token_owner + 1).get(&(collection_id, series_id)),
+			Some(&(3))
+		);
+		assert_eq!(
+			Nft::next_serial_number(collection_id, series_id),
+			quantity + additional_quantity
+		);
+
+		assert_eq!(
+			Nft::collected_tokens(collection_id, &token_owner),
+			vec![0, 1, 2, 3, 4]
+				.into_iter()
+				.map(|t| (collection_id, series_id, t))
+				.collect::<Vec<TokenId>>()
+		);
+		assert_eq!(
+			Nft::collected_tokens(collection_id, &(token_owner + 1)),
+			vec![5, 6, 7]
+				.into_iter()
+				.map(|t| (collection_id, series_id, t))
+				.collect::<Vec<TokenId>>()
+		);
+		assert_eq!(
+			Nft::series_issuance(collection_id, series_id),
+			quantity + additional_quantity
+		);
 	});
 }
 
 #[test]
-fn get_collection_listings_over_limit() {
+fn mint_series_zero_quantity() {
 	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let cursor: u128 = 0;
-		let limit: u16 = 1000;
-
-		let quantity = 200;
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let quantity = 0; // Initial quantity of 0 should work
 		let series_id = Nft::next_series_id(collection_id);
-		// mint token Ids
+
+		// mint new series
 		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
+			Some(collection_owner).into(),
 			collection_id,
 			quantity,
 			None,
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
+
 		assert!(has_event(RawEvent::CreateSeries(
 			collection_id,
 			series_id,
 			quantity,
-			owner
+			collection_owner
 		)));
 
-		let payment_asset = PAYMENT_ASSET;
-		let price = 1_000;
-		let close = 10;
-		// List tokens for sale
-		for serial_number in 0..quantity {
-			let token_id: TokenId = (collection_id, series_id, serial_number);
-			assert_ok!(Nft::sell(
-				Some(owner).into(),
-				token_id,
+		// check issuance
+		assert_eq!(Nft::series_issuance(collection_id, series_id), quantity);
+	});
+}
+
+#[test]
+fn mint_series_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+
+		assert_noop!(
+			Nft::mint_series(
+				Some(2_u64).into(),
+				collection_id,
+				3,
+				Some(collection_owner),
+				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 				None,
-				payment_asset,
-				price,
-				Some(close),
 				None,
-			));
-		}
+				None,
+			),
+			Error::<Test>::NoPermission
+		);
 
-		// Should return an empty array as no NFTs have been listed
-		let (new_cursor, _listings) = Nft::collection_listings(collection_id, cursor, limit);
-		assert_eq!(new_cursor, Some(100));
+		assert_noop!(
+			Nft::mint_series(
+				Some(collection_owner).into(),
+				collection_id + 1, // collection doesn't exist
+				3,
+				None,
+				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+				None,
+				None,
+				None,
+			),
+			Error::<Test>::NoCollection
+		);
 	});
 }
 
 #[test]
-fn get_collection_listings_cursor_too_high() {
+fn mint_series_royalties_invalid() {
 	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let cursor: u128 = 300;
-		let limit: u16 = 1000;
+		let token_owner = 1_u64;
+		let collection_id = setup_collection(token_owner);
+		let quantity = 5;
 
-		let quantity = 200;
-		let series_id = Nft::next_series_id(collection_id);
-		// mint token Ids
-		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
-			collection_id,
-			quantity,
-			None,
-			MetadataScheme::Https(b"example.com/metadata".to_vec()),
-			None,
-		));
-		assert!(has_event(RawEvent::CreateSeries(
-			collection_id,
-			series_id,
-			quantity,
-			owner
-		)));
+		// Create with empty royalty vec should fail
+		assert_noop!(
+			Nft::mint_series(
+				Some(token_owner).into(),
+				collection_id,
+				quantity,
+				Some(token_owner),
+				MetadataScheme::Https(b"example.com/metadata".to_vec()),
+				Some(RoyaltiesSchedule::<AccountId> { entitlements: vec![] }),
+				None,
+				None,
+			),
+			Error::<Test>::RoyaltiesInvalid
+		);
 
-		// Should return an empty array as no NFTs have been listed
-		let (new_cursor, listings) = Nft::collection_listings(collection_id, cursor, limit);
-		assert_eq!(listings, vec![]);
-		assert_eq!(new_cursor, None);
-	});
+		// Too big royalties should fail
+		assert_noop!(
+			Nft::mint_series(
+				Some(token_owner).into(),
+				collection_id,
+				quantity,
+				Some(token_owner),
+				MetadataScheme::Https(b"example.com/metadata".to_vec()),
+				Some(RoyaltiesSchedule::<AccountId> {
+					entitlements: vec![(3_u64, Permill::from_float(1.2)), (4_u64, Permill::from_float(3.3))]
+				}),
+				None,
+				None,
+			),
+			Error::<Test>::RoyaltiesInvalid
+		);
+	})
 }
 
 #[test]
-fn token_uri_construction() {
+fn mint_additional_fails() {
 	ExtBuilder::default().build().execute_with(|| {
-		let owner = 1_u64;
-		let collection_id = setup_collection(owner);
-		let quantity = 5;
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
 		let series_id = Nft::next_series_id(collection_id);
-		// mint token Ids
+
+		// mint token Ids 0-4
 		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
+			Some(collection_owner).into(),
 			collection_id,
-			quantity,
+			5,
 			None,
 			MetadataScheme::Https(b"example.com/metadata".to_vec()),
 			None,
+			None,
+			None,
 		));
 
-		assert_eq!(
-			Nft::token_uri((collection_id, series_id, 0)),
-			b"https://example.com/metadata/0.json".to_vec(),
-		);
-		assert_eq!(
-			Nft::token_uri((collection_id, series_id, 1)),
-			b"https://example.com/metadata/1.json".to_vec(),
+		// add 0 additional fails
+		assert_noop!(
+			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id, 0, None),
+			Error::<Test>::NoToken
 		);
 
-		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
-			collection_id,
-			quantity,
-			None,
-			MetadataScheme::Http(b"test.example.com/metadata".to_vec()),
-			None,
-		));
+		// add to non-existing series fails
+		assert_noop!(
+			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id + 1, 5, None),
+			Error::<Test>::NoToken
+		);
 
-		assert_eq!(
-			Nft::token_uri((collection_id, series_id + 1, 1)),
-			b"http://test.example.com/metadata/1.json".to_vec(),
+		// not collection owner
+		assert_noop!(
+			Nft::mint_additional(Some(collection_owner + 1).into(), collection_id, series_id, 5, None),
+			Error::<Test>::NoPermission
 		);
 
 		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
+			Some(collection_owner).into(),
 			collection_id,
-			quantity,
+			1,
 			None,
-			MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
-		));
-		assert_eq!(
-			Nft::token_uri((collection_id, series_id + 2, 1)),
-			b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/1.json".to_vec(),
-		);
-
-		assert_ok!(Nft::mint_series(
-			Some(owner).into(),
-			collection_id,
-			quantity,
 			None,
-			MetadataScheme::IpfsShared(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
 			None,
 		));
-		assert_eq!(
-			Nft::token_uri((collection_id, series_id + 3, 1)),
-			b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi.json".to_vec(),
-		);
 	});
 }
 
 #[test]
-fn make_simple_offer() {
+fn get_collection_info() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-
-		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
-		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
-		// Check funds have been locked
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let name = b"test-collection".to_vec();
+		assert!(has_event(RawEvent::CreateCollection(
+			collection_id,
+			name.clone(),
+			owner
+		)));
+
+		let collection_info = CollectionInfo {
+			name,
+			owner,
+			royalties: vec![],
+			remaining_supply: vec![],
+		};
+		assert_eq!(Nft::collection_info::<AccountId>(collection_id), Some(collection_info));
+	});
+}
+
+#[test]
+fn get_collection_listings_on_no_active_listings() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let cursor: u128 = 0;
+		let limit: u16 = 100;
+
+		// Should return an empty array as no NFTs have been listed
+		let response = Nft::collection_listings(collection_id, cursor, limit);
+
+		assert_eq!(response.0, None);
+		assert_eq!(response.1, vec![]);
+	});
+}
+
+#[test]
+fn get_collection_listings() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let cursor: u128 = 0;
+		let limit: u16 = 100;
+		let quantity = 200;
+
+		let series_id = Nft::next_series_id(collection_id);
+		// mint token Ids
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			None,
+			None,
+			None,
+		));
+		assert!(has_event(RawEvent::CreateSeries(
+			collection_id,
+			series_id,
+			quantity,
+			owner
+		)));
+
+		let payment_asset = PAYMENT_ASSET;
+		let price = 1_000;
+		let close = 10;
+		// List tokens for sale
+		for serial_number in 0..quantity {
+			let token_id: TokenId = (collection_id, series_id, serial_number);
+			assert_ok!(Nft::sell(
+				Some(owner).into(),
+				token_id,
+				None,
+				payment_asset,
+				price,
+				Some(close),
+				None,
+			));
+		}
+
+		// Should return an empty array as no NFTs have been listed
+		let (new_cursor, listings) = Nft::collection_listings(collection_id, cursor, limit);
+		let royalties_schedule = RoyaltiesSchedule { entitlements: vec![] };
+		assert_eq!(new_cursor, Some(limit as u128));
+
+		// Check the response is as expected
+		for id in 0..limit {
+			let token_id: Vec<TokenId> = vec![(collection_id, series_id, id as u32)];
+			let expected_listing = FixedPriceListing {
+				payment_asset,
+				fixed_price: price,
+				close: close + 1,
+				buyer: None,
+				seller: owner,
+				tokens: token_id,
+				royalties_schedule: royalties_schedule.clone(),
+				marketplace_id: None,
+			};
+			let expected_listing = Listing::FixedPrice(expected_listing);
+			assert_eq!(listings[id as usize], (id as u128, expected_listing));
+		}
+	});
+}
+
+#[test]
+fn get_collection_listings_over_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let cursor: u128 = 0;
+		let limit: u16 = 1000;
+
+		let quantity = 200;
+		let series_id = Nft::next_series_id(collection_id);
+		// mint token Ids
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			None,
+			None,
+			None,
+		));
+		assert!(has_event(RawEvent::CreateSeries(
+			collection_id,
+			series_id,
+			quantity,
+			owner
+		)));
+
+		let payment_asset = PAYMENT_ASSET;
+		let price = 1_000;
+		let close = 10;
+		// List tokens for sale
+		for serial_number in 0..quantity {
+			let token_id: TokenId = (collection_id, series_id, serial_number);
+			assert_ok!(Nft::sell(
+				Some(owner).into(),
+				token_id,
+				None,
+				payment_asset,
+				price,
+				Some(close),
+				None,
+			));
+		}
+
+		// Should return an empty array as no NFTs have been listed
+		let (new_cursor, _listings) = Nft::collection_listings(collection_id, cursor, limit);
+		assert_eq!(new_cursor, Some(100));
+	});
+}
+
+#[test]
+fn get_collection_listings_cursor_too_high() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let cursor: u128 = 300;
+		let limit: u16 = 1000;
+
+		let quantity = 200;
+		let series_id = Nft::next_series_id(collection_id);
+		// mint token Ids
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			None,
+			None,
+			None,
+		));
+		assert!(has_event(RawEvent::CreateSeries(
+			collection_id,
+			series_id,
+			quantity,
+			owner
+		)));
+
+		// Should return an empty array as no NFTs have been listed
+		let (new_cursor, listings) = Nft::collection_listings(collection_id, cursor, limit);
+		assert_eq!(listings, vec![]);
+		assert_eq!(new_cursor, None);
+	});
+}
+
+#[test]
+fn token_uri_construction() {
+	ExtBuilder::default().build().execute_with(|| {
+		let owner = 1_u64;
+		let collection_id = setup_collection(owner);
+		let quantity = 5;
+		let series_id = Nft::next_series_id(collection_id);
+		// mint token Ids
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::Https(b"example.com/metadata".to_vec()),
+			None,
+			None,
+			None,
+		));
+
 		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+			Nft::token_uri((collection_id, series_id, 0)),
+			b"https://example.com/metadata/0.json".to_vec(),
+		);
+		assert_eq!(
+			Nft::token_uri((collection_id, series_id, 1)),
+			b"https://example.com/metadata/1.json".to_vec(),
+		);
+
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::Http(b"test.example.com/metadata".to_vec()),
+			None,
+			None,
+			None,
+		));
+
+		assert_eq!(
+			Nft::token_uri((collection_id, series_id + 1, 1)),
+			b"http://test.example.com/metadata/1.json".to_vec(),
+		);
+
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::IpfsDir(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
+			None,
+			None,
+			None,
+		));
+		assert_eq!(
+			Nft::token_uri((collection_id, series_id + 2, 1)),
+			b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi/1.json".to_vec(),
+		);
+
+		assert_ok!(Nft::mint_series(
+			Some(owner).into(),
+			collection_id,
+			quantity,
+			None,
+			MetadataScheme::IpfsShared(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec()),
+			None,
+			None,
+			None,
+		));
+		assert_eq!(
+			Nft::token_uri((collection_id, series_id + 3, 1)),
+			b"ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi.json".to_vec(),
+		);
+	});
+}
+
+#[test]
+fn make_simple_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+
+		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
+		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
+		// Check funds have been locked
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+	});
+}
+
+#[test]
+fn make_simple_offer_insufficient_funds_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let next_offer_id = Nft::next_offer_id();
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), 0);
+
+		assert_noop!(
+			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None, None),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
+		);
+
+		// Check storage has not been updated
+		assert_eq!(Nft::next_offer_id(), next_offer_id);
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(next_offer_id), None);
+		// Check funds have not been locked
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn make_simple_offer_zero_amount_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 0;
+		let next_offer_id = Nft::next_offer_id();
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), 0);
+
+		assert_noop!(
+			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None, None),
+			Error::<Test>::ZeroOffer
+		);
+
+		// Check storage has not been updated
+		assert_eq!(Nft::next_offer_id(), next_offer_id);
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(next_offer_id), None);
+		// Check funds have not been locked
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn make_simple_offer_token_owner_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let offer_amount: Balance = 100;
+		let next_offer_id = Nft::next_offer_id();
+
+		assert_noop!(
+			Nft::make_simple_offer(
+				Some(token_owner).into(),
+				token_id,
+				offer_amount,
+				PAYMENT_ASSET,
+				None,
+				None
+			),
+			Error::<Test>::IsTokenOwner
+		);
+
+		// Check storage has not been updated
+		assert_eq!(Nft::next_offer_id(), next_offer_id);
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(next_offer_id), None);
+		// Check funds have not been locked
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &token_owner), 0);
+	});
+}
+
+#[test]
+fn make_simple_offer_on_fixed_price_listing() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+
+		let sell_price = 100_000;
+		assert_ok!(Nft::sell(
+			Some(token_owner).into(),
+			token_id,
+			None,
+			PAYMENT_ASSET,
+			sell_price,
+			None,
+			None,
+		));
+
+		make_new_simple_offer(offer_amount, token_id, buyer, None);
+		// Check funds have been locked
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+	});
+}
+
+#[test]
+fn make_simple_offer_on_auction_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+
+		let reserve_price = 100_000;
+
+		assert_ok!(Nft::auction(
+			Some(token_owner).into(),
+			token_id,
+			PAYMENT_ASSET,
+			reserve_price,
+			Some(System::block_number() + 1),
+			None,
+			None,
+		));
+
+		let next_offer_id = Nft::next_offer_id();
+		assert_noop!(
+			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None, None),
+			Error::<Test>::TokenOnAuction
+		);
+
+		// Check storage has not been updated
+		assert_eq!(Nft::next_offer_id(), next_offer_id);
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(next_offer_id), None);
+		// Check funds have not been locked
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn cancel_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
+		assert_ok!(Nft::cancel_offer(Some(buyer).into(), offer_id));
+
+		assert!(has_event(RawEvent::OfferCancelled(offer_id)));
+
+		// Check storage has been removed
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(offer_id), None);
+		// Check funds have been unlocked after offer cancelled
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn cancel_offer_multiple_offers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer_1: u64 = 3;
+		let buyer_2: u64 = 4;
+
+		let offer_amount_1: Balance = 100;
+		let offer_amount_2: Balance = 150;
+		let initial_balance_buyer_1: Balance = 1000;
+		let initial_balance_buyer_2: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_1, PAYMENT_ASSET, initial_balance_buyer_1);
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_2, PAYMENT_ASSET, initial_balance_buyer_2);
+
+		let (offer_id_1, _) = make_new_simple_offer(offer_amount_1, token_id, buyer_1, None);
+		let (offer_id_2, offer_2) = make_new_simple_offer(offer_amount_2, token_id, buyer_2, None);
+
+		// Can't cancel other offer
+		assert_noop!(
+			Nft::cancel_offer(Some(buyer_1).into(), offer_id_2),
+			Error::<Test>::NotBuyer
+		);
+		// Can cancel their offer
+		assert_ok!(Nft::cancel_offer(Some(buyer_1).into(), offer_id_1));
+		assert!(has_event(RawEvent::OfferCancelled(offer_id_1)));
+
+		// Check storage has been removed
+		let offer_vector: Vec<OfferId> = vec![offer_id_2];
+		assert_eq!(Nft::token_offers(token_id), offer_vector);
+		assert_eq!(Nft::offers(offer_id_2), Some(OfferType::Simple(offer_2.clone())));
+		assert_eq!(Nft::offers(offer_id_1), None);
+
+		// Check funds have been unlocked after offer cancelled
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_1),
+			initial_balance_buyer_1
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_1), 0);
+		// Check buyer_2 funds have not been unlocked
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_2),
+			initial_balance_buyer_2 - offer_amount_2
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_2), offer_amount_2);
+	});
+}
+
+#[test]
+fn cancel_offer_not_buyer_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+
+		let (offer_id, offer) = make_new_simple_offer(offer_amount, token_id, buyer, None);
+		assert_noop!(Nft::cancel_offer(Some(4).into(), offer_id), Error::<Test>::NotBuyer);
+
+		// Check storage has not been removed
+		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
+		assert_eq!(Nft::offers(offer_id), Some(OfferType::Simple(offer.clone())));
+		// Check funds have not been unlocked
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+	});
+}
+
+#[test]
+fn accept_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
+		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id));
+		assert!(has_event(RawEvent::OfferAccepted(offer_id)));
+
+		// Check storage has been removed
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(offer_id), None);
+		// Check funds have been transferred
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount);
+	});
+}
+
+#[test]
+fn accept_offer_multiple_offers() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer_1: u64 = 3;
+		let buyer_2: u64 = 4;
+
+		let offer_amount_1: Balance = 100;
+		let offer_amount_2: Balance = 150;
+		let initial_balance_buyer_1: Balance = 1000;
+		let initial_balance_buyer_2: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_1, PAYMENT_ASSET, initial_balance_buyer_1);
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_2, PAYMENT_ASSET, initial_balance_buyer_2);
+
+		let (offer_id_1, offer_1) = make_new_simple_offer(offer_amount_1, token_id, buyer_1, None);
+		let (offer_id_2, _) = make_new_simple_offer(offer_amount_2, token_id, buyer_2, None);
+
+		// Accept second offer
+		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id_2));
+		assert!(has_event(RawEvent::OfferAccepted(offer_id_2)));
+
+		// Check storage has been removed
+		let offer_vector: Vec<OfferId> = vec![offer_id_1];
+		assert_eq!(Nft::token_offers(token_id), offer_vector);
+		assert_eq!(Nft::offers(offer_id_1), Some(OfferType::Simple(offer_1.clone())));
+		assert_eq!(Nft::offers(offer_id_2), None);
+
+		// Check funds have been transferred
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_2),
+			initial_balance_buyer_2 - offer_amount_2
+		);
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_1),
+			initial_balance_buyer_1 - offer_amount_1
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_1), offer_amount_1);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_2), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount_2);
+
+		// Accept first offer should fail as token_owner is no longer owner
+		assert_noop!(
+			Nft::accept_offer(Some(token_owner).into(), offer_id_1),
+			Error::<Test>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn accept_offer_pays_marketplace_royalties() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		let marketplace_account = 4;
+		let entitlements: Permill = Permill::from_float(0.1);
+		let marketplace_id = Nft::next_marketplace_id();
+		assert_ok!(Nft::register_marketplace(
+			Some(marketplace_account).into(),
+			None,
+			entitlements
+		));
+
+		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, Some(marketplace_id));
+		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id));
+		assert!(has_event(RawEvent::OfferAccepted(offer_id)));
+
+		// Check storage has been removed
+		let empty_offer_vector: Vec<OfferId> = vec![];
+		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
+		assert_eq!(Nft::offers(offer_id), None);
+		// Check funds have been transferred with royalties
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &marketplace_account),
+			entitlements * offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &token_owner),
+			offer_amount - (entitlements * offer_amount)
+		);
+	});
+}
+
+#[test]
+fn accept_offer_not_token_owner_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		let (offer_id, offer) = make_new_simple_offer(offer_amount, token_id, buyer, None);
+		assert_noop!(Nft::accept_offer(Some(4).into(), offer_id), Error::<Test>::NoPermission);
+
+		// Check storage has not been removed
+		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
+		assert_eq!(Nft::offers(offer_id), Some(OfferType::Simple(offer.clone())));
+		// Check funds have not been transferred
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), 0);
+	});
+}
+
+#[test]
+fn make_collection_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, _, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		let offer_id = Nft::next_offer_id();
+
+		assert_ok!(Nft::make_collection_offer(
+			Some(buyer).into(),
+			collection_id,
+			None,
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			None,
+		));
+		assert!(has_event(RawEvent::OfferMade(
+			offer_id,
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			buyer
+		)));
+		assert_eq!(Nft::next_offer_id(), offer_id + 1);
+		assert_eq!(
+			Nft::offers(offer_id),
+			Some(OfferType::Collection(CollectionOffer {
+				collection_id,
+				series_id: None,
+				asset_id: PAYMENT_ASSET,
+				amount: offer_amount,
+				buyer,
+				marketplace_id: None,
+				expiry: None,
+			}))
+		);
+		assert_eq!(Nft::collection_offers(collection_id), vec![offer_id]);
+		// Check funds have been locked
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+	});
+}
+
+#[test]
+fn make_collection_offer_fails_prechecks() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, _, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+
+		// no collection
+		assert_noop!(
+			Nft::make_collection_offer(
+				Some(buyer).into(),
+				collection_id + 1,
+				None,
+				offer_amount,
+				PAYMENT_ASSET,
+				None,
+				None
+			),
+			Error::<Test>::NoCollection
+		);
+		// no series
+		assert_noop!(
+			Nft::make_collection_offer(
+				Some(buyer).into(),
+				collection_id,
+				Some(1),
+				offer_amount,
+				PAYMENT_ASSET,
+				None,
+				None
+			),
+			Error::<Test>::NoSeries
+		);
+		// zero offer
+		assert_noop!(
+			Nft::make_collection_offer(Some(buyer).into(), collection_id, None, 0, PAYMENT_ASSET, None, None),
+			Error::<Test>::ZeroOffer
+		);
+		// attribute too long
+		assert_noop!(
+			Nft::make_attribute_offer(
+				Some(buyer).into(),
+				collection_id,
+				NFTAttributeValue::String(vec![0_u8; <Test as Config>::MaxAttributeLength::get() as usize + 1]),
+				offer_amount,
+				PAYMENT_ASSET,
+				None,
+				None
+			),
+			Error::<Test>::MaxAttributeLength
+		);
+		// insufficient funds
+		assert_noop!(
+			Nft::make_collection_offer(
+				Some(buyer).into(),
+				collection_id,
+				None,
+				offer_amount,
+				PAYMENT_ASSET,
+				None,
+				None
+			),
+			crml_generic_asset::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn accept_collection_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2_u64;
+		for _ in 0..2 {
+			assert_ok!(Nft::mint_series(
+				Some(collection_owner).into(),
+				collection_id,
+				2,
+				Some(token_owner),
+				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+				None,
+				None,
+				None,
+			));
+		}
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		// offer on any token of series 1
+		let offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_collection_offer(
+			Some(buyer).into(),
+			collection_id,
+			Some(1),
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			None,
+		));
+
+		// token from the wrong series
+		assert_noop!(
+			Nft::accept_collection_offer(Some(token_owner).into(), offer_id, (collection_id, 0, 1)),
+			Error::<Test>::OfferTokenMismatch
+		);
+		// not the token owner
+		assert_noop!(
+			Nft::accept_collection_offer(Some(token_owner + 1).into(), offer_id, (collection_id, 1, 1)),
+			Error::<Test>::NoPermission
+		);
+		// simple offers must be accepted with `accept_offer`
+		assert_noop!(
+			Nft::accept_offer(Some(token_owner).into(), offer_id),
+			Error::<Test>::InvalidOffer
+		);
+
+		assert_ok!(Nft::accept_collection_offer(
+			Some(token_owner).into(),
+			offer_id,
+			(collection_id, 1, 1)
+		));
+		assert!(has_event(RawEvent::OfferAccepted(offer_id)));
+
+		// Check storage has been removed
+		assert!(Nft::collection_offers(collection_id).is_empty());
+		assert_eq!(Nft::offers(offer_id), None);
+		// Check funds have been transferred
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			initial_balance_buyer - offer_amount
+		);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount);
+		assert_eq!(Nft::token_owner((collection_id, 1), 1), buyer);
+	});
+}
+
+#[test]
+fn accept_attribute_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let token_owner = 2_u64;
+		for _ in 0..2 {
+			assert_ok!(Nft::mint_series(
+				Some(collection_owner).into(),
+				collection_id,
+				1,
+				Some(token_owner),
+				MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+				None,
+				None,
+				None,
+			));
+		}
+		let attribute = NFTAttributeValue::String(b"legendary".to_vec());
+		SeriesAttributes::insert(collection_id, 1, vec![NFTAttributeValue::U8(1), attribute.clone()]);
+
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, offer_amount);
+		let offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_attribute_offer(
+			Some(buyer).into(),
+			collection_id,
+			attribute.clone(),
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			None,
+		));
+		assert_eq!(
+			Nft::offers(offer_id),
+			Some(OfferType::Attribute(AttributeOffer {
+				collection_id,
+				attribute,
+				asset_id: PAYMENT_ASSET,
+				amount: offer_amount,
+				buyer,
+				marketplace_id: None,
+				expiry: None,
+			}))
+		);
+
+		// token without the attribute
+		assert_noop!(
+			Nft::accept_collection_offer(Some(token_owner).into(), offer_id, (collection_id, 0, 0)),
+			Error::<Test>::OfferTokenMismatch
+		);
+
+		// listed tokens are protected
+		assert_ok!(Nft::sell(
+			Some(token_owner).into(),
+			(collection_id, 1, 0),
+			None,
+			PAYMENT_ASSET,
+			1_000,
+			None,
+			None
+		));
+		assert_noop!(
+			Nft::accept_collection_offer(Some(token_owner).into(), offer_id, (collection_id, 1, 0)),
+			Error::<Test>::TokenListingProtection
+		);
+		assert_ok!(Nft::cancel_sale(Some(token_owner).into(), Nft::next_listing_id() - 1));
+
+		assert_ok!(Nft::accept_collection_offer(
+			Some(token_owner).into(),
+			offer_id,
+			(collection_id, 1, 0)
+		));
+		assert_eq!(Nft::offers(offer_id), None);
+		assert!(Nft::collection_offers(collection_id).is_empty());
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount);
+		assert_eq!(Nft::token_owner((collection_id, 1), 0), buyer);
+	});
+}
+
+#[test]
+fn cancel_collection_offer() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, _, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+
+		let offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_collection_offer(
+			Some(buyer).into(),
+			collection_id,
+			None,
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			None,
+		));
+		assert_noop!(
+			Nft::cancel_offer(Some(buyer + 1).into(), offer_id),
+			Error::<Test>::NotBuyer
+		);
+		assert_ok!(Nft::cancel_offer(Some(buyer).into(), offer_id));
+		assert!(has_event(RawEvent::OfferCancelled(offer_id)));
+
+		// Check storage has been removed
+		assert!(Nft::collection_offers(collection_id).is_empty());
+		assert_eq!(Nft::offers(offer_id), None);
+		// Check funds have been unlocked after offer cancelled
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn offer_expiry_releases_funds() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, token_id, token_owner) = setup_token();
+		let buyer: u64 = 3;
+		let offer_amount: Balance = 100;
+		let initial_balance_buyer: Balance = 1000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		let expiry = System::block_number() + 5;
+
+		let simple_offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_simple_offer(
+			Some(buyer).into(),
+			token_id,
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			Some(expiry),
+		));
+		let collection_offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_collection_offer(
+			Some(buyer).into(),
+			collection_id,
+			None,
+			offer_amount,
+			PAYMENT_ASSET,
+			None,
+			Some(expiry),
+		));
+		assert!(Nft::offer_end_schedule(expiry, simple_offer_id));
+		assert!(Nft::offer_end_schedule(expiry, collection_offer_id));
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount * 2);
+
+		// offers can't be accepted once expired, even if not yet processed
+		System::set_block_number(expiry);
+		assert_noop!(
+			Nft::accept_offer(Some(token_owner).into(), simple_offer_id),
+			Error::<Test>::InvalidOffer
+		);
+		assert_noop!(
+			Nft::accept_collection_offer(Some(token_owner).into(), collection_offer_id, token_id),
+			Error::<Test>::InvalidOffer
+		);
+
+		let _ = Nft::on_initialize(expiry);
+		assert!(has_event(RawEvent::OfferExpired(simple_offer_id)));
+		assert!(has_event(RawEvent::OfferExpired(collection_offer_id)));
+
+		// Check storage has been removed
+		assert_eq!(Nft::offers(simple_offer_id), None);
+		assert_eq!(Nft::offers(collection_offer_id), None);
+		assert!(Nft::token_offers(token_id).is_empty());
+		assert!(Nft::collection_offers(collection_id).is_empty());
+		assert!(!Nft::offer_end_schedule(expiry, simple_offer_id));
+		assert!(Nft::offer_expiry_backlog().is_empty());
+		// Check funds have been unlocked after offers expired
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+	});
+}
+
+#[test]
+fn offer_expiry_invalid() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, 1_000);
+		let now = System::block_number();
+
+		assert_noop!(
+			Nft::make_simple_offer(Some(buyer).into(), token_id, 100, PAYMENT_ASSET, None, Some(now)),
+			Error::<Test>::OfferExpiryInvalid
+		);
+		assert_noop!(
+			Nft::make_collection_offer(
+				Some(buyer).into(),
+				collection_id,
+				None,
+				100,
+				PAYMENT_ASSET,
+				None,
+				Some(now - 1)
+			),
+			Error::<Test>::OfferExpiryInvalid
+		);
+	});
+}
+
+#[test]
+fn cancel_offer_removes_expiry() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_, token_id, _) = setup_token();
+		let buyer: u64 = 3;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, 1_000);
+		let expiry = System::block_number() + 5;
+
+		let offer_id = Nft::next_offer_id();
+		assert_ok!(Nft::make_simple_offer(
+			Some(buyer).into(),
+			token_id,
+			100,
+			PAYMENT_ASSET,
+			None,
+			Some(expiry),
+		));
+		assert_ok!(Nft::cancel_offer(Some(buyer).into(), offer_id));
+		assert!(!Nft::offer_end_schedule(expiry, offer_id));
+
+		let _ = Nft::on_initialize(expiry);
+		assert!(!has_event(RawEvent::OfferExpired(offer_id)));
+		assert!(Nft::offer_expiry_backlog().is_empty());
+	});
+}
+
+#[test]
+fn offer_expiry_is_bounded_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (collection_id, _, _) = setup_token();
+		let buyer: u64 = 3;
+		let offer_count = MAX_OFFER_EXPIRIES_PER_BLOCK + 5;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, offer_count as Balance);
+		let expiry = System::block_number() + 5;
+
+		for _ in 0..offer_count {
+			assert_ok!(Nft::make_collection_offer(
+				Some(buyer).into(),
+				collection_id,
+				None,
+				1,
+				PAYMENT_ASSET,
+				None,
+				Some(expiry),
+			));
+		}
+
+		// only the maximum is expired, the remainder is carried over
+		let _ = Nft::on_initialize(expiry);
+		assert_eq!(
+			Nft::collection_offers(collection_id).len(),
+			(offer_count - MAX_OFFER_EXPIRIES_PER_BLOCK) as usize
+		);
+		assert_eq!(Nft::offer_expiry_backlog(), vec![expiry]);
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 5);
+
+		let _ = Nft::on_initialize(expiry + 1);
+		assert!(Nft::collection_offers(collection_id).is_empty());
+		assert!(Nft::offer_expiry_backlog().is_empty());
+		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_eq!(
+			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
+			offer_count as Balance
+		);
+	});
+}
+
+#[test]
+fn transfer_changes_token_balance() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let series_id: SeriesId = 0;
+		let token_owner = 2_u64;
+		let new_owner = 3_u64;
+		let initial_quantity: u32 = 1;
+		// Create BTreeMaps for both owners
+		let mut owner_map = BTreeMap::new();
+		let mut new_owner_map = BTreeMap::new();
+
+		// Mint 1 token
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner).into(),
+			collection_id,
+			initial_quantity,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+
+		owner_map.insert((collection_id, series_id), initial_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+
+		// Mint an additional 2 tokens
+		let additional_quantity: u32 = 2;
+		assert_ok!(Nft::mint_additional(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			additional_quantity,
+			Some(token_owner),
+		));
+
+		owner_map.insert((collection_id, series_id), initial_quantity + additional_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+
+		// Transfer 2 tokens
+		let tokens = vec![0_u32, 1_u32];
+		let transfer_quantity: u32 = tokens.len() as u32;
+		assert_ok!(Nft::transfer_batch(
+			Some(token_owner).into(),
+			collection_id,
+			series_id,
+			tokens.clone(),
+			new_owner,
+		));
+
+		owner_map.insert(
+			(collection_id, series_id),
+			initial_quantity + additional_quantity - transfer_quantity,
+		);
+		new_owner_map.insert((collection_id, series_id), transfer_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+	});
+}
+
+#[test]
+fn transfer_many_tokens_changes_token_balance() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let series_id: SeriesId = 0;
+		let token_owner = 2_u64;
+		let new_owner = 3_u64;
+		let initial_quantity: u32 = 100;
+		// Create BTreeMaps for both owners
+		let mut owner_map = BTreeMap::new();
+		let mut new_owner_map = BTreeMap::new();
+
+		// Mint 1 token
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner).into(),
+			collection_id,
+			initial_quantity,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+
+		owner_map.insert((collection_id, series_id), initial_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+
+		for i in 0_u32..initial_quantity {
+			// Transfer token
+			let token_id: TokenId = (collection_id, series_id, i);
+			assert_ok!(Nft::transfer(Some(token_owner).into(), token_id, new_owner,));
+
+			// Check storage
+			let changed_quantity = i + 1;
+			if changed_quantity == initial_quantity {
+				assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
+			} else {
+				owner_map.insert((collection_id, series_id), initial_quantity - changed_quantity);
+				assert_eq!(Nft::token_balance(token_owner), owner_map);
+			}
+			new_owner_map.insert((collection_id, series_id), changed_quantity);
+			assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+		}
+	});
+}
+
+#[test]
+fn update_token_balance_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Runs update_token_balance on a series
+		let collection_owner = 1_u64;
+		let collection_id = setup_collection(collection_owner);
+		let series_id: SeriesId = 0;
+		let token_owner = 2_u64;
+		let quantity: TokenCount = 10;
+
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner).into(),
+			collection_id,
+			1,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+
+		let mut token_owners: Vec<u64> = vec![];
+		for i in 1..quantity {
+			let token_owner: u64 = collection_owner + 1 + (i as u64);
+			token_owners.push(token_owner);
+			assert_ok!(Nft::mint_additional(
+				Some(collection_owner).into(),
+				collection_id,
+				series_id,
+				1,
+				Some(token_owner),
+			));
+
+			// Remove token_balance storage to simulate before EVM update
+			TokenBalance::<Test>::remove(token_owner);
+			assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
+		}
+
+		// Update token balances to fix storage
+		assert_ok!(Nft::update_token_balance(
+			Some(100_u64).into(),
+			collection_id,
+			series_id,
+		));
+		// event logged
+		assert!(has_event(RawEvent::TokenBalancesUpdated(collection_id, series_id)));
+
+		// Check that storage for each owner is correct
+		// Initial owner
+		let mut owner_map = BTreeMap::new();
+		owner_map.insert((collection_id, series_id), 1);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		// additional owners
+		for owner in token_owners {
+			let mut owner_map = BTreeMap::new();
+			owner_map.insert((collection_id, series_id), 1);
+			assert_eq!(Nft::token_balance(owner), owner_map);
+		}
+	});
+}
+
+#[test]
+fn update_token_balance_no_collection_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Update token balances on no collection
+		assert_noop!(
+			Nft::update_token_balance(Some(100_u64).into(), 0, 0),
+			Error::<Test>::NoCollection
+		);
+	});
+}
+
+#[test]
+fn update_token_balance_no_series_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Update token balances on no series
+		let collection_id = setup_collection(1_u64);
+
+		assert_noop!(
+			Nft::update_token_balance(Some(100_u64).into(), collection_id, 0),
+			Error::<Test>::NoSeries
+		);
+	});
+}
+
+#[test]
+fn update_token_balance_multiple_collections() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Runs update_token_balance on a series while checking that it doesn't affect other collections
+		let collection_owner = 1_u64;
+		let collection_owner_2 = 200_u64;
+		let collection_id = setup_collection(collection_owner);
+		let collection_id_2 = setup_collection(collection_owner_2);
+		let series_id: SeriesId = 0;
+		let series_id_2: SeriesId = 0;
+		let token_owner = 2_u64;
+
+		// Mint first collection
+		let first_collection_quantity: TokenCount = 3;
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner).into(),
+			collection_id,
+			first_collection_quantity,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+		// Remove token_balance storage to simulate before EVM update
+		TokenBalance::<Test>::remove(token_owner);
+		assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
+
+		// Mint second collection with same owner
+		let second_collection_quantity: TokenCount = 5;
+		assert_ok!(Nft::mint_series(
+			Some(collection_owner_2).into(),
+			collection_id_2,
+			second_collection_quantity,
+			Some(token_owner),
+			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+			None,
+			None,
+			None,
+		));
+		// Check storage for token owner only shows second collection
+		let mut owner_map = BTreeMap::new();
+		owner_map.insert((collection_id_2, series_id_2), second_collection_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+
+		// Update token balances to fix storage
+		assert_ok!(Nft::update_token_balance(
+			Some(100_u64).into(),
+			collection_id,
+			series_id,
+		));
+
+		// Check that storage for token owner is correct
+		let mut owner_map = BTreeMap::new();
+		owner_map.insert((collection_id, series_id), first_collection_quantity);
+		owner_map.insert((collection_id_2, series_id_2), second_collection_quantity);
+		assert_eq!(Nft::token_balance(token_owner), owner_map);
+	});
+}
+
+#[test]
+fn lend_starts_rental() {
+	ExtBuilder::default().build().execute_with(|| {
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let user = 3;
+		let duration = 10;
+
+		assert_ok!(Nft::lend(Some(token_owner).into(), token_id, user, duration, None));
+		let expiry = System::block_number() + duration;
+		assert_eq!(Nft::token_rentals(token_id), Some(TokenRental { user, expiry }));
+		assert_eq!(Nft::token_user(token_id), Some(TokenRental { user, expiry }));
+		assert_eq!(Nft::token_locks(token_id), Some(TokenLockReason::Rented));
+		assert!(Nft::rental_end_schedule(expiry, token_id));
+		assert!(has_event(RawEvent::TokenRented(token_id, user, expiry)));
+
+		// ownership is unchanged, the token is protected while rented
+		assert_eq!(Nft::token_owner((token_id.0, token_id.1), token_id.2), token_owner);
+		assert_noop!(
+			Nft::transfer(Some(token_owner).into(), token_id, 4),
+			Error::<Test>::TokenListingProtection
+		);
+		assert_noop!(
+			Nft::sell(
+				Some(token_owner).into(),
+				token_id,
+				None,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None
+			),
+			Error::<Test>::TokenListingProtection
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
+
+		// usage rights return to the owner at expiry
+		let _ = Nft::on_initialize(expiry);
+		assert!(has_event(RawEvent::RentalEnded(token_id, user)));
+		assert!(Nft::token_rentals(token_id).is_none());
+		assert!(Nft::token_user(token_id).is_none());
+		assert!(Nft::token_locks(token_id).is_none());
+		assert!(!Nft::rental_end_schedule(expiry, token_id));
+		assert_ok!(Nft::transfer(Some(token_owner).into(), token_id, 4));
 	});
 }
 
 #[test]
-fn make_simple_offer_insufficient_funds_should_fail() {
+fn rental_ends_are_bounded_per_block() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let next_offer_id = Nft::next_offer_id();
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), 0);
+		let rental_count = MAX_RENTAL_ENDS_PER_BLOCK + 5;
+		let (collection_id, token_id, token_owner) =
+			setup_token_with_royalties(RoyaltiesSchedule::default(), rental_count);
+		let user = 3;
+		let duration = 10;
+
+		for serial_number in 0..rental_count {
+			assert_ok!(Nft::lend(
+				Some(token_owner).into(),
+				(collection_id, token_id.1, serial_number),
+				user,
+				duration,
+				None
+			));
+		}
+		let expiry = System::block_number() + duration;
 
-		assert_noop!(
-			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None),
-			crml_generic_asset::Error::<Test>::InsufficientBalance
+		// only the maximum is ended, the remainder is carried over
+		let _ = Nft::on_initialize(expiry);
+		assert_eq!(
+			TokenRentals::<Test>::iter().count(),
+			(rental_count - MAX_RENTAL_ENDS_PER_BLOCK) as usize
 		);
+		assert_eq!(Nft::rental_end_backlog(), vec![expiry]);
 
-		// Check storage has not been updated
-		assert_eq!(Nft::next_offer_id(), next_offer_id);
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(next_offer_id), None);
-		// Check funds have not been locked
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		let _ = Nft::on_initialize(expiry + 1);
+		assert_eq!(TokenRentals::<Test>::iter().count(), 0);
+		assert!(Nft::rental_end_backlog().is_empty());
+		assert_eq!(TokenLocks::iter().count(), 0);
 	});
 }
 
 #[test]
-fn make_simple_offer_zero_amount_should_fail() {
+fn lend_with_price() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 0;
-		let next_offer_id = Nft::next_offer_id();
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), 0);
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let user = 3;
+		let duration = 10;
+		let price: Balance = 100;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&user, PAYMENT_ASSET, price);
+
+		assert_ok!(Nft::lend(
+			Some(token_owner).into(),
+			token_id,
+			user,
+			duration,
+			Some((PAYMENT_ASSET, price))
+		));
+		assert!(has_event(RawEvent::RentalOffered(token_id, user, PAYMENT_ASSET, price)));
+		// the rental is not active until accepted
+		assert!(Nft::token_rentals(token_id).is_none());
+		assert!(Nft::token_locks(token_id).is_none());
 
+		// only the offered user may accept
 		assert_noop!(
-			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None),
-			Error::<Test>::ZeroOffer
+			Nft::accept_rental(Some(user + 1).into(), token_id),
+			Error::<Test>::NoRentalOffer
 		);
-
-		// Check storage has not been updated
-		assert_eq!(Nft::next_offer_id(), next_offer_id);
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(next_offer_id), None);
-		// Check funds have not been locked
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		assert_ok!(Nft::accept_rental(Some(user).into(), token_id));
+		let expiry = System::block_number() + duration;
+		assert_eq!(Nft::token_rentals(token_id), Some(TokenRental { user, expiry }));
+		assert!(Nft::rental_offers(token_id).is_none());
+
+		// rental price paid to the owner
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &user), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), price);
 	});
 }
 
 #[test]
-fn make_simple_offer_token_owner_should_fail() {
+fn accept_rental_after_owner_changed_fails() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let offer_amount: Balance = 100;
-		let next_offer_id = Nft::next_offer_id();
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let user = 3;
+		let price: Balance = 100;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&user, PAYMENT_ASSET, price);
 
+		assert_ok!(Nft::lend(
+			Some(token_owner).into(),
+			token_id,
+			user,
+			10,
+			Some((PAYMENT_ASSET, price))
+		));
+		assert_ok!(Nft::transfer(Some(token_owner).into(), token_id, 4));
 		assert_noop!(
-			Nft::make_simple_offer(Some(token_owner).into(), token_id, offer_amount, PAYMENT_ASSET, None),
-			Error::<Test>::IsTokenOwner
+			Nft::accept_rental(Some(user).into(), token_id),
+			Error::<Test>::NoPermission
 		);
-
-		// Check storage has not been updated
-		assert_eq!(Nft::next_offer_id(), next_offer_id);
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(next_offer_id), None);
-		// Check funds have not been locked
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &token_owner), 0);
 	});
 }
 
 #[test]
-fn make_simple_offer_on_fixed_price_listing() {
+fn cancel_rental_offer() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let user = 3;
 
-		let sell_price = 100_000;
-		assert_ok!(Nft::sell(
+		assert_ok!(Nft::lend(
 			Some(token_owner).into(),
 			token_id,
-			None,
-			PAYMENT_ASSET,
-			sell_price,
-			None,
-			None,
+			user,
+			10,
+			Some((PAYMENT_ASSET, 100))
 		));
-
-		make_new_simple_offer(offer_amount, token_id, buyer, None);
-		// Check funds have been locked
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+		assert_noop!(
+			Nft::cancel_rental_offer(Some(user).into(), token_id),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nft::cancel_rental_offer(Some(token_owner).into(), token_id));
+		assert!(has_event(RawEvent::RentalOfferCancelled(token_id)));
+		assert!(Nft::rental_offers(token_id).is_none());
+		assert_noop!(
+			Nft::accept_rental(Some(user).into(), token_id),
+			Error::<Test>::NoRentalOffer
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
 	});
 }
 
 #[test]
-fn make_simple_offer_on_auction_should_fail() {
+fn lend_fails_prechecks() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-
-		let reserve_price = 100_000;
+		let (_collection_id, token_id, token_owner) = setup_token();
+		let user = 3;
 
-		assert_ok!(Nft::auction(
+		// not token owner
+		assert_noop!(
+			Nft::lend(Some(user).into(), token_id, user + 1, 10, None),
+			Error::<Test>::NoPermission
+		);
+		// lend to self
+		assert_noop!(
+			Nft::lend(Some(token_owner).into(), token_id, token_owner, 10, None),
+			Error::<Test>::IsTokenOwner
+		);
+		// zero duration
+		assert_noop!(
+			Nft::lend(Some(token_owner).into(), token_id, user, 0, None),
+			Error::<Test>::RentalDurationInvalid
+		);
+		// listed token
+		assert_ok!(Nft::sell(
 			Some(token_owner).into(),
 			token_id,
+			None,
 			PAYMENT_ASSET,
-			reserve_price,
-			Some(System::block_number() + 1),
+			1_000,
 			None,
+			None
 		));
-
-		let next_offer_id = Nft::next_offer_id();
 		assert_noop!(
-			Nft::make_simple_offer(Some(buyer).into(), token_id, offer_amount, PAYMENT_ASSET, None),
-			Error::<Test>::TokenOnAuction
+			Nft::lend(Some(token_owner).into(), token_id, user, 10, None),
+			Error::<Test>::TokenListingProtection
 		);
-
-		// Check storage has not been updated
-		assert_eq!(Nft::next_offer_id(), next_offer_id);
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(next_offer_id), None);
-		// Check funds have not been locked
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
 	});
 }
 
-#[test]
-fn cancel_offer() {
-	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-
-		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
-		assert_ok!(Nft::cancel_offer(Some(buyer).into(), offer_id));
-
-		assert!(has_event(RawEvent::OfferCancelled(offer_id)));
+/// Setup a token in a series with the given `transfer_policy`, return collection id, token id, token owner
+fn setup_token_with_transfer_policy(
+	transfer_policy: TransferPolicy,
+) -> (CollectionId, TokenId, <Test as frame_system::Config>::AccountId) {
+	let collection_owner = 1_u64;
+	let collection_id = setup_collection(collection_owner);
+	let token_owner = 2_u64;
+	let token_id = first_token_id(collection_id);
+	assert_ok!(Nft::mint_series(
+		Some(collection_owner).into(),
+		collection_id,
+		2,
+		Some(token_owner),
+		MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+		None,
+		Some(transfer_policy),
+		None,
+	));
+	assert_eq!(Nft::series_transfer_policy(collection_id, 0), transfer_policy);
 
-		// Check storage has been removed
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(offer_id), None);
-		// Check funds have been unlocked after offer cancelled
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &buyer), initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-	});
+	(collection_id, token_id, token_owner)
 }
 
 #[test]
-fn cancel_offer_multiple_offers() {
+fn soulbound_series_cannot_move() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer_1: u64 = 3;
-		let buyer_2: u64 = 4;
-
-		let offer_amount_1: Balance = 100;
-		let offer_amount_2: Balance = 150;
-		let initial_balance_buyer_1: Balance = 1000;
-		let initial_balance_buyer_2: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_1, PAYMENT_ASSET, initial_balance_buyer_1);
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_2, PAYMENT_ASSET, initial_balance_buyer_2);
-
-		let (offer_id_1, _) = make_new_simple_offer(offer_amount_1, token_id, buyer_1, None);
-		let (offer_id_2, offer_2) = make_new_simple_offer(offer_amount_2, token_id, buyer_2, None);
+		let (_collection_id, token_id, token_owner) = setup_token_with_transfer_policy(TransferPolicy::Soulbound);
 
-		// Can't cancel other offer
 		assert_noop!(
-			Nft::cancel_offer(Some(buyer_1).into(), offer_id_2),
-			Error::<Test>::NotBuyer
+			Nft::transfer(Some(token_owner).into(), token_id, 3),
+			Error::<Test>::TokenNotTransferable
 		);
-		// Can cancel their offer
-		assert_ok!(Nft::cancel_offer(Some(buyer_1).into(), offer_id_1));
-		assert!(has_event(RawEvent::OfferCancelled(offer_id_1)));
-
-		// Check storage has been removed
-		let offer_vector: Vec<OfferId> = vec![offer_id_2];
-		assert_eq!(Nft::token_offers(token_id), offer_vector);
-		assert_eq!(Nft::offers(offer_id_2), Some(OfferType::Simple(offer_2.clone())));
-		assert_eq!(Nft::offers(offer_id_1), None);
-
-		// Check funds have been unlocked after offer cancelled
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_1),
-			initial_balance_buyer_1
+		assert_noop!(
+			Nft::sell(
+				Some(token_owner).into(),
+				token_id,
+				None,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None
+			),
+			Error::<Test>::TokenNotTransferable
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_1), 0);
-		// Check buyer_2 funds have not been unlocked
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_2),
-			initial_balance_buyer_2 - offer_amount_2
+		assert_noop!(
+			Nft::auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+				None
+			),
+			Error::<Test>::TokenNotTransferable
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_2), offer_amount_2);
-	});
-}
-
-#[test]
-fn cancel_offer_not_buyer_should_fail() {
-	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, _) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-
-		let (offer_id, offer) = make_new_simple_offer(offer_amount, token_id, buyer, None);
-		assert_noop!(Nft::cancel_offer(Some(4).into(), offer_id), Error::<Test>::NotBuyer);
+		assert_noop!(
+			Nft::dutch_auction(
+				Some(token_owner).into(),
+				token_id,
+				PAYMENT_ASSET,
+				2_000,
+				1_000,
+				None,
+				None
+			),
+			Error::<Test>::TokenNotTransferable
+		);
+		assert_noop!(
+			Nft::burn(Some(token_owner).into(), token_id),
+			Error::<Test>::TokenNotBurnable
+		);
+		assert_noop!(Nft::revoke(Some(1).into(), token_id), Error::<Test>::TokenNotRevocable);
 
-		// Check storage has not been removed
-		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
-		assert_eq!(Nft::offers(offer_id), Some(OfferType::Simple(offer.clone())));
-		// Check funds have not been unlocked
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+		// offers can't be settled either
+		let buyer = 3;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, 100);
+		let (offer_id, _) = make_new_simple_offer(100, token_id, buyer, None);
+		assert_noop!(
+			Nft::accept_offer(Some(token_owner).into(), offer_id),
+			Error::<Test>::TokenNotTransferable
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
 	});
 }
 
 #[test]
-fn accept_offer() {
+fn burn_only_series() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-
-		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, None);
-		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id));
-		assert!(has_event(RawEvent::OfferAccepted(offer_id)));
+		let (collection_id, token_id, token_owner) = setup_token_with_transfer_policy(TransferPolicy::BurnOnly);
 
-		// Check storage has been removed
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(offer_id), None);
-		// Check funds have been transferred
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+		assert_noop!(
+			Nft::transfer(Some(token_owner).into(), token_id, 3),
+			Error::<Test>::TokenNotTransferable
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount);
+		assert_ok!(Nft::burn(Some(token_owner).into(), token_id));
+		assert!(has_event(RawEvent::Burn(collection_id, 0, vec![0])));
+		assert_eq!(Nft::token_balance(token_owner).get(&(collection_id, 0)), Some(&1));
 	});
 }
 
 #[test]
-fn accept_offer_multiple_offers() {
+fn issuer_revocable_series() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer_1: u64 = 3;
-		let buyer_2: u64 = 4;
-
-		let offer_amount_1: Balance = 100;
-		let offer_amount_2: Balance = 150;
-		let initial_balance_buyer_1: Balance = 1000;
-		let initial_balance_buyer_2: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_1, PAYMENT_ASSET, initial_balance_buyer_1);
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer_2, PAYMENT_ASSET, initial_balance_buyer_2);
-
-		let (offer_id_1, offer_1) = make_new_simple_offer(offer_amount_1, token_id, buyer_1, None);
-		let (offer_id_2, _) = make_new_simple_offer(offer_amount_2, token_id, buyer_2, None);
-
-		// Accept second offer
-		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id_2));
-		assert!(has_event(RawEvent::OfferAccepted(offer_id_2)));
-
-		// Check storage has been removed
-		let offer_vector: Vec<OfferId> = vec![offer_id_1];
-		assert_eq!(Nft::token_offers(token_id), offer_vector);
-		assert_eq!(Nft::offers(offer_id_1), Some(OfferType::Simple(offer_1.clone())));
-		assert_eq!(Nft::offers(offer_id_2), None);
+		let collection_owner = 1;
+		let (collection_id, token_id, token_owner) = setup_token_with_transfer_policy(TransferPolicy::IssuerRevocable);
 
-		// Check funds have been transferred
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_2),
-			initial_balance_buyer_2 - offer_amount_2
+		assert_noop!(
+			Nft::transfer(Some(token_owner).into(), token_id, 3),
+			Error::<Test>::TokenNotTransferable
+		);
+		assert_noop!(
+			Nft::burn(Some(token_owner).into(), token_id),
+			Error::<Test>::TokenNotBurnable
+		);
+		// only the collection owner may revoke
+		assert_noop!(
+			Nft::revoke(Some(token_owner).into(), token_id),
+			Error::<Test>::NoPermission
 		);
+		assert_ok!(Nft::revoke(Some(collection_owner).into(), token_id));
+		assert!(has_event(RawEvent::Burn(collection_id, 0, vec![0])));
+		assert!(!<TokenOwner<Test>>::contains_key((collection_id, 0), 0));
+		assert_eq!(Nft::token_balance(token_owner).get(&(collection_id, 0)), Some(&1));
+		assert_eq!(Nft::series_issuance(collection_id, 0), 1);
+
+		// revoking the last token keeps the series transfer policy
+		assert_ok!(Nft::revoke(Some(collection_owner).into(), (collection_id, 0, 1)));
+		assert!(Nft::token_balance(token_owner).get(&(collection_id, 0)).is_none());
 		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer_1),
-			initial_balance_buyer_1 - offer_amount_1
+			Nft::series_transfer_policy(collection_id, 0),
+			TransferPolicy::IssuerRevocable
+		);
+		assert_noop!(
+			Nft::revoke(Some(collection_owner).into(), (collection_id, 0, 1)),
+			Error::<Test>::NoToken
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_1), offer_amount_1);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer_2), 0);
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), offer_amount_2);
 
-		// Accept first offer should fail as token_owner is no longer owner
+		// tokens minted into the series later are still bound by the policy
+		assert_ok!(Nft::mint_additional(
+			Some(collection_owner).into(),
+			collection_id,
+			0,
+			1,
+			Some(token_owner)
+		));
 		assert_noop!(
-			Nft::accept_offer(Some(token_owner).into(), offer_id_1),
-			Error::<Test>::NoPermission
+			Nft::transfer(Some(token_owner).into(), (collection_id, 0, 2), 3),
+			Error::<Test>::TokenNotTransferable
 		);
 	});
 }
 
 #[test]
-fn accept_offer_pays_marketplace_royalties() {
+fn update_token_attributes() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
+		let collection_owner = 1;
+		let (collection_id, token_id, token_owner) = setup_token();
+		let level = NFTAttributeValue::U32(7);
+		let name = NFTAttributeValue::String(b"Sword".to_vec());
 
-		let marketplace_account = 4;
-		let entitlements: Permill = Permill::from_float(0.1);
-		let marketplace_id = Nft::next_marketplace_id();
-		assert_ok!(Nft::register_marketplace(
-			Some(marketplace_account).into(),
-			None,
-			entitlements
+		// the collection owner may update attributes by default
+		assert_noop!(
+			Nft::update_token_attributes(
+				Some(token_owner).into(),
+				token_id,
+				vec![(b"level".to_vec(), Some(level.clone()))]
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nft::update_token_attributes(
+			Some(collection_owner).into(),
+			token_id,
+			vec![
+				(b"level".to_vec(), Some(level.clone())),
+				(b"name".to_vec(), Some(name.clone()))
+			],
 		));
+		assert!(has_event(RawEvent::TokenAttributeSet(
+			token_id,
+			b"level".to_vec(),
+			level.clone()
+		)));
+		assert_eq!(
+			Nft::token_info(collection_id, 0, 0).token_attributes,
+			vec![(b"level".to_vec(), level.clone()), (b"name".to_vec(), name.clone())]
+		);
 
-		let (offer_id, _) = make_new_simple_offer(offer_amount, token_id, buyer, Some(marketplace_id));
-		assert_ok!(Nft::accept_offer(Some(token_owner).into(), offer_id));
-		assert!(has_event(RawEvent::OfferAccepted(offer_id)));
+		// hand control to the token owner
+		assert_ok!(Nft::set_attribute_policy(
+			Some(collection_owner).into(),
+			collection_id,
+			0,
+			AttributePolicy::OwnerOnly
+		));
+		assert!(has_event(RawEvent::AttributePolicySet(
+			collection_id,
+			0,
+			AttributePolicy::OwnerOnly
+		)));
+		assert_noop!(
+			Nft::update_token_attributes(Some(collection_owner).into(), token_id, vec![(b"name".to_vec(), None)]),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nft::update_token_attributes(
+			Some(token_owner).into(),
+			token_id,
+			vec![(b"name".to_vec(), None)]
+		));
+		assert!(has_event(RawEvent::TokenAttributeRemoved(token_id, b"name".to_vec())));
+		assert_eq!(Nft::token_attributes(token_id).len(), 1);
 
-		// Check storage has been removed
-		let empty_offer_vector: Vec<OfferId> = vec![];
-		assert_eq!(Nft::token_offers(token_id), empty_offer_vector);
-		assert_eq!(Nft::offers(offer_id), None);
-		// Check funds have been transferred with royalties
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+		// frozen is permanent
+		assert_ok!(Nft::set_attribute_policy(
+			Some(collection_owner).into(),
+			collection_id,
+			0,
+			AttributePolicy::Frozen
+		));
+		assert_noop!(
+			Nft::update_token_attributes(Some(token_owner).into(), token_id, vec![(b"level".to_vec(), None)]),
+			Error::<Test>::AttributesFrozen
 		);
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &marketplace_account),
-			entitlements * offer_amount
+		assert_noop!(
+			Nft::set_attribute_policy(
+				Some(collection_owner).into(),
+				collection_id,
+				0,
+				AttributePolicy::IssuerOnly
+			),
+			Error::<Test>::AttributesFrozen
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), 0);
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &token_owner),
-			offer_amount - (entitlements * offer_amount)
+
+		// attributes are removed with the token, the frozen policy outlives the last token
+		assert_ok!(Nft::burn(Some(token_owner).into(), token_id));
+		assert!(!TokenAttributes::contains_key(token_id));
+		assert_eq!(Nft::series_attribute_policy(collection_id, 0), AttributePolicy::Frozen);
+		assert_noop!(
+			Nft::set_attribute_policy(
+				Some(collection_owner).into(),
+				collection_id,
+				0,
+				AttributePolicy::IssuerOnly
+			),
+			Error::<Test>::AttributesFrozen
 		);
 	});
 }
 
 #[test]
-fn accept_offer_not_token_owner_should_fail() {
+fn update_token_attributes_fails_prechecks() {
 	ExtBuilder::default().build().execute_with(|| {
-		let (_, token_id, token_owner) = setup_token();
-		let buyer: u64 = 3;
-		let offer_amount: Balance = 100;
-		let initial_balance_buyer: Balance = 1000;
-		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, initial_balance_buyer);
-
-		let (offer_id, offer) = make_new_simple_offer(offer_amount, token_id, buyer, None);
-		assert_noop!(Nft::accept_offer(Some(4).into(), offer_id), Error::<Test>::NoPermission);
+		let collection_owner = 1;
+		let (collection_id, token_id, _token_owner) = setup_token();
 
-		// Check storage has not been removed
-		assert_eq!(Nft::token_offers(token_id), vec![offer_id]);
-		assert_eq!(Nft::offers(offer_id), Some(OfferType::Simple(offer.clone())));
-		// Check funds have not been transferred
-		assert_eq!(
-			GenericAsset::free_balance(PAYMENT_ASSET, &buyer),
-			initial_balance_buyer - offer_amount
+		// no token
+		assert_noop!(
+			Nft::update_token_attributes(
+				Some(collection_owner).into(),
+				(collection_id, 0, 1),
+				vec![(b"level".to_vec(), Some(NFTAttributeValue::U8(1)))]
+			),
+			Error::<Test>::NoToken
+		);
+		// invalid keys
+		assert_noop!(
+			Nft::update_token_attributes(
+				Some(collection_owner).into(),
+				token_id,
+				vec![(vec![], Some(NFTAttributeValue::U8(1)))]
+			),
+			Error::<Test>::InvalidAttributeKey
+		);
+		assert_noop!(
+			Nft::update_token_attributes(
+				Some(collection_owner).into(),
+				token_id,
+				vec![(vec![0xff, 0xfe], Some(NFTAttributeValue::U8(1)))]
+			),
+			Error::<Test>::InvalidAttributeKey
+		);
+		// value too long
+		assert_noop!(
+			Nft::update_token_attributes(
+				Some(collection_owner).into(),
+				token_id,
+				vec![(
+					b"name".to_vec(),
+					Some(NFTAttributeValue::String(vec![
+						b'a';
+						<Test as Config>::MaxAttributeLength::get()
+							as usize + 1
+					]))
+				)]
+			),
+			Error::<Test>::MaxAttributeLength
+		);
+		// too many attributes
+		let updates = (0..=MAX_SCHEMA_FIELDS)
+			.map(|i| (format!("key{}", i).into_bytes(), Some(NFTAttributeValue::U32(i))))
+			.collect();
+		assert_noop!(
+			Nft::update_token_attributes(Some(collection_owner).into(), token_id, updates),
+			Error::<Test>::SchemaMaxAttributes
+		);
+		// no series
+		assert_noop!(
+			Nft::set_attribute_policy(Some(collection_owner).into(), collection_id, 1, AttributePolicy::Frozen),
+			Error::<Test>::NoSeries
 		);
-		assert_eq!(GenericAsset::reserved_balance(PAYMENT_ASSET, &buyer), offer_amount);
-		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), 0);
 	});
 }
 
 #[test]
-fn transfer_changes_token_balance() {
+fn mint_series_with_max_issuance() {
 	ExtBuilder::default().build().execute_with(|| {
 		let collection_owner = 1_u64;
 		let collection_id = setup_collection(collection_owner);
-		let series_id: SeriesId = 0;
 		let token_owner = 2_u64;
-		let new_owner = 3_u64;
-		let initial_quantity: u32 = 1;
-		// Create BTreeMaps for both owners
-		let mut owner_map = BTreeMap::new();
-		let mut new_owner_map = BTreeMap::new();
 
-		// Mint 1 token
+		// max issuance must be non-zero and cover the initial quantity
+		for max_issuance in [0, 2] {
+			assert_noop!(
+				Nft::mint_series(
+					Some(collection_owner).into(),
+					collection_id,
+					3,
+					Some(token_owner),
+					MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+					None,
+					None,
+					Some(max_issuance),
+				),
+				Error::<Test>::InvalidMaxIssuance
+			);
+		}
+
+		let series_id = Nft::next_series_id(collection_id);
 		assert_ok!(Nft::mint_series(
 			Some(collection_owner).into(),
 			collection_id,
-			initial_quantity,
+			3,
 			Some(token_owner),
 			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
+			None,
+			Some(5),
 		));
+		assert_eq!(Nft::series_max_issuance(collection_id, series_id), Some(5));
+		assert_eq!(
+			Nft::collection_info::<AccountId>(collection_id)
+				.unwrap()
+				.remaining_supply,
+			vec![(series_id, 2)]
+		);
 
-		owner_map.insert((collection_id, series_id), initial_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
-		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
-
-		// Mint an additional 2 tokens
-		let additional_quantity: u32 = 2;
+		assert_noop!(
+			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id, 3, None),
+			Error::<Test>::MaxIssuanceReached
+		);
 		assert_ok!(Nft::mint_additional(
 			Some(collection_owner).into(),
 			collection_id,
 			series_id,
-			additional_quantity,
-			Some(token_owner),
+			2,
+			None
 		));
+		assert_eq!(
+			Nft::collection_info::<AccountId>(collection_id)
+				.unwrap()
+				.remaining_supply,
+			vec![(series_id, 0)]
+		);
 
-		owner_map.insert((collection_id, series_id), initial_quantity + additional_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
-		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
+		// burned tokens are not re-issued
+		assert_ok!(Nft::burn(Some(token_owner).into(), (collection_id, series_id, 0)));
+		assert_noop!(
+			Nft::mint_additional(Some(collection_owner).into(), collection_id, series_id, 1, None),
+			Error::<Test>::MaxIssuanceReached
+		);
+	});
+}
+
+#[test]
+fn public_mint() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let (collection_id, _token_id, _token_owner) = setup_token();
+		let series_id = 0;
+		let minter = 3_u64;
+		let price: Balance = 10;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&minter, PAYMENT_ASSET, 100);
+		let public_mint = PublicMint {
+			start: 5,
+			end: Some(10),
+			payment_asset: PAYMENT_ASSET,
+			price,
+			max_per_account: Some(3),
+		};
+
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, series_id, 1),
+			Error::<Test>::PublicMintNotOpen
+		);
+		assert_noop!(
+			Nft::set_public_mint(Some(minter).into(), collection_id, series_id, Some(public_mint.clone())),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nft::set_public_mint(
+				Some(collection_owner).into(),
+				collection_id,
+				series_id,
+				Some(PublicMint {
+					end: Some(5),
+					..public_mint.clone()
+				})
+			),
+			Error::<Test>::InvalidPublicMint
+		);
+		assert_ok!(Nft::set_public_mint(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			Some(public_mint.clone())
+		));
+		assert!(has_event(RawEvent::PublicMintUpdated(collection_id, series_id)));
+
+		// not open yet
+		System::set_block_number(4);
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, series_id, 1),
+			Error::<Test>::PublicMintNotOpen
+		);
+
+		System::set_block_number(5);
+		assert_ok!(Nft::public_mint(Some(minter).into(), collection_id, series_id, 2));
+		assert!(has_event(RawEvent::CreateTokens(collection_id, series_id, 2, minter)));
+		assert_eq!(Nft::token_owner((collection_id, series_id), 1), minter);
+		assert_eq!(Nft::token_owner((collection_id, series_id), 2), minter);
+		assert_eq!(Nft::public_mint_count((collection_id, series_id), minter), 2);
+		// mint price paid to the collection owner
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &minter), 100 - 2 * price);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &collection_owner), 2 * price);
+
+		// per account limit
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, series_id, 2),
+			Error::<Test>::PublicMintLimitReached
+		);
+
+		// closed
+		System::set_block_number(10);
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, series_id, 1),
+			Error::<Test>::PublicMintNotOpen
+		);
 
-		// Transfer 2 tokens
-		let tokens = vec![0_u32, 1_u32];
-		let transfer_quantity: u32 = tokens.len() as u32;
-		assert_ok!(Nft::transfer_batch(
-			Some(token_owner).into(),
+		// removed
+		System::set_block_number(6);
+		assert_ok!(Nft::set_public_mint(
+			Some(collection_owner).into(),
 			collection_id,
 			series_id,
-			tokens.clone(),
-			new_owner,
+			None
 		));
-
-		owner_map.insert(
-			(collection_id, series_id),
-			initial_quantity + additional_quantity - transfer_quantity,
+		assert!(Nft::series_public_mint(collection_id, series_id).is_none());
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, series_id, 1),
+			Error::<Test>::PublicMintNotOpen
 		);
-		new_owner_map.insert((collection_id, series_id), transfer_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
-		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
 	});
 }
 
 #[test]
-fn transfer_many_tokens_changes_token_balance() {
+fn public_mint_respects_max_issuance() {
 	ExtBuilder::default().build().execute_with(|| {
 		let collection_owner = 1_u64;
 		let collection_id = setup_collection(collection_owner);
-		let series_id: SeriesId = 0;
-		let token_owner = 2_u64;
-		let new_owner = 3_u64;
-		let initial_quantity: u32 = 100;
-		// Create BTreeMaps for both owners
-		let mut owner_map = BTreeMap::new();
-		let mut new_owner_map = BTreeMap::new();
-
-		// Mint 1 token
+		let minter = 3_u64;
 		assert_ok!(Nft::mint_series(
 			Some(collection_owner).into(),
 			collection_id,
-			initial_quantity,
-			Some(token_owner),
+			0,
+			None,
 			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 			None,
+			None,
+			Some(2),
+		));
+		assert_ok!(Nft::set_public_mint(
+			Some(collection_owner).into(),
+			collection_id,
+			0,
+			Some(PublicMint {
+				start: 0,
+				end: None,
+				payment_asset: PAYMENT_ASSET,
+				price: 0,
+				max_per_account: None,
+			})
 		));
 
-		owner_map.insert((collection_id, series_id), initial_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
-		assert_eq!(Nft::token_balance(new_owner), new_owner_map);
-
-		for i in 0_u32..initial_quantity {
-			// Transfer token
-			let token_id: TokenId = (collection_id, series_id, i);
-			assert_ok!(Nft::transfer(Some(token_owner).into(), token_id, new_owner,));
-
-			// Check storage
-			let changed_quantity = i + 1;
-			if changed_quantity == initial_quantity {
-				assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
-			} else {
-				owner_map.insert((collection_id, series_id), initial_quantity - changed_quantity);
-				assert_eq!(Nft::token_balance(token_owner), owner_map);
-			}
-			new_owner_map.insert((collection_id, series_id), changed_quantity);
-			assert_eq!(Nft::token_balance(new_owner), new_owner_map);
-		}
+		assert_noop!(
+			Nft::public_mint(Some(minter).into(), collection_id, 0, 3),
+			Error::<Test>::MaxIssuanceReached
+		);
+		assert_ok!(Nft::public_mint(Some(minter).into(), collection_id, 0, 2));
+		assert_eq!(Nft::token_balance(minter).get(&(collection_id, 0)), Some(&2));
 	});
 }
 
 #[test]
-fn update_token_balance_works() {
+fn set_series_royalties_is_delayed() {
 	ExtBuilder::default().build().execute_with(|| {
-		// Runs update_token_balance on a series
 		let collection_owner = 1_u64;
-		let collection_id = setup_collection(collection_owner);
-		let series_id: SeriesId = 0;
-		let token_owner = 2_u64;
-		let quantity: TokenCount = 10;
-
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner).into(),
-			collection_id,
-			1,
-			Some(token_owner),
-			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
-			None,
-		));
+		let (collection_id, _token_id, token_owner) = setup_token();
+		let series_id = 0;
+		let royalties_schedule = RoyaltiesSchedule {
+			entitlements: vec![(collection_owner, Permill::from_percent(10))],
+		};
 
-		let mut token_owners: Vec<u64> = vec![];
-		for i in 1..quantity {
-			let token_owner: u64 = collection_owner + 1 + (i as u64);
-			token_owners.push(token_owner);
-			assert_ok!(Nft::mint_additional(
+		assert_noop!(
+			Nft::set_series_royalties(
+				Some(token_owner).into(),
+				collection_id,
+				series_id,
+				Some(royalties_schedule.clone())
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nft::set_series_royalties(
 				Some(collection_owner).into(),
 				collection_id,
 				series_id,
-				1,
-				Some(token_owner),
-			));
-
-			// Remove token_balance storage to simulate before EVM update
-			TokenBalance::<Test>::remove(token_owner);
-			assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
-		}
+				Some(RoyaltiesSchedule {
+					entitlements: vec![(collection_owner, Permill::from_percent(101))],
+				})
+			),
+			Error::<Test>::RoyaltiesInvalid
+		);
 
-		// Update token balances to fix storage
-		assert_ok!(Nft::update_token_balance(
-			Some(100_u64).into(),
+		assert_ok!(Nft::set_series_royalties(
+			Some(collection_owner).into(),
 			collection_id,
 			series_id,
+			Some(royalties_schedule.clone())
 		));
-		// event logged
-		assert!(has_event(RawEvent::TokenBalancesUpdated(collection_id, series_id)));
+		let effective_block = System::block_number() + RoyaltiesUpdateDelay::get();
+		assert!(has_event(RawEvent::SeriesRoyaltiesUpdateScheduled(
+			collection_id,
+			series_id,
+			effective_block
+		)));
+		assert!(Nft::series_royalties(collection_id, series_id).is_none());
 
-		// Check that storage for each owner is correct
-		// Initial owner
-		let mut owner_map = BTreeMap::new();
-		owner_map.insert((collection_id, series_id), 1);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
-		// additional owners
-		for owner in token_owners {
-			let mut owner_map = BTreeMap::new();
-			owner_map.insert((collection_id, series_id), 1);
-			assert_eq!(Nft::token_balance(owner), owner_map);
-		}
-	});
-}
+		// not yet in effect
+		Nft::on_initialize(effective_block - 1);
+		assert!(Nft::series_royalties(collection_id, series_id).is_none());
 
-#[test]
-fn update_token_balance_no_collection_should_fail() {
-	ExtBuilder::default().build().execute_with(|| {
-		// Update token balances on no collection
-		assert_noop!(
-			Nft::update_token_balance(Some(100_u64).into(), 0, 0),
-			Error::<Test>::NoCollection
+		Nft::on_initialize(effective_block);
+		assert_eq!(
+			Nft::series_royalties(collection_id, series_id),
+			Some(royalties_schedule)
 		);
+		assert!(has_event(RawEvent::SeriesRoyaltiesUpdated(collection_id, series_id)));
+		assert!(Nft::pending_series_royalties(collection_id, series_id).is_none());
 	});
 }
 
 #[test]
-fn update_token_balance_no_series_should_fail() {
+fn set_series_royalties_replaces_pending_update() {
 	ExtBuilder::default().build().execute_with(|| {
-		// Update token balances on no series
-		let collection_id = setup_collection(1_u64);
+		let collection_owner = 1_u64;
+		let royalties_schedule = RoyaltiesSchedule {
+			entitlements: vec![(collection_owner, Permill::from_percent(10))],
+		};
+		let (collection_id, _token_id, _token_owner) = setup_token_with_royalties(royalties_schedule.clone(), 1);
+		let series_id = 0;
+		let first_effective_block = System::block_number() + RoyaltiesUpdateDelay::get();
+		assert_ok!(Nft::set_series_royalties(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			Some(RoyaltiesSchedule {
+				entitlements: vec![(collection_owner, Permill::from_percent(20))],
+			})
+		));
 
-		assert_noop!(
-			Nft::update_token_balance(Some(100_u64).into(), collection_id, 0),
-			Error::<Test>::NoSeries
+		// remove the series royalties instead
+		System::set_block_number(5);
+		assert_ok!(Nft::set_series_royalties(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			None
+		));
+		assert!(!Nft::series_royalties_update_schedule(
+			first_effective_block,
+			(collection_id, series_id)
+		));
+
+		Nft::on_initialize(first_effective_block);
+		assert_eq!(
+			Nft::series_royalties(collection_id, series_id),
+			Some(royalties_schedule)
 		);
+
+		Nft::on_initialize(5 + RoyaltiesUpdateDelay::get());
+		assert!(Nft::series_royalties(collection_id, series_id).is_none());
 	});
 }
 
 #[test]
-fn update_token_balance_multiple_collections() {
+fn token_royalties_override() {
 	ExtBuilder::default().build().execute_with(|| {
-		// Runs update_token_balance on a series while checking that it doesn't affect other collections
 		let collection_owner = 1_u64;
-		let collection_owner_2 = 200_u64;
-		let collection_id = setup_collection(collection_owner);
-		let collection_id_2 = setup_collection(collection_owner_2);
-		let series_id: SeriesId = 0;
-		let series_id_2: SeriesId = 0;
-		let token_owner = 2_u64;
+		let beneficiary = 11_u64;
+		let series_royalties = RoyaltiesSchedule {
+			entitlements: vec![(collection_owner, Permill::from_percent(10))],
+		};
+		let (collection_id, token_id, token_owner) = setup_token_with_royalties(series_royalties.clone(), 2);
+		let token_royalties = RoyaltiesSchedule {
+			entitlements: vec![(beneficiary, Permill::from_percent(20))],
+		};
 
-		// Mint first collection
-		let first_collection_quantity: TokenCount = 3;
-		assert_ok!(Nft::mint_series(
+		assert_noop!(
+			Nft::set_token_royalties(Some(token_owner).into(), token_id, Some(token_royalties.clone())),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nft::set_token_royalties(
+				Some(collection_owner).into(),
+				(collection_id, 0, 2),
+				Some(token_royalties.clone())
+			),
+			Error::<Test>::NoToken
+		);
+		assert_ok!(Nft::set_token_royalties(
 			Some(collection_owner).into(),
-			collection_id,
-			first_collection_quantity,
-			Some(token_owner),
-			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
-			None,
+			token_id,
+			Some(token_royalties.clone())
 		));
-		// Remove token_balance storage to simulate before EVM update
-		TokenBalance::<Test>::remove(token_owner);
-		assert_eq!(Nft::token_balance(token_owner), BTreeMap::new());
+		let effective_block = System::block_number() + RoyaltiesUpdateDelay::get();
+		assert!(has_event(RawEvent::TokenRoyaltiesUpdateScheduled(
+			token_id,
+			effective_block
+		)));
+		// the override is delayed so it can't front-run a pending sale
+		assert_eq!(
+			Nft::token_info(collection_id, 0, 0).royalties,
+			series_royalties.entitlements
+		);
+		assert_eq!(
+			Nft::pending_token_royalties(token_id),
+			Some((effective_block, Some(token_royalties.clone())))
+		);
 
-		// Mint second collection with same owner
-		let second_collection_quantity: TokenCount = 5;
-		assert_ok!(Nft::mint_series(
-			Some(collection_owner_2).into(),
-			collection_id_2,
-			second_collection_quantity,
-			Some(token_owner),
-			MetadataScheme::IpfsDir(b"<CID>".to_vec()),
+		Nft::on_initialize(effective_block);
+		assert!(has_event(RawEvent::TokenRoyaltiesUpdated(token_id)));
+		assert!(Nft::pending_token_royalties(token_id).is_none());
+		assert!(!Nft::token_royalties_update_schedule(effective_block, token_id));
+		assert_eq!(
+			Nft::token_info(collection_id, 0, 0).royalties,
+			token_royalties.entitlements
+		);
+		assert_eq!(
+			Nft::token_info(collection_id, 0, 1).royalties,
+			series_royalties.entitlements
+		);
+
+		// tokens with different individual royalties cannot be bundled
+		assert_noop!(
+			Nft::sell_bundle(
+				Some(token_owner).into(),
+				vec![token_id, (collection_id, 0, 1)],
+				None,
+				PAYMENT_ASSET,
+				1_000,
+				None,
+				None,
+			),
+			Error::<Test>::RoyaltiesProtection
+		);
+
+		// the token royalties are paid on sale
+		let buyer = 5_u64;
+		let sale_price: Balance = 1_000;
+		let _ = <Test as Config>::MultiCurrency::deposit_creating(&buyer, PAYMENT_ASSET, sale_price);
+		let listing_id = Nft::next_listing_id();
+		assert_ok!(Nft::sell(
+			Some(token_owner).into(),
+			token_id,
 			None,
+			PAYMENT_ASSET,
+			sale_price,
+			None,
+			None
 		));
-		// Check storage for token owner only shows second collection
-		let mut owner_map = BTreeMap::new();
-		owner_map.insert((collection_id_2, series_id_2), second_collection_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		// listed tokens are protected
+		assert_noop!(
+			Nft::set_token_royalties(Some(collection_owner).into(), token_id, None),
+			Error::<Test>::TokenListingProtection
+		);
+		assert_ok!(Nft::buy(Some(buyer).into(), listing_id));
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &beneficiary), 200);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &collection_owner), 0);
+		assert_eq!(GenericAsset::free_balance(PAYMENT_ASSET, &token_owner), 800);
+
+		// removed with the token, along with any scheduled update
+		assert_ok!(Nft::set_token_royalties(Some(collection_owner).into(), token_id, None));
+		assert_ok!(Nft::burn(Some(buyer).into(), token_id));
+		assert!(Nft::token_royalties(token_id).is_none());
+		assert!(Nft::pending_token_royalties(token_id).is_none());
+	});
+}
 
-		// Update token balances to fix storage
-		assert_ok!(Nft::update_token_balance(
-			Some(100_u64).into(),
-			collection_id,
-			series_id,
-		));
+#[test]
+fn royalties_updates_are_bounded_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		let collection_owner = 1_u64;
+		let update_count = MAX_ROYALTIES_UPDATES_PER_BLOCK + 5;
+		let (collection_id, token_id, _token_owner) =
+			setup_token_with_royalties(RoyaltiesSchedule::default(), update_count);
+		let token_royalties = RoyaltiesSchedule {
+			entitlements: vec![(collection_owner, Permill::from_percent(10))],
+		};
 
-		// Check that storage for token owner is correct
-		let mut owner_map = BTreeMap::new();
-		owner_map.insert((collection_id, series_id), first_collection_quantity);
-		owner_map.insert((collection_id_2, series_id_2), second_collection_quantity);
-		assert_eq!(Nft::token_balance(token_owner), owner_map);
+		for serial_number in 0..update_count {
+			assert_ok!(Nft::set_token_royalties(
+				Some(collection_owner).into(),
+				(collection_id, token_id.1, serial_number),
+				Some(token_royalties.clone())
+			));
+		}
+		let effective_block = System::block_number() + RoyaltiesUpdateDelay::get();
+
+		// only the maximum is applied, the remainder is carried over
+		let _ = Nft::on_initialize(effective_block);
+		assert_eq!(
+			PendingTokenRoyalties::<Test>::iter().count(),
+			(update_count - MAX_ROYALTIES_UPDATES_PER_BLOCK) as usize
+		);
+		assert_eq!(TokenRoyalties::<Test>::iter().count(), MAX_ROYALTIES_UPDATES_PER_BLOCK as usize);
+		assert_eq!(Nft::royalties_update_backlog(), vec![effective_block]);
+
+		let _ = Nft::on_initialize(effective_block + 1);
+		assert_eq!(PendingTokenRoyalties::<Test>::iter().count(), 0);
+		assert_eq!(TokenRoyalties::<Test>::iter().count(), update_count as usize);
+		assert!(Nft::royalties_update_backlog().is_empty());
 	});
 }
@@ -16,13 +16,16 @@
 //! NFT module types
 
 use crate::Config;
-use cennznet_primitives::types::{AssetId, Balance, BlockNumber, TokenId};
+use cennznet_primitives::types::{AssetId, Balance, BlockNumber, CollectionId, SeriesId, TokenCount, TokenId};
 use codec::{Decode, Encode};
 use crml_support::MultiCurrency;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize, Serializer};
-use sp_runtime::{PerThing, Permill};
+use sp_runtime::{
+	traits::{One, SaturatedConversion, Saturating},
+	PerThing, Permill, Perquintill,
+};
 use sp_std::prelude::*;
 
 // Counts enum variants at compile time
@@ -30,24 +33,85 @@ use variant_count::VariantCount;
 
 // Time before auction ends that auction is extended if a bid is placed
 pub const AUCTION_EXTENSION_PERIOD: BlockNumber = 40;
+// Maximum soft close window and extension period an auction may set, bounding how long bids stay reserved
+pub const MAX_AUCTION_EXTENSION_PERIOD: BlockNumber = 7_200;
 
 /// OfferId type used to distinguish different offers on NFTs
 pub type OfferId = u64;
 
 /// Holds information relating to NFT offers
 #[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
-pub struct SimpleOffer<AccountId> {
+pub struct SimpleOffer<AccountId, BlockNumber> {
 	pub token_id: TokenId,
 	pub asset_id: AssetId,
 	pub amount: Balance,
 	pub buyer: AccountId,
 	pub marketplace_id: Option<MarketplaceId>,
+	/// If set, the offer is withdrawn and funds released at this block
+	pub expiry: Option<BlockNumber>,
 }
 
+/// Holds information relating to offers on any token within a collection or series
 #[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
-pub enum OfferType<AccountId> {
-	Simple(SimpleOffer<AccountId>),
+pub struct CollectionOffer<AccountId, BlockNumber> {
+	pub collection_id: CollectionId,
+	/// If set, only tokens from this series may be sold to the offer
+	pub series_id: Option<SeriesId>,
+	pub asset_id: AssetId,
+	pub amount: Balance,
+	pub buyer: AccountId,
+	pub marketplace_id: Option<MarketplaceId>,
+	/// If set, the offer is withdrawn and funds released at this block
+	pub expiry: Option<BlockNumber>,
+}
+
+/// Holds information relating to offers on any token within a collection having the given series attribute
+#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+pub struct AttributeOffer<AccountId, BlockNumber> {
+	pub collection_id: CollectionId,
+	pub attribute: NFTAttributeValue,
+	pub asset_id: AssetId,
+	pub amount: Balance,
+	pub buyer: AccountId,
+	pub marketplace_id: Option<MarketplaceId>,
+	/// If set, the offer is withdrawn and funds released at this block
+	pub expiry: Option<BlockNumber>,
+}
+
+#[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
+pub enum OfferType<AccountId, BlockNumber> {
+	Simple(SimpleOffer<AccountId, BlockNumber>),
+	Collection(CollectionOffer<AccountId, BlockNumber>),
+	Attribute(AttributeOffer<AccountId, BlockNumber>),
 }
+
+impl<AccountId, BlockNumber: Copy> OfferType<AccountId, BlockNumber> {
+	/// The account which made the offer
+	pub fn buyer(&self) -> &AccountId {
+		match self {
+			OfferType::Simple(offer) => &offer.buyer,
+			OfferType::Collection(offer) => &offer.buyer,
+			OfferType::Attribute(offer) => &offer.buyer,
+		}
+	}
+	/// The asset and amount reserved by the offer
+	pub fn payment(&self) -> (AssetId, Balance) {
+		match self {
+			OfferType::Simple(offer) => (offer.asset_id, offer.amount),
+			OfferType::Collection(offer) => (offer.asset_id, offer.amount),
+			OfferType::Attribute(offer) => (offer.asset_id, offer.amount),
+		}
+	}
+	/// The block at which the offer expires, if any
+	pub fn expiry(&self) -> Option<BlockNumber> {
+		match self {
+			OfferType::Simple(offer) => offer.expiry,
+			OfferType::Collection(offer) => offer.expiry,
+			OfferType::Attribute(offer) => offer.expiry,
+		}
+	}
+}
+
 /// Denotes the metadata URI referencing scheme used by a series
 /// Enable token metadata URI construction by clients
 #[derive(Decode, Encode, Debug, Clone, PartialEq, TypeInfo)]
@@ -131,7 +195,7 @@ pub type NFTAttributeTypeId = u8;
 /// Describes the data structure of an NFT class (attribute name, attribute type)
 pub type NFTSchema = Vec<(NFTAttributeName, NFTAttributeTypeId)>;
 
-/// Contains information of a collection (collection name, collection owner, royalties)
+/// Contains information of a collection (collection name, collection owner, royalties, remaining supply)
 #[derive(Default, Debug, Clone, Encode, Decode, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CollectionInfo<AccountId> {
@@ -140,6 +204,8 @@ pub struct CollectionInfo<AccountId> {
 	pub owner: AccountId,
 	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_royalties"))]
 	pub royalties: Vec<(AccountId, Permill)>,
+	/// Tokens left to mint for each series with a max issuance (series id, remaining)
+	pub remaining_supply: Vec<(SeriesId, TokenCount)>,
 }
 
 #[cfg(feature = "std")]
@@ -171,6 +237,39 @@ pub struct TokenInfo<AccountId> {
 	pub owner: AccountId,
 	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_royalties"))]
 	pub royalties: Vec<(AccountId, Permill)>,
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_token_attributes"))]
+	pub token_attributes: Vec<(AttributeKey, NFTAttributeValue)>,
+}
+
+#[cfg(feature = "std")]
+pub fn serialize_token_attributes<S: Serializer>(
+	attributes: &Vec<(AttributeKey, NFTAttributeValue)>,
+	s: S,
+) -> Result<S::Ok, S::Error> {
+	use serde::ser::SerializeMap;
+	let mut map = s.serialize_map(Some(attributes.len()))?;
+	for (key, value) in attributes.iter() {
+		let key = core::str::from_utf8(key).map_err(|_| serde::ser::Error::custom("Byte vec not UTF-8"))?;
+		map.serialize_entry(key, value)?;
+	}
+	map.end()
+}
+
+/// Determines who may update the per-token attributes of a series
+#[derive(Decode, Encode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum AttributePolicy {
+	/// Only the collection owner may update token attributes
+	IssuerOnly,
+	/// Only the token owner may update token attributes
+	OwnerOnly,
+	/// Token attributes may no longer be updated, this is permanent
+	Frozen,
+}
+
+impl Default for AttributePolicy {
+	fn default() -> Self {
+		AttributePolicy::IssuerOnly
+	}
 }
 
 /// Reason for an NFT being locked (un-transferrable)
@@ -178,6 +277,92 @@ pub struct TokenInfo<AccountId> {
 pub enum TokenLockReason {
 	/// Token is listed for sale
 	Listed(ListingId),
+	/// Token is rented out, usage rights belong to the renter until the rental ends
+	Rented,
+}
+
+/// Determines how tokens of a series may change hands
+#[derive(Decode, Encode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum TransferPolicy {
+	/// Tokens may be transferred, sold and burned freely
+	Transferable,
+	/// Tokens are permanently bound to their owner, they may not be transferred or burned
+	Soulbound,
+	/// Tokens may not be transferred but may be burned by their owner
+	BurnOnly,
+	/// Tokens may not be transferred, the collection owner may revoke (burn) them
+	IssuerRevocable,
+}
+
+impl Default for TransferPolicy {
+	fn default() -> Self {
+		TransferPolicy::Transferable
+	}
+}
+
+impl TransferPolicy {
+	/// Whether tokens may change owner via transfer, sale or offer
+	pub fn is_transferable(&self) -> bool {
+		*self == TransferPolicy::Transferable
+	}
+	/// Whether tokens may be burned by their owner
+	pub fn is_burnable(&self) -> bool {
+		matches!(self, TransferPolicy::Transferable | TransferPolicy::BurnOnly)
+	}
+}
+
+/// A public mint of a series, allowing any account to mint tokens for a price
+#[derive(Decode, Encode, Debug, Clone, Eq, PartialEq, TypeInfo)]
+pub struct PublicMint<BlockNumber> {
+	/// The block the public mint opens
+	pub start: BlockNumber,
+	/// The block the public mint closes, if any
+	pub end: Option<BlockNumber>,
+	/// The asset to pay the mint price with
+	pub payment_asset: AssetId,
+	/// The price per token, paid to the collection owner
+	pub price: Balance,
+	/// The max. number of tokens an account may mint, if any
+	pub max_per_account: Option<TokenCount>,
+}
+
+impl<BlockNumber: PartialOrd> PublicMint<BlockNumber> {
+	/// Whether the mint window is well formed i.e. it closes after it opens
+	pub fn validate(&self) -> bool {
+		match &self.end {
+			Some(end) => *end > self.start,
+			None => true,
+		}
+	}
+	/// Whether the public mint is open at block `now`
+	pub fn is_open(&self, now: BlockNumber) -> bool {
+		now >= self.start && self.end.as_ref().map_or(true, |end| now < *end)
+	}
+}
+
+/// An active rental of a token, the user holds usage rights until `expiry`
+#[derive(Decode, Encode, Debug, Clone, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TokenRental<AccountId, BlockNumber> {
+	/// The account with usage rights of the token
+	pub user: AccountId,
+	/// The block at which the rental ends and usage rights return to the owner
+	pub expiry: BlockNumber,
+}
+
+/// A rental offered by a token owner, awaiting acceptance by the user
+#[derive(Decode, Encode, Debug, Clone, Eq, PartialEq, TypeInfo)]
+pub struct RentalOffer<AccountId, BlockNumber> {
+	/// The token owner offering the rental
+	pub lender: AccountId,
+	/// The account allowed to accept the rental
+	pub user: AccountId,
+	/// Length of the rental (in blocks)
+	pub duration: BlockNumber,
+	/// The asset to pay the rental price with
+	pub payment_asset: AssetId,
+	/// The rental price, paid to the lender on acceptance
+	pub price: Balance,
 }
 
 /// The supported attribute data types for an NFT
@@ -287,6 +472,50 @@ pub enum AuctionClosureReason {
 	VendorCancelled,
 }
 
+/// The minimum amount a new bid must raise the current winning bid by
+#[derive(Decode, Encode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
+pub enum BidIncrement {
+	/// A fixed amount of the payment asset
+	Absolute(Balance),
+	/// A percentage of the current winning bid
+	Percentage(Permill),
+}
+
+/// Bidding rules for an auction listing
+#[derive(Decode, Encode, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct AuctionRules<BlockNumber> {
+	/// The minimum increment for a bid over the current winning bid, if any
+	pub min_bid_increment: Option<BidIncrement>,
+	/// A bid placed within this many blocks of the auction close will extend the auction
+	/// `0` disables extensions
+	pub soft_close_window: BlockNumber,
+	/// The auction is extended to close this many blocks after the extending bid
+	pub extension_period: BlockNumber,
+}
+
+impl<BlockNumber: From<u32>> Default for AuctionRules<BlockNumber> {
+	fn default() -> Self {
+		Self {
+			min_bid_increment: None,
+			soft_close_window: AUCTION_EXTENSION_PERIOD.into(),
+			extension_period: AUCTION_EXTENSION_PERIOD.into(),
+		}
+	}
+}
+
+impl<BlockNumber> AuctionRules<BlockNumber> {
+	/// The minimum acceptable bid to outbid `current_bid`
+	pub fn min_next_bid(&self, current_bid: Balance) -> Balance {
+		let increment = match self.min_bid_increment {
+			Some(BidIncrement::Absolute(amount)) => amount,
+			Some(BidIncrement::Percentage(percent)) => percent * current_bid,
+			None => 0,
+		};
+		// a new bid must always be strictly greater than the current bid
+		current_bid.saturating_add(increment.max(1))
+	}
+}
+
 /// Describes the royalty scheme for secondary sales for an NFT collection/token
 #[derive(Default, Debug, Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
 pub struct RoyaltiesSchedule<AccountId> {
@@ -372,6 +601,7 @@ pub fn serialize_u128_option<S: Serializer>(val: &Option<u128>, s: S) -> Result<
 pub enum Listing<T: Config> {
 	FixedPrice(FixedPriceListing<T>),
 	Auction(AuctionListing<T>),
+	Dutch(DutchAuctionListing<T>),
 }
 
 /// Information about a marketplace
@@ -425,6 +655,50 @@ pub struct FixedPriceListing<T: Config> {
 	pub marketplace_id: Option<MarketplaceId>,
 }
 
+/// Information about a dutch (descending price) auction listing
+#[derive(Debug, Clone, Encode, Decode, PartialEq, Eq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct DutchAuctionListing<T: Config> {
+	/// The asset to allow payment with
+	pub payment_asset: <<T as Config>::MultiCurrency as MultiCurrency>::CurrencyId,
+	/// The price when the listing opens
+	pub start_price: <<T as Config>::MultiCurrency as MultiCurrency>::Balance,
+	/// The floor price, reached in the last block before the listing closes
+	pub reserve_price: <<T as Config>::MultiCurrency as MultiCurrency>::Balance,
+	/// When the listing opened
+	pub open: T::BlockNumber,
+	/// When the listing closes
+	pub close: T::BlockNumber,
+	/// The seller of the tokens
+	pub seller: T::AccountId,
+	/// The token Ids for sale in this listing
+	pub tokens: Vec<TokenId>,
+	/// The royalties applicable to this sale
+	pub royalties_schedule: RoyaltiesSchedule<T::AccountId>,
+	/// The marketplace this is being sold on
+	pub marketplace_id: Option<MarketplaceId>,
+}
+
+impl<T: Config> DutchAuctionListing<T> {
+	/// Calculate the asking price at block `now`
+	/// The price falls linearly from `start_price` at `open` to `reserve_price` at `close - 1`, the last block
+	/// the listing can be bought in before it is closed by `on_initialize` at `close`
+	pub fn current_price(&self, now: T::BlockNumber) -> Balance {
+		let last = self.close.saturating_sub(One::one());
+		if now >= last {
+			return self.reserve_price;
+		}
+		if now <= self.open {
+			return self.start_price;
+		}
+		let elapsed: u128 = (now - self.open).saturated_into();
+		let duration: u128 = (last - self.open).saturated_into();
+		let price_range = self.start_price.saturating_sub(self.reserve_price);
+		self.start_price
+			.saturating_sub(Perquintill::from_rational(elapsed, duration) * price_range)
+	}
+}
+
 /// NFT colleciton moniker
 pub type CollectionNameType = Vec<u8>;
 
@@ -435,6 +709,9 @@ pub type MarketplaceId = u32;
 /// Unique Id for a listing
 pub type ListingId = u128;
 
+/// The name of a per-token attribute (utf-8)
+pub type AttributeKey = Vec<u8>;
+
 // A value placed in storage that represents the current version of the NFT storage. This value
 // is used by the `on_runtime_upgrade` logic to determine whether we run storage migration logic.
 // This should match directly with the semantic versions of the Rust crate.
@@ -446,6 +723,8 @@ pub enum Releases {
 	V1 = 1,
 	// storage version > runtime v46
 	V2 = 2,
+	/// storage version with offer expiry
+	V3 = 3,
 }
 
 #[cfg(test)]
@@ -536,6 +815,7 @@ mod test {
 				name: collection_name,
 				owner: collection_owner,
 				royalties: royalties.entitlements,
+				remaining_supply: vec![(0, 10)],
 			};
 			let json_str = "{\
 				\"name\":\"test-collection\",\
@@ -549,6 +829,12 @@ mod test {
 						4,\
 						\"0.300000\"\
 					]\
+				],\
+				\"remaining_supply\":[\
+					[\
+						0,\
+						10\
+					]\
 				]\
 			}";
 
@@ -582,6 +868,10 @@ mod test {
 				attributes: series_attributes,
 				owner: collection_owner,
 				royalties: royalties.entitlements,
+				token_attributes: vec![
+					(b"level".to_vec(), NFTAttributeValue::U32(7)),
+					(b"name".to_vec(), NFTAttributeValue::String(Vec::from("Sword"))),
+				],
 			};
 
 			let json_str = "{\
@@ -604,7 +894,11 @@ mod test {
 						3,\
 						\"0.200000\"\
 					]\
-				]\
+				],\
+				\"token_attributes\":{\
+					\"level\":7,\
+					\"name\":\"Sword\"\
+				}\
 			}";
 
 			assert_eq!(serde_json::to_string(&token_info).unwrap(), json_str);
@@ -24,6 +24,15 @@ pub trait WeightInfo {
 	fn make_simple_offer() -> Weight;
 	fn cancel_offer() -> Weight;
 	fn accept_offer() -> Weight;
+	fn set_series_royalties() -> Weight;
+	fn set_token_royalties() -> Weight;
+	fn set_public_mint() -> Weight;
+	fn public_mint(q: u32) -> Weight;
+	fn set_attribute_policy() -> Weight;
+	fn update_token_attributes(n: u32) -> Weight;
+	fn lend() -> Weight;
+	fn accept_rental() -> Weight;
+	fn cancel_rental_offer() -> Weight;
 }
 
 impl WeightInfo for () {
@@ -103,4 +112,54 @@ impl WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(11 as Weight))
 			.saturating_add(DbWeight::get().writes(9 as Weight))
 	}
+	fn set_series_royalties() -> Weight {
+		(37_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_token_royalties() -> Weight {
+		(39_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_public_mint() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn public_mint(q: u32) -> Weight {
+		(124_000_000 as Weight)
+			// Standard Error: 2_166_000
+			.saturating_add((3_536_000 as Weight).saturating_mul(q as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(q as Weight)))
+	}
+	fn set_attribute_policy() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn update_token_attributes(n: u32) -> Weight {
+		(41_000_000 as Weight)
+			// Standard Error: 96_000
+			.saturating_add((2_850_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn lend() -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn accept_rental() -> Weight {
+		(131_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_rental_offer() -> Weight {
+		(34_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
 }
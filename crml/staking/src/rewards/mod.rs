@@ -643,6 +643,9 @@ mod tests {
 		type Event = Event;
 		type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
 		type WeightInfo = ();
+		type MaxVestingSchedules = MaxVestingSchedules;
+		type MinVestedTransfer = MinVestedTransfer;
+		type OnTransferSubscription = ();
 	}
 
 	impl pallet_authorship::Config for Test {
@@ -654,6 +657,8 @@ mod tests {
 
 	parameter_types! {
 		pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+		pub const MaxVestingSchedules: u32 = 4;
+		pub const MinVestedTransfer: Balance = 1;
 		pub const HistoricalPayoutEras: u16 = 7;
 		pub const FiscalEraLength: u32 = 5;
 	}
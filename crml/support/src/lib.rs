@@ -25,9 +25,10 @@ use frame_support::{
 	dispatch::GetDispatchInfo,
 	pallet_prelude::DispatchResultWithPostInfo,
 	traits::{ExistenceRequirement, Imbalance, SignedImbalance, WithdrawReasons},
+	weights::Weight,
 };
 use pallet_evm::AddressMapping;
-use precompile_utils::AddressMappingReversibleExt;
+pub use precompile_utils::AddressMappingReversibleExt;
 pub use primitive_types::{H160, H256, U256};
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -242,6 +243,9 @@ pub trait MultiCurrency {
 	/// `ExistentialDeposit`.
 	fn minimum_balance(currency: Self::CurrencyId) -> Self::Balance;
 
+	/// The number of decimal places balances of `currency` are denominated in.
+	fn decimal_places(currency: Self::CurrencyId) -> u8;
+
 	/// Return the currency Id of the system fee currency
 	fn fee_currency() -> Self::CurrencyId;
 
@@ -395,6 +399,24 @@ pub trait OnTransferSubscriber {
 	fn on_nft_transfer(token_id: &TokenId);
 }
 
+/// Notified when generic asset free balance moves between accounts
+pub trait OnAssetTransferSubscriber<AccountId, AssetId> {
+	/// Some `asset_id` is about to move from `from` to `to`
+	fn on_before_asset_transfer(asset_id: AssetId, from: &AccountId, to: &AccountId);
+	/// Some `asset_id` was moved from `from` to `to`
+	fn on_asset_transfer(asset_id: AssetId, from: &AccountId, to: &AccountId);
+	/// The worst case weight of notifying both hooks of one transfer
+	fn on_asset_transfer_weight() -> Weight;
+}
+
+impl<AccountId, AssetId> OnAssetTransferSubscriber<AccountId, AssetId> for () {
+	fn on_before_asset_transfer(_asset_id: AssetId, _from: &AccountId, _to: &AccountId) {}
+	fn on_asset_transfer(_asset_id: AssetId, _from: &AccountId, _to: &AccountId) {}
+	fn on_asset_transfer_weight() -> Weight {
+		0
+	}
+}
+
 /// Provides an oracle for ethereum chain state
 pub trait EthereumStateOracle {
 	/// EVM address type
@@ -452,6 +474,122 @@ pub trait ContractExecutor {
 	) -> DispatchResultWithPostInfo;
 }
 
+/// Calls EVM contracts from runtime modules within the current transaction
+pub trait ContractCaller {
+	/// EVM address type
+	type Address;
+	/// Call the `target` contract from `caller` with `input`, spending at most `gas_limit` gas
+	/// The call's gas is not charged to `caller`, the calling module should charge it as weight
+	///
+	/// Returns the gas used, or an error if the call failed or reverted
+	fn call(caller: &Self::Address, target: &Self::Address, input: &[u8], gas_limit: u64)
+		-> Result<u64, DispatchError>;
+}
+
+/// Provides manipulation resistant prices of exchange pairs
+pub trait PriceOracle {
+	/// The asset ID type
+	type AssetId;
+	/// The block number type
+	type BlockNumber;
+	/// Returns the time-weighted average prices of the `asset_id` exchange over the last `window` blocks
+	/// as (asset price in core asset, core asset price in asset) scaled by 10^18
+	/// or `None` if there is insufficient price history
+	fn twap(asset_id: Self::AssetId, window: Self::BlockNumber) -> Option<(U256, U256)>;
+	/// Returns ~ weight of a `twap` query
+	fn twap_weight() -> Weight;
+}
+
+/// Provides flash loans from exchange reserves, repaid within the same call
+pub trait FlashSwap {
+	/// The account ID type
+	type AccountId;
+	/// The asset ID type
+	type AssetId;
+	/// The balance type
+	type Balance;
+	/// Lend `borrow_amount` of `asset_to_borrow` to `borrower` then run `callback`, the loan is repaid from
+	/// `borrower` in `asset_to_repay` once the callback returns
+	/// Returns the amount of `asset_to_repay` paid
+	fn flash_swap(
+		borrower: &Self::AccountId,
+		asset_to_borrow: Self::AssetId,
+		borrow_amount: Self::Balance,
+		asset_to_repay: Self::AssetId,
+		callback: impl FnOnce() -> DispatchResult,
+	) -> Result<Self::Balance, DispatchError>;
+	/// Returns ~ weight of a `flash_swap`, excluding the callback execution
+	fn flash_swap_weight() -> Weight;
+}
+
+/// Trading and liquidity provision with an exchange of core asset / trade asset pairs
+pub trait Exchange {
+	/// The account ID type
+	type AccountId;
+	/// The asset ID type
+	type AssetId;
+	/// The balance type
+	type Balance;
+	/// The asset paired with every trade asset
+	fn core_asset_id() -> Self::AssetId;
+	/// Returns the (core asset, trade asset) reserves of the `asset_id` exchange
+	fn reserves(asset_id: Self::AssetId) -> (Self::Balance, Self::Balance);
+	/// Returns the amount of each asset in `path` traded when selling `amount_in` of the first asset
+	fn sell_price_via_path(
+		path: &[Self::AssetId],
+		amount_in: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError>;
+	/// Returns the amount of each asset in `path` traded when buying `amount_out` of the last asset
+	fn buy_price_via_path(
+		path: &[Self::AssetId],
+		amount_out: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError>;
+	/// Sell `amount_in` of the first asset in `path` for at least `min_amount_out` of the last, paid to `recipient`
+	/// Returns the amount of each asset in `path` traded
+	fn sell_via_path(
+		trader: &Self::AccountId,
+		recipient: &Self::AccountId,
+		path: &[Self::AssetId],
+		amount_in: Self::Balance,
+		min_amount_out: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError>;
+	/// Buy `amount_out` of the last asset in `path` for at most `max_amount_in` of the first, paid to `recipient`
+	/// Returns the amount of each asset in `path` traded
+	fn buy_via_path(
+		trader: &Self::AccountId,
+		recipient: &Self::AccountId,
+		path: &[Self::AssetId],
+		amount_out: Self::Balance,
+		max_amount_in: Self::Balance,
+	) -> Result<Vec<Self::Balance>, DispatchError>;
+	/// Deposit `core_amount` and up to `max_asset_amount` of `asset_id` into its exchange at the current ratio
+	/// Returns the (trade asset deposited, liquidity minted)
+	fn add_liquidity(
+		provider: &Self::AccountId,
+		asset_id: Self::AssetId,
+		min_liquidity: Self::Balance,
+		max_asset_amount: Self::Balance,
+		core_amount: Self::Balance,
+	) -> Result<(Self::Balance, Self::Balance), DispatchError>;
+	/// Burn `liquidity` of the `asset_id` exchange to withdraw its share of the reserves
+	/// Returns the (core asset, trade asset) withdrawn
+	fn remove_liquidity(
+		provider: &Self::AccountId,
+		asset_id: Self::AssetId,
+		liquidity: Self::Balance,
+		min_asset_withdraw: Self::Balance,
+		min_core_withdraw: Self::Balance,
+	) -> Result<(Self::Balance, Self::Balance), DispatchError>;
+	/// Returns ~ weight of a trade via path
+	fn trade_weight() -> Weight;
+	/// Returns ~ weight of pricing a trade via path, this covers reading exchange reserves
+	fn price_weight() -> Weight;
+	/// Returns ~ weight of `add_liquidity`
+	fn add_liquidity_weight() -> Weight;
+	/// Returns ~ weight of `remove_liquidity`
+	fn remove_liquidity_weight() -> Weight;
+}
+
 /// Simplified failure reasons for an eth_call request
 #[derive(Encode, Decode, Debug, PartialEq, TypeInfo)]
 pub enum EthCallFailure {
@@ -67,8 +67,9 @@ decl_storage! {
 	trait Store for Module<T: Config> as TokenApprovals {
 		// Account with transfer approval for a single NFT
 		pub ERC721Approvals get(fn erc721_approvals): map hasher(twox_64_concat) (CollectionId, SeriesId, SerialNumber) => H160;
-		// Account with transfer approval for an NFT series of another account
-		pub ERC721ApprovalsForAll get(fn erc721_approvals_for_all): double_map hasher(twox_64_concat) H160, hasher(twox_64_concat) (CollectionId, SeriesId) => Vec<H160>;
+		// Whether an operator has transfer approval for all NFTs of a series owned by another account
+		// (owner) => ((collection, series), operator) => approved
+		pub ERC721ApprovalsForAll get(fn erc721_approvals_for_all): double_map hasher(twox_64_concat) H160, hasher(twox_64_concat) ((CollectionId, SeriesId), H160) => bool;
 		// Mapping from account/ asset_id to an approved balance of another account
 		pub ERC20Approvals get(fn erc20_approvals): double_map hasher(twox_64_concat) (H160, AssetId), hasher(twox_64_concat) H160 => Balance;
 	}
@@ -132,24 +133,27 @@ decl_module! {
 			Ok(())
 		}
 
-		// #[weight = 175_000_000]
-		// pub fn erc721_approval_for_all(
-		// 	origin,
-		// 	caller: H160,
-		// 	operator_account: H160,
-		// 	collection_id: CollectionId,
-		// 	series_id: SeriesId,
-		// ) -> DispatchResult {
-		// 	let _ = ensure_none(origin)?;
-		// 	// mapping(address => mapping(address => bool)) private _operatorApprovals;
-		// 	ensure!(caller != operator_account, Error::<T>::CallerNotOperator);
-		// 	let approvals = Self::erc721_approvals_for_all(&caller, (collection_id, series_id));
-		// 	ensure!(!approvals.contains(&operator_account), Error::<T>::AlreadyApproved);
-		//
-		// 	ERC721ApprovalsForAll::<T>::append(caller, (collection_id, series_id), operator_account.clone());
-		//
-		// 	Ok(())
-		// }
+		/// Set or remove an operator's approval over all NFTs of a series owned by the caller
+		/// Unlike single NFT approvals, operator approvals are kept on transfer
+		#[weight = 175_000_000]
+		pub fn erc721_approval_for_all(
+			origin,
+			caller: H160,
+			operator_account: H160,
+			collection_id: CollectionId,
+			series_id: SeriesId,
+			approved: bool,
+		) -> DispatchResult {
+			let _ = ensure_none(origin)?;
+			// mapping(address => mapping(address => bool)) private _operatorApprovals;
+			ensure!(caller != operator_account, Error::<T>::CallerNotOperator);
+			if approved {
+				ERC721ApprovalsForAll::insert(caller, ((collection_id, series_id), operator_account), true);
+			} else {
+				ERC721ApprovalsForAll::remove(caller, ((collection_id, series_id), operator_account));
+			}
+			Ok(())
+		}
 	}
 }
 
@@ -160,4 +164,8 @@ impl<T: Config> Module<T> {
 		// Check that origin owns NFT
 		ERC721Approvals::remove(token_id);
 	}
+	/// Whether `operator` is approved to transfer all NFTs of the series owned by `owner`
+	pub fn is_approved_for_all(owner: H160, operator: H160, series_id_parts: (CollectionId, SeriesId)) -> bool {
+		Self::erc721_approvals_for_all(owner, (series_id_parts, operator))
+	}
 }
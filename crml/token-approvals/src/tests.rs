@@ -76,6 +76,84 @@ fn erc721_approval_removed_on_transfer() {
 	});
 }
 
+#[test]
+fn set_erc721_approval_for_all() {
+	ExtBuilder::default().build().execute_with(|| {
+		let caller = H160::from_slice(&hex!("a86e122EdbDcBA4bF24a2Abf89F5C230b37DF49d"));
+		let operator = H160::from_slice(&hex!("1000000000000000000000000000000000000000"));
+		let (collection_id, series_id) = (0, 0);
+
+		assert!(!TokenApprovals::is_approved_for_all(
+			caller,
+			operator,
+			(collection_id, series_id)
+		));
+		assert_ok!(TokenApprovals::erc721_approval_for_all(
+			None.into(),
+			caller,
+			operator,
+			collection_id,
+			series_id,
+			true
+		));
+		assert!(TokenApprovals::is_approved_for_all(
+			caller,
+			operator,
+			(collection_id, series_id)
+		));
+		// approval is scoped to the series
+		assert!(!TokenApprovals::is_approved_for_all(
+			caller,
+			operator,
+			(collection_id, 1)
+		));
+		// and the approving account
+		assert!(!TokenApprovals::is_approved_for_all(
+			operator,
+			caller,
+			(collection_id, series_id)
+		));
+
+		// kept on transfer
+		TokenApprovals::on_nft_transfer(&(collection_id, series_id, 0));
+		assert!(TokenApprovals::is_approved_for_all(
+			caller,
+			operator,
+			(collection_id, series_id)
+		));
+
+		assert_ok!(TokenApprovals::erc721_approval_for_all(
+			None.into(),
+			caller,
+			operator,
+			collection_id,
+			series_id,
+			false
+		));
+		assert!(!TokenApprovals::is_approved_for_all(
+			caller,
+			operator,
+			(collection_id, series_id)
+		));
+		assert!(!ERC721ApprovalsForAll::contains_key(
+			caller,
+			((collection_id, series_id), operator)
+		));
+	});
+}
+
+#[test]
+fn set_erc721_approval_for_all_caller_is_operator_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let caller = H160::from_slice(&hex!("a86e122EdbDcBA4bF24a2Abf89F5C230b37DF49d"));
+
+		assert_noop!(
+			TokenApprovals::erc721_approval_for_all(None.into(), caller, caller, 0, 0, true),
+			Error::<Test>::CallerNotOperator,
+		);
+	});
+}
+
 #[test]
 fn set_erc20_approval() {
 	ExtBuilder::default().build().execute_with(|| {
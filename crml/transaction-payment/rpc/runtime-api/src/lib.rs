@@ -20,15 +20,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use sp_runtime::traits::MaybeDisplay;
+use sp_runtime::{traits::MaybeDisplay, DispatchError};
 
-pub use crml_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use crml_transaction_payment::{FeeDetails, FeeInAsset, InclusionFee, RuntimeDispatchInfo};
 
 sp_api::decl_runtime_apis! {
-	pub trait TransactionPaymentApi<Balance> where
+	#[api_version(2)]
+	pub trait TransactionPaymentApi<Balance, AssetId> where
 		Balance: Codec + MaybeDisplay,
+		AssetId: Codec,
 	{
 		fn query_info(uxt: Block::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
+		/// Query the fee of an extrinsic in `asset_id` when paid through `FeeExchange`,
+		/// with a recommended `max_payment`
+		fn query_fee_in_asset(uxt: Block::Extrinsic, len: u32, asset_id: AssetId) -> Result<FeeInAsset<Balance>, DispatchError>;
 	}
 }
@@ -20,7 +20,7 @@
 pub use self::gen_client::Client as TransactionPaymentClient;
 use codec::{Codec, Decode};
 pub use crml_transaction_payment_rpc_runtime_api::TransactionPaymentApi as TransactionPaymentRuntimeApi;
-use crml_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+use crml_transaction_payment_rpc_runtime_api::{FeeDetails, FeeInAsset, InclusionFee, RuntimeDispatchInfo};
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 use sp_api::ProvideRuntimeApi;
@@ -34,11 +34,20 @@ use sp_runtime::{
 use std::sync::Arc;
 
 #[rpc]
-pub trait TransactionPaymentApi<BlockHash, ResponseType> {
+pub trait TransactionPaymentApi<BlockHash, AssetId, ResponseType> {
 	#[rpc(name = "payment_queryInfo")]
 	fn query_info(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> Result<ResponseType>;
 	#[rpc(name = "payment_queryFeeDetails")]
 	fn query_fee_details(&self, encoded_xt: Bytes, at: Option<BlockHash>) -> Result<FeeDetails<NumberOrHex>>;
+	/// Query the fee of `encoded_xt` in `asset_id` when paid through `FeeExchange`,
+	/// with a recommended `max_payment`
+	#[rpc(name = "payment_queryFeeInAsset")]
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<BlockHash>,
+	) -> Result<FeeInAsset<NumberOrHex>>;
 }
 
 /// A struct that implements the [`TransactionPaymentApi`].
@@ -63,6 +72,8 @@ pub enum Error {
 	DecodeError,
 	/// The call to runtime failed.
 	RuntimeError,
+	/// The fee could not be priced in the requested asset.
+	ExchangeError,
 }
 
 impl From<Error> for i64 {
@@ -70,17 +81,19 @@ impl From<Error> for i64 {
 		match e {
 			Error::RuntimeError => 1,
 			Error::DecodeError => 2,
+			Error::ExchangeError => 3,
 		}
 	}
 }
 
-impl<C, Block, Balance> TransactionPaymentApi<<Block as BlockT>::Hash, RuntimeDispatchInfo<Balance>>
+impl<C, Block, Balance, AssetId> TransactionPaymentApi<<Block as BlockT>::Hash, AssetId, RuntimeDispatchInfo<Balance>>
 	for TransactionPayment<C, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: TransactionPaymentRuntimeApi<Block, Balance, AssetId>,
 	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+	AssetId: Codec,
 {
 	fn query_info(
 		&self,
@@ -148,6 +161,52 @@ where
 				None
 			},
 			tip: Default::default(),
+			sponsored: fee_details.sponsored,
+		})
+	}
+
+	fn query_fee_in_asset(
+		&self,
+		encoded_xt: Bytes,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<FeeInAsset<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let encoded_len = encoded_xt.len() as u32;
+
+		let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query fee in asset.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let fee_in_asset = api
+			.query_fee_in_asset(&at, uxt, encoded_len, asset_id)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to query fee in asset.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::ExchangeError.into()),
+				message: "Unable to price fee in asset.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})?;
+
+		let try_into_rpc_balance = |value: Balance| {
+			value.try_into().map_err(|_| RpcError {
+				code: ErrorCode::InvalidParams,
+				message: format!("{} doesn't fit in NumberOrHex representation", value),
+				data: None,
+			})
+		};
+
+		Ok(FeeInAsset {
+			estimated_fee: try_into_rpc_balance(fee_in_asset.estimated_fee)?,
+			max_payment: try_into_rpc_balance(fee_in_asset.max_payment)?,
 		})
 	}
 }
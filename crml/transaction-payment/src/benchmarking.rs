@@ -0,0 +1,125 @@
+// This file is part of CENNZnet.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd. and Centrality Investments Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transaction payment benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+
+use crate::Module as TransactionPayment;
+
+/// `n` distinct beneficiary accounts
+fn beneficiaries<T: Config>(n: u32) -> Vec<T::AccountId> {
+	(0..n).map(|i| account("beneficiary", i, 0)).collect()
+}
+
+/// Set a sponsorship for `sponsor` with `n` beneficiaries
+fn setup_sponsorship<T: Config>(sponsor: &T::AccountId, n: u32) {
+	let _ = <TransactionPayment<T>>::set_sponsorship(
+		RawOrigin::Signed(sponsor.clone()).into(),
+		1_000u32.into(),
+		vec![(0, 0)],
+		100u32.into(),
+		10u32.into(),
+	);
+	let _ = <TransactionPayment<T>>::add_sponsored_accounts(
+		RawOrigin::Signed(sponsor.clone()).into(),
+		beneficiaries::<T>(n),
+	);
+}
+
+benchmarks! {
+	set_sponsorship {
+		let c in 0 .. T::MaxSponsoredCalls::get();
+		let sponsor: T::AccountId = whitelisted_caller();
+		let calls: Vec<(u8, u8)> = (0..c).map(|i| (i as u8, 0)).collect();
+
+	}: _(RawOrigin::Signed(sponsor.clone()), 1_000u32.into(), calls, 100u32.into(), 10u32.into())
+	verify {
+		assert_eq!(<TransactionPayment<T>>::sponsorship(&sponsor).unwrap().calls.len() as u32, c);
+	}
+
+	remove_sponsorship {
+		let n in 0 .. T::MaxSponsoredAccounts::get();
+		let sponsor: T::AccountId = whitelisted_caller();
+		setup_sponsorship::<T>(&sponsor, n);
+
+	}: _(RawOrigin::Signed(sponsor.clone()))
+	verify {
+		assert!(<TransactionPayment<T>>::sponsorship(&sponsor).is_none());
+		assert_eq!(<TransactionPayment<T>>::sponsored_account_count(&sponsor), 0);
+	}
+
+	add_sponsored_accounts {
+		let n in 1 .. T::MaxSponsoredAccounts::get();
+		let sponsor: T::AccountId = whitelisted_caller();
+		setup_sponsorship::<T>(&sponsor, 0);
+
+	}: _(RawOrigin::Signed(sponsor.clone()), beneficiaries::<T>(n))
+	verify {
+		assert_eq!(<TransactionPayment<T>>::sponsored_account_count(&sponsor), n);
+	}
+
+	remove_sponsored_accounts {
+		let n in 1 .. T::MaxSponsoredAccounts::get();
+		let sponsor: T::AccountId = whitelisted_caller();
+		setup_sponsorship::<T>(&sponsor, n);
+
+	}: _(RawOrigin::Signed(sponsor.clone()), beneficiaries::<T>(n))
+	verify {
+		assert_eq!(<TransactionPayment<T>>::sponsored_account_count(&sponsor), 0);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{ExtBuilder, Runtime};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn set_sponsorship() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_set_sponsorship::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn remove_sponsorship() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_remove_sponsorship::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn add_sponsored_accounts() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_add_sponsored_accounts::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn remove_sponsored_accounts() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_ok!(test_benchmark_remove_sponsored_accounts::<Runtime>());
+		});
+	}
+}
@@ -29,6 +29,12 @@ pub mod error_code {
 	pub const INSUFFICIENT_EXCHANGE_POOL_RESERVE: u8 = 205;
 	pub const MAXIMUM_SELL_REQUIREMENT_NOT_MET: u8 = 206;
 	pub const INSUFFICIENT_BALANCE: u8 = 195;
+	pub const NO_SPONSORSHIP: u8 = 207;
+	pub const ACCOUNT_NOT_SPONSORED: u8 = 208;
+	pub const CALL_NOT_SPONSORED: u8 = 209;
+	pub const SPONSORED_ALLOWANCE_EXCEEDED: u8 = 210;
+	pub const SPONSORSHIP_BUDGET_EXCEEDED: u8 = 211;
+	pub const SPONSORED_TIP: u8 = 212;
 
 	// Matches and converts crml-cennzx module errors, such that
 	// they are propagated in crml-transaction-payment module
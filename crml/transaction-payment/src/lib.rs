@@ -44,6 +44,12 @@
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Config::FeeMultiplierUpdate`]
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
+//!
+//! Fees may also be paid by a sponsor. A sponsor registers a budget, the calls it pays for and a
+//! per beneficiary allowance with [`Call::set_sponsorship`], then allow-lists up to
+//! [`Config::MaxSponsoredAccounts`] beneficiaries with [`Call::add_sponsored_accounts`]. A
+//! beneficiary names the sponsor in its transaction with `FeeExchange::Sponsored` and the fee is
+//! withdrawn from the sponsor instead, any unused fee is refunded to the sponsor and its budget.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -52,31 +58,36 @@ use cennznet_primitives::{traits::BuyFeeAsset, types::FeeExchange};
 use codec::{Decode, Encode};
 use crml_support::TransactionFeeHandler;
 use frame_support::{
-	decl_module, decl_storage,
-	dispatch::DispatchResult,
+	decl_error, decl_event, decl_module, decl_storage,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	pallet_prelude::*,
+	transactional,
 	weights::{DispatchInfo, GetDispatchInfo, PostDispatchInfo, WeightToFeeCoefficient, WeightToFeePolynomial},
 	Parameter,
 };
+use frame_system::ensure_signed;
 use sp_arithmetic::traits::BaseArithmetic;
 use sp_runtime::{
 	traits::{
-		Convert, DispatchInfoOf, Dispatchable, Member, PostDispatchInfoOf, SaturatedConversion, Saturating,
+		CheckedSub, Convert, DispatchInfoOf, Dispatchable, Member, PostDispatchInfoOf, SaturatedConversion, Saturating,
 		SignedExtension, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, Perquintill, RuntimeDebug,
+	DispatchError, FixedPointNumber, FixedPointOperand, FixedU128, Permill, Perquintill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 
+mod benchmarking;
 mod payment;
 mod types;
+pub mod weights;
 
 pub use payment::*;
-pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use types::{FeeDetails, FeeInAsset, InclusionFee, RuntimeDispatchInfo};
 pub mod constants;
+use weights::WeightInfo;
 
 /// Fee multiplier.
 pub type Multiplier = FixedU128;
@@ -239,7 +250,32 @@ impl Default for Releases {
 	}
 }
 
+/// The terms on which a sponsor pays the transaction fees of its beneficiaries
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Sponsorship<Balance, BlockNumber> {
+	/// The total fees the sponsor will still pay
+	pub budget: Balance,
+	/// The calls the sponsor pays for as (pallet index, call index)
+	pub calls: Vec<(u8, u8)>,
+	/// The most fees the sponsor pays for any one beneficiary per `period`
+	pub allowance: Balance,
+	/// The length of a beneficiary's allowance period in blocks
+	pub period: BlockNumber,
+}
+
+/// A beneficiary's use of a sponsorship in its current allowance period
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct SponsoredUsage<Balance, BlockNumber> {
+	/// The block the current allowance period started
+	pub period_start: BlockNumber,
+	/// The fees paid by the sponsor in the current allowance period
+	pub spent: Balance,
+}
+
 pub trait Config: frame_system::Config {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
 	/// The arithmetic type of asset identifier.
 	type AssetId: Parameter + Member + BaseArithmetic + Default + Copy + TypeInfo;
 
@@ -263,9 +299,23 @@ pub trait Config: frame_system::Config {
 	/// A service which will buy fee assets if signalled by the extrinsic.
 	type BuyFeeAsset: BuyFeeAsset<
 		AccountId = Self::AccountId,
+		AssetId = Self::AssetId,
 		Balance = BalanceOf<Self>,
-		FeeExchange = FeeExchange<Self::AssetId, BalanceOf<Self>>,
+		FeeExchange = FeeExchange<Self::AssetId, BalanceOf<Self>, Self::AccountId>,
 	>;
+
+	/// The maximum number of calls a sponsorship may pay for.
+	type MaxSponsoredCalls: Get<u32>;
+
+	/// The maximum number of beneficiaries a sponsorship may have.
+	type MaxSponsoredAccounts: Get<u32>;
+
+	/// Weight information for extrinsics in this module.
+	type WeightInfo: WeightInfo;
+
+	/// The allowance over an estimated fee in another asset used to recommend a `FeeExchange` max. payment,
+	/// and under the purchase price accepted when a fee refund is exchanged back.
+	type FeeExchangeSlippage: Get<Permill>;
 }
 
 decl_storage! {
@@ -273,11 +323,59 @@ decl_storage! {
 		pub NextFeeMultiplier get(fn next_fee_multiplier): Multiplier = Multiplier::saturating_from_integer(1);
 
 		StorageVersion build(|_: &GenesisConfig| Releases::V2): Releases;
+
+		/// The sponsorship terms of each sponsor
+		pub Sponsorships get(fn sponsorship):
+			map hasher(twox_64_concat) T::AccountId => Option<Sponsorship<BalanceOf<T>, T::BlockNumber>>;
+		/// The beneficiaries of each sponsor and their use of its sponsorship (sponsor, beneficiary)
+		pub SponsoredAccounts get(fn sponsored_usage):
+			double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+			=> Option<SponsoredUsage<BalanceOf<T>, T::BlockNumber>>;
+		/// The number of beneficiaries of each sponsor
+		pub SponsoredAccountCount get(fn sponsored_account_count): map hasher(twox_64_concat) T::AccountId => u32;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Config>::AccountId,
+		Balance = BalanceOf<T>,
+	{
+		/// A sponsor set its sponsorship terms (sponsor, budget)
+		SponsorshipSet(AccountId, Balance),
+		/// A sponsor removed its sponsorship and beneficiaries (sponsor)
+		SponsorshipRemoved(AccountId),
+		/// A sponsor allow-listed beneficiaries (sponsor, beneficiaries)
+		SponsoredAccountsAdded(AccountId, Vec<AccountId>),
+		/// A sponsor removed beneficiaries from its allow-list (sponsor, beneficiaries)
+		SponsoredAccountsRemoved(AccountId, Vec<AccountId>),
+		/// A sponsor paid the transaction fee of a beneficiary (sponsor, beneficiary, fee)
+		SponsoredFeePaid(AccountId, AccountId, Balance),
+		/// A fee refund couldn't be exchanged back to the asset the fee was bought with,
+		/// it was refunded in the fee asset instead (who, refund)
+		FeeRefundNotExchanged(AccountId, Balance),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Config> {
+		/// The account has no sponsorship
+		NoSponsorship,
+		/// A sponsorship may pay for at most `MaxSponsoredCalls` calls
+		TooManySponsoredCalls,
+		/// A sponsorship's allowance period must be at least one block
+		InvalidSponsorshipPeriod,
+		/// A sponsorship may have at most `MaxSponsoredAccounts` beneficiaries
+		TooManySponsoredAccounts,
 	}
 }
 
 decl_module! {
 	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
 		/// The fee to be paid for making a transaction; the per-byte portion.
 		const TransactionByteFee: BalanceOf<T> = T::TransactionByteFee::get();
 
@@ -285,6 +383,89 @@ decl_module! {
 		const WeightToFee: Vec<WeightToFeeCoefficient<BalanceOf<T>>> =
 			T::WeightToFee::polynomial().to_vec();
 
+		/// The maximum number of calls a sponsorship may pay for.
+		const MaxSponsoredCalls: u32 = T::MaxSponsoredCalls::get();
+
+		/// The maximum number of beneficiaries a sponsorship may have.
+		const MaxSponsoredAccounts: u32 = T::MaxSponsoredAccounts::get();
+
+		/// Set the terms on which the caller sponsors the fees of its beneficiaries, replacing any prior terms.
+		/// Fees are withdrawn from the caller's balance as they are charged.
+		///
+		/// `budget` - the total fees to pay
+		/// `calls` - the (pallet index, call index) of each call to pay for
+		/// `allowance` - the most fees to pay for any one beneficiary per `period` blocks
+		#[weight = T::WeightInfo::set_sponsorship(calls.len() as u32)]
+		pub fn set_sponsorship(
+			origin,
+			budget: BalanceOf<T>,
+			calls: Vec<(u8, u8)>,
+			allowance: BalanceOf<T>,
+			period: T::BlockNumber,
+		) {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(calls.len() <= T::MaxSponsoredCalls::get() as usize, Error::<T>::TooManySponsoredCalls);
+			ensure!(!period.is_zero(), Error::<T>::InvalidSponsorshipPeriod);
+
+			<Sponsorships<T>>::insert(&sponsor, Sponsorship { budget, calls, allowance, period });
+			Self::deposit_event(RawEvent::SponsorshipSet(sponsor, budget));
+		}
+
+		/// Stop sponsoring fees, removing the caller's sponsorship terms and beneficiaries
+		#[weight = T::WeightInfo::remove_sponsorship(T::MaxSponsoredAccounts::get())]
+		pub fn remove_sponsorship(origin) -> DispatchResultWithPostInfo {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(<Sponsorships<T>>::contains_key(&sponsor), Error::<T>::NoSponsorship);
+
+			// beneficiaries are bounded by `MaxSponsoredAccounts`
+			let count = <SponsoredAccountCount<T>>::take(&sponsor);
+			<Sponsorships<T>>::remove(&sponsor);
+			<SponsoredAccounts<T>>::remove_prefix(&sponsor, Some(count));
+			Self::deposit_event(RawEvent::SponsorshipRemoved(sponsor));
+			Ok(Some(T::WeightInfo::remove_sponsorship(count)).into())
+		}
+
+		/// Allow-list `beneficiaries` to have their fees paid by the caller's sponsorship
+		/// A sponsorship may have at most `MaxSponsoredAccounts` beneficiaries
+		#[weight = T::WeightInfo::add_sponsored_accounts(beneficiaries.len() as u32)]
+		#[transactional]
+		pub fn add_sponsored_accounts(origin, beneficiaries: Vec<T::AccountId>) {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(<Sponsorships<T>>::contains_key(&sponsor), Error::<T>::NoSponsorship);
+			let max_accounts = T::MaxSponsoredAccounts::get();
+			ensure!(beneficiaries.len() <= max_accounts as usize, Error::<T>::TooManySponsoredAccounts);
+
+			let mut count = Self::sponsored_account_count(&sponsor);
+			for beneficiary in beneficiaries.iter() {
+				if !<SponsoredAccounts<T>>::contains_key(&sponsor, beneficiary) {
+					ensure!(count < max_accounts, Error::<T>::TooManySponsoredAccounts);
+					<SponsoredAccounts<T>>::insert(&sponsor, beneficiary, SponsoredUsage::default());
+					count += 1;
+				}
+			}
+			<SponsoredAccountCount<T>>::insert(&sponsor, count);
+			Self::deposit_event(RawEvent::SponsoredAccountsAdded(sponsor, beneficiaries));
+		}
+
+		/// Remove `beneficiaries` from the caller's sponsorship allow-list
+		#[weight = T::WeightInfo::remove_sponsored_accounts(beneficiaries.len() as u32)]
+		pub fn remove_sponsored_accounts(origin, beneficiaries: Vec<T::AccountId>) {
+			let sponsor = ensure_signed(origin)?;
+
+			let mut count = Self::sponsored_account_count(&sponsor);
+			for beneficiary in beneficiaries.iter() {
+				if <SponsoredAccounts<T>>::take(&sponsor, beneficiary).is_some() {
+					count = count.saturating_sub(1);
+				}
+			}
+			if count.is_zero() {
+				<SponsoredAccountCount<T>>::remove(&sponsor);
+			} else {
+				<SponsoredAccountCount<T>>::insert(&sponsor, count);
+			}
+			Self::deposit_event(RawEvent::SponsoredAccountsRemoved(sponsor, beneficiaries));
+		}
+
 		fn on_finalize() {
 			NextFeeMultiplier::mutate(|fm| {
 				*fm = T::FeeMultiplierUpdate::convert(*fm);
@@ -366,6 +547,7 @@ where
 			weight,
 			class,
 			partial_fee,
+			sponsored: false,
 		}
 	}
 
@@ -381,6 +563,41 @@ where
 		Self::compute_fee_details(len, &dispatch_info, 0u32.into())
 	}
 
+	/// Query the fee of a given `call` in `asset_id`, priced as `FeeExchange` would buy it at dispatch.
+	///
+	/// The recommended `max_payment` adds `FeeExchangeSlippage` to the estimate to allow for exchange
+	/// prices and the fee multiplier to move before the transaction is dispatched. The fee asset itself
+	/// is quoted the plain fee.
+	pub fn query_fee_in_asset<Extrinsic: GetDispatchInfo>(
+		unchecked_extrinsic: Extrinsic,
+		len: u32,
+		asset_id: T::AssetId,
+	) -> Result<FeeInAsset<BalanceOf<T>>, DispatchError>
+	where
+		T::Call: Dispatchable<Info = DispatchInfo>,
+	{
+		let fee = Self::query_fee_details(unchecked_extrinsic, len).final_fee();
+		if fee.is_zero() {
+			return Ok(FeeInAsset {
+				estimated_fee: Zero::zero(),
+				max_payment: Zero::zero(),
+			});
+		}
+
+		if asset_id == T::BuyFeeAsset::fee_asset_id() {
+			return Ok(FeeInAsset {
+				estimated_fee: fee,
+				max_payment: fee,
+			});
+		}
+
+		let estimated_fee = T::BuyFeeAsset::buy_fee_asset_price(asset_id, fee)?;
+		Ok(FeeInAsset {
+			estimated_fee,
+			max_payment: estimated_fee.saturating_add(T::FeeExchangeSlippage::get() * estimated_fee),
+		})
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	pub fn compute_fee(len: u32, info: &DispatchInfoOf<T::Call>, tip: BalanceOf<T>) -> BalanceOf<T>
 	where
@@ -460,11 +677,13 @@ where
 					adjusted_weight_fee,
 				}),
 				tip,
+				sponsored: false,
 			}
 		} else {
 			FeeDetails {
 				inclusion_fee: None,
 				tip,
+				sponsored: false,
 			}
 		}
 	}
@@ -498,6 +717,26 @@ where
 		)
 	}
 
+	/// Exchange a fee `refund` made to `who` back to `asset_id`, the asset the fee was bought with.
+	/// `fee` was bought for `paid`, the refund must sell for its share of `paid` less `FeeExchangeSlippage`.
+	/// The refund is left in the fee asset if it can't be exchanged.
+	fn exchange_refund(
+		who: &T::AccountId,
+		asset_id: T::AssetId,
+		fee: BalanceOf<T>,
+		paid: BalanceOf<T>,
+		refund: BalanceOf<T>,
+	) {
+		if refund.is_zero() {
+			return;
+		}
+		let min_buy = Perquintill::from_rational(refund, fee) * paid;
+		let min_buy = min_buy.saturating_sub(T::FeeExchangeSlippage::get() * min_buy);
+		if T::BuyFeeAsset::sell_fee_asset(who, refund, asset_id, min_buy).is_err() {
+			Self::deposit_event(RawEvent::FeeRefundNotExchanged(who.clone(), refund));
+		}
+	}
+
 	fn weight_to_fee(weight: Weight) -> BalanceOf<T> {
 		// cap the weight to the maximum defined in runtime, otherwise it will be the
 		// `Bounded` maximum of its data type, which is not desired.
@@ -506,6 +745,92 @@ where
 	}
 }
 
+impl<T: Config> Module<T>
+where
+	T::Call: Encode,
+{
+	/// Whether `sponsor` would pay the `fee` for `who` to make `call`.
+	///
+	/// Used by the runtime to report who pays in `query_info` and `query_fee_details`.
+	pub fn is_sponsored(sponsor: &T::AccountId, who: &T::AccountId, call: &T::Call, fee: BalanceOf<T>) -> bool {
+		Self::sponsored_charge(sponsor, who, call, fee).is_ok()
+	}
+
+	/// Charge the `fee` for `who` to make `call` against the sponsorship of `sponsor`
+	fn charge_sponsorship(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		call: &T::Call,
+		fee: BalanceOf<T>,
+	) -> Result<(), TransactionValidityError> {
+		let (sponsorship, usage) = Self::sponsored_charge(sponsor, who, call, fee)?;
+		<Sponsorships<T>>::insert(sponsor, sponsorship);
+		<SponsoredAccounts<T>>::insert(sponsor, who, usage);
+		Self::deposit_event(RawEvent::SponsoredFeePaid(sponsor.clone(), who.clone(), fee));
+		Ok(())
+	}
+
+	/// Credit a `refund` of the fee charged for `who` back to the sponsorship of `sponsor`
+	fn credit_sponsorship(sponsor: &T::AccountId, who: &T::AccountId, refund: BalanceOf<T>) {
+		if refund.is_zero() {
+			return;
+		}
+		<Sponsorships<T>>::mutate(sponsor, |sponsorship| {
+			if let Some(sponsorship) = sponsorship {
+				sponsorship.budget = sponsorship.budget.saturating_add(refund);
+			}
+		});
+		<SponsoredAccounts<T>>::mutate(sponsor, who, |usage| {
+			if let Some(usage) = usage {
+				usage.spent = usage.spent.saturating_sub(refund);
+			}
+		});
+	}
+
+	/// The sponsorship of `sponsor` and the usage of `who` after paying the `fee` for `call`
+	fn sponsored_charge(
+		sponsor: &T::AccountId,
+		who: &T::AccountId,
+		call: &T::Call,
+		fee: BalanceOf<T>,
+	) -> Result<
+		(
+			Sponsorship<BalanceOf<T>, T::BlockNumber>,
+			SponsoredUsage<BalanceOf<T>, T::BlockNumber>,
+		),
+		TransactionValidityError,
+	> {
+		let invalid = |code| TransactionValidityError::Invalid(InvalidTransaction::Custom(code));
+		let mut sponsorship = Self::sponsorship(sponsor).ok_or_else(|| invalid(error_code::NO_SPONSORSHIP))?;
+		let mut usage =
+			Self::sponsored_usage(sponsor, who).ok_or_else(|| invalid(error_code::ACCOUNT_NOT_SPONSORED))?;
+
+		// the outer call encodes its pallet index followed by the pallet's call index
+		let call_index = call.using_encoded(|c| (c[0], c[1]));
+		if !sponsorship.calls.contains(&call_index) {
+			return Err(invalid(error_code::CALL_NOT_SPONSORED));
+		}
+
+		let now = <frame_system::Pallet<T>>::block_number();
+		if now >= usage.period_start.saturating_add(sponsorship.period) {
+			usage = SponsoredUsage {
+				period_start: now,
+				spent: Zero::zero(),
+			};
+		}
+		usage.spent = usage.spent.saturating_add(fee);
+		if usage.spent > sponsorship.allowance {
+			return Err(invalid(error_code::SPONSORED_ALLOWANCE_EXCEEDED));
+		}
+		sponsorship.budget = sponsorship
+			.budget
+			.checked_sub(&fee)
+			.ok_or_else(|| invalid(error_code::SPONSORSHIP_BUDGET_EXCEEDED))?;
+
+		Ok((sponsorship, usage))
+	}
+}
+
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq)]
 /// The variable parts of a transaction's fees
 /// The `tip` is also excluded as it is known by the transactor/caller.
@@ -564,19 +889,39 @@ where
 pub struct ChargeTransactionPayment<T: Config> {
 	#[codec(compact)]
 	tip: BalanceOf<T>,
-	fee_exchange: Option<FeeExchange<T::AssetId, BalanceOf<T>>>,
+	fee_exchange: Option<FeeExchange<T::AssetId, BalanceOf<T>, T::AccountId>>,
 }
 
 impl<T: Config> ChargeTransactionPayment<T>
 where
-	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	T::Call: Encode + Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
 	BalanceOf<T>: Send + Sync + FixedPointOperand,
 {
 	/// utility constructor. Used only in client/factory code.
-	pub fn from(tip: BalanceOf<T>, fee_exchange: Option<FeeExchange<T::AssetId, BalanceOf<T>>>) -> Self {
+	pub fn from(tip: BalanceOf<T>, fee_exchange: Option<FeeExchange<T::AssetId, BalanceOf<T>, T::AccountId>>) -> Self {
 		Self { tip, fee_exchange }
 	}
 
+	/// The sponsor nominated to pay the fee, if any
+	pub fn sponsor(&self) -> Option<&T::AccountId> {
+		self.fee_exchange.as_ref().and_then(|exchange| exchange.sponsor())
+	}
+
+	/// The asset nominated to buy the fee with, if any
+	fn exchange_asset_id(&self) -> Option<T::AssetId> {
+		self.fee_exchange.as_ref().and_then(|exchange| exchange.asset_id())
+	}
+
+	/// The weight of the fee exchange, charged on top of the call weight when the fee is bought with another asset.
+	/// Covers buying the fee and selling any refund back in `exchange_refund`, both are swaps of the same exchange.
+	fn exchange_weight(exchanged: bool) -> Weight {
+		if exchanged {
+			T::BuyFeeAsset::buy_fee_weight().saturating_mul(2)
+		} else {
+			Zero::zero()
+		}
+	}
+
 	fn withdraw_fee(
 		&self,
 		who: &T::AccountId,
@@ -586,28 +931,51 @@ where
 	) -> Result<
 		(
 			BalanceOf<T>,
+			T::AccountId,
 			<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
+			Option<BalanceOf<T>>,
 		),
 		TransactionValidityError,
 	> {
 		let tip = self.tip;
+		let info = &DispatchInfo {
+			weight: info
+				.weight
+				.saturating_add(Self::exchange_weight(self.exchange_asset_id().is_some())),
+			..*info
+		};
 		let fee = Module::<T>::compute_fee(len as u32, info, tip);
 
-		if let Some(exchange) = &self.fee_exchange {
-			// Buy the CENNZnet fee currency paying with the user's nominated fee currency
-			T::BuyFeeAsset::buy_fee_asset(who, fee, &exchange).map_err(|e| {
-				let code = match e {
-					DispatchError::Module { message, .. } => {
-						error_code::buy_fee_asset_error_msg_to_code(message.unwrap_or("Unknown buy fee asset error"))
-					}
-					_ => error_code::UNKNOWN_BUY_FEE_ASSET,
-				};
-				TransactionValidityError::Invalid(InvalidTransaction::Custom(code))
-			})?;
-		}
+		let mut paid = None;
+		let payer = match &self.fee_exchange {
+			Some(FeeExchange::Sponsored(sponsor)) => {
+				// Sponsors pay for inclusion, not for the priority a tip buys
+				if !tip.is_zero() {
+					return Err(InvalidTransaction::Custom(error_code::SPONSORED_TIP).into());
+				}
+				// The sponsor pays the fee if its sponsorship covers the call
+				Module::<T>::charge_sponsorship(sponsor, who, call, fee)?;
+				sponsor
+			}
+			Some(exchange) => {
+				// Buy the CENNZnet fee currency paying with the user's nominated fee currency
+				let sold = T::BuyFeeAsset::buy_fee_asset(who, fee, &exchange).map_err(|e| {
+					let code = match e {
+						DispatchError::Module { message, .. } => error_code::buy_fee_asset_error_msg_to_code(
+							message.unwrap_or("Unknown buy fee asset error"),
+						),
+						_ => error_code::UNKNOWN_BUY_FEE_ASSET,
+					};
+					TransactionValidityError::Invalid(InvalidTransaction::Custom(code))
+				})?;
+				paid = Some(sold);
+				who
+			}
+			None => who,
+		};
 
-		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(who, call, info, fee, tip)
-			.map(|i| (fee, i))
+		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::withdraw_fee(payer, call, info, fee, tip)
+			.map(|i| (fee, payer.clone(), i, paid))
 	}
 
 	/// Get an appropriate priority for a transaction with the given length and info.
@@ -645,7 +1013,7 @@ impl<T: Config> sp_std::fmt::Debug for ChargeTransactionPayment<T> {
 impl<T: Config> SignedExtension for ChargeTransactionPayment<T>
 where
 	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
-	T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	T::Call: Encode + Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
 {
 	const IDENTIFIER: &'static str = "ChargeTransactionPayment";
 	type AccountId = T::AccountId;
@@ -658,6 +1026,10 @@ where
 		Self::AccountId,
 		// imbalance resulting from withdrawing the fee
 		<<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::LiquidityInfo,
+		// the asset the fee was bought with, the fee bought and the amount paid for it, if paid via exchange
+		Option<(T::AssetId, BalanceOf<T>, BalanceOf<T>)>,
+		// the beneficiary and the fee charged to the sponsorship, if paid by a sponsor
+		Option<(Self::AccountId, BalanceOf<T>)>,
 	);
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
 		Ok(())
@@ -670,7 +1042,7 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (fee, _, _, _) = self.withdraw_fee(who, call, info, len)?;
 		Ok(ValidTransaction {
 			priority: Self::get_priority(len, info, fee),
 			..Default::default()
@@ -684,8 +1056,13 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (_, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.tip, who.clone(), imbalance))
+		let (fee, payer, imbalance, paid) = self.withdraw_fee(who, call, info, len)?;
+		let exchanged = self
+			.exchange_asset_id()
+			.zip(paid)
+			.map(|(asset_id, paid)| (asset_id, fee, paid));
+		let sponsored = self.sponsor().map(|_| (who.clone(), fee));
+		Ok((self.tip, payer, imbalance, exchanged, sponsored))
 	}
 
 	fn post_dispatch(
@@ -695,9 +1072,29 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance) = pre;
+		let (tip, who, imbalance, exchanged, sponsored) = pre;
+		// the fee exchange is charged for on top of whatever weight the call ends up using
+		let exchange_weight = Self::exchange_weight(exchanged.is_some());
+		let info = &DispatchInfo {
+			weight: info.weight.saturating_add(exchange_weight),
+			..*info
+		};
+		let post_info = &PostDispatchInfo {
+			actual_weight: post_info
+				.actual_weight
+				.map(|weight| weight.saturating_add(exchange_weight)),
+			pays_fee: post_info.pays_fee,
+		};
 		let actual_fee = Module::<T>::compute_actual_fee(len as u32, info, post_info, tip);
 		T::OnChargeTransaction::correct_and_deposit_fee(&who, info, post_info, actual_fee, tip, imbalance)?;
+		if let Some((asset_id, fee, paid)) = exchanged {
+			// the refund is made in the fee asset, exchange it back to the asset the fee was bought with
+			Module::<T>::exchange_refund(&who, asset_id, fee, paid, fee.saturating_sub(actual_fee));
+		}
+		if let Some((beneficiary, fee)) = sponsored {
+			// the refund is made to the sponsor, credit it back to the sponsorship
+			Module::<T>::credit_sponsorship(&who, &beneficiary, fee.saturating_sub(actual_fee));
+		}
 		Ok(())
 	}
 }
@@ -734,8 +1131,8 @@ mod tests {
 	use crate as crml_transaction_payment;
 	use codec::Encode;
 	use frame_support::{
-		parameter_types,
-		traits::Currency,
+		assert_noop, assert_ok, parameter_types,
+		traits::{Currency, ExistenceRequirement, WithdrawReasons},
 		weights::{
 			DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo, Weight, WeightToFeeCoefficient,
 			WeightToFeeCoefficients, WeightToFeePolynomial,
@@ -763,17 +1160,22 @@ mod tests {
 		{
 			System: system::{Pallet, Call, Config, Storage, Event<T>},
 			Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-			TransactionPayment: crml_transaction_payment::{Pallet, Storage},
+			TransactionPayment: crml_transaction_payment::{Pallet, Call, Storage, Event<T>},
 		}
 	);
 
 	const CALL: &<Runtime as frame_system::Config>::Call =
 		&tests::Call::Balances(BalancesCall::transfer { dest: 2, value: 69 });
+	const FEE_ASSET_ID: u32 = 0;
 	const VALID_ASSET_TO_BUY_FEE: u32 = 1;
 	const INVALID_ASSET_TO_BUY_FEE: u32 = 2;
+	/// An asset which can buy the fee asset but not be bought back with it
+	const ILLIQUID_ASSET_TO_BUY_FEE: u32 = 3;
 
 	thread_local! {
 		static EXTRINSIC_BASE_WEIGHT: RefCell<u64> = RefCell::new(0);
+		static FEE_ASSET_SOLD: RefCell<u64> = RefCell::new(0);
+		static FEE_ASSET_MIN_BUY: RefCell<u64> = RefCell::new(0);
 	}
 
 	pub struct BlockWeights;
@@ -795,6 +1197,9 @@ mod tests {
 		pub const BlockHashCount: u64 = 250;
 		pub static TransactionByteFee: u64 = 1;
 		pub static WeightToFee: u64 = 1;
+		pub const MaxSponsoredCalls: u32 = 2;
+		pub const MaxSponsoredAccounts: u32 = 3;
+		pub const FeeExchangeSlippage: Permill = Permill::from_percent(10);
 	}
 
 	impl frame_system::Config for Runtime {
@@ -856,15 +1261,19 @@ mod tests {
 	pub struct MockBuyFeeAsset;
 	impl BuyFeeAsset for MockBuyFeeAsset {
 		type AccountId = u64;
+		type AssetId = u32;
 		type Balance = u64;
-		type FeeExchange = FeeExchange<<Runtime as Config>::AssetId, Self::Balance>;
+		type FeeExchange = FeeExchange<<Runtime as Config>::AssetId, Self::Balance, Self::AccountId>;
 		fn buy_fee_asset(
 			who: &Self::AccountId,
 			amount: Self::Balance,
 			exchange_op: &Self::FeeExchange,
 		) -> sp_std::result::Result<Self::Balance, DispatchError> {
-			if exchange_op.asset_id() == VALID_ASSET_TO_BUY_FEE {
-				if exchange_op.max_payment() == 0 {
+			if matches!(
+				exchange_op.asset_id(),
+				Some(VALID_ASSET_TO_BUY_FEE | ILLIQUID_ASSET_TO_BUY_FEE)
+			) {
+				if exchange_op.max_payment() == Some(0) {
 					return Err(DispatchError::Module {
 						index: 1,
 						error: 15,
@@ -882,18 +1291,57 @@ mod tests {
 			}
 			Ok(amount)
 		}
+		fn sell_fee_asset(
+			who: &Self::AccountId,
+			amount: Self::Balance,
+			asset_id: Self::AssetId,
+			min_buy: Self::Balance,
+		) -> sp_std::result::Result<Self::Balance, DispatchError> {
+			FEE_ASSET_MIN_BUY.with(|v| *v.borrow_mut() = min_buy);
+			if asset_id != VALID_ASSET_TO_BUY_FEE {
+				return Err(DispatchError::Module {
+					index: 1,
+					error: 3,
+					message: Some("InsufficientLiquidity"),
+				});
+			}
+			// sell fee asset at a 1:1 ratio
+			let _ = Balances::withdraw(who, amount, WithdrawReasons::all(), ExistenceRequirement::KeepAlive)?;
+			FEE_ASSET_SOLD.with(|v| *v.borrow_mut() += amount);
+			Ok(amount)
+		}
+		fn buy_fee_asset_price(asset_id: Self::AssetId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+			if asset_id == VALID_ASSET_TO_BUY_FEE {
+				// price fee asset at a 1:1 ratio
+				Ok(amount)
+			} else {
+				Err(DispatchError::Module {
+					index: 1,
+					error: 33,
+					message: Some("InvalidAssetId"),
+				})
+			}
+		}
+		fn fee_asset_id() -> Self::AssetId {
+			FEE_ASSET_ID
+		}
 		fn buy_fee_weight() -> Weight {
 			Zero::zero()
 		}
 	}
 
 	impl Config for Runtime {
+		type Event = Event;
 		type AssetId = u32;
 		type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 		type TransactionByteFee = TransactionByteFee;
 		type WeightToFee = WeightToFee;
 		type FeeMultiplierUpdate = ();
 		type BuyFeeAsset = MockBuyFeeAsset;
+		type MaxSponsoredCalls = MaxSponsoredCalls;
+		type MaxSponsoredAccounts = MaxSponsoredAccounts;
+		type WeightInfo = ();
+		type FeeExchangeSlippage = FeeExchangeSlippage;
 	}
 
 	pub struct ExtBuilder {
@@ -935,6 +1383,7 @@ mod tests {
 			EXTRINSIC_BASE_WEIGHT.with(|v| *v.borrow_mut() = self.base_weight);
 			TRANSACTION_BYTE_FEE.with(|v| *v.borrow_mut() = self.byte_fee);
 			WEIGHT_TO_FEE.with(|v| *v.borrow_mut() = self.weight_to_fee);
+			FEE_ASSET_SOLD.with(|v| *v.borrow_mut() = 0);
 		}
 		pub fn build(self) -> sp_io::TestExternalities {
 			self.set_constants();
@@ -1166,12 +1615,51 @@ mod tests {
 						class: info.class,
 						partial_fee: 5 * 2 /* base * weight_fee */
 								+ len as u64  /* len * 1 */
-							+ info.weight.min(BlockWeights::get().max_block) as u64 * 2 * 3 / 2 /* weight */
+							+ info.weight.min(BlockWeights::get().max_block) as u64 * 2 * 3 / 2, /* weight */
+						sponsored: false,
 					},
 				);
 			});
 	}
 
+	#[test]
+	fn query_fee_in_asset_works() {
+		let call = tests::Call::Balances(BalancesCall::transfer { dest: 2, value: 69 });
+		let xt = TestXt::new(call, Some((111111, ())));
+		let info = xt.get_dispatch_info();
+		let len = xt.encode().len() as u32;
+		ExtBuilder::default()
+			.base_weight(5)
+			.weight_fee(2)
+			.build()
+			.execute_with(|| {
+				let fee = 5 * 2 + len as u64 + info.weight.min(BlockWeights::get().max_block) as u64 * 2;
+				assert_eq!(
+					TransactionPayment::query_fee_in_asset(xt.clone(), len, VALID_ASSET_TO_BUY_FEE),
+					Ok(FeeInAsset {
+						estimated_fee: fee,
+						max_payment: fee + Permill::from_percent(10) * fee,
+					}),
+				);
+				// no exchange or slippage for the fee asset itself
+				assert_eq!(
+					TransactionPayment::query_fee_in_asset(xt.clone(), len, FEE_ASSET_ID),
+					Ok(FeeInAsset {
+						estimated_fee: fee,
+						max_payment: fee,
+					}),
+				);
+				assert_eq!(
+					TransactionPayment::query_fee_in_asset(xt, len, INVALID_ASSET_TO_BUY_FEE),
+					Err(DispatchError::Module {
+						index: 1,
+						error: 33,
+						message: Some("InvalidAssetId"),
+					}),
+				);
+			});
+	}
+
 	#[test]
 	fn compute_fee_works_without_multiplier() {
 		ExtBuilder::default()
@@ -1494,6 +1982,92 @@ mod tests {
 			})
 	}
 
+	#[test]
+	fn fee_exchange_refund_is_exchanged_back() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				let len = 10;
+				let fee_exchange = FeeExchange::new_v1(VALID_ASSET_TO_BUY_FEE, 100_000);
+
+				// 5 base fee, 10 byte fee, 100 weight fee are bought and paid
+				let pre = ChargeTransactionPayment::<Runtime>::from(0, Some(fee_exchange))
+					.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+					.unwrap();
+				assert_eq!(Balances::free_balance(1), 100);
+
+				assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+					pre,
+					&info_from_weight(100),
+					&post_info_from_weight(60),
+					len,
+					&Ok(())
+				));
+				// the 40 weight fee refund is sold back rather than kept in the fee asset
+				assert_eq!(Balances::free_balance(1), 100);
+				assert_eq!(FEE_ASSET_SOLD.with(|v| *v.borrow()), 40);
+				// for no less than its share of the 115 paid, less 10% slippage
+				assert_eq!(FEE_ASSET_MIN_BUY.with(|v| *v.borrow()), 36);
+			});
+	}
+
+	#[test]
+	fn fee_exchange_without_refund_exchanges_nothing_back() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				let len = 10;
+				let fee_exchange = FeeExchange::new_v1(VALID_ASSET_TO_BUY_FEE, 100_000);
+
+				let pre = ChargeTransactionPayment::<Runtime>::from(0, Some(fee_exchange))
+					.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+					.unwrap();
+				assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+					pre,
+					&info_from_weight(100),
+					&default_post_info(),
+					len,
+					&Ok(())
+				));
+				assert_eq!(Balances::free_balance(1), 100);
+				assert_eq!(FEE_ASSET_SOLD.with(|v| *v.borrow()), 0);
+			});
+	}
+
+	#[test]
+	fn fee_exchange_refund_stays_in_fee_asset_when_exchange_fails() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				System::set_block_number(1);
+				let len = 10;
+				let fee_exchange = FeeExchange::new_v1(ILLIQUID_ASSET_TO_BUY_FEE, 100_000);
+
+				let pre = ChargeTransactionPayment::<Runtime>::from(0, Some(fee_exchange))
+					.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+					.unwrap();
+				assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+					pre,
+					&info_from_weight(100),
+					&post_info_from_weight(60),
+					len,
+					&Ok(())
+				));
+				// the refund is kept in the fee asset
+				assert_eq!(Balances::free_balance(1), 100 + 40);
+				assert_eq!(FEE_ASSET_SOLD.with(|v| *v.borrow()), 0);
+				assert!(System::events()
+					.iter()
+					.any(|record| record.event == Event::TransactionPayment(RawEvent::FeeRefundNotExchanged(1, 40))));
+			});
+	}
+
 	#[test]
 	fn rejects_valid_currency_fee_exchange_with_zero_max_payment() {
 		ExtBuilder::default()
@@ -1512,4 +2086,272 @@ mod tests {
 				);
 			})
 	}
+
+	/// Sponsor 1 and 2's balance transfers from account 3
+	fn setup_sponsorship(budget: u64, allowance: u64, period: u64) {
+		let call_index = CALL.using_encoded(|c| (c[0], c[1]));
+		assert_ok!(TransactionPayment::set_sponsorship(
+			Origin::signed(3),
+			budget,
+			vec![call_index],
+			allowance,
+			period
+		));
+		assert_ok!(TransactionPayment::add_sponsored_accounts(
+			Origin::signed(3),
+			vec![1, 2]
+		));
+	}
+
+	#[test]
+	fn sponsored_fee_is_paid_by_sponsor() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				System::set_block_number(1);
+				setup_sponsorship(1_000, 500, 10);
+				let len = 10;
+				let fee_exchange = FeeExchange::Sponsored(3);
+
+				let pre = ChargeTransactionPayment::<Runtime>::from(0, Some(fee_exchange))
+					.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+					.unwrap();
+				assert_eq!(Balances::free_balance(1), 100);
+				assert_eq!(Balances::free_balance(3), 300 - 5 - 10 - 100);
+
+				assert_ok!(ChargeTransactionPayment::<Runtime>::post_dispatch(
+					pre,
+					&info_from_weight(100),
+					&post_info_from_weight(50),
+					len,
+					&Ok(())
+				));
+				// the unused weight fee is refunded to the sponsor
+				assert_eq!(Balances::free_balance(1), 100);
+				assert_eq!(Balances::free_balance(3), 300 - 5 - 10 - 50);
+
+				// the actual fee is charged to the sponsorship, the refund is credited back
+				assert_eq!(TransactionPayment::sponsorship(3).unwrap().budget, 1_000 - 65);
+				assert_eq!(
+					TransactionPayment::sponsored_usage(3, 1),
+					Some(SponsoredUsage {
+						period_start: 0,
+						spent: 65
+					})
+				);
+				assert!(System::events()
+					.iter()
+					.any(|record| record.event == Event::TransactionPayment(RawEvent::SponsoredFeePaid(3, 1, 115))));
+			});
+	}
+
+	#[test]
+	fn sponsored_transactions_cannot_tip() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				setup_sponsorship(1_000, 500, 10);
+				let len = 10;
+
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(1, Some(FeeExchange::Sponsored(3)))
+						.pre_dispatch(&1, CALL, &info_from_weight(100), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::SPONSORED_TIP)
+				);
+				assert_eq!(Balances::free_balance(1), 100);
+				assert_eq!(Balances::free_balance(3), 300);
+				assert_eq!(TransactionPayment::sponsorship(3).unwrap().budget, 1_000);
+			});
+	}
+
+	#[test]
+	fn sponsorship_only_pays_for_allowed_calls_and_accounts() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				setup_sponsorship(1_000, 500, 10);
+				let len = 10;
+				let other_call: &<Runtime as frame_system::Config>::Call =
+					&Call::System(system::Call::remark { remark: vec![] });
+
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3)))
+						.validate(&1, other_call, &info_from_weight(5), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::CALL_NOT_SPONSORED)
+				);
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3)))
+						.validate(&4, CALL, &info_from_weight(5), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::ACCOUNT_NOT_SPONSORED)
+				);
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(4)))
+						.validate(&1, CALL, &info_from_weight(5), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::NO_SPONSORSHIP)
+				);
+
+				assert_ok!(TransactionPayment::remove_sponsored_accounts(
+					Origin::signed(3),
+					vec![1]
+				));
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3)))
+						.validate(&1, CALL, &info_from_weight(5), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::ACCOUNT_NOT_SPONSORED)
+				);
+				assert_ok!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3))).validate(
+						&2,
+						CALL,
+						&info_from_weight(5),
+						len
+					)
+				);
+
+				assert_ok!(TransactionPayment::remove_sponsorship(Origin::signed(3)));
+				assert!(TransactionPayment::sponsored_usage(3, 2).is_none());
+				assert_eq!(
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3)))
+						.validate(&2, CALL, &info_from_weight(5), len)
+						.err()
+						.unwrap(),
+					error_from_code(error_code::NO_SPONSORSHIP)
+				);
+			});
+	}
+
+	#[test]
+	fn sponsorship_limits_each_account_per_period() {
+		ExtBuilder::default()
+			.balance_factor(10)
+			.base_weight(5)
+			.build()
+			.execute_with(|| {
+				// each transaction costs 20, each account may spend 40 per 10 blocks
+				setup_sponsorship(100, 40, 10);
+				let len = 10;
+				let charge = |who: u64| {
+					ChargeTransactionPayment::<Runtime>::from(0, Some(FeeExchange::Sponsored(3)))
+						.pre_dispatch(&who, CALL, &info_from_weight(5), len)
+						.map(|_| ())
+				};
+
+				System::set_block_number(1);
+				assert_ok!(charge(1));
+				assert_ok!(charge(1));
+				assert_eq!(
+					charge(1).err().unwrap(),
+					error_from_code(error_code::SPONSORED_ALLOWANCE_EXCEEDED)
+				);
+				// other accounts have their own allowance
+				assert_ok!(charge(2));
+
+				// the allowance renews with the next period
+				System::set_block_number(11);
+				assert_ok!(charge(1));
+				assert_eq!(
+					TransactionPayment::sponsored_usage(3, 1),
+					Some(SponsoredUsage {
+						period_start: 11,
+						spent: 20
+					})
+				);
+
+				// 20 of the budget remains
+				assert_ok!(charge(2));
+				assert_eq!(
+					charge(2).err().unwrap(),
+					error_from_code(error_code::SPONSORSHIP_BUDGET_EXCEEDED)
+				);
+				assert_eq!(TransactionPayment::sponsorship(3).unwrap().budget, 0);
+				assert_eq!(Balances::free_balance(3), 300 - 5 * 20);
+			});
+	}
+
+	#[test]
+	fn set_sponsorship_validates_terms() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert_noop!(
+				TransactionPayment::set_sponsorship(Origin::signed(3), 100, vec![(0, 0), (0, 1), (1, 0)], 10, 10),
+				Error::<Runtime>::TooManySponsoredCalls
+			);
+			assert_noop!(
+				TransactionPayment::set_sponsorship(Origin::signed(3), 100, vec![(1, 0)], 10, 0),
+				Error::<Runtime>::InvalidSponsorshipPeriod
+			);
+			assert_noop!(
+				TransactionPayment::add_sponsored_accounts(Origin::signed(3), vec![1]),
+				Error::<Runtime>::NoSponsorship
+			);
+			assert_noop!(
+				TransactionPayment::remove_sponsorship(Origin::signed(3)),
+				Error::<Runtime>::NoSponsorship
+			);
+		});
+	}
+
+	#[test]
+	fn sponsored_accounts_are_bounded() {
+		ExtBuilder::default().build().execute_with(|| {
+			setup_sponsorship(100, 40, 10);
+			assert_eq!(TransactionPayment::sponsored_account_count(3), 2);
+
+			// re-adding a beneficiary doesn't count towards the limit
+			assert_ok!(TransactionPayment::add_sponsored_accounts(
+				Origin::signed(3),
+				vec![1, 4]
+			));
+			assert_eq!(TransactionPayment::sponsored_account_count(3), 3);
+			assert_noop!(
+				TransactionPayment::add_sponsored_accounts(Origin::signed(3), vec![5]),
+				Error::<Runtime>::TooManySponsoredAccounts
+			);
+			assert_noop!(
+				TransactionPayment::add_sponsored_accounts(Origin::signed(3), vec![1, 2, 4, 4]),
+				Error::<Runtime>::TooManySponsoredAccounts
+			);
+
+			// removing beneficiaries frees their places
+			assert_ok!(TransactionPayment::remove_sponsored_accounts(
+				Origin::signed(3),
+				vec![4, 6]
+			));
+			assert_eq!(TransactionPayment::sponsored_account_count(3), 2);
+			assert_ok!(TransactionPayment::add_sponsored_accounts(Origin::signed(3), vec![5]));
+
+			assert_ok!(TransactionPayment::remove_sponsorship(Origin::signed(3)));
+			assert_eq!(TransactionPayment::sponsored_account_count(3), 0);
+			assert!(TransactionPayment::sponsored_usage(3, 5).is_none());
+		});
+	}
+
+	#[test]
+	fn is_sponsored_reflects_who_pays() {
+		ExtBuilder::default().balance_factor(10).build().execute_with(|| {
+			setup_sponsorship(100, 40, 10);
+			assert!(TransactionPayment::is_sponsored(&3, &1, CALL, 40));
+			// over the allowance, unlisted account, no sponsorship
+			assert!(!TransactionPayment::is_sponsored(&3, &1, CALL, 41));
+			assert!(!TransactionPayment::is_sponsored(&3, &4, CALL, 10));
+			assert!(!TransactionPayment::is_sponsored(&4, &1, CALL, 10));
+			// a query doesn't charge the sponsorship
+			assert_eq!(TransactionPayment::sponsorship(3).unwrap().budget, 100);
+		});
+	}
 }
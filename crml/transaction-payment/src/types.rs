@@ -61,7 +61,8 @@ impl<Balance: AtLeast32BitUnsigned + Copy> InclusionFee<Balance> {
 ///   - (Optional) `inclusion_fee`: Only the `Pays::Yes` transaction can have the inclusion fee.
 ///   - `tip`: If included in the transaction, the tip will be added on top. Only
 ///     signed transactions can have a tip.
-#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+///   - `sponsored`: Whether the fee is paid by the sponsor the transaction names rather than its signer.
+#[derive(Encode, Clone, Eq, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct FeeDetails<Balance> {
@@ -70,6 +71,24 @@ pub struct FeeDetails<Balance> {
 	// Do not serialize and deserialize `tip` as we actually can not pass any tip to the RPC.
 	#[cfg_attr(feature = "std", serde(skip))]
 	pub tip: Balance,
+	/// Whether the fee is paid by the sponsor the transaction names rather than its signer.
+	#[cfg_attr(feature = "std", serde(default))]
+	pub sponsored: bool,
+}
+
+impl<Balance: Decode> Decode for FeeDetails<Balance> {
+	// Custom decode implementation to handle `FeeDetails` from runtimes predating fee sponsorship
+	fn decode<I: codec::Input>(value: &mut I) -> Result<Self, codec::Error> {
+		let inclusion_fee = Option::<InclusionFee<Balance>>::decode(value)?;
+		let tip = Balance::decode(value)?;
+		let sponsored = decode_sponsored(value)?;
+
+		Ok(Self {
+			inclusion_fee,
+			tip,
+			sponsored,
+		})
+	}
 }
 
 impl<Balance: AtLeast32BitUnsigned + Copy> FeeDetails<Balance> {
@@ -87,6 +106,17 @@ impl<Balance: AtLeast32BitUnsigned + Copy> FeeDetails<Balance> {
 	}
 }
 
+/// The fee of a transaction in an asset other than the fee asset, bought through `FeeExchange`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeInAsset<Balance> {
+	/// The estimated fee in the asset at current exchange prices.
+	pub estimated_fee: Balance,
+	/// A recommended `FeeExchange` `max_payment`, allowing the price and fee to move before dispatch.
+	pub max_payment: Balance,
+}
+
 /// Information related to a dispatchable's class, weight, and fee that can be queried from the runtime.
 #[derive(Eq, PartialEq, Encode, Default, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -104,6 +134,9 @@ pub struct RuntimeDispatchInfo<Balance> {
 	/// depends on the signature (i.e. depends on a `SignedExtension`).
 	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
 	pub partial_fee: Balance,
+	/// Whether the fee is paid by the sponsor the transaction names rather than its signer.
+	#[cfg_attr(feature = "std", serde(default))]
+	pub sponsored: bool,
 }
 
 // The weight type used by legacy runtimes (pre-frame 2.0.0 versions)
@@ -129,15 +162,25 @@ impl<Balance: Decode> Decode for RuntimeDispatchInfo<Balance> {
 
 		let class = DispatchClass::decode(value)?;
 		let partial_fee = Balance::decode(value)?;
+		let sponsored = decode_sponsored(value)?;
 
 		return Ok(Self {
 			weight,
 			class,
 			partial_fee,
+			sponsored,
 		});
 	}
 }
 
+/// Decode the trailing `sponsored` flag, absent from the encoding of runtimes predating fee sponsorship
+fn decode_sponsored<I: codec::Input>(value: &mut I) -> Result<bool, codec::Error> {
+	match value.remaining_len()? {
+		Some(0) => Ok(false),
+		_ => bool::decode(value),
+	}
+}
+
 #[cfg(feature = "std")]
 mod serde_balance {
 	use serde::{Deserialize, Deserializer, Serializer};
@@ -165,6 +208,7 @@ mod tests {
 		assert_eq!(decoded.weight, legacy_dispatch_info.0 as u64);
 		assert_eq!(decoded.class, legacy_dispatch_info.1);
 		assert_eq!(decoded.partial_fee, legacy_dispatch_info.2);
+		assert!(!decoded.sponsored);
 	}
 
 	#[test]
@@ -174,6 +218,7 @@ mod tests {
 			weight: 1,
 			class: DispatchClass::Normal,
 			partial_fee: 1_u128,
+			sponsored: true,
 		};
 		let decoded =
 			RuntimeDispatchInfo::<u128>::decode(&mut &runtime_dispatch_info.encode()[..]).expect("it decodes");
@@ -186,9 +231,10 @@ mod tests {
 			weight: 5,
 			class: DispatchClass::Normal,
 			partial_fee: 1_000_000_u64,
+			sponsored: false,
 		};
 
-		let json_str = r#"{"weight":5,"class":"normal","partialFee":"1000000"}"#;
+		let json_str = r#"{"weight":5,"class":"normal","partialFee":"1000000","sponsored":false}"#;
 
 		assert_eq!(serde_json::to_string(&info).unwrap(), json_str);
 		assert_eq!(
@@ -206,9 +252,11 @@ mod tests {
 			weight: 5,
 			class: DispatchClass::Normal,
 			partial_fee: u128::max_value(),
+			sponsored: true,
 		};
 
-		let json_str = r#"{"weight":5,"class":"normal","partialFee":"340282366920938463463374607431768211455"}"#;
+		let json_str =
+			r#"{"weight":5,"class":"normal","partialFee":"340282366920938463463374607431768211455","sponsored":true}"#;
 
 		assert_eq!(serde_json::to_string(&info).unwrap(), json_str);
 		assert_eq!(
@@ -219,4 +267,33 @@ mod tests {
 		// should not panic
 		serde_json::to_value(&info).unwrap();
 	}
+
+	#[test]
+	fn it_decodes_pre_sponsorship_dispatch_info_and_fee_details() {
+		// runtimes predating fee sponsorship don't encode the `sponsored` flag
+		let dispatch_info = (1_u64, DispatchClass::Normal, 1_u128);
+		let decoded = RuntimeDispatchInfo::<u128>::decode(&mut &dispatch_info.encode()[..]).expect("it decodes");
+		assert_eq!(decoded.partial_fee, dispatch_info.2);
+		assert!(!decoded.sponsored);
+
+		let fee_details = (Option::<InclusionFee<u128>>::None, 5_u128);
+		let decoded = FeeDetails::<u128>::decode(&mut &fee_details.encode()[..]).expect("it decodes");
+		assert_eq!(decoded.tip, fee_details.1);
+		assert!(!decoded.sponsored);
+	}
+
+	#[test]
+	fn it_decodes_sponsored_fee_details() {
+		let fee_details = FeeDetails {
+			inclusion_fee: Some(InclusionFee {
+				base_fee: 1_u128,
+				len_fee: 2,
+				adjusted_weight_fee: 3,
+			}),
+			tip: 0,
+			sponsored: true,
+		};
+		let decoded = FeeDetails::<u128>::decode(&mut &fee_details.encode()[..]).expect("it decodes");
+		assert_eq!(decoded, fee_details);
+	}
 }
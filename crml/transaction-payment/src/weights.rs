@@ -0,0 +1,62 @@
+// This file is part of CENNZnet.
+
+// Copyright (C) 2019-2021 Parity Technologies (UK) Ltd. and Centrality Investments Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for crml_transaction_payment
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
+
+/// Transaction payment module weights
+pub trait WeightInfo {
+	fn set_sponsorship(c: u32) -> Weight;
+	fn remove_sponsorship(n: u32) -> Weight;
+	fn add_sponsored_accounts(n: u32) -> Weight;
+	fn remove_sponsored_accounts(n: u32) -> Weight;
+}
+
+impl WeightInfo for () {
+	fn set_sponsorship(c: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((52_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_sponsorship(n: u32) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add((1_310_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn add_sponsored_accounts(n: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((3_470_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+	fn remove_sponsored_accounts(n: u32) -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add((3_120_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+}
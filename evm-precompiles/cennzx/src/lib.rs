@@ -19,15 +19,29 @@ extern crate alloc;
 
 use cennznet_primitives::{
 	traits::BuyFeeAsset,
-	types::{AccountId, AssetId, Balance, FeeExchange, FeeExchangeV1},
+	types::{AccountId, AssetId, Balance, BlockNumber, FeeExchange, FeeExchangeV1},
 };
+use crml_support::{Exchange, FlashSwap, PriceOracle};
 use fp_evm::{Context, ExitSucceed, PrecompileFailure, PrecompileOutput};
 use pallet_evm::{AddressMapping, ExitRevert, GasWeightMapping, Precompile};
 use pallet_evm_precompiles_erc20::Erc20IdConversion;
-use precompile_utils::{Address, EvmDataReader, EvmResult, FunctionModifier, Gasometer};
+use precompile_utils::{
+	keccak256, Address, Bytes, EvmDataReader, EvmDataWriter, EvmResult, FunctionModifier, Gasometer, LogsBuilder,
+	RuntimeHelper,
+};
 use sp_core::{H160, U256};
-use sp_runtime::SaturatedConversion;
-use sp_std::marker::PhantomData;
+use sp_runtime::DispatchError;
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*, vec};
+
+/// Solidity selector of the Swap log, which is the Keccak of the Log signature
+pub const SELECTOR_LOG_SWAP: [u8; 32] = keccak256!("Swap(address,address,address,address,uint256,uint256)");
+
+/// Solidity selector of the AddLiquidity log, which is the Keccak of the Log signature
+pub const SELECTOR_LOG_ADD_LIQUIDITY: [u8; 32] = keccak256!("AddLiquidity(address,address,uint256,uint256,uint256)");
+
+/// Solidity selector of the RemoveLiquidity log, which is the Keccak of the Log signature
+pub const SELECTOR_LOG_REMOVE_LIQUIDITY: [u8; 32] =
+	keccak256!("RemoveLiquidity(address,address,uint256,uint256,uint256)");
 
 #[precompile_utils::generate_function_selector]
 #[derive(Debug, PartialEq)]
@@ -35,17 +49,48 @@ pub enum Action {
 	/// Swap some input asset for some exact CPAY amount, defining a limit on the max. input
 	/// (assetIn, exactCpayOut, maxAssetIn)
 	SwapForExactCPAY = "swapForExactCPAY(address,uint128,uint256)",
+	/// Time-weighted average prices of an asset's exchange over some blocks, scaled by 10^18
+	/// (asset, window) returns (assetPriceInCore, corePriceInAsset)
+	Twap = "twap(address,uint32)",
+	/// Borrow some asset from an exchange, calling back `msg.sender` with `data` before repayment is taken
+	/// (assetToBorrow, borrowAmount, assetToRepay, data) returns (repayAmount)
+	FlashSwap = "flashSwap(address,uint256,address,bytes)",
+	/// Sell an exact amount of the first asset in `path` for the last, the assets bought are sent to `to`
+	/// (amountIn, amountOutMin, path, to) returns (amounts)
+	SwapExactTokensForTokens = "swapExactTokensForTokens(uint256,uint256,address[],address)",
+	/// Buy an exact amount of the last asset in `path` with the first, the assets bought are sent to `to`
+	/// (amountOut, amountInMax, path, to) returns (amounts)
+	SwapTokensForExactTokens = "swapTokensForExactTokens(uint256,uint256,address[],address)",
+	/// Deposit CPAY and an asset at the exchange ratio to mint liquidity
+	/// (asset, minLiquidity, maxAssetAmount, coreAmount) returns (assetAmount, liquidity)
+	AddLiquidity = "addLiquidity(address,uint256,uint256,uint256)",
+	/// Burn liquidity to withdraw CPAY and an asset at the exchange ratio
+	/// (asset, liquidity, minAssetWithdraw, minCoreWithdraw) returns (coreAmount, assetAmount)
+	RemoveLiquidity = "removeLiquidity(address,uint256,uint256,uint256)",
+	/// The amount of `assetOut` received for selling `amountIn` of `assetIn`
+	/// (amountIn, assetIn, assetOut) returns (amountOut)
+	GetAmountOut = "getAmountOut(uint256,address,address)",
+	/// The amount of `assetIn` paid for buying `amountOut` of `assetOut`
+	/// (amountOut, assetIn, assetOut) returns (amountIn)
+	GetAmountIn = "getAmountIn(uint256,address,address)",
+	/// The reserves of an asset's exchange
+	/// (asset) returns (coreReserve, assetReserve)
+	GetReserves = "getReserves(address)",
 }
 
 /// Provides access to the state oracle pallet
-pub struct CennzxPrecompile<T, U, G, C>(PhantomData<(T, U, G, C)>);
+pub struct CennzxPrecompile<T, U, G, C, R>(PhantomData<(T, U, G, C, R)>);
 
-impl<T, U, G, C> Precompile for CennzxPrecompile<T, U, G, C>
+impl<T, U, G, C, R> Precompile for CennzxPrecompile<T, U, G, C, R>
 where
-	T: BuyFeeAsset<AccountId = AccountId, Balance = Balance, FeeExchange = FeeExchange<AssetId, Balance>>,
+	T: BuyFeeAsset<AccountId = AccountId, Balance = Balance, FeeExchange = FeeExchange<AssetId, Balance>>
+		+ PriceOracle<AssetId = AssetId, BlockNumber = BlockNumber>
+		+ FlashSwap<AccountId = AccountId, AssetId = AssetId, Balance = Balance>
+		+ Exchange<AccountId = AccountId, AssetId = AssetId, Balance = Balance>,
 	U: AddressMapping<AccountId>,
 	G: GasWeightMapping,
 	C: Erc20IdConversion<EvmId = Address, RuntimeId = AssetId>,
+	R: pallet_evm::Config,
 {
 	fn execute(
 		input: &[u8],
@@ -67,6 +112,15 @@ where
 			is_static,
 			match selector {
 				Action::SwapForExactCPAY => FunctionModifier::NonPayable,
+				Action::Twap => FunctionModifier::View,
+				Action::FlashSwap => FunctionModifier::NonPayable,
+				Action::SwapExactTokensForTokens => FunctionModifier::NonPayable,
+				Action::SwapTokensForExactTokens => FunctionModifier::NonPayable,
+				Action::AddLiquidity => FunctionModifier::NonPayable,
+				Action::RemoveLiquidity => FunctionModifier::NonPayable,
+				Action::GetAmountOut => FunctionModifier::View,
+				Action::GetAmountIn => FunctionModifier::View,
+				Action::GetReserves => FunctionModifier::View,
 			},
 		) {
 			return Err(err);
@@ -74,16 +128,29 @@ where
 
 		match selector {
 			Action::SwapForExactCPAY => Self::swap_for_exact_cpay(input, gasometer, &context.caller),
+			Action::Twap => Self::twap(input, gasometer),
+			Action::FlashSwap => Self::flash_swap(input, gasometer, context),
+			Action::SwapExactTokensForTokens => Self::swap_exact_tokens_for_tokens(input, gasometer, context),
+			Action::SwapTokensForExactTokens => Self::swap_tokens_for_exact_tokens(input, gasometer, context),
+			Action::AddLiquidity => Self::add_liquidity(input, gasometer, context),
+			Action::RemoveLiquidity => Self::remove_liquidity(input, gasometer, context),
+			Action::GetAmountOut => Self::get_amount_out(input, gasometer),
+			Action::GetAmountIn => Self::get_amount_in(input, gasometer),
+			Action::GetReserves => Self::get_reserves(input, gasometer),
 		}
 	}
 }
 
-impl<T, U, G, C> CennzxPrecompile<T, U, G, C>
+impl<T, U, G, C, R> CennzxPrecompile<T, U, G, C, R>
 where
-	T: BuyFeeAsset<AccountId = AccountId, Balance = Balance, FeeExchange = FeeExchange<AssetId, Balance>>,
+	T: BuyFeeAsset<AccountId = AccountId, Balance = Balance, FeeExchange = FeeExchange<AssetId, Balance>>
+		+ PriceOracle<AssetId = AssetId, BlockNumber = BlockNumber>
+		+ FlashSwap<AccountId = AccountId, AssetId = AssetId, Balance = Balance>
+		+ Exchange<AccountId = AccountId, AssetId = AssetId, Balance = Balance>,
 	U: AddressMapping<AccountId>,
 	G: GasWeightMapping,
 	C: Erc20IdConversion<EvmId = Address, RuntimeId = AssetId>,
+	R: pallet_evm::Config,
 {
 	fn swap_for_exact_cpay(
 		input: &mut EvmDataReader,
@@ -97,24 +164,22 @@ where
 		// it is not supported by cennzx
 		let asset_id = C::evm_id_to_runtime_id(input_asset).ok_or(gasometer.revert("unsupported asset"))?;
 		// in CPAY units
-		let exact_output: U256 = input.read::<U256>(gasometer)?.into();
+		let exact_output = Self::read_balance(input, gasometer)?;
 		// in ASSET units
-		let max_input: U256 = input.read::<U256>(gasometer)?.into();
+		let max_input = Self::read_balance(input, gasometer)?;
 
 		let fee_exchange = FeeExchange::V1(FeeExchangeV1::<AssetId, Balance> {
 			asset_id,
-			max_payment: max_input.saturated_into(),
+			max_payment: max_input,
 		});
 
 		let caller = U::into_account_id(*caller);
 
 		gasometer.record_cost(G::weight_to_gas(T::buy_fee_weight()))?;
-		let _ = T::buy_fee_asset(&caller, exact_output.saturated_into(), &fee_exchange).map_err(|err| {
-			PrecompileFailure::Revert {
-				exit_status: ExitRevert::Reverted,
-				output: alloc::format!("swap failed: {:?}", err.stripped()).as_bytes().to_vec(),
-				cost: 0_u64,
-			}
+		let _ = T::buy_fee_asset(&caller, exact_output, &fee_exchange).map_err(|err| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: alloc::format!("swap failed: {:?}", err.stripped()).as_bytes().to_vec(),
+			cost: 0_u64,
 		})?;
 
 		// Build output.
@@ -125,4 +190,335 @@ where
 			logs: Default::default(),
 		})
 	}
+	fn twap(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+		input.expect_arguments(gasometer, 2)?;
+
+		let asset: Address = input.read::<Address>(gasometer)?.into();
+		let asset_id = C::evm_id_to_runtime_id(asset).ok_or(gasometer.revert("unsupported asset"))?;
+		let window: u32 = input.read::<u32>(gasometer)?.into();
+
+		gasometer.record_cost(G::weight_to_gas(T::twap_weight()))?;
+		let (asset_price, core_price) =
+			T::twap(asset_id, window).ok_or(gasometer.revert("insufficient price history"))?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(asset_price).write(core_price).build(),
+			logs: Default::default(),
+		})
+	}
+	/// Flash swap on behalf of the caller, which receives the loan and the callback and must hold the repayment once
+	/// the callback returns
+	fn flash_swap(
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		input.expect_arguments(gasometer, 4)?;
+
+		let asset_to_borrow: Address = input.read::<Address>(gasometer)?.into();
+		let asset_to_borrow = C::evm_id_to_runtime_id(asset_to_borrow).ok_or(gasometer.revert("unsupported asset"))?;
+		let borrow_amount = Self::read_balance(input, gasometer)?;
+		let asset_to_repay: Address = input.read::<Address>(gasometer)?.into();
+		let asset_to_repay = C::evm_id_to_runtime_id(asset_to_repay).ok_or(gasometer.revert("unsupported asset"))?;
+		let callback_input: Bytes = input.read::<Bytes>(gasometer)?.into();
+
+		gasometer.record_cost(G::weight_to_gas(T::flash_swap_weight()))?;
+		let borrower = U::into_account_id(context.caller);
+		// the callback is a sub-call from this precompile, its gas is charged to this call
+		let mut callback_failure = None;
+		let result = T::flash_swap(&borrower, asset_to_borrow, borrow_amount, asset_to_repay, || {
+			RuntimeHelper::<R>::try_call(context.address, context.caller, callback_input.into(), gasometer)
+				.map_err(|failure| {
+					callback_failure = Some(failure);
+					DispatchError::Other("flash swap callback failed")
+				})
+				.map(|_| ())
+		});
+		if let Some(failure) = callback_failure {
+			return Err(failure);
+		}
+		let repay_amount = result.map_err(|err| PrecompileFailure::Revert {
+			exit_status: ExitRevert::Reverted,
+			output: alloc::format!("flash swap failed: {:?}", err.stripped())
+				.as_bytes()
+				.to_vec(),
+			cost: gasometer.used_gas(),
+		})?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(U256::from(repay_amount)).build(),
+			logs: Default::default(),
+		})
+	}
+	fn swap_exact_tokens_for_tokens(
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 4 * 32)?;
+		input.expect_arguments(gasometer, 4)?;
+
+		let amount_in = Self::read_balance(input, gasometer)?;
+		let amount_out_min = Self::read_balance(input, gasometer)?;
+		let path = Self::read_path(input, gasometer)?;
+		let to: H160 = input.read::<Address>(gasometer)?.into();
+
+		gasometer.record_cost(G::weight_to_gas(T::trade_weight()))?;
+		let amounts = T::sell_via_path(
+			&U::into_account_id(context.caller),
+			&U::into_account_id(to),
+			&path.runtime_ids,
+			amount_in,
+			amount_out_min,
+		)
+		.map_err(|err| gasometer.revert(alloc::format!("swap failed: {:?}", err.stripped())))?;
+
+		Ok(Self::swap_output(gasometer, context, to, &path.evm_ids, amounts))
+	}
+
+	fn swap_tokens_for_exact_tokens(
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 4 * 32)?;
+		input.expect_arguments(gasometer, 4)?;
+
+		let amount_out = Self::read_balance(input, gasometer)?;
+		let amount_in_max = Self::read_balance(input, gasometer)?;
+		let path = Self::read_path(input, gasometer)?;
+		let to: H160 = input.read::<Address>(gasometer)?.into();
+
+		gasometer.record_cost(G::weight_to_gas(T::trade_weight()))?;
+		let amounts = T::buy_via_path(
+			&U::into_account_id(context.caller),
+			&U::into_account_id(to),
+			&path.runtime_ids,
+			amount_out,
+			amount_in_max,
+		)
+		.map_err(|err| gasometer.revert(alloc::format!("swap failed: {:?}", err.stripped())))?;
+
+		Ok(Self::swap_output(gasometer, context, to, &path.evm_ids, amounts))
+	}
+
+	fn add_liquidity(
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 3 * 32)?;
+		input.expect_arguments(gasometer, 4)?;
+
+		let asset: Address = input.read::<Address>(gasometer)?;
+		let asset_id = C::evm_id_to_runtime_id(asset).ok_or(gasometer.revert("unsupported asset"))?;
+		let min_liquidity = Self::read_balance(input, gasometer)?;
+		let max_asset_amount = Self::read_balance(input, gasometer)?;
+		let core_amount = Self::read_balance(input, gasometer)?;
+
+		gasometer.record_cost(G::weight_to_gas(T::add_liquidity_weight()))?;
+		let (asset_amount, liquidity) = T::add_liquidity(
+			&U::into_account_id(context.caller),
+			asset_id,
+			min_liquidity,
+			max_asset_amount,
+			core_amount,
+		)
+		.map_err(|err| gasometer.revert(alloc::format!("add liquidity failed: {:?}", err.stripped())))?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new()
+				.write(U256::from(asset_amount))
+				.write(U256::from(liquidity))
+				.build(),
+			logs: LogsBuilder::new(context.address)
+				.log3(
+					SELECTOR_LOG_ADD_LIQUIDITY,
+					context.caller,
+					asset.0,
+					EvmDataWriter::new()
+						.write(U256::from(core_amount))
+						.write(U256::from(asset_amount))
+						.write(U256::from(liquidity))
+						.build(),
+				)
+				.build(),
+		})
+	}
+
+	fn remove_liquidity(
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 3 * 32)?;
+		input.expect_arguments(gasometer, 4)?;
+
+		let asset: Address = input.read::<Address>(gasometer)?;
+		let asset_id = C::evm_id_to_runtime_id(asset).ok_or(gasometer.revert("unsupported asset"))?;
+		let liquidity = Self::read_balance(input, gasometer)?;
+		let min_asset_withdraw = Self::read_balance(input, gasometer)?;
+		let min_core_withdraw = Self::read_balance(input, gasometer)?;
+
+		gasometer.record_cost(G::weight_to_gas(T::remove_liquidity_weight()))?;
+		let (core_amount, asset_amount) = T::remove_liquidity(
+			&U::into_account_id(context.caller),
+			asset_id,
+			liquidity,
+			min_asset_withdraw,
+			min_core_withdraw,
+		)
+		.map_err(|err| gasometer.revert(alloc::format!("remove liquidity failed: {:?}", err.stripped())))?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new()
+				.write(U256::from(core_amount))
+				.write(U256::from(asset_amount))
+				.build(),
+			logs: LogsBuilder::new(context.address)
+				.log3(
+					SELECTOR_LOG_REMOVE_LIQUIDITY,
+					context.caller,
+					asset.0,
+					EvmDataWriter::new()
+						.write(U256::from(core_amount))
+						.write(U256::from(asset_amount))
+						.write(U256::from(liquidity))
+						.build(),
+				)
+				.build(),
+		})
+	}
+
+	fn get_amount_out(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+		input.expect_arguments(gasometer, 3)?;
+
+		let amount_in = Self::read_balance(input, gasometer)?;
+		let path = Self::read_route(input, gasometer)?;
+
+		gasometer.record_cost(G::weight_to_gas(T::price_weight()))?;
+		let amounts = T::sell_price_via_path(&path, amount_in)
+			.map_err(|err| gasometer.revert(alloc::format!("pricing failed: {:?}", err.stripped())))?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new()
+				.write(U256::from(amounts[amounts.len() - 1]))
+				.build(),
+			logs: Default::default(),
+		})
+	}
+
+	fn get_amount_in(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+		input.expect_arguments(gasometer, 3)?;
+
+		let amount_out = Self::read_balance(input, gasometer)?;
+		let path = Self::read_route(input, gasometer)?;
+
+		gasometer.record_cost(G::weight_to_gas(T::price_weight()))?;
+		let amounts = T::buy_price_via_path(&path, amount_out)
+			.map_err(|err| gasometer.revert(alloc::format!("pricing failed: {:?}", err.stripped())))?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(U256::from(amounts[0])).build(),
+			logs: Default::default(),
+		})
+	}
+
+	fn get_reserves(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+		input.expect_arguments(gasometer, 1)?;
+
+		let asset: Address = input.read::<Address>(gasometer)?;
+		let asset_id = C::evm_id_to_runtime_id(asset).ok_or(gasometer.revert("unsupported asset"))?;
+
+		gasometer.record_cost(G::weight_to_gas(T::price_weight()))?;
+		let (core_reserve, asset_reserve) = T::reserves(asset_id);
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new()
+				.write(U256::from(core_reserve))
+				.write(U256::from(asset_reserve))
+				.build(),
+			logs: Default::default(),
+		})
+	}
+
+	/// Read a `uint256` amount, reverting rather than saturating if it does not fit a `Balance`
+	fn read_balance(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<Balance> {
+		let amount: U256 = input.read::<U256>(gasometer)?;
+		Balance::try_from(amount).map_err(|_| gasometer.revert("amount exceeds u128 max"))
+	}
+
+	/// Read a trade path of asset addresses, keeping the addresses for logging
+	fn read_path(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<Path> {
+		let evm_ids = input.read::<Vec<Address>>(gasometer)?;
+		let runtime_ids = evm_ids
+			.iter()
+			.map(|asset| C::evm_id_to_runtime_id(*asset).ok_or(gasometer.revert("unsupported asset")))
+			.collect::<Result<Vec<AssetId>, _>>()?;
+		Ok(Path { evm_ids, runtime_ids })
+	}
+
+	/// Read an (assetIn, assetOut) pair, routing via the core asset if neither is core
+	fn read_route(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<Vec<AssetId>> {
+		let asset_in: Address = input.read::<Address>(gasometer)?;
+		let asset_in = C::evm_id_to_runtime_id(asset_in).ok_or(gasometer.revert("unsupported asset"))?;
+		let asset_out: Address = input.read::<Address>(gasometer)?;
+		let asset_out = C::evm_id_to_runtime_id(asset_out).ok_or(gasometer.revert("unsupported asset"))?;
+
+		let core_asset_id = T::core_asset_id();
+		if asset_in == core_asset_id || asset_out == core_asset_id {
+			Ok(vec![asset_in, asset_out])
+		} else {
+			Ok(vec![asset_in, core_asset_id, asset_out])
+		}
+	}
+
+	/// Build the output and Swap log of a trade along `path`
+	fn swap_output(
+		gasometer: &Gasometer,
+		context: &Context,
+		to: H160,
+		path: &[Address],
+		amounts: Vec<Balance>,
+	) -> PrecompileOutput {
+		let amounts: Vec<U256> = amounts.into_iter().map(U256::from).collect();
+		PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(amounts.clone()).build(),
+			logs: LogsBuilder::new(context.address)
+				.log3(
+					SELECTOR_LOG_SWAP,
+					context.caller,
+					to,
+					EvmDataWriter::new()
+						.write(path[0])
+						.write(path[path.len() - 1])
+						.write(amounts[0])
+						.write(amounts[amounts.len() - 1])
+						.build(),
+				)
+				.build(),
+		}
+	}
+}
+
+/// A trade path as given and as runtime asset IDs
+struct Path {
+	evm_ids: Vec<Address>,
+	runtime_ids: Vec<AssetId>,
 }
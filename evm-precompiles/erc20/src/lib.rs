@@ -316,6 +316,7 @@ where
 		{
 			let origin = Runtime::AddressMapping::into_account_id(context.caller);
 			let to = Runtime::AddressMapping::into_account_id(to);
+			Self::ensure_not_frozen(asset_id, &origin, gasometer)?;
 
 			// Dispatch call (if enough gas).
 			RuntimeHelper::<Runtime>::try_dispatch(
@@ -355,6 +356,9 @@ where
 		let to: H160 = input.read::<Address>(gasometer)?.into();
 		let amount: Balance = input.read::<U256>(gasometer)?.saturated_into();
 
+		// Check before any approval is spent
+		Self::ensure_not_frozen(asset_id, &Runtime::AddressMapping::into_account_id(from), gasometer)?;
+
 		// If caller is "from", it can spend as much as it wants from its own balance.
 		if context.caller == from {
 			let from: Runtime::AccountId = Runtime::AddressMapping::into_account_id(from.clone());
@@ -424,6 +428,20 @@ where
 		})
 	}
 
+	/// Fail if the asset, or `who`'s balance of it, is frozen
+	fn ensure_not_frozen(asset_id: AssetId, who: &Runtime::AccountId, gasometer: &mut Gasometer) -> EvmResult<()> {
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
+
+		if crml_generic_asset::Pallet::<Runtime>::asset_frozen(asset_id) {
+			return Err(error("Asset is frozen").into());
+		}
+		if crml_generic_asset::Pallet::<Runtime>::account_frozen(asset_id, who) {
+			return Err(error("Account is frozen").into());
+		}
+
+		Ok(())
+	}
+
 	fn name(asset_id: AssetId, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
 		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
 
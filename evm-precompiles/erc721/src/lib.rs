@@ -25,10 +25,10 @@ use frame_support::{
 pub use pallet_evm::{AddressMapping, PrecompileSet};
 pub use precompile_utils::{
 	error, keccak256, Address, AddressMappingReversibleExt, Bytes, EvmData, EvmDataReader, EvmDataWriter, EvmResult,
-	FunctionModifier, Gasometer, LogsBuilder, RuntimeHelper,
+	FunctionModifier, Gasometer, LogsBuilder, PrecompileFailure, RuntimeHelper,
 };
 use sp_core::{H160, H256, U256};
-use sp_runtime::traits::SaturatedConversion;
+use sp_runtime::{traits::SaturatedConversion, PerThing, Permill};
 use sp_std::{marker::PhantomData, vec};
 
 /// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
@@ -37,6 +37,22 @@ pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address
 /// Solidity selector of the Transfer log, which is the Keccak of the Log signature.
 pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
 
+/// Solidity selector of the ApprovalForAll log, which is the Keccak of the Log signature.
+pub const SELECTOR_LOG_APPROVAL_FOR_ALL: [u8; 32] = keccak256!("ApprovalForAll(address,address,bool)");
+
+/// Solidity selector of `onERC721Received(address,address,uint256,bytes)`
+/// Contract recipients of a safe transfer must return it to accept the token
+pub const ERC721_RECEIVED: [u8; 4] = [0x15, 0x0b, 0x7a, 0x02];
+
+/// ERC-165 interface Id of ERC-165
+pub const INTERFACE_ID_ERC165: [u8; 4] = [0x01, 0xff, 0xc9, 0xa7];
+/// ERC-165 interface Id of ERC-721
+pub const INTERFACE_ID_ERC721: [u8; 4] = [0x80, 0xac, 0x58, 0xcd];
+/// ERC-165 interface Id of the ERC-721 metadata extension
+pub const INTERFACE_ID_ERC721_METADATA: [u8; 4] = [0x5b, 0x5e, 0x13, 0x9f];
+/// ERC-165 interface Id of ERC-2981 (NFT royalties)
+pub const INTERFACE_ID_ERC2981: [u8; 4] = [0x2a, 0x55, 0x20, 0x5a];
+
 #[precompile_utils::generate_function_selector]
 #[derive(Debug, PartialEq)]
 pub enum Action {
@@ -49,10 +65,18 @@ pub enum Action {
 	GetApproved = "getApproved(uint256)",
 	IsApprovedForAll = "isApprovedForAll(address,address)",
 	SetApprovalForAll = "setApprovalForAll(address,bool)",
+	TotalSupply = "totalSupply()",
 	// Metadata extensions
 	Name = "name()",
 	Symbol = "symbol()",
 	TokenURI = "tokenURI(uint256)",
+	// Interface detection (ERC-165)
+	SupportsInterface = "supportsInterface(bytes4)",
+	// Royalty extensions (ERC-2981)
+	RoyaltyInfo = "royaltyInfo(uint256,uint256)",
+	// Rental extensions (ERC-4907)
+	UserOf = "userOf(uint256)",
+	UserExpires = "userExpires(uint256)",
 }
 
 /// Convert EVM addresses into NFT module identifiers and vice versa
@@ -125,7 +149,8 @@ where
 							Action::Approve
 							| Action::SafeTransferFrom
 							| Action::TransferFrom
-							| Action::SafeTransferFromCallData => FunctionModifier::NonPayable,
+							| Action::SafeTransferFromCallData
+							| Action::SetApprovalForAll => FunctionModifier::NonPayable,
 							_ => FunctionModifier::View,
 						},
 					) {
@@ -142,12 +167,20 @@ where
 						Action::TokenURI => Self::token_uri(series_id_parts, input, gasometer),
 						Action::Approve => Self::approve(series_id_parts, input, gasometer, context),
 						Action::GetApproved => Self::get_approved(series_id_parts, input, gasometer),
-						// TODO: implement approval stuff
-						Action::SafeTransferFrom
-						| Action::SafeTransferFromCallData
-						| Action::IsApprovedForAll
-						| Action::SetApprovalForAll => {
-							return Some(Err(error("function not implemented yet").into()));
+						Action::UserOf => Self::user_of(series_id_parts, input, gasometer),
+						Action::UserExpires => Self::user_expires(series_id_parts, input, gasometer),
+						Action::TotalSupply => Self::total_supply(series_id_parts, gasometer),
+						Action::SupportsInterface => Self::supports_interface(input, gasometer),
+						Action::RoyaltyInfo => Self::royalty_info(series_id_parts, input, gasometer),
+						Action::SafeTransferFrom => {
+							Self::safe_transfer_from(series_id_parts, input, gasometer, context, false)
+						}
+						Action::SafeTransferFromCallData => {
+							Self::safe_transfer_from(series_id_parts, input, gasometer, context, true)
+						}
+						Action::IsApprovedForAll => Self::is_approved_for_all(series_id_parts, input, gasometer),
+						Action::SetApprovalForAll => {
+							Self::set_approval_for_all(series_id_parts, input, gasometer, context)
 						}
 					}
 				};
@@ -219,6 +252,177 @@ where
 		})
 	}
 
+	/// Returns the CENNZnet address with usage rights of the given token (ERC-4907)
+	/// The zero address is returned if the token is not rented
+	fn user_of(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, 1)?;
+		let serial_number: U256 = input.read::<U256>(gasometer)?;
+
+		// For now we only support Ids < u32 max
+		// since `u32` is the native `SerialNumber` type used by the NFT module.
+		// it's not possible for the module to issue Ids larger than this
+		if serial_number > u32::max_value().into() {
+			return Err(error("expected token id <= 2^32").into());
+		}
+		let serial_number: SerialNumber = serial_number.saturated_into();
+
+		// Fetch info.
+		let user_account_id =
+			crml_nft::Pallet::<Runtime>::token_user((series_id_parts.0, series_id_parts.1, serial_number))
+				.map(|rental| H256::from(rental.user.into()))
+				.unwrap_or_default();
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(user_account_id).build(),
+			logs: vec![],
+		})
+	}
+
+	/// Returns the block number at which the given token's rental ends (ERC-4907)
+	/// `0` is returned if the token is not rented
+	/// Note: this deviates from ERC-4907 which specifies a unix timestamp, rentals on CENNZnet are
+	/// scheduled by block number and block times are not fixed so a timestamp could not be exact
+	fn user_expires(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, 1)?;
+		let serial_number: U256 = input.read::<U256>(gasometer)?;
+
+		// For now we only support Ids < u32 max
+		// since `u32` is the native `SerialNumber` type used by the NFT module.
+		// it's not possible for the module to issue Ids larger than this
+		if serial_number > u32::max_value().into() {
+			return Err(error("expected token id <= 2^32").into());
+		}
+		let serial_number: SerialNumber = serial_number.saturated_into();
+
+		// Fetch info.
+		let expiry: U256 =
+			crml_nft::Pallet::<Runtime>::token_user((series_id_parts.0, series_id_parts.1, serial_number))
+				.map(|rental| rental.expiry.saturated_into::<u64>())
+				.unwrap_or_default()
+				.into();
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(expiry).build(),
+			logs: vec![],
+		})
+	}
+
+	/// Returns the number of tokens in existence for the series
+	fn total_supply(
+		series_id_parts: (CollectionId, SeriesId),
+		gasometer: &mut Gasometer,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Fetch info.
+		let total_supply: U256 =
+			crml_nft::Pallet::<Runtime>::series_issuance(series_id_parts.0, series_id_parts.1).into();
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(total_supply).build(),
+			logs: vec![],
+		})
+	}
+
+	/// Returns whether the interface is supported (ERC-165)
+	fn supports_interface(input: &mut EvmDataReader, gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+		// Parse input.
+		// `bytes4` is left aligned in its 32 byte word
+		input.expect_arguments(gasometer, 1)?;
+		let word: H256 = input.read::<H256>(gasometer)?;
+		let mut interface_id = [0_u8; 4];
+		interface_id.copy_from_slice(&word.as_bytes()[..4]);
+
+		let supported = [
+			INTERFACE_ID_ERC165,
+			INTERFACE_ID_ERC721,
+			INTERFACE_ID_ERC721_METADATA,
+			INTERFACE_ID_ERC2981,
+		]
+		.contains(&interface_id);
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(supported).build(),
+			logs: vec![],
+		})
+	}
+
+	/// Returns the royalty receiver and amount owed for a sale of the given token at `salePrice` (ERC-2981)
+	/// ERC-2981 supports a single receiver, so the first entitled account is returned with the total royalty amount
+	/// The zero address and `0` are returned if the token has no royalties
+	fn royalty_info(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+	) -> EvmResult<PrecompileOutput> {
+		// token, series & collection royalties
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 3)?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, 2)?;
+		let serial_number: U256 = input.read::<U256>(gasometer)?;
+		let sale_price: U256 = input.read::<U256>(gasometer)?;
+
+		// For now we only support Ids < u32 max
+		// since `u32` is the native `SerialNumber` type used by the NFT module.
+		// it's not possible for the module to issue Ids larger than this
+		if serial_number > u32::max_value().into() {
+			return Err(error("expected token id <= 2^32").into());
+		}
+		let serial_number: SerialNumber = serial_number.saturated_into();
+
+		// Fetch info.
+		let (receiver, royalty_amount) =
+			match crml_nft::Pallet::<Runtime>::royalties_for((series_id_parts.0, series_id_parts.1, serial_number)) {
+				Some(royalties) if !royalties.entitlements.is_empty() => {
+					let receiver = Runtime::AddressMapping::from_account_id(royalties.entitlements[0].0.clone());
+					let total_entitlement = royalties.calculate_total_entitlement();
+					// sale price * parts per million, saturates on overflow
+					let royalty_amount = sale_price.saturating_mul(total_entitlement.deconstruct().into())
+						/ U256::from(Permill::ACCURACY);
+					(receiver, royalty_amount)
+				}
+				_ => (H160::default(), U256::zero()),
+			};
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new()
+				.write::<Address>(receiver.into())
+				.write(royalty_amount)
+				.build(),
+			logs: vec![],
+		})
+	}
+
 	fn balance_of(
 		series_id_parts: (CollectionId, SeriesId),
 		input: &mut EvmDataReader,
@@ -264,6 +468,82 @@ where
 		let to: H160 = input.read::<Address>(gasometer)?.into();
 		let serial_number = input.read::<U256>(gasometer)?;
 
+		let serial_number = Self::do_transfer_from(series_id_parts, from, to, serial_number, gasometer, context)?;
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: EvmDataWriter::new().write(true).build(),
+			logs: LogsBuilder::new(context.address)
+				.log3(
+					SELECTOR_LOG_TRANSFER,
+					from,
+					to,
+					EvmDataWriter::new().write(serial_number).build(),
+				)
+				.build(),
+		})
+	}
+
+	/// Transfer a token as `transferFrom` does, then check a contract recipient accepts it
+	/// by calling its `onERC721Received` hook
+	fn safe_transfer_from(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+		with_data: bool,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 32)?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, if with_data { 4 } else { 3 })?;
+
+		let from: H160 = input.read::<Address>(gasometer)?.into();
+		let to: H160 = input.read::<Address>(gasometer)?.into();
+		let serial_number = input.read::<U256>(gasometer)?;
+		let data: Bytes = if with_data {
+			input.read::<Bytes>(gasometer)?
+		} else {
+			Bytes(vec![])
+		};
+
+		let serial_number = Self::do_transfer_from(series_id_parts, from, to, serial_number, gasometer, context)?;
+
+		// Contract recipients must accept the token, accounts always do
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		if !pallet_evm::Pallet::<Runtime>::account_codes(to).is_empty() {
+			Self::check_erc721_received(from, to, serial_number, data, gasometer, context)?;
+		}
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: Default::default(),
+			logs: LogsBuilder::new(context.address)
+				.log3(
+					SELECTOR_LOG_TRANSFER,
+					from,
+					to,
+					EvmDataWriter::new().write(serial_number).build(),
+				)
+				.build(),
+		})
+	}
+
+	/// Transfer a token from `from` to `to`
+	/// The caller must be `from`, the account approved for the token, or an operator approved by `from`
+	/// Returns the transferred serial number
+	fn do_transfer_from(
+		series_id_parts: (CollectionId, SeriesId),
+		from: H160,
+		to: H160,
+		serial_number: U256,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<SerialNumber> {
 		// For now we only support Ids < u32 max
 		// since `u32` is the native `SerialNumber` type used by the NFT module.
 		// it's not possible for the module to issue Ids larger than this
@@ -272,11 +552,20 @@ where
 		}
 		let serial_number: SerialNumber = serial_number.saturated_into();
 		let token_id = (series_id_parts.0, series_id_parts.1, serial_number);
-		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
 		let approved_account: H160 = crml_token_approvals::Module::<Runtime>::erc721_approvals(token_id);
+		let is_operator =
+			crml_token_approvals::Module::<Runtime>::is_approved_for_all(from, context.caller, series_id_parts);
+
+		// Soulbound and other non-transferable series can't change hands
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		if !crml_nft::Pallet::<Runtime>::series_transfer_policy(series_id_parts.0, series_id_parts.1).is_transferable()
+		{
+			return Err(error("token not transferable").into());
+		}
 
 		// Build call with origin.
-		if context.caller == from || context.caller == approved_account {
+		if context.caller == from || context.caller == approved_account || is_operator {
 			let from = Runtime::AddressMapping::into_account_id(from);
 			let to = Runtime::AddressMapping::into_account_id(to);
 
@@ -293,17 +582,101 @@ where
 			return Err(error("caller not approved").into());
 		}
 
+		Ok(serial_number)
+	}
+
+	/// Call `onERC721Received` on the `to` contract, reverting unless it returns the expected selector
+	fn check_erc721_received(
+		from: H160,
+		to: H160,
+		serial_number: SerialNumber,
+		data: Bytes,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult {
+		let input = EvmDataWriter::new_with_selector(u32::from_be_bytes(ERC721_RECEIVED))
+			.write::<Address>(context.caller.into())
+			.write::<Address>(from.into())
+			.write::<U256>(serial_number.into())
+			.write::<Bytes>(data)
+			.build();
+		// The hook is called by this contract, as it would be by a solidity ERC721 contract
+		let output = match RuntimeHelper::<Runtime>::try_call(context.address, to, input, gasometer) {
+			Err(PrecompileFailure::Revert { .. }) => None,
+			result => Some(result?),
+		};
+
+		if output.as_ref().and_then(|output| output.get(..4)) != Some(&ERC721_RECEIVED[..]) {
+			return Err(gasometer.revert("transfer to non ERC721Receiver implementer"));
+		}
+
+		Ok(())
+	}
+
+	/// Returns whether `operator` may transfer all of the series tokens owned by `owner`
+	fn is_approved_for_all(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, 2)?;
+		let owner: H160 = input.read::<Address>(gasometer)?.into();
+		let operator: H160 = input.read::<Address>(gasometer)?.into();
+
+		// Fetch info.
+		let is_approved =
+			crml_token_approvals::Module::<Runtime>::is_approved_for_all(owner, operator, series_id_parts);
+
 		// Build output.
 		Ok(PrecompileOutput {
 			exit_status: ExitSucceed::Returned,
 			cost: gasometer.used_gas(),
-			output: EvmDataWriter::new().write(true).build(),
+			output: EvmDataWriter::new().write(is_approved).build(),
+			logs: vec![],
+		})
+	}
+
+	/// Approve or remove `operator` as an operator for all of the caller's tokens in the series
+	fn set_approval_for_all(
+		series_id_parts: (CollectionId, SeriesId),
+		input: &mut EvmDataReader,
+		gasometer: &mut Gasometer,
+		context: &Context,
+	) -> EvmResult<PrecompileOutput> {
+		gasometer.record_log_costs_manual(3, 32)?;
+
+		// Parse input.
+		input.expect_arguments(gasometer, 2)?;
+		let operator: H160 = input.read::<Address>(gasometer)?.into();
+		let approved: bool = input.read::<bool>(gasometer)?;
+
+		// Dispatch call (if enough gas).
+		RuntimeHelper::<Runtime>::try_dispatch(
+			None.into(),
+			crml_token_approvals::Call::<Runtime>::erc721_approval_for_all {
+				caller: context.caller,
+				operator_account: operator,
+				collection_id: series_id_parts.0,
+				series_id: series_id_parts.1,
+				approved,
+			},
+			gasometer,
+		)?;
+
+		// Build output.
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: gasometer.used_gas(),
+			output: Default::default(),
 			logs: LogsBuilder::new(context.address)
 				.log3(
-					SELECTOR_LOG_TRANSFER,
+					SELECTOR_LOG_APPROVAL_FOR_ALL,
 					context.caller,
-					to,
-					EvmDataWriter::new().write(serial_number).build(),
+					operator,
+					EvmDataWriter::new().write(approved).build(),
 				)
 				.build(),
 		})
@@ -332,11 +705,25 @@ where
 		let serial_number: SerialNumber = serial_number.saturated_into();
 
 		let token_id: TokenId = (series_id_parts.0, series_id_parts.1, serial_number);
+
+		// Operators may approve on behalf of the token owner
+		gasometer.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost() * 2)?;
+		let owner: H160 = Runtime::AddressMapping::from_account_id(crml_nft::Pallet::<Runtime>::token_owner(
+			series_id_parts,
+			serial_number,
+		));
+		let caller =
+			if crml_token_approvals::Module::<Runtime>::is_approved_for_all(owner, context.caller, series_id_parts) {
+				owner
+			} else {
+				context.caller
+			};
+
 		// Dispatch call (if enough gas).
 		RuntimeHelper::<Runtime>::try_dispatch(
 			None.into(),
 			crml_token_approvals::Call::<Runtime>::erc721_approval {
-				caller: context.caller,
+				caller,
 				operator_account: to,
 				token_id,
 			},
@@ -351,7 +738,7 @@ where
 			logs: LogsBuilder::new(context.address)
 				.log3(
 					SELECTOR_LOG_APPROVAL,
-					context.caller,
+					caller,
 					to,
 					EvmDataWriter::new().write(serial_number).build(),
 				)
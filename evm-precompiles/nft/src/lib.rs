@@ -149,8 +149,16 @@ where
 		gasometer.record_cost(<T as crml_nft::Config>::WeightInfo::mint_series(0))?;
 
 		// Dispatch call (if enough gas).
-		let series_id =
-			crml_nft::Module::<T>::do_mint_series(origin, collection_id, 0, None, metadata_scheme, royalties_schedule);
+		let series_id = crml_nft::Module::<T>::do_mint_series(
+			origin,
+			collection_id,
+			0,
+			None,
+			metadata_scheme,
+			royalties_schedule,
+			None,
+			None,
+		);
 
 		// Build output.
 		match series_id {
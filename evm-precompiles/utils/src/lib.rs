@@ -23,7 +23,7 @@ use frame_support::{
 	dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
 	traits::Get,
 };
-use pallet_evm::{AddressMapping, ExitRevert, GasWeightMapping, Log};
+use pallet_evm::{AddressMapping, ExitRevert, GasWeightMapping, Log, Runner};
 use sp_core::{H160, H256, U256};
 use sp_std::{marker::PhantomData, vec, vec::Vec};
 
@@ -222,6 +222,42 @@ where
 			<Runtime as frame_system::Config>::DbWeight::get().read,
 		)
 	}
+
+	/// Call the `target` contract from a precompile at `caller`.
+	/// All but one 64th of the remaining gas is forwarded (EIP-150) and the gas used by the call,
+	/// including the runner's base transaction cost, is recorded against `gasometer`.
+	/// Returns the call output, or a revert if the call fails or reverts.
+	///
+	/// The runner increments the nonce of `caller`, this is only observable for precompile
+	/// addresses which never create contracts, so `caller` should always be the precompile itself.
+	pub fn try_call(caller: H160, target: H160, input: Vec<u8>, gasometer: &mut Gasometer) -> EvmResult<Vec<u8>> {
+		let remaining_gas = gasometer
+			.remaining_gas()?
+			.unwrap_or_else(|| <Runtime as pallet_evm::Config>::BlockGasLimit::get().low_u64());
+		let gas_limit = remaining_gas - remaining_gas / 64;
+
+		let info = <Runtime as pallet_evm::Config>::Runner::call(
+			caller,
+			target,
+			input,
+			U256::zero(),
+			gas_limit,
+			None,
+			None,
+			None,
+			vec![],
+			<Runtime as pallet_evm::Config>::config(),
+		)
+		.map_err(|_| gasometer.revert("call failed"))?;
+		// bounded by the forwarded `gas_limit`
+		gasometer.record_cost(info.used_gas.low_u64())?;
+
+		if !info.exit_reason.is_succeed() {
+			return Err(gasometer.revert("call reverted"));
+		}
+
+		Ok(info.value)
+	}
 }
 
 /// Represents modifiers a Solidity function can be annotated with.
@@ -22,6 +22,8 @@ use frame_support::dispatch::{DispatchError, Weight};
 pub trait BuyFeeAsset {
 	/// The account identifier type
 	type AccountId;
+	/// The asset identifier type
+	type AssetId;
 	/// The type to denote monetary values
 	type Balance;
 	/// A type with fee payment information
@@ -35,6 +37,20 @@ pub trait BuyFeeAsset {
 		amount: Self::Balance,
 		fee_exchange: &Self::FeeExchange,
 	) -> Result<Self::Balance, DispatchError>;
+	/// Sell `amount` of fee asset refunded to `who` back for at least `min_buy` of `asset_id`, reversing part of a
+	/// `buy_fee_asset`.
+	/// If the sale has been successful, return Ok with the amount of `asset_id` bought, otherwise return Err.
+	fn sell_fee_asset(
+		who: &Self::AccountId,
+		amount: Self::Balance,
+		asset_id: Self::AssetId,
+		min_buy: Self::Balance,
+	) -> Result<Self::Balance, DispatchError>;
+	/// Return the amount of `asset_id` a `buy_fee_asset` call would currently sell to buy `amount` of fee asset
+	/// or `amount` itself when `asset_id` is the fee asset
+	fn buy_fee_asset_price(asset_id: Self::AssetId, amount: Self::Balance) -> Result<Self::Balance, DispatchError>;
+	/// Returns the asset fees are paid in
+	fn fee_asset_id() -> Self::AssetId;
 	/// Returns ~ weight of a `buy_fee_asset` call
 	fn buy_fee_weight() -> Weight;
 }
@@ -92,9 +92,11 @@ pub type TokenCount = SerialNumber;
 /// The outer `FeeExchange` type. It is versioned to provide flexibility for future iterations
 /// while maintaining backward compatibility.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, TypeInfo)]
-pub enum FeeExchange<AssetId, Balance> {
+pub enum FeeExchange<AssetId, Balance, Account = AccountId> {
 	/// A V1 FeeExchange
 	V1(FeeExchangeV1<AssetId, Balance>),
+	/// Fees are paid by the named sponsor account, subject to its sponsorship terms, the transaction may not tip
+	Sponsored(Account),
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Debug, TypeInfo)]
@@ -129,7 +131,7 @@ impl<AssetId, Balance> FeeExchangeV1<AssetId, Balance> {
 	}
 }
 
-impl<AssetId: Copy, Balance: Copy> FeeExchange<AssetId, Balance> {
+impl<AssetId: Copy, Balance: Copy, Account> FeeExchange<AssetId, Balance, Account> {
 	/// Create a `FeeExchangeV1`
 	pub fn new_v1(id: AssetId, balance: Balance) -> Self {
 		FeeExchange::V1(FeeExchangeV1 {
@@ -138,17 +140,27 @@ impl<AssetId: Copy, Balance: Copy> FeeExchange<AssetId, Balance> {
 		})
 	}
 
-	/// Return the nominated fee asset id
-	pub fn asset_id(&self) -> AssetId {
+	/// Return the nominated fee asset id, if the fee is paid via exchange
+	pub fn asset_id(&self) -> Option<AssetId> {
 		match self {
-			FeeExchange::V1(x) => x.asset_id,
+			FeeExchange::V1(x) => Some(x.asset_id),
+			FeeExchange::Sponsored(_) => None,
 		}
 	}
 
-	/// Return the max. payment limit
-	pub fn max_payment(&self) -> Balance {
+	/// Return the max. payment limit, if the fee is paid via exchange
+	pub fn max_payment(&self) -> Option<Balance> {
 		match self {
-			FeeExchange::V1(x) => x.max_payment,
+			FeeExchange::V1(x) => Some(x.max_payment),
+			FeeExchange::Sponsored(_) => None,
+		}
+	}
+
+	/// Return the nominated fee sponsor, if any
+	pub fn sponsor(&self) -> Option<&Account> {
+		match self {
+			FeeExchange::V1(_) => None,
+			FeeExchange::Sponsored(sponsor) => Some(sponsor),
 		}
 	}
 }
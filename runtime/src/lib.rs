@@ -86,8 +86,8 @@ pub use crml_generic_asset::{
 	StakingAssetCurrency,
 };
 use crml_governance::{ProposalId, ProposalVoteInfo};
-use crml_nft::{CollectionInfo, Listing, ListingId, TokenInfo};
-use crml_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use crml_nft::{CollectionInfo, Listing, ListingId, TokenInfo, TokenRental};
+use crml_transaction_payment::{FeeDetails, FeeInAsset, RuntimeDispatchInfo};
 pub use crml_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 use fp_rpc::TransactionStatus;
 use pallet_ethereum::{Call::transact, Transaction as EthereumTransaction};
@@ -105,7 +105,7 @@ use impls::{
 };
 
 pub mod precompiles;
-use precompiles::{CENNZnetPrecompiles, StateOracleCallbackExecutor};
+use precompiles::{CENNZnetPrecompiles, EvmContractCaller, StateOracleCallbackExecutor};
 
 pub mod runner;
 use runner::FeePreferencesRunner;
@@ -140,10 +140,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set `impl_version` to equal spec_version. If only runtime
 	// implementation changes and behavior does not, then leave `spec_version` as
 	// is and increment `impl_version`.
-	spec_version: 64,
+	spec_version: 65,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 5,
+	transaction_version: 6,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -270,12 +270,15 @@ parameter_types! {
 	/// The maximum length of an attribute value (140 = old tweet limit)
 	/// Only applies to string/vec allocated types
 	pub const MaxAttributeLength: u8 = 140;
+	/// How long series royalty changes are delayed before taking effect
+	pub const RoyaltiesUpdateDelay: BlockNumber = DAYS * 3;
 }
 impl crml_nft::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = GenericAsset;
 	type MaxAttributeLength = MaxAttributeLength;
 	type DefaultListingDuration = DefaultListingDuration;
+	type RoyaltiesUpdateDelay = RoyaltiesUpdateDelay;
 	type WeightInfo = ();
 	type OnTransferSubscription = TokenApprovals;
 }
@@ -425,12 +428,19 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxVestingSchedules: u32 = 28;
+	pub const MinVestedTransfer: Balance = 1 * DOLLARS;
+}
 impl crml_generic_asset::Config for Runtime {
 	type AssetId = AssetId;
 	type Balance = Balance;
 	type Event = Event;
 	type OnDustImbalance = TransferDustImbalance<TreasuryPalletId>;
 	type WeightInfo = ();
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MinVestedTransfer = MinVestedTransfer;
+	type OnTransferSubscription = Cennzx;
 }
 
 parameter_types! {
@@ -444,14 +454,25 @@ parameter_types! {
 	// We do this to constrain fee adjustment to the recommended +/-23% fee adjustment per day
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 50_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 500_000_000u128);
+	/// The maximum number of calls a fee sponsorship may pay for
+	pub const MaxSponsoredCalls: u32 = 32;
+	/// The maximum number of beneficiaries of a fee sponsorship
+	pub const MaxSponsoredAccounts: u32 = 1_000;
+	/// The allowance over fee estimates in other assets used to recommend a `FeeExchange` max. payment
+	pub const FeeExchangeSlippage: Permill = Permill::from_percent(5);
 }
 impl crml_transaction_payment::Config for Runtime {
+	type Event = Event;
 	type AssetId = AssetId;
 	type OnChargeTransaction = crml_transaction_payment::CurrencyAdapter<SpendingAssetCurrency<Runtime>, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToCpayFee<WeightToCpayFactor>;
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
 	type BuyFeeAsset = Cennzx;
+	type MaxSponsoredCalls = MaxSponsoredCalls;
+	type MaxSponsoredAccounts = MaxSponsoredAccounts;
+	type WeightInfo = ();
+	type FeeExchangeSlippage = FeeExchangeSlippage;
 }
 
 pub const fn deposit(items: u32, bytes: u32) -> Balance {
@@ -616,12 +637,24 @@ impl crml_staking_rewards::Config for Runtime {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	/// Covers a TWAP window of at least ~2.8 hours at 5 second blocks
+	pub const MaxPriceObservations: u32 = 2_048;
+	/// Keepers filling limit orders receive 0.1% of the proceeds
+	pub KeeperFeeRate: FeeRate<PerMillion> = FeeRate::from(1_000_u128);
+}
 impl crml_cennzx::Config for Runtime {
 	type Balance = Balance;
 	type AssetId = AssetId;
 	type Event = Event;
 	type MultiCurrency = GenericAsset;
 	type ExchangeAddressFor = ExchangeAddressGenerator<Self>;
+	type TreasuryPalletId = TreasuryPalletId;
+	type MaxPriceObservations = MaxPriceObservations;
+	type KeeperFeeRate = KeeperFeeRate;
+	type AddressMapping = AddressMappingOf<Self>;
+	type ContractCaller = EvmContractCaller<Self>;
+	type GasWeightMapping = CENNZnetGasWeightMapping;
 	type WeightInfo = ();
 }
 
@@ -918,7 +951,7 @@ construct_runtime!(
 		Treasury: pallet_treasury::{Pallet, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Pallet, Call, Event},
 		Identity: pallet_identity::{Pallet, Call, Storage, Event<T>},
-		TransactionPayment: crml_transaction_payment::{Pallet, Storage},
+		TransactionPayment: crml_transaction_payment::{Pallet, Call, Storage, Event<T>},
 		Historical: session_historical::{Pallet},
 		Cennzx: crml_cennzx::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Rewards: crml_staking_rewards::{Pallet, Call, Storage, Config, Event<T>},
@@ -965,6 +998,18 @@ pub type CheckedExtrinsic = fp_self_contained::CheckedExtrinsic<AccountId, Call,
 pub type Executive =
 	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
 
+/// Whether the fee of `uxt` is paid by the sponsor it names, given the fee it would be charged
+fn is_fee_sponsored(uxt: &UncheckedExtrinsic, fee: Balance) -> bool {
+	uxt.0
+		.signature
+		.as_ref()
+		.and_then(|(who, _, extra)| {
+			let sponsor = extra.6.sponsor()?;
+			Some(TransactionPayment::is_sponsored(sponsor, who, &uxt.0.function, fee))
+		})
+		.unwrap_or(false)
+}
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -1157,12 +1202,24 @@ impl_runtime_apis! {
 	impl crml_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
+		AssetId,
 	> for Runtime {
 		fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			let mut info = TransactionPayment::query_info(uxt.clone(), len);
+			info.sponsored = is_fee_sponsored(&uxt, info.partial_fee);
+			info
 		}
 		fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> FeeDetails<Balance> {
-			TransactionPayment::query_fee_details(uxt, len)
+			let mut fee_details = TransactionPayment::query_fee_details(uxt.clone(), len);
+			fee_details.sponsored = is_fee_sponsored(&uxt, fee_details.final_fee());
+			fee_details
+		}
+		fn query_fee_in_asset(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: AssetId,
+		) -> Result<FeeInAsset<Balance>, sp_runtime::DispatchError> {
+			TransactionPayment::query_fee_in_asset(uxt, len, asset_id)
 		}
 	}
 
@@ -1194,6 +1251,9 @@ impl_runtime_apis! {
 		) -> (Option<u128>, Vec<(ListingId, Listing<Runtime>)>) {
 			Nft::collection_listings(collection_id, offset, limit)
 		}
+		fn token_user(token_id: TokenId) -> Option<TokenRental<AccountId, BlockNumber>> {
+			Nft::token_user(token_id)
+		}
 	}
 
 	impl crml_cennzx_rpc_runtime_api::CennzxApi<
@@ -1241,6 +1301,39 @@ impl_runtime_apis! {
 			let value = Cennzx::liquidity_price(asset_id, liquidity_to_buy);
 			(value.core, value.asset)
 		}
+
+		fn twap(
+			asset_id: AssetId,
+			window: u32,
+		) -> CennzxResult<(Balance, Balance)> {
+			let saturate = |price: U256| price.min(Balance::MAX.into()).low_u128();
+			match Cennzx::twap(asset_id, window) {
+				Some((asset_price, core_price)) => CennzxResult::Success((saturate(asset_price), saturate(core_price))),
+				None => CennzxResult::Error,
+			}
+		}
+
+		fn best_buy_route(
+			buy_asset: AssetId,
+			buy_amount: Balance,
+			sell_asset: AssetId,
+		) -> CennzxResult<(Vec<AssetId>, Balance)> {
+			match Cennzx::best_buy_route(buy_asset, buy_amount, sell_asset) {
+				Ok(route) => CennzxResult::Success(route),
+				Err(_) => CennzxResult::Error,
+			}
+		}
+
+		fn best_sell_route(
+			sell_asset: AssetId,
+			sell_amount: Balance,
+			buy_asset: AssetId,
+		) -> CennzxResult<(Vec<AssetId>, Balance)> {
+			match Cennzx::best_sell_route(sell_asset, sell_amount, buy_asset) {
+				Ok(route) => CennzxResult::Success(route),
+				Err(_) => CennzxResult::Error,
+			}
+		}
 	}
 
 	impl crml_staking_rpc_runtime_api::StakingApi<Block, AccountId> for Runtime {
@@ -1441,6 +1534,7 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, crml_cennzx, Cennzx);
 			add_benchmark!(params, batches, crml_nft, Nft);
+			add_benchmark!(params, batches, crml_transaction_payment, TransactionPayment);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
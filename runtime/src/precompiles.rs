@@ -4,8 +4,11 @@ use crate::{
 	StateOraclePrecompileAddress,
 };
 use cennznet_primitives::types::{AssetId, CollectionId, SeriesId};
-use crml_support::{ContractExecutor, H160, U256};
-use frame_support::{dispatch::DispatchResultWithPostInfo, traits::Get};
+use crml_support::{ContractCaller, ContractExecutor, H160, U256};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResultWithPostInfo},
+	traits::Get,
+};
 use pallet_evm::{Context, Precompile, PrecompileResult, PrecompileSet};
 use pallet_evm_precompile_blake2::Blake2F;
 use pallet_evm_precompile_modexp::Modexp;
@@ -101,6 +104,7 @@ where
 				AddressMappingOf<Runtime>,
 				CENNZnetGasWeightMapping,
 				Runtime,
+				Runtime,
 			>::execute(input, target_gas, context, is_static)),
 			_a if routing_prefix == ERC721_PRECOMPILE_ADDRESS_PREFIX => {
 				<Erc721PrecompileSet<Runtime> as PrecompileSet>::execute(
@@ -241,3 +245,42 @@ where
 		<pallet_ethereum::Pallet<R>>::transact(Origin::from(RawOrigin::EthereumTransaction(*caller)), callback_tx)
 	}
 }
+
+/// Calls EVM contracts from runtime modules as a sub-call of the current transaction
+pub struct EvmContractCaller<R>(PhantomData<R>);
+
+impl<R> ContractCaller for EvmContractCaller<R>
+where
+	R: pallet_evm::Config,
+{
+	type Address = H160;
+	fn call(
+		caller: &Self::Address,
+		target: &Self::Address,
+		input: &[u8],
+		gas_limit: u64,
+	) -> Result<u64, DispatchError> {
+		use pallet_evm::Runner;
+
+		// no gas price, the calling module charges the gas used as weight
+		let info = <R as pallet_evm::Config>::Runner::call(
+			*caller,
+			*target,
+			input.to_vec(),
+			U256::zero(),
+			gas_limit,
+			None,
+			None,
+			None,
+			vec![],
+			<R as pallet_evm::Config>::config(),
+		)
+		.map_err(|_| DispatchError::Other("contract call failed"))?;
+
+		if !info.exit_reason.is_succeed() {
+			return Err(DispatchError::Other("contract call reverted"));
+		}
+
+		Ok(info.used_gas.low_u64())
+	}
+}
@@ -16,8 +16,11 @@
 //! Test helper functions
 
 use cennznet_cli::chain_spec::{get_authority_keys_from_seed, AuthorityKeys};
-use cennznet_primitives::types::{Balance, BlockNumber, Header};
-use cennznet_runtime::{CheckedExtrinsic, Runtime, UncheckedExtrinsic};
+use cennznet_primitives::{
+	traits::BuyFeeAsset,
+	types::{Balance, BlockNumber, Header},
+};
+use cennznet_runtime::{Cennzx, CheckedExtrinsic, Runtime, UncheckedExtrinsic};
 use codec::Encode;
 use frame_support::weights::GetDispatchInfo;
 use sp_runtime::{testing::Digest, traits::Header as HeaderT};
@@ -40,6 +43,15 @@ pub fn extrinsic_fee_for(xt: &UncheckedExtrinsic) -> Balance {
 	crml_transaction_payment::Module::<Runtime>::compute_fee(xt.encode().len() as u32, &xt.get_dispatch_info(), 0)
 }
 
+/// Calculate the transaction fees of `xt` when they are bought via fee exchange, which also charges for buying the
+/// fee and selling any refund back.
+/// Ignores tip.
+pub fn extrinsic_fee_with_exchange_for(xt: &UncheckedExtrinsic) -> Balance {
+	let mut info = xt.get_dispatch_info();
+	info.weight += 2 * <Cennzx as BuyFeeAsset>::buy_fee_weight();
+	crml_transaction_payment::Module::<Runtime>::compute_fee(xt.encode().len() as u32, &info, 0)
+}
+
 pub fn header_for_block_number(n: BlockNumber) -> Header {
 	HeaderT::new(
 		n,                        // block number
@@ -21,11 +21,12 @@ use cennznet_runtime::{
 	AddressMappingOf, Cennzx, GenericAsset, Origin, Runtime, CENNZNET_EVM_CONFIG,
 };
 use crml_support::{MultiCurrency, H160, U256};
-use ethabi::Token;
+use ethabi::{ParamType, Token};
 use fp_rpc::runtime_decl_for_EthereumRuntimeRPCApi::EthereumRuntimeRPCApi;
 use frame_support::assert_ok;
 use hex_literal::hex;
-use pallet_evm::{AddressMapping, ExitReason, ExitRevert, Runner as RunnerT};
+use pallet_evm::{AddressMapping, CallInfo, ExitReason, ExitRevert, ExitSucceed, Runner as RunnerT};
+use pallet_evm_precompiles_cennzx::SELECTOR_LOG_SWAP;
 use pallet_evm_precompiles_erc20::Erc20IdConversion;
 
 mod common;
@@ -46,6 +47,45 @@ fn encode_swap_input(amount: Balance, max_input: Balance) -> Vec<u8> {
 	input.clone()
 }
 
+fn encode_flash_swap_input(borrow_amount: Balance, asset_to_repay: u32) -> Vec<u8> {
+	// keccak('flashSwap(address,uint256,address,bytes)')[..4]
+	let flash_swap_selector = [0x03, 0x22, 0xc0, 0x64];
+	let parameters = ethabi::encode(&[
+		Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0), // borrow cennz
+		Token::Uint(U256::from(borrow_amount)),
+		Token::Address(Runtime::runtime_id_to_evm_id(asset_to_repay).0),
+		Token::Bytes(vec![]),
+	]);
+	[flash_swap_selector.to_vec(), parameters].concat()
+}
+
+/// Call the cennzx precompile from `caller`
+fn call_cennzx(caller: H160, input: Vec<u8>) -> CallInfo {
+	<Runtime as pallet_evm::Config>::Runner::call(
+		caller,
+		H160::from_low_u64_be(CENNZX_PRECOMPILE),
+		input,
+		U256::zero(),
+		1_000_000_u64,
+		Some(Runtime::gas_price()),
+		Some(U256::zero()),
+		None,
+		Default::default(),
+		&CENNZNET_EVM_CONFIG,
+	)
+	.unwrap()
+}
+
+fn with_cennz_exchange(liquidity: Balance) {
+	assert_ok!(Cennzx::add_liquidity(
+		Origin::signed(alice()),
+		CENNZ_ASSET_ID,
+		liquidity, // min. liquidity
+		liquidity, // liquidity CENNZ
+		liquidity, // liquidity CPAY
+	));
+}
+
 #[test]
 fn buy_cpay_with_cennz() {
 	ExtBuilder::default()
@@ -133,3 +173,312 @@ fn buy_cpay_reverts() {
 		);
 	});
 }
+
+#[test]
+fn flash_swap_repaid_by_caller() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let initial_liquidity = 500_000 * DOLLARS;
+			assert_ok!(Cennzx::add_liquidity(
+				Origin::signed(alice()),
+				CENNZ_ASSET_ID,
+				initial_liquidity, // min. liquidity
+				initial_liquidity, // liquidity CENNZ
+				initial_liquidity, // liquidity CPAY
+			));
+
+			// the caller has no code so the callback is a no-op, repayment comes from its own CENNZ
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let initial_cennz_balance = 5 * DOLLARS;
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CENNZ_ASSET_ID, initial_cennz_balance);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 50 * DOLLARS);
+
+			let borrow_amount = 100 * DOLLARS;
+			let info = <Runtime as pallet_evm::Config>::Runner::call(
+				caller,
+				H160::from_low_u64_be(CENNZX_PRECOMPILE),
+				encode_flash_swap_input(borrow_amount, CENNZ_ASSET_ID),
+				U256::zero(),
+				1_000_000_u64,
+				Some(Runtime::gas_price()),
+				Some(U256::zero()),
+				None,
+				Default::default(),
+				&CENNZNET_EVM_CONFIG,
+			)
+			.unwrap();
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+
+			// 0.3% fee on the borrowed amount
+			let fee = 3_000;
+			assert_eq!(U256::from_big_endian(&info.value), U256::from(borrow_amount + fee));
+			assert_eq!(
+				GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58),
+				initial_cennz_balance - fee
+			);
+		});
+}
+
+#[test]
+fn flash_swap_reverts_when_not_repaid() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let initial_liquidity = 500_000 * DOLLARS;
+			assert_ok!(Cennzx::add_liquidity(
+				Origin::signed(alice()),
+				CENNZ_ASSET_ID,
+				initial_liquidity, // min. liquidity
+				initial_liquidity, // liquidity CENNZ
+				initial_liquidity, // liquidity CPAY
+			));
+
+			// the caller can pay for gas but not the CPAY repayment
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 50 * DOLLARS);
+
+			let info = <Runtime as pallet_evm::Config>::Runner::call(
+				caller,
+				H160::from_low_u64_be(CENNZX_PRECOMPILE),
+				encode_flash_swap_input(100 * DOLLARS, CPAY_ASSET_ID),
+				U256::zero(),
+				1_000_000_u64,
+				Some(Runtime::gas_price()),
+				Some(U256::zero()),
+				None,
+				Default::default(),
+				&CENNZNET_EVM_CONFIG,
+			)
+			.unwrap();
+			assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			assert_eq!(GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58), 0);
+		});
+}
+
+#[test]
+fn swap_exact_tokens_for_tokens() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			with_cennz_exchange(500_000 * DOLLARS);
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CENNZ_ASSET_ID, 100 * DOLLARS);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 50 * DOLLARS);
+			let recipient: H160 = hex!("0000000000000000000000000000000000001234").into();
+
+			let amount_out = Cennzx::get_sell_price(CENNZ_ASSET_ID, 100 * DOLLARS, CPAY_ASSET_ID).unwrap();
+			// keccak('swapExactTokensForTokens(uint256,uint256,address[],address)')[..4]
+			let selector = [0x47, 0x2b, 0x43, 0xf3];
+			let parameters = ethabi::encode(&[
+				Token::Uint(U256::from(100 * DOLLARS)),
+				Token::Uint(U256::from(amount_out)),
+				Token::Array(vec![
+					Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0),
+					Token::Address(Runtime::runtime_id_to_evm_id(CPAY_ASSET_ID).0),
+				]),
+				Token::Address(recipient),
+			]);
+			let info = call_cennzx(caller, [selector.to_vec(), parameters].concat());
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+
+			assert_eq!(
+				ethabi::decode(&[ParamType::Array(Box::new(ParamType::Uint(256)))], &info.value).unwrap(),
+				vec![Token::Array(vec![
+					Token::Uint(U256::from(100 * DOLLARS)),
+					Token::Uint(U256::from(amount_out))
+				])],
+			);
+			assert_eq!(GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58), 0);
+			assert_eq!(
+				GenericAsset::free_balance(CPAY_ASSET_ID, &AddressMappingOf::<Runtime>::into_account_id(recipient)),
+				amount_out
+			);
+			assert_eq!(info.logs.len(), 1);
+			assert_eq!(info.logs[0].topics[0], SELECTOR_LOG_SWAP.into());
+		});
+}
+
+#[test]
+fn swap_reverts_below_minimum_output() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			with_cennz_exchange(500_000 * DOLLARS);
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CENNZ_ASSET_ID, 100 * DOLLARS);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 50 * DOLLARS);
+
+			// keccak('swapExactTokensForTokens(uint256,uint256,address[],address)')[..4]
+			let selector = [0x47, 0x2b, 0x43, 0xf3];
+			let parameters = ethabi::encode(&[
+				Token::Uint(U256::from(100 * DOLLARS)),
+				Token::Uint(U256::from(100 * DOLLARS)), // more than the exchange will pay
+				Token::Array(vec![
+					Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0),
+					Token::Address(Runtime::runtime_id_to_evm_id(CPAY_ASSET_ID).0),
+				]),
+				Token::Address(caller),
+			]);
+			let info = call_cennzx(caller, [selector.to_vec(), parameters].concat());
+			assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			assert_eq!(GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58), 100 * DOLLARS);
+		});
+}
+
+#[test]
+fn swap_reverts_when_amount_exceeds_u128() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			with_cennz_exchange(500_000 * DOLLARS);
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CENNZ_ASSET_ID, 100 * DOLLARS);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 50 * DOLLARS);
+
+			// keccak('swapTokensForExactTokens(uint256,uint256,address[],address)')[..4]
+			let selector = [0x42, 0x71, 0x2a, 0x67];
+			let parameters = ethabi::encode(&[
+				Token::Uint(U256::from(DOLLARS)),
+				// would saturate to u128::MAX, an unlimited input
+				Token::Uint(U256::from(u128::MAX) + 1),
+				Token::Array(vec![
+					Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0),
+					Token::Address(Runtime::runtime_id_to_evm_id(CPAY_ASSET_ID).0),
+				]),
+				Token::Address(caller),
+			]);
+			let info = call_cennzx(caller, [selector.to_vec(), parameters].concat());
+			assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			assert_eq!(GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58), 100 * DOLLARS);
+		});
+}
+
+#[test]
+fn get_reserves_and_amounts() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			with_cennz_exchange(500_000 * DOLLARS);
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let _ = GenericAsset::deposit_creating(
+				&AddressMappingOf::<Runtime>::into_account_id(caller),
+				CPAY_ASSET_ID,
+				50 * DOLLARS,
+			);
+			let cennz = Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0);
+			let cpay = Token::Address(Runtime::runtime_id_to_evm_id(CPAY_ASSET_ID).0);
+
+			// keccak('getReserves(address)')[..4]
+			let input = [vec![0x3e, 0x99, 0xc1, 0xe4], ethabi::encode(&[cennz.clone()])].concat();
+			let info = call_cennzx(caller, input);
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+			assert_eq!(
+				ethabi::decode(&[ParamType::Uint(256), ParamType::Uint(256)], &info.value).unwrap(),
+				vec![
+					Token::Uint(U256::from(500_000 * DOLLARS)),
+					Token::Uint(U256::from(500_000 * DOLLARS))
+				],
+			);
+
+			// keccak('getAmountOut(uint256,address,address)')[..4]
+			let input = [
+				vec![0x5e, 0x1e, 0x63, 0x25],
+				ethabi::encode(&[Token::Uint(U256::from(100 * DOLLARS)), cennz.clone(), cpay.clone()]),
+			]
+			.concat();
+			let info = call_cennzx(caller, input);
+			assert_eq!(
+				U256::from_big_endian(&info.value),
+				U256::from(Cennzx::get_sell_price(CENNZ_ASSET_ID, 100 * DOLLARS, CPAY_ASSET_ID).unwrap())
+			);
+
+			// keccak('getAmountIn(uint256,address,address)')[..4]
+			let input = [
+				vec![0x0a, 0x9a, 0x2b, 0x72],
+				ethabi::encode(&[Token::Uint(U256::from(100 * DOLLARS)), cennz, cpay]),
+			]
+			.concat();
+			let info = call_cennzx(caller, input);
+			assert_eq!(
+				U256::from_big_endian(&info.value),
+				U256::from(Cennzx::get_buy_price(CPAY_ASSET_ID, 100 * DOLLARS, CENNZ_ASSET_ID).unwrap())
+			);
+		});
+}
+
+#[test]
+fn add_and_remove_liquidity() {
+	ExtBuilder::default()
+		.initial_balance(1_000_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			with_cennz_exchange(500_000 * DOLLARS);
+			let caller: H160 = hex!("420aC537F1a4f78d4Dfb3A71e902be0E3d480AFB").into();
+			let caller_ss58 = AddressMappingOf::<Runtime>::into_account_id(caller);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CENNZ_ASSET_ID, 200 * DOLLARS);
+			let _ = GenericAsset::deposit_creating(&caller_ss58, CPAY_ASSET_ID, 150 * DOLLARS);
+			let cennz = Token::Address(Runtime::runtime_id_to_evm_id(CENNZ_ASSET_ID).0);
+
+			// keccak('addLiquidity(address,uint256,uint256,uint256)')[..4]
+			let input = [
+				vec![0x1e, 0xce, 0x36, 0x6a],
+				ethabi::encode(&[
+					cennz.clone(),
+					Token::Uint(U256::from(100 * DOLLARS)), // min. liquidity
+					Token::Uint(U256::from(200 * DOLLARS)), // max. CENNZ
+					Token::Uint(U256::from(100 * DOLLARS)), // CPAY
+				]),
+			]
+			.concat();
+			let info = call_cennzx(caller, input);
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+			// the exchange ratio is 1:1, rounded up in the exchange's favour
+			assert_eq!(
+				ethabi::decode(&[ParamType::Uint(256), ParamType::Uint(256)], &info.value).unwrap(),
+				vec![
+					Token::Uint(U256::from(100 * DOLLARS + 1)),
+					Token::Uint(U256::from(100 * DOLLARS))
+				],
+			);
+			assert_eq!(info.logs.len(), 1);
+			assert_eq!(
+				Cennzx::account_liquidity_value(&caller_ss58, CENNZ_ASSET_ID).liquidity,
+				100 * DOLLARS
+			);
+
+			// keccak('removeLiquidity(address,uint256,uint256,uint256)')[..4]
+			let input = [
+				vec![0x93, 0xca, 0x2f, 0x8a],
+				ethabi::encode(&[
+					cennz,
+					Token::Uint(U256::from(100 * DOLLARS)), // liquidity
+					Token::Uint(U256::from(100 * DOLLARS)), // min. CENNZ
+					Token::Uint(U256::from(100 * DOLLARS)), // min. CPAY
+				]),
+			]
+			.concat();
+			let info = call_cennzx(caller, input);
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+			assert_eq!(info.logs.len(), 1);
+			assert_eq!(
+				Cennzx::account_liquidity_value(&caller_ss58, CENNZ_ASSET_ID).liquidity,
+				0
+			);
+			assert_eq!(
+				GenericAsset::free_balance(CENNZ_ASSET_ID, &caller_ss58),
+				200 * DOLLARS - 1
+			);
+		});
+}
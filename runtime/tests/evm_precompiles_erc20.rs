@@ -16,7 +16,7 @@
 use cennznet_primitives::types::{AccountId, AssetId, Balance};
 use cennznet_runtime::{GenericAsset, Runtime, TokenApprovals};
 use crml_support::{MultiCurrency, PrefixedAddressMapping};
-use frame_support::assert_ok;
+use frame_support::{assert_ok, StorageDoubleMap, StorageMap};
 use hex_literal::hex;
 use pallet_evm_precompiles_erc20::{
 	Action, Address, AddressMapping, Context, Erc20IdConversion, Erc20PrecompileSet, EvmDataWriter, PrecompileSet,
@@ -408,3 +408,94 @@ fn erc20_update_existing_approval() {
 			);
 		})
 }
+
+#[test]
+fn erc20_transfer_frozen_asset_should_fail() {
+	let initial_balance = 1000;
+	let caller = AccountId::from(hex!("63766d3a00000000000000a86e122edbdcba4bf24a2abf89f5c230b37df49d4a"));
+	ExtBuilder::default()
+		.initialise_eth_accounts(vec![caller.clone()])
+		.initial_balance(initial_balance)
+		.build()
+		.execute_with(|| {
+			let caller_eth = PrefixedAddressMapping::from_account_id(caller.clone());
+			let receiver_eth = H160::from_slice(&hex!("0000022EdbDcBA4bF24a2Abf89F5C230b3700000"));
+			let receiver: AccountId = PrefixedAddressMapping::into_account_id(receiver_eth.clone());
+			let transfer_amount: Balance = 100;
+			crml_generic_asset::FrozenAssets::<Runtime>::insert(STAKING_ASSET_ID, true);
+
+			let (address, context) = setup_context(STAKING_ASSET_ID, caller_eth);
+			let input_data = EvmDataWriter::new_with_selector(Action::Transfer)
+				.write::<Address>(receiver_eth.into())
+				.write::<U256>(transfer_amount.into())
+				.build();
+			let precompile_set = Erc20PrecompileSet::<Runtime>::new();
+
+			assert!(precompile_set
+				.execute(address.into(), &input_data, None, &context, false)
+				.unwrap()
+				.is_err());
+
+			// Check final balances haven't changed
+			assert_eq!(
+				<GenericAsset as MultiCurrency>::free_balance(&caller, STAKING_ASSET_ID),
+				initial_balance,
+			);
+			assert_eq!(
+				<GenericAsset as MultiCurrency>::free_balance(&receiver, STAKING_ASSET_ID),
+				0,
+			);
+		})
+}
+
+#[test]
+fn erc20_transfer_from_frozen_account_should_fail() {
+	let initial_balance = 1000;
+	let owner = AccountId::from(hex!("63766d3a00000000000000a86e122edbdcba4bf24a2abf89f5c230b37df49d4a"));
+	ExtBuilder::default()
+		.initialise_eth_accounts(vec![owner.clone()])
+		.initial_balance(initial_balance)
+		.build()
+		.execute_with(|| {
+			let owner_eth = PrefixedAddressMapping::from_account_id(owner.clone());
+			let receiver_eth = H160::from_slice(&hex!("0000022EdbDcBA4bF24a2Abf89F5C230b3700000"));
+			let approved_eth = H160::from_slice(&hex!("0000022EdbDcBA4bF24a2Abf89F5C230b3700000"));
+			let approved_amount: Balance = 200;
+			let transfer_amount: Balance = 100;
+
+			// Set Approval
+			let (address, context) = setup_context(STAKING_ASSET_ID, owner_eth);
+			let input_data = EvmDataWriter::new_with_selector(Action::Approve)
+				.write::<Address>(approved_eth.into())
+				.write::<U256>(approved_amount.into())
+				.build();
+			let precompile_set = Erc20PrecompileSet::<Runtime>::new();
+			assert_ok!(precompile_set
+				.execute(address.into(), &input_data, None, &context, false)
+				.unwrap());
+
+			crml_generic_asset::FrozenAccounts::<Runtime>::insert(STAKING_ASSET_ID, &owner, true);
+
+			// Transfer
+			let (address, context) = setup_context(STAKING_ASSET_ID, approved_eth);
+			let input_data = EvmDataWriter::new_with_selector(Action::TransferFrom)
+				.write::<Address>(owner_eth.into())
+				.write::<Address>(receiver_eth.into())
+				.write::<U256>(transfer_amount.into())
+				.build();
+			assert!(precompile_set
+				.execute(address.into(), &input_data, None, &context, false)
+				.unwrap()
+				.is_err());
+
+			// Check balance and approval are unchanged
+			assert_eq!(
+				<GenericAsset as MultiCurrency>::free_balance(&owner, STAKING_ASSET_ID),
+				initial_balance
+			);
+			assert_eq!(
+				TokenApprovals::erc20_approvals((owner_eth, STAKING_ASSET_ID), approved_eth),
+				approved_amount
+			);
+		})
+}
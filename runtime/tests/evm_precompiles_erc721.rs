@@ -15,13 +15,14 @@
 
 use cennznet_primitives::types::{AccountId, CollectionId, SerialNumber, SeriesId};
 use cennznet_runtime::{Nft, Runtime, TokenApprovals};
-use crml_nft::MetadataScheme;
+use crml_nft::{MetadataScheme, RoyaltiesSchedule};
 use crml_support::PrefixedAddressMapping;
-use frame_support::assert_ok;
+use frame_support::{assert_ok, traits::OnInitialize};
 use pallet_evm_precompiles_erc721::{
 	Action, Address, AddressMapping, Context, Erc721IdConversion, Erc721PrecompileSet, EvmDataWriter, PrecompileSet,
 };
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
+use sp_runtime::Permill;
 
 mod common;
 use common::mock::ExtBuilder;
@@ -43,6 +44,8 @@ fn setup_nft_series(token_owner: AccountId) -> (CollectionId, SeriesId, SerialNu
 		Some(token_owner),
 		MetadataScheme::IpfsDir(b"<CID>".to_vec()),
 		None,
+		None,
+		None,
 	));
 	(collection_id, 0, 0)
 }
@@ -60,7 +63,7 @@ fn setup_context(collection_id: CollectionId, series_id: SeriesId, caller: H160)
 fn setup_input_data(serial_number: SerialNumber, to: H160, from: Option<H160>, selector: Action) -> Vec<u8> {
 	// Write to input data
 	match selector {
-		Action::TransferFrom => EvmDataWriter::new_with_selector(selector)
+		Action::TransferFrom | Action::SafeTransferFrom => EvmDataWriter::new_with_selector(selector)
 			.write::<Address>(from.unwrap().into())
 			.write::<Address>(to.into())
 			.write::<U256>(serial_number.into())
@@ -69,6 +72,9 @@ fn setup_input_data(serial_number: SerialNumber, to: H160, from: Option<H160>, s
 			.write::<Address>(to.into())
 			.write::<U256>(serial_number.into())
 			.build(),
+		Action::UserOf | Action::UserExpires => EvmDataWriter::new_with_selector(selector)
+			.write::<U256>(serial_number.into())
+			.build(),
 		_ => vec![],
 	}
 }
@@ -232,3 +238,339 @@ fn erc721_approve_caller_not_token_owner_should_fail() {
 		);
 	})
 }
+
+#[test]
+fn erc721_user_of_rented_token() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let token_owner_eth: H160 = b"test2000000000000000".into();
+		let user_eth: H160 = b"test3000000000000000".into();
+		let token_owner: AccountId = PrefixedAddressMapping::into_account_id(token_owner_eth.clone());
+		let user: AccountId = PrefixedAddressMapping::into_account_id(user_eth.clone());
+
+		let (collection_id, series_id, serial_number) = setup_nft_series(token_owner.clone());
+		let (address, context) = setup_context(collection_id, series_id, user_eth);
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+
+		// not rented, no user
+		let output = precompile_set
+			.execute(
+				address.into(),
+				&setup_input_data(serial_number, user_eth, None, Action::UserOf),
+				None,
+				&context,
+				false,
+			)
+			.unwrap()
+			.expect("userOf succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(H256::zero()).build());
+
+		let duration = 100;
+		assert_ok!(Nft::lend(
+			Some(token_owner).into(),
+			(collection_id, series_id, serial_number),
+			user.clone(),
+			duration,
+			None,
+		));
+		let expiry = frame_system::Pallet::<Runtime>::block_number() + duration;
+
+		let output = precompile_set
+			.execute(
+				address.into(),
+				&setup_input_data(serial_number, user_eth, None, Action::UserOf),
+				None,
+				&context,
+				false,
+			)
+			.unwrap()
+			.expect("userOf succeeds")
+			.output;
+		assert_eq!(
+			output,
+			EvmDataWriter::new().write(H256::from(<[u8; 32]>::from(user))).build()
+		);
+
+		let output = precompile_set
+			.execute(
+				address.into(),
+				&setup_input_data(serial_number, user_eth, None, Action::UserExpires),
+				None,
+				&context,
+				false,
+			)
+			.unwrap()
+			.expect("userExpires succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(U256::from(expiry)).build());
+	})
+}
+
+#[test]
+fn erc721_supports_interface() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let caller_eth: H160 = b"test2000000000000000".into();
+		let caller: AccountId = PrefixedAddressMapping::into_account_id(caller_eth.clone());
+		let (collection_id, series_id, _serial_number) = setup_nft_series(caller);
+		let (address, context) = setup_context(collection_id, series_id, caller_eth);
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+
+		for (interface_id, supported) in [
+			([0x01, 0xff, 0xc9, 0xa7], true),
+			([0x80, 0xac, 0x58, 0xcd], true),
+			([0x5b, 0x5e, 0x13, 0x9f], true),
+			([0x2a, 0x55, 0x20, 0x5a], true),
+			([0xff, 0xff, 0xff, 0xff], false),
+		] {
+			let mut word = [0_u8; 32];
+			word[..4].copy_from_slice(&interface_id);
+			let input_data = EvmDataWriter::new_with_selector(Action::SupportsInterface)
+				.write(H256::from(word))
+				.build();
+
+			let output = precompile_set
+				.execute(address.into(), &input_data, None, &context, false)
+				.unwrap()
+				.expect("supportsInterface succeeds")
+				.output;
+			assert_eq!(output, EvmDataWriter::new().write(supported).build());
+		}
+	})
+}
+
+#[test]
+fn erc721_royalty_info() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let collection_owner_eth: H160 = b"test1000000000000000".into();
+		let collection_owner: AccountId = PrefixedAddressMapping::into_account_id(collection_owner_eth.clone());
+		let token_owner_eth: H160 = b"test2000000000000000".into();
+		let token_owner: AccountId = PrefixedAddressMapping::into_account_id(token_owner_eth.clone());
+		let receiver_eth: H160 = b"test3000000000000000".into();
+		let receiver: AccountId = PrefixedAddressMapping::into_account_id(receiver_eth.clone());
+		let beneficiary: AccountId = PrefixedAddressMapping::into_account_id(H160::from(b"test4000000000000000"));
+
+		let (collection_id, series_id, serial_number) = setup_nft_series(token_owner);
+		let (address, context) = setup_context(collection_id, series_id, token_owner_eth);
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+		let sale_price = U256::from(1_000);
+		let input_data = EvmDataWriter::new_with_selector(Action::RoyaltyInfo)
+			.write::<U256>(serial_number.into())
+			.write(sale_price)
+			.build();
+
+		// no royalties
+		let output = precompile_set
+			.execute(address.into(), &input_data, None, &context, false)
+			.unwrap()
+			.expect("royaltyInfo succeeds")
+			.output;
+		assert_eq!(
+			output,
+			EvmDataWriter::new()
+				.write::<Address>(H160::default().into())
+				.write(U256::zero())
+				.build()
+		);
+
+		assert_ok!(Nft::set_token_royalties(
+			Some(collection_owner).into(),
+			(collection_id, series_id, serial_number),
+			Some(RoyaltiesSchedule {
+				entitlements: vec![
+					(receiver, Permill::from_percent(10)),
+					(beneficiary, Permill::from_percent(5))
+				],
+			}),
+		));
+		// apply the delayed royalties update
+		let (effective_block, _) = Nft::pending_token_royalties((collection_id, series_id, serial_number)).unwrap();
+		Nft::on_initialize(effective_block);
+
+		// the first entitled account receives the total royalties
+		let output = precompile_set
+			.execute(address.into(), &input_data, None, &context, false)
+			.unwrap()
+			.expect("royaltyInfo succeeds")
+			.output;
+		assert_eq!(
+			output,
+			EvmDataWriter::new()
+				.write::<Address>(receiver_eth.into())
+				.write(U256::from(150))
+				.build()
+		);
+	})
+}
+
+#[test]
+fn erc721_total_supply() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let collection_owner_eth: H160 = b"test1000000000000000".into();
+		let collection_owner: AccountId = PrefixedAddressMapping::into_account_id(collection_owner_eth.clone());
+		let token_owner_eth: H160 = b"test2000000000000000".into();
+		let token_owner: AccountId = PrefixedAddressMapping::into_account_id(token_owner_eth.clone());
+
+		let (collection_id, series_id, _serial_number) = setup_nft_series(token_owner);
+		let (address, context) = setup_context(collection_id, series_id, token_owner_eth);
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+		let input_data = EvmDataWriter::new_with_selector(Action::TotalSupply).build();
+
+		let output = precompile_set
+			.execute(address.into(), &input_data, None, &context, false)
+			.unwrap()
+			.expect("totalSupply succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(U256::from(1)).build());
+
+		assert_ok!(Nft::mint_additional(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			2,
+			None
+		));
+		let output = precompile_set
+			.execute(address.into(), &input_data, None, &context, false)
+			.unwrap()
+			.expect("totalSupply succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(U256::from(3)).build());
+	})
+}
+
+#[test]
+fn erc721_set_approval_for_all_and_transfer() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let token_owner_eth: H160 = b"test2000000000000000".into();
+		let operator_eth: H160 = b"test3000000000000000".into();
+		let new_owner_eth: H160 = b"test4000000000000000".into();
+		let token_owner: AccountId = PrefixedAddressMapping::into_account_id(token_owner_eth.clone());
+		let new_owner: AccountId = PrefixedAddressMapping::into_account_id(new_owner_eth.clone());
+
+		let (collection_id, series_id, serial_number) = setup_nft_series(token_owner.clone());
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+		let is_approved_for_all_input = EvmDataWriter::new_with_selector(Action::IsApprovedForAll)
+			.write::<Address>(token_owner_eth.into())
+			.write::<Address>(operator_eth.into())
+			.build();
+
+		// owner approves operator
+		let (address, context) = setup_context(collection_id, series_id, token_owner_eth);
+		assert_ok!(precompile_set
+			.execute(
+				address.into(),
+				&EvmDataWriter::new_with_selector(Action::SetApprovalForAll)
+					.write::<Address>(operator_eth.into())
+					.write(true)
+					.build(),
+				None,
+				&context,
+				false,
+			)
+			.unwrap());
+		let output = precompile_set
+			.execute(address.into(), &is_approved_for_all_input, None, &context, false)
+			.unwrap()
+			.expect("isApprovedForAll succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(true).build());
+
+		// operator transfers on behalf of the owner
+		let (address, context) = setup_context(collection_id, series_id, operator_eth);
+		let input_data = setup_input_data(
+			serial_number,
+			new_owner_eth,
+			Some(token_owner_eth),
+			Action::TransferFrom,
+		);
+		assert_ok!(precompile_set
+			.execute(address.into(), &input_data, None, &context, false)
+			.unwrap());
+		assert_eq!(Nft::token_owner((collection_id, series_id), serial_number), new_owner);
+
+		// operator approvals remain after transfer until revoked
+		assert!(TokenApprovals::is_approved_for_all(
+			token_owner_eth,
+			operator_eth,
+			(collection_id, series_id)
+		));
+		let (address, context) = setup_context(collection_id, series_id, token_owner_eth);
+		assert_ok!(precompile_set
+			.execute(
+				address.into(),
+				&EvmDataWriter::new_with_selector(Action::SetApprovalForAll)
+					.write::<Address>(operator_eth.into())
+					.write(false)
+					.build(),
+				None,
+				&context,
+				false,
+			)
+			.unwrap());
+		let output = precompile_set
+			.execute(address.into(), &is_approved_for_all_input, None, &context, false)
+			.unwrap()
+			.expect("isApprovedForAll succeeds")
+			.output;
+		assert_eq!(output, EvmDataWriter::new().write(false).build());
+	})
+}
+
+#[test]
+fn erc721_safe_transfer_from() {
+	ExtBuilder::default().initial_balance(1).build().execute_with(|| {
+		let token_owner_eth: H160 = b"test2000000000000000".into();
+		let receiver_eth: H160 = b"test3000000000000000".into();
+		let rejecter_eth: H160 = b"test4000000000000000".into();
+		let token_owner: AccountId = PrefixedAddressMapping::into_account_id(token_owner_eth.clone());
+		let receiver: AccountId = PrefixedAddressMapping::into_account_id(receiver_eth.clone());
+
+		// returns the `onERC721Received` selector
+		// PUSH4 0x150b7a02 PUSH1 0xe0 SHL PUSH1 0 MSTORE PUSH1 0x20 PUSH1 0 RETURN
+		pallet_evm::AccountCodes::<Runtime>::insert(
+			receiver_eth,
+			vec![
+				0x63, 0x15, 0x0b, 0x7a, 0x02, 0x60, 0xe0, 0x1b, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+			],
+		);
+		// returns zero
+		// PUSH1 0x20 PUSH1 0 RETURN
+		pallet_evm::AccountCodes::<Runtime>::insert(rejecter_eth, vec![0x60, 0x20, 0x60, 0x00, 0xf3]);
+
+		let (collection_id, series_id, serial_number) = setup_nft_series(token_owner.clone());
+		let (address, context) = setup_context(collection_id, series_id, token_owner_eth);
+		let precompile_set = Erc721PrecompileSet::<Runtime>::new();
+
+		// contract accepts the token
+		let input_data = setup_input_data(
+			serial_number,
+			receiver_eth,
+			Some(token_owner_eth),
+			Action::SafeTransferFrom,
+		);
+		assert_ok!(precompile_set
+			.execute(address.into(), &input_data, Some(1_000_000), &context, false)
+			.unwrap());
+		assert_eq!(Nft::token_owner((collection_id, series_id), serial_number), receiver);
+
+		// contract does not accept the token, the EVM reverts the transfer
+		let collection_owner: AccountId = PrefixedAddressMapping::into_account_id(H160::from(b"test1000000000000000"));
+		assert_ok!(Nft::mint_additional(
+			Some(collection_owner).into(),
+			collection_id,
+			series_id,
+			1,
+			Some(token_owner)
+		));
+		let input_data = setup_input_data(
+			serial_number + 1,
+			rejecter_eth,
+			Some(token_owner_eth),
+			Action::SafeTransferFrom,
+		);
+		assert!(precompile_set
+			.execute(address.into(), &input_data, Some(1_000_000), &context, false)
+			.unwrap()
+			.is_err());
+	})
+}
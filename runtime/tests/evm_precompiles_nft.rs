@@ -195,6 +195,8 @@ fn mint() {
 				None,
 				metadata_scheme.clone(),
 				Some(royalties_schedule.clone()),
+				None,
+				None,
 			));
 
 			// Setup call for EVM Precompile
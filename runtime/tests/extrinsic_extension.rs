@@ -24,7 +24,7 @@ use crml_support::MultiCurrency;
 use frame_support::assert_ok;
 
 mod common;
-use common::helpers::{extrinsic_fee_for, header, sign};
+use common::helpers::{extrinsic_fee_for, extrinsic_fee_with_exchange_for, header, sign};
 use common::keyring::{alice, bob, signed_extra};
 use common::mock::ExtBuilder;
 
@@ -103,7 +103,7 @@ fn generic_asset_transfer_works_with_fee_exchange() {
 
 			// Calculate how much CENNZ should be sold to make the above extrinsic
 			let cennz_sold_amount =
-				Cennzx::get_asset_to_core_buy_price(CENNZ_ASSET_ID, extrinsic_fee_for(&xt)).unwrap();
+				Cennzx::get_asset_to_core_buy_price(CENNZ_ASSET_ID, extrinsic_fee_with_exchange_for(&xt)).unwrap();
 
 			// Initialise block and apply the extrinsic
 			Executive::initialize_block(&header());